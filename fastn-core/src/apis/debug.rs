@@ -0,0 +1,167 @@
+/// Number of requests `fastn serve --profile` keeps in memory for `/-/debug/requests/`.
+pub(crate) const PROFILED_REQUESTS: usize = 100;
+
+/// Name of the span created for every request handled by `fastn serve`, these are the spans we
+/// record when profiling.
+pub(crate) const REQUEST_SPAN: &str = "route";
+
+/// path: /-/debug/requests/
+/// path: /-/debug/requests/<id>/
+pub(crate) async fn requests(
+    req: &fastn_core::http::Request,
+    dev_mode: bool,
+) -> fastn_core::Result<fastn_core::http::Response> {
+    if !fastn_observer::is_recording() {
+        return Ok(fastn_core::not_found!(
+            "request profiling is not enabled, start the server with `fastn serve --profile`"
+        ));
+    }
+
+    if !dev_mode && !is_admin(req).await? {
+        return Ok(fastn_core::unauthorised!(
+            "You are unauthorized to access: {}",
+            req.path()
+        ));
+    }
+
+    let id = req
+        .path()
+        .trim_start_matches("/-/debug/requests/")
+        .trim_matches('/');
+
+    if id.is_empty() {
+        return Ok(html_response("Recent Requests", list().as_str()));
+    }
+
+    let recorded = match id
+        .parse::<u64>()
+        .ok()
+        .and_then(fastn_observer::get_recorded)
+    {
+        Some(r) => r,
+        None => return Ok(fastn_core::not_found!("no such request: {}", id)),
+    };
+
+    let tree = fastn_observer::Html
+        .fmt_span(&recorded.span)
+        .map_err(|e| fastn_core::Error::GenericError(e.to_string()))?;
+
+    Ok(html_response(
+        format!("Request #{}", recorded.id).as_str(),
+        format!(
+            "<p><a href=\"/-/debug/requests/\">&larr; all requests</a></p>\n{}\n{}",
+            summary(&recorded),
+            tree
+        )
+        .as_str(),
+    ))
+}
+
+/// Admin pages are available to the members of the user group configured as `admin-group` in
+/// `fastn.package`. If no such group is configured, they are only available in dev mode.
+async fn is_admin(req: &fastn_core::http::Request) -> fastn_core::Result<bool> {
+    use itertools::Itertools;
+
+    let config = fastn_core::Config::read(None, false, Some(req)).await?;
+    let group_id = match config.package.admin_group {
+        Some(ref g) => g,
+        None => return Ok(false),
+    };
+
+    let group =
+        fastn_core::user_group::user_group_by_id(&config, group_id.as_str())?.ok_or_else(|| {
+            fastn_core::Error::GroupNotFound {
+                id: group_id.to_string(),
+                message: "admin-group not found".to_string(),
+            }
+        })?;
+
+    let group_identities = group.get_identities(&config)?;
+    let identities =
+        match fastn_core::auth::get_auth_identities(req.cookies(), group_identities.as_slice())
+            .await
+        {
+            Ok(ids) => ids,
+            Err(fastn_core::Error::GenericError(_)) => vec![],
+            Err(e) => return Err(e),
+        };
+
+    group.belongs_to(&config, identities.iter().collect_vec().as_slice())
+}
+
+fn list() -> String {
+    let recorded = fastn_observer::recorded();
+    if recorded.is_empty() {
+        return "<p>No requests recorded yet.</p>".to_string();
+    }
+
+    let rows = recorded
+        .iter()
+        .map(|r| {
+            format!(
+                "<tr><td><a href=\"/-/debug/requests/{id}/\">#{id}</a></td><td>{on}</td>\
+                <td>{fields}</td><td class=\"duration\">{duration:?}</td></tr>",
+                id = r.id,
+                on = recorded_on(r),
+                fields = fields(r.span.shared().fields()),
+                duration = r.span.duration(),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        "<table>\n<tr><th>#</th><th>When</th><th>Request</th><th>Total Time</th></tr>\n{}\n</table>",
+        rows
+    )
+}
+
+fn summary(recorded: &fastn_observer::Recorded) -> String {
+    format!(
+        "<p>{} &middot; {} &middot; total: <b>{:?}</b></p>",
+        recorded_on(recorded),
+        fields(recorded.span.shared().fields()),
+        recorded.span.duration()
+    )
+}
+
+fn recorded_on(recorded: &fastn_observer::Recorded) -> String {
+    match recorded.recorded_on.elapsed() {
+        Ok(d) => format!("{}s ago", d.as_secs()),
+        Err(_) => "just now".to_string(),
+    }
+}
+
+fn fields(fields: &fastn_observer::FieldSet) -> String {
+    fields
+        .iter()
+        .map(|f| fastn_observer::escape(f.value().trim_matches('"')))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn html_response(title: &str, body: &str) -> fastn_core::http::Response {
+    fastn_core::http::ok_with_content_type(
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+            <style>{css}</style>\n</head>\n<body>\n<h1>{title}</h1>\n{body}\n</body>\n</html>",
+            title = title,
+            css = DEBUG_CSS,
+            body = body,
+        )
+        .into_bytes(),
+        mime_guess::mime::TEXT_HTML_UTF_8,
+    )
+}
+
+const DEBUG_CSS: &str = r#"
+body { font-family: monospace; margin: 2em; }
+table { border-collapse: collapse; }
+td, th { padding: 4px 12px; border-bottom: 1px solid #ddd; text-align: left; }
+td.duration { text-align: right; }
+ul { list-style: none; padding-left: 1.5em; border-left: 1px dotted #ccc; }
+summary { cursor: pointer; }
+.on { color: #888; }
+.event.warn { color: #b7791f; }
+.event.error, .span.error > details > summary { color: #c53030; }
+"#;
@@ -0,0 +1,91 @@
+// Liveness, readiness and metrics endpoints, for running `fastn serve` behind a load balancer or
+// in Kubernetes.
+
+/// Set once the server has finished warming up, see `warmup()`.
+static READY: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Set when the server has been asked to shut down, in-flight requests are still being served
+/// but the load balancer should stop sending new ones.
+static SHUTTING_DOWN: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// path: /-/health/
+pub(crate) async fn health() -> fastn_core::Result<fastn_core::http::Response> {
+    Ok(fastn_core::http::ok("ok".as_bytes().to_vec()))
+}
+
+/// path: /-/ready/
+pub(crate) async fn ready() -> fastn_core::Result<fastn_core::http::Response> {
+    use std::sync::atomic::Ordering;
+
+    if SHUTTING_DOWN.load(Ordering::Relaxed) {
+        return Ok(actix_web::HttpResponse::ServiceUnavailable().body("shutting down"));
+    }
+    if !READY.load(Ordering::Relaxed) {
+        return Ok(actix_web::HttpResponse::ServiceUnavailable().body("warming up"));
+    }
+    Ok(fastn_core::http::ok("ready".as_bytes().to_vec()))
+}
+
+/// path: /-/metrics/
+pub(crate) async fn metrics() -> fastn_core::Result<fastn_core::http::Response> {
+    Ok(actix_web::HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(fastn_core::metrics::render()))
+}
+
+/// The server is started after the config is loaded and the dependencies are downloaded, the only
+/// thing left is to connect to the database, which we keep retrying till it succeeds.
+pub(crate) fn warmup() {
+    tokio::spawn(async {
+        loop {
+            match fastn_core::library2022::processor::pg::warmup().await {
+                Ok(()) => break,
+                Err(e) => {
+                    fastn_core::warning!("database not reachable, retrying in 5s: {}", e);
+                    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                }
+            }
+        }
+        READY.store(true, std::sync::atomic::Ordering::Relaxed);
+    });
+}
+
+/// actix-web stops accepting connections as soon as it is asked to shut down, so we handle
+/// `SIGTERM` ourselves: the readiness check fails first, so the load balancer stops routing new
+/// requests to this server, and only after `grace` seconds are the servers stopped, which then
+/// drain their in-flight requests. `Ctrl-C` stops them right away.
+pub(crate) fn shutdown_on_signal(grace: u64, servers: Vec<actix_web::dev::ServerHandle>) {
+    tokio::spawn(async move {
+        tokio::select! {
+            _ = terminate() => {
+                println!("Shutting down in {}s, failing the readiness check meanwhile", grace);
+                SHUTTING_DOWN.store(true, std::sync::atomic::Ordering::Relaxed);
+                tokio::time::sleep(std::time::Duration::from_secs(grace)).await;
+            }
+            _ = tokio::signal::ctrl_c() => {}
+        }
+
+        println!("Waiting for in-flight requests to finish");
+        for server in servers {
+            server.stop(true).await;
+        }
+    });
+}
+
+#[cfg(unix)]
+async fn terminate() {
+    match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+        Ok(mut terminate) => {
+            terminate.recv().await;
+        }
+        Err(e) => {
+            fastn_core::warning!("can not listen for SIGTERM: {}", e);
+            std::future::pending::<()>().await
+        }
+    }
+}
+
+#[cfg(not(unix))]
+async fn terminate() {
+    std::future::pending::<()>().await
+}
@@ -1,8 +1,10 @@
 pub(crate) mod cache;
 pub(crate) mod clone;
 pub(crate) mod cr;
+pub(crate) mod debug;
 pub(crate) mod edit;
 pub(crate) mod edit_source;
+pub(crate) mod health;
 pub(crate) mod sync;
 pub(crate) mod sync2;
 pub(crate) mod view_source;
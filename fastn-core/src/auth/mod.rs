@@ -215,6 +215,36 @@ pub async fn get_user_data_from_cookies(
     Ok(None)
 }
 
+/// `<platform>:<user id>` of the first auth cookie that this server issued, ie that decrypts
+/// with `secret_key()` to a user detail. Cookies made up by the client are not sessions.
+pub(crate) async fn verified_user(
+    cookies: &std::collections::HashMap<String, String>,
+) -> Option<String> {
+    for provider in AuthProviders::AUTH_ITER.iter() {
+        let encrypted = match cookies.get(provider.as_str()) {
+            Some(v) => v,
+            None => continue,
+        };
+        let user_detail = match utils::decrypt_str(encrypted)
+            .await
+            .ok()
+            .and_then(|v| serde_json::from_str::<serde_json::Value>(v.as_str()).ok())
+        {
+            Some(v) => v,
+            None => continue,
+        };
+        // every `UserDetail` has a `user_name`, most also have a `user_id`
+        if let Some(id) = user_detail
+            .get("user_id")
+            .or_else(|| user_detail.get("user_name"))
+            .and_then(|v| v.as_str())
+        {
+            return Some(format!("{}:{}", provider.as_str(), id));
+        }
+    }
+    None
+}
+
 // TODO: rename the method later
 // bridge between fastn_core to auth to check
 pub async fn get_auth_identities(
@@ -36,6 +36,7 @@ pub async fn login(
             req.path = "/sorry/".to_string();
             fastn_core::commands::serve::serve(
                 req,
+                None,
                 edition,
                 external_js,
                 inline_js,
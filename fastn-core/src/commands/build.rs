@@ -10,12 +10,7 @@ pub async fn build(
     tokio::fs::create_dir_all(config.build_dir()).await?;
 
     // Default css and js
-    default_build_files(
-        config.root.join(".build"),
-        &config.ftd_edition,
-        &config.package.name,
-    )
-    .await?;
+    default_build_files(config.root.join(".build"), &config.ftd_edition).await?;
 
     {
         let documents = get_documents_for_current_package(config).await?;
@@ -671,15 +666,18 @@ async fn handle_file_(
 pub async fn default_build_files(
     base_path: camino::Utf8PathBuf,
     ftd_edition: &fastn_core::FTDEdition,
-    package_name: &str,
 ) -> fastn_core::Result<()> {
     if ftd_edition.is_2023() {
-        let default_ftd_js_content = ftd::js::all_js_without_test(package_name);
-        let hashed_ftd_js_name = fastn_core::utils::hashed_default_ftd_js(package_name);
-        let save_default_ftd_js = base_path.join(hashed_ftd_js_name);
-        fastn_core::utils::update(save_default_ftd_js, default_ftd_js_content.as_bytes())
+        // the name changes with the content, so an existing file is already up to date
+        let save_default_ftd_js = base_path.join(fastn_core::utils::hashed_default_ftd_js());
+        if !save_default_ftd_js.exists() {
+            fastn_core::utils::update(
+                save_default_ftd_js,
+                ftd::js::all_js_without_test().as_bytes(),
+            )
             .await
             .ok();
+        }
 
         let markdown_js_content = ftd::markdown_js();
         let hashed_markdown_js_name = fastn_core::utils::hashed_markdown_js();
@@ -48,44 +48,9 @@ async fn serve_file(
             return fastn_core::server_error!("request not set");
         };
 
-        match config.can_read(req, path.as_str(), true).await {
-            Ok(can_read) => {
-                if !can_read {
-                    tracing::error!(
-                        msg = "unauthorized-error: can not read",
-                        path = path.as_str()
-                    );
-                    return fastn_core::unauthorised!("You are unauthorized to access: {}", path);
-                }
-            }
-            Err(e) => {
-                tracing::error!(msg = "can_read-error", path = path.as_str());
-                return fastn_core::server_error!("fastn-Error: can_read error: {}, {:?}", path, e);
-            }
-        };
-
-        match fastn_core::package::app::can_read(config, path.as_str()).await {
-            Ok(can_read) => {
-                if !can_read {
-                    tracing::error!(
-                        msg = "unauthorized-error: can not access app",
-                        path = path.as_str()
-                    );
-                    return fastn_core::unauthorised!("You are unauthorized to access: {}", path);
-                }
-            }
-            Err(err) => {
-                tracing::error!(
-                    msg = "app::can_read-error: can not access app",
-                    path = path.as_str()
-                );
-                return fastn_core::server_error!(
-                    "fastn-Error: can_read error: {}, {:?}",
-                    path,
-                    err
-                );
-            }
-        };
+        if let Some(response) = read_denied(config, req, path.as_str()).await {
+            return response;
+        }
     }
 
     match f {
@@ -113,11 +78,17 @@ async fn serve_file(
                 }
             }
         }
-        fastn_core::File::Image(image) => fastn_core::http::ok_with_content_type(
-            image.content,
-            guess_mime_type(image.id.as_str()),
+        fastn_core::File::Image(image) => fastn_core::http::with_last_modified(
+            fastn_core::http::ok_with_content_type(
+                image.content,
+                guess_mime_type(image.id.as_str()),
+            ),
+            image.base_path.join(image.id.as_str()).as_path(),
+        ),
+        fastn_core::File::Static(s) => fastn_core::http::with_last_modified(
+            fastn_core::http::ok(s.content),
+            s.base_path.join(s.id.as_str()).as_path(),
         ),
-        fastn_core::File::Static(s) => fastn_core::http::ok(s.content),
         fastn_core::File::Code(s) => fastn_core::http::ok_with_content_type(
             s.content.into_bytes(),
             guess_mime_type(s.id.as_str()),
@@ -129,6 +100,76 @@ async fn serve_file(
     }
 }
 
+/// The response for a user who is not a reader of the document at `path`, or of the app it is
+/// in, `None` if they can read it.
+pub(crate) async fn read_denied(
+    config: &fastn_core::Config,
+    req: &fastn_core::http::Request,
+    path: &str,
+) -> Option<fastn_core::http::Response> {
+    match config.can_read(req, path, true).await {
+        Ok(can_read) => {
+            if !can_read {
+                tracing::error!(msg = "unauthorized-error: can not read", path = path);
+                return Some(fastn_core::unauthorised!(
+                    "You are unauthorized to access: {}",
+                    path
+                ));
+            }
+        }
+        Err(e) => {
+            tracing::error!(msg = "can_read-error", path = path);
+            return Some(fastn_core::server_error!(
+                "fastn-Error: can_read error: {}, {:?}",
+                path,
+                e
+            ));
+        }
+    };
+
+    match fastn_core::package::app::can_read(config, path).await {
+        Ok(can_read) => {
+            if !can_read {
+                tracing::error!(msg = "unauthorized-error: can not access app", path = path);
+                return Some(fastn_core::unauthorised!(
+                    "You are unauthorized to access: {}",
+                    path
+                ));
+            }
+        }
+        Err(err) => {
+            tracing::error!(msg = "app::can_read-error: can not access app", path = path);
+            return Some(fastn_core::server_error!(
+                "fastn-Error: can_read error: {}, {:?}",
+                path,
+                err
+            ));
+        }
+    };
+
+    None
+}
+
+/// The fragment of the page at `path`, see `spa` of `fastn.package`, which is rendered as the page
+/// would be.
+#[tracing::instrument(skip_all)]
+async fn serve_spa_fragment(
+    config: &mut fastn_core::Config,
+    path: &camino::Utf8Path,
+) -> fastn_core::http::Response {
+    let response = serve_file(config, path).await;
+    if !response.status().is_success() {
+        return response;
+    }
+    match config.spa_fragment.take() {
+        Some(fragment) => fastn_core::http::ok_with_content_type(
+            fragment.into_bytes(),
+            mime_guess::mime::APPLICATION_JSON,
+        ),
+        None => fastn_core::not_found!("fastn-Error: no fragment for path: {}", path),
+    }
+}
+
 async fn serve_cr_file(
     req: &fastn_core::http::Request,
     config: &mut fastn_core::Config,
@@ -226,7 +267,10 @@ async fn static_file(file_path: camino::Utf8PathBuf) -> fastn_core::http::Respon
     }
 
     match tokio::fs::read(file_path.as_path()).await {
-        Ok(r) => fastn_core::http::ok_with_content_type(r, guess_mime_type(file_path.as_str())),
+        Ok(r) => fastn_core::http::with_last_modified(
+            fastn_core::http::ok_with_content_type(r, guess_mime_type(file_path.as_str())),
+            file_path.as_path(),
+        ),
         Err(e) => {
             tracing::error!(
                 msg = "file-system-error ({})",
@@ -240,14 +284,15 @@ async fn static_file(file_path: camino::Utf8PathBuf) -> fastn_core::http::Respon
 
 #[tracing::instrument(skip_all)]
 pub async fn serve(
-    req: fastn_core::http::Request,
+    mut req: fastn_core::http::Request,
+    stream: Option<Stream>,
     edition: Option<String>,
     external_js: Vec<String>,
     inline_js: Vec<String>,
     external_css: Vec<String>,
     inline_css: Vec<String>,
 ) -> fastn_core::Result<fastn_core::http::Response> {
-    let _lock = LOCK.read().await;
+    let lock = LOCK.read().await;
 
     // TODO: remove unwrap
     let path: camino::Utf8PathBuf = req.path().replacen('/', "", 1).parse().unwrap();
@@ -260,13 +305,46 @@ pub async fn serve(
         .add_external_css(external_css)
         .add_inline_css(inline_css);
 
+    // a streamed body is passed along as it arrives only to an end-point, fastn reads it first
+    // when it handles the request itself
+    if let Some(stream) = stream {
+        if resolves_to_end_point(&mut config, &path, &req).await? {
+            // a WebSocket may stay open for long, it must not keep the edits waiting
+            drop(lock);
+            return fastn_core::proxy::stream(
+                &config,
+                stream.req,
+                stream.payload,
+                stream.max_body_size,
+            )
+            .await;
+        }
+        req = match read_body(stream.payload).await {
+            Ok(body) => fastn_core::http::Request::from_actix(stream.req, body),
+            Err(response) => return Ok(response),
+        };
+        config.request = Some(req.clone());
+    }
+
     Ok(if path.eq(&camino::Utf8PathBuf::new().join("FASTN.ftd")) {
         serve_fastn_file(&config).await
     } else if path.eq(&camino::Utf8PathBuf::new().join("")) {
         serve_file(&mut config, &path.join("/")).await
+    } else if let Some(page) = fastn_core::utils::spa_fragment_page(path.as_str()) {
+        let page = if page.is_empty() { "/" } else { page };
+        serve_spa_fragment(&mut config, camino::Utf8Path::new(page)).await
     } else if let Some(cr_number) = fastn_core::cr::get_cr_path_from_url(path.as_str()) {
         serve_cr_file(&req, &mut config, &path, cr_number).await
     } else {
+        if let Some(template) = fastn_core::package::proxy_template::find_proxy_template(
+            config.package.proxy_templates.as_slice(),
+            req.path(),
+        )
+        .cloned()
+        {
+            return fastn_core::proxy::render_template(&mut config, &template, &req).await;
+        }
+
         // url is present in config or not
         // If not present than proxy pass it
 
@@ -336,49 +414,18 @@ pub async fn serve(
             // TODO: Check if path exists in dynamic urls also, otherwise pass to endpoint
             // Already checked in the above method serve_file
             tracing::info!("executing proxy: path: {}", &path);
-            let (package_name, url, mut conf) =
-                fastn_core::config::utils::get_clean_url(&config, path.as_str())?;
-            let package_name = package_name.unwrap_or_else(|| config.package.name.to_string());
-
-            let host = if let Some(port) = url.port() {
-                format!("{}://{}:{}", url.scheme(), url.host_str().unwrap(), port)
-            } else {
-                format!("{}://{}", url.scheme(), url.host_str().unwrap())
-            };
-            let req = if let Some(r) = config.request {
-                r
+            let req = if let Some(ref r) = config.request {
+                r.clone()
             } else {
                 tracing::error!(msg = "request not set");
                 return Ok(fastn_core::server_error!("request not set"));
             };
 
-            // TODO: read app config and send them to service as header
-            // Adjust x-fastn header from based on the platform and the requested field
-            if let Some(user_id) = conf.get("user-id") {
-                match user_id.split_once('-') {
-                    Some((platform, requested_field)) => {
-                        if let Some(user_data) = fastn_core::auth::get_user_data_from_cookies(
-                            platform,
-                            requested_field,
-                            req.cookies(),
-                        )
-                        .await?
-                        {
-                            conf.insert("X-FASTN-USER-ID".to_string(), user_data);
-                        }
-                    }
-                    _ => return Ok(fastn_core::unauthorised!("invalid user-id provided")),
-                }
-            }
-
-            return fastn_core::proxy::get_out(
-                host.as_str(),
-                req,
-                url.path(),
-                package_name.as_str(),
-                &conf,
-            )
-            .await;
+            let upstream = match fastn_core::proxy::upstream(&config, path.as_str(), &req).await? {
+                Ok(upstream) => upstream,
+                Err(response) => return Ok(response),
+            };
+            return fastn_core::proxy::get_out(upstream, req).await;
         }
 
         // Fallback to WASM execution in case of no successful response
@@ -404,6 +451,44 @@ pub async fn serve(
     })
 }
 
+/// The path is passed to an end-point by `serve()` when it is none of FASTN.ftd, a document, a
+/// proxy template or a redirect, this makes the same checks without rendering the document.
+async fn resolves_to_end_point(
+    config: &mut fastn_core::Config,
+    path: &camino::Utf8Path,
+    req: &fastn_core::http::Request,
+) -> fastn_core::Result<bool> {
+    if path.as_str() == "FASTN.ftd"
+        || path.as_str().is_empty()
+        || fastn_core::utils::spa_fragment_page(path.as_str()).is_some()
+        || fastn_core::cr::get_cr_path_from_url(path.as_str()).is_some()
+        || fastn_core::package::proxy_template::find_proxy_template(
+            config.package.proxy_templates.as_slice(),
+            req.path(),
+        )
+        .is_some()
+        || handle_redirect(config, path).is_some()
+    {
+        return Ok(false);
+    }
+
+    // redirected to the mount-point of the app
+    if req.method() == "GET"
+        && !fastn_core::file::is_static(path.as_str())?
+        && config.package.apps.iter().any(|app| {
+            fastn_core::config::utils::trim_package_name(path.as_str(), app.package.name.as_str())
+                .is_some()
+        })
+    {
+        return Ok(false);
+    }
+
+    Ok(config
+        .get_file_and_package_by_id(path.as_str())
+        .await
+        .is_err())
+}
+
 pub(crate) async fn download_init_package(url: Option<String>) -> std::io::Result<()> {
     let mut package = fastn_core::Package::new("unknown-package");
     package.download_base_url = url;
@@ -537,19 +622,29 @@ pub async fn create_cr_page(
     fastn_core::apis::cr::create_cr_page(req).await
 }
 
+pub(crate) async fn debug_requests(
+    req: fastn_core::http::Request,
+    dev_mode: bool,
+) -> fastn_core::Result<fastn_core::http::Response> {
+    let _lock = LOCK.read().await;
+    fastn_core::apis::debug::requests(&req, dev_mode).await
+}
+
 struct AppData {
     edition: Option<String>,
     external_js: Vec<String>,
     inline_js: Vec<String>,
     external_css: Vec<String>,
     inline_css: Vec<String>,
-    package_name: String,
+    /// server is only reachable from this machine, admin only pages are open to everyone
+    dev_mode: bool,
+    /// `fastn.cache` policies from FASTN.ftd
+    cache: Vec<fastn_core::package::cache::CachePolicy>,
+    /// `fastn.rate-limit` from FASTN.ftd
+    rate_limits: Vec<fastn_core::rate_limit::RateLimit>,
 }
 
-fn handle_default_route(
-    req: &actix_web::HttpRequest,
-    package_name: &str,
-) -> Option<fastn_core::http::Response> {
+fn handle_default_route(req: &actix_web::HttpRequest) -> Option<fastn_core::http::Response> {
     if req
         .path()
         .ends_with(fastn_core::utils::hashed_default_css_name())
@@ -574,12 +669,22 @@ fn handle_default_route(
         );
     } else if req
         .path()
-        .ends_with(fastn_core::utils::hashed_default_ftd_js(package_name))
+        .ends_with(fastn_core::utils::hashed_default_ftd_js())
+    {
+        return Some(
+            actix_web::HttpResponse::Ok()
+                .content_type(mime_guess::mime::TEXT_JAVASCRIPT)
+                .body(ftd::js::all_js_without_test()),
+        );
+    } else if let Some(js) = req
+        .path()
+        .split_once("/-/js/")
+        .and_then(|(_, file_name)| fastn_core::utils::cached_js_file(file_name))
     {
         return Some(
             actix_web::HttpResponse::Ok()
                 .content_type(mime_guess::mime::TEXT_JAVASCRIPT)
-                .body(ftd::js::all_js_without_test(package_name)),
+                .body(js),
         );
     } else if req
         .path()
@@ -636,17 +741,200 @@ async fn test() -> fastn_core::Result<fastn_core::http::Response> {
     Ok(actix_web::HttpResponse::Ok().finish())
 }
 
-#[tracing::instrument(skip_all)]
+#[tracing::instrument(skip_all, fields(method = req.method().as_str(), path = req.path()))]
 async fn route(
     req: actix_web::HttpRequest,
-    body: actix_web::web::Bytes,
+    payload: actix_web::web::Payload,
     app_data: actix_web::web::Data<AppData>,
 ) -> fastn_core::Result<fastn_core::http::Response> {
     tracing::info!(method = req.method().as_str(), uri = req.path());
 
-    let package_name = &app_data.package_name;
+    let start = std::time::Instant::now();
+    let method = req.method().to_string();
+    let route = fastn_core::metrics::route_label(req.path());
+
+    let response = if fastn_core::proxy::is_streaming(&req) && !handled_by_fastn(&req) {
+        // the body is passed along as it arrives if the path resolves to an end-point, see
+        // `serve()`, its size is checked by `proxy::stream()`
+        match rate_limited(&req, Default::default(), &app_data).await {
+            Some(r) => Ok(r),
+            None => {
+                let max_body_size = fastn_core::rate_limit::find_rate_limit(
+                    app_data.rate_limits.as_slice(),
+                    req.path(),
+                )
+                .and_then(|l| l.max_body_size);
+                let stream = Stream {
+                    req: req.clone(),
+                    payload,
+                    max_body_size,
+                };
+                dispatch(req, Default::default(), Some(stream), app_data).await
+            }
+        }
+    } else {
+        match read_body(payload).await {
+            Ok(body) => match rate_limited(&req, body.clone(), &app_data).await {
+                Some(r) => Ok(r),
+                None => cached(req, body, app_data).await,
+            },
+            Err(r) => Ok(r),
+        }
+    };
+
+    let status = match response {
+        Ok(ref r) => r.status().as_u16(),
+        Err(_) => 500,
+    };
+    fastn_core::metrics::observe(route, method.as_str(), status, start.elapsed());
+    response
+}
+
+/// Same as the default `Bytes` extractor limit, bigger bodies must be streamed (chunked), and
+/// are then passed along to the end-point without buffering.
+const MAX_BUFFERED_BODY_SIZE: usize = 256 * 1024;
+
+async fn read_body(
+    mut payload: actix_web::web::Payload,
+) -> Result<actix_web::web::Bytes, fastn_core::http::Response> {
+    use futures::StreamExt;
+
+    let mut body = actix_web::web::BytesMut::new();
+    while let Some(chunk) = payload.next().await {
+        let chunk = match chunk {
+            Ok(c) => c,
+            Err(e) => return Err(actix_web::HttpResponse::BadRequest().body(e.to_string())),
+        };
+        if body.len() + chunk.len() > MAX_BUFFERED_BODY_SIZE {
+            return Err(actix_web::HttpResponse::PayloadTooLarge().body(format!(
+                "request body is larger than {} bytes",
+                MAX_BUFFERED_BODY_SIZE
+            )));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body.freeze())
+}
+
+async fn rate_limited(
+    req: &actix_web::HttpRequest,
+    body: actix_web::web::Bytes,
+    app_data: &AppData,
+) -> Option<fastn_core::http::Response> {
+    let limit =
+        fastn_core::rate_limit::find_rate_limit(app_data.rate_limits.as_slice(), req.path())?;
+    fastn_core::rate_limit::check(
+        limit,
+        &fastn_core::http::Request::from_actix(req.clone(), body),
+    )
+    .await
+}
+
+/// A WebSocket upgrade or a chunked upload, see `fastn_core::proxy::is_streaming()`, whose body
+/// is passed along as it arrives if the request goes to an end-point.
+pub struct Stream {
+    req: actix_web::HttpRequest,
+    payload: actix_web::web::Payload,
+    max_body_size: Option<usize>,
+}
+
+async fn cached(
+    req: actix_web::HttpRequest,
+    body: actix_web::web::Bytes,
+    app_data: actix_web::web::Data<AppData>,
+) -> fastn_core::Result<fastn_core::http::Response> {
+    if !matches!(
+        req.method(),
+        &actix_web::http::Method::GET | &actix_web::http::Method::HEAD
+    ) {
+        return dispatch(req, body, None, app_data).await;
+    }
 
-    if let Some(default_response) = handle_default_route(&req, package_name.as_str()) {
+    let cache_control =
+        fastn_core::package::cache::find_cache_policy(app_data.cache.as_slice(), req.path())
+            .to_string();
+    let if_none_match = req
+        .headers()
+        .get(actix_web::http::header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .map(ToString::to_string);
+    let if_modified_since = req
+        .headers()
+        .get(actix_web::http::header::IF_MODIFIED_SINCE)
+        .and_then(|v| v.to_str().ok())
+        .map(ToString::to_string);
+
+    let response = dispatch(req, body, None, app_data).await?;
+    Ok(fastn_core::http::with_cache_headers(
+        response,
+        if_none_match.as_deref(),
+        if_modified_since.as_deref(),
+        cache_control.as_str(),
+    ))
+}
+
+/// The requests fastn handles itself, a streamed body of these is read before they are handled.
+fn handled_by_fastn(req: &actix_web::HttpRequest) -> bool {
+    handle_default_route(req).is_some()
+        || req.path().starts_with("/auth/")
+        || fastn_route(req.method().as_str().to_lowercase().as_str(), req.path()).is_some()
+}
+
+/// The routes of fastn, the other paths are documents, or are passed to the end-points of the
+/// apps, see `serve()`.
+#[derive(Debug, PartialEq)]
+enum FastnRoute {
+    Sync,
+    Sync2,
+    Clone,
+    ViewSource,
+    EditSource,
+    Edit,
+    Revert,
+    EditorSync,
+    CreateCr,
+    CreateCrPage,
+    ClearCache,
+    Poll,
+    Health,
+    Ready,
+    Metrics,
+    DebugRequests,
+    Favicon,
+    Test,
+}
+
+fn fastn_route(method: &str, path: &str) -> Option<FastnRoute> {
+    Some(match (method, path) {
+        ("post", "/-/sync/") if cfg!(feature = "remote") => FastnRoute::Sync,
+        ("post", "/-/sync2/") if cfg!(feature = "remote") => FastnRoute::Sync2,
+        ("get", "/-/clone/") if cfg!(feature = "remote") => FastnRoute::Clone,
+        ("get", t) if t.starts_with("/-/view-src/") => FastnRoute::ViewSource,
+        ("get", t) if t.starts_with("/-/edit-src/") => FastnRoute::EditSource,
+        ("post", "/-/edit/") => FastnRoute::Edit,
+        ("post", "/-/revert/") => FastnRoute::Revert,
+        ("get", "/-/editor-sync/") => FastnRoute::EditorSync,
+        ("post", "/-/create-cr/") => FastnRoute::CreateCr,
+        ("get", "/-/create-cr-page/") => FastnRoute::CreateCrPage,
+        ("get", "/-/clear-cache/") => FastnRoute::ClearCache,
+        ("get", "/-/poll/") => FastnRoute::Poll,
+        ("get", "/-/health/" | "/-/health") => FastnRoute::Health,
+        ("get", "/-/ready/" | "/-/ready") => FastnRoute::Ready,
+        ("get", "/-/metrics/" | "/-/metrics") => FastnRoute::Metrics,
+        ("get", t) if t.starts_with("/-/debug/requests/") => FastnRoute::DebugRequests,
+        ("get", "/favicon.ico") => FastnRoute::Favicon,
+        ("get", "/test/") => FastnRoute::Test,
+        _ => return None,
+    })
+}
+
+async fn dispatch(
+    req: actix_web::HttpRequest,
+    body: actix_web::web::Bytes,
+    stream: Option<Stream>,
+    app_data: actix_web::web::Data<AppData>,
+) -> fastn_core::Result<fastn_core::http::Response> {
+    if let Some(default_response) = handle_default_route(&req) {
         return Ok(default_response);
     }
 
@@ -661,25 +949,31 @@ async fn route(
         )
         .await;
     }
+    let route = fastn_route(req.method().as_str().to_lowercase().as_str(), req.path());
     let req = fastn_core::http::Request::from_actix(req, body);
-    match (req.method().to_lowercase().as_str(), req.path()) {
-        ("post", "/-/sync/") if cfg!(feature = "remote") => sync(req).await,
-        ("post", "/-/sync2/") if cfg!(feature = "remote") => sync2(req).await,
-        ("get", "/-/clone/") if cfg!(feature = "remote") => clone(req).await,
-        ("get", t) if t.starts_with("/-/view-src/") => view_source(req).await,
-        ("get", t) if t.starts_with("/-/edit-src/") => edit_source(req).await,
-        ("post", "/-/edit/") => edit(req).await,
-        ("post", "/-/revert/") => revert(req).await,
-        ("get", "/-/editor-sync/") => editor_sync(req).await,
-        ("post", "/-/create-cr/") => create_cr(req).await,
-        ("get", "/-/create-cr-page/") => create_cr_page(req).await,
-        ("get", "/-/clear-cache/") => clear_cache(req).await,
-        ("get", "/-/poll/") => fastn_core::watcher::poll().await,
-        ("get", "/favicon.ico") => favicon().await,
-        ("get", "/test/") => test().await,
-        (_, _) => {
+    match route {
+        Some(FastnRoute::Sync) => sync(req).await,
+        Some(FastnRoute::Sync2) => sync2(req).await,
+        Some(FastnRoute::Clone) => clone(req).await,
+        Some(FastnRoute::ViewSource) => view_source(req).await,
+        Some(FastnRoute::EditSource) => edit_source(req).await,
+        Some(FastnRoute::Edit) => edit(req).await,
+        Some(FastnRoute::Revert) => revert(req).await,
+        Some(FastnRoute::EditorSync) => editor_sync(req).await,
+        Some(FastnRoute::CreateCr) => create_cr(req).await,
+        Some(FastnRoute::CreateCrPage) => create_cr_page(req).await,
+        Some(FastnRoute::ClearCache) => clear_cache(req).await,
+        Some(FastnRoute::Poll) => fastn_core::watcher::poll().await,
+        Some(FastnRoute::Health) => fastn_core::apis::health::health().await,
+        Some(FastnRoute::Ready) => fastn_core::apis::health::ready().await,
+        Some(FastnRoute::Metrics) => fastn_core::apis::health::metrics().await,
+        Some(FastnRoute::DebugRequests) => debug_requests(req, app_data.dev_mode).await,
+        Some(FastnRoute::Favicon) => favicon().await,
+        Some(FastnRoute::Test) => test().await,
+        None => {
             serve(
                 req,
+                stream,
                 app_data.edition.clone(),
                 app_data.external_js.clone(),
                 app_data.inline_js.clone(),
@@ -701,11 +995,24 @@ pub async fn listen(
     inline_js: Vec<String>,
     external_css: Vec<String>,
     inline_css: Vec<String>,
-    package_name: String,
+    profile: bool,
+    tls: Option<fastn_core::tls::Tls>,
+    shutdown_grace: u64,
+    shutdown_timeout: u64,
 ) -> fastn_core::Result<()> {
-    use colored::Colorize;
     env_logger::init_from_env(env_logger::Env::new().default_filter_or("info"));
 
+    if profile {
+        fastn_observer::record(
+            fastn_core::apis::debug::REQUEST_SPAN,
+            fastn_core::apis::debug::PROFILED_REQUESTS,
+        );
+    }
+    let dev_mode = match bind_address.parse::<std::net::IpAddr>() {
+        Ok(ip) => ip.is_loopback(),
+        Err(_) => bind_address == "localhost",
+    };
+
     if package_download_base_url.is_some() {
         download_init_package(package_download_base_url).await?;
     }
@@ -724,23 +1031,19 @@ pub async fn listen(
         }
     }
 
+    let package = fastn_core::Config::read(None, false, None).await?.package;
+    let cache = package.cache;
+    let rate_limits = package.rate_limits;
+    // `--tls-cert` and `--tls-key` take precedence over `fastn.tls` in FASTN.ftd
+    let tls = tls.or(package.tls);
+
     let tcp_listener = match fastn_core::http::get_available_port(port, bind_address) {
         Some(listener) => listener,
         None => {
-            eprintln!(
-                "{}",
-                port.map(|x| format!(
-                    r#"Provided port {} is not available.
-
-You can try without providing port, it will automatically pick unused port."#,
-                    x.to_string().red()
-                ))
-                .unwrap_or_else(|| {
-                    "Tried picking port between port 8000 to 9000, none are available :-("
-                        .to_string()
-                })
-            );
-            std::process::exit(2);
+            return Err(fastn_core::Error::PortNotAvailable {
+                bind: bind_address.to_string(),
+                port,
+            })
         }
     };
 
@@ -752,8 +1055,11 @@ You can try without providing port, it will automatically pick unused port."#,
                 inline_js: inline_js.clone(),
                 external_css: external_css.clone(),
                 inline_css: inline_css.clone(),
-                package_name: package_name.clone(),
+                dev_mode,
+                cache: cache.clone(),
+                rate_limits: rate_limits.clone(),
             }))
+            .wrap(actix_web::middleware::Compress::default())
             .wrap(
                 actix_web::middleware::Logger::new(
                     r#""%r" %Ts %s %b %a "%{Referer}i" "%{User-Agent}i""#,
@@ -763,19 +1069,98 @@ You can try without providing port, it will automatically pick unused port."#,
             .route("/{path:.*}", actix_web::web::route().to(route))
     };
 
+    let scheme = if tls.is_some() { "https" } else { "http" };
+    let port = tcp_listener.local_addr()?.port();
+
     println!("### Server Started ###");
-    println!(
-        "Go to: http://{}:{}",
-        bind_address,
-        tcp_listener.local_addr()?.port()
-    );
-    actix_web::HttpServer::new(app)
-        .listen(tcp_listener)?
-        .run()
-        .await?;
+    println!("Go to: {}://{}:{}", scheme, bind_address, port);
+    if profile {
+        println!(
+            "Request profiles: {}://{}:{}/-/debug/requests/",
+            scheme, bind_address, port
+        );
+    }
+
+    fastn_core::apis::health::warmup();
+
+    // on SIGTERM we fail the readiness check for `shutdown_grace` seconds before stopping the
+    // server, which then waits `shutdown_timeout` seconds for in-flight requests
+    let server = actix_web::HttpServer::new(app)
+        .shutdown_timeout(shutdown_timeout)
+        .disable_signals();
+    let tls = match tls {
+        Some(tls) => tls,
+        None => {
+            let server = server.listen(tcp_listener)?.run();
+            fastn_core::apis::health::shutdown_on_signal(shutdown_grace, vec![server.handle()]);
+            server.await?;
+            return Ok(());
+        }
+    };
+
+    let (config, resolver) = fastn_core::tls::server_config(&tls)?;
+    fastn_core::tls::reload_on_sighup(resolver);
+    let https = server.listen_rustls_0_21(tcp_listener, config)?.run();
+
+    match tls.redirect_port {
+        Some(redirect_port) => {
+            let listener =
+                match fastn_core::http::get_available_port(Some(redirect_port), bind_address) {
+                    Some(listener) => listener,
+                    None => {
+                        return Err(fastn_core::Error::PortNotAvailable {
+                            bind: bind_address.to_string(),
+                            port: Some(redirect_port),
+                        })
+                    }
+                };
+            println!(
+                "Redirecting http://{}:{} to https",
+                bind_address, redirect_port
+            );
+            let redirect = fastn_core::tls::redirect_server(listener, port)?;
+            fastn_core::apis::health::shutdown_on_signal(
+                shutdown_grace,
+                vec![https.handle(), redirect.handle()],
+            );
+            futures::try_join!(https, redirect)?;
+        }
+        None => {
+            fastn_core::apis::health::shutdown_on_signal(shutdown_grace, vec![https.handle()]);
+            https.await?
+        }
+    }
     Ok(())
 }
 
 // cargo install --features controller --path=.
 // FASTN_CONTROLLER=http://127.0.0.1:8000 FASTN_INSTANCE_ID=12345 fastn serve 8001
 // TRACING=INFO fastn serve
+
+#[cfg(test)]
+mod test {
+    fn chunked_post(path: &str) -> actix_web::HttpRequest {
+        actix_web::test::TestRequest::post()
+            .uri(path)
+            .insert_header((actix_web::http::header::TRANSFER_ENCODING, "chunked"))
+            .to_http_request()
+    }
+
+    #[test]
+    fn handled_by_fastn() {
+        let edit = chunked_post("/-/edit/");
+        assert!(fastn_core::proxy::is_streaming(&edit));
+        assert!(super::handled_by_fastn(&edit));
+        assert_eq!(
+            super::fastn_route("post", edit.path()),
+            Some(super::FastnRoute::Edit)
+        );
+
+        assert!(super::handled_by_fastn(&chunked_post("/auth/logout/")));
+        assert!(super::handled_by_fastn(&chunked_post("/-/create-cr/")));
+        assert!(!super::handled_by_fastn(&chunked_post(
+            "/todos/api/upload/"
+        )));
+        assert!(!super::handled_by_fastn(&chunked_post("/api/upload/")));
+    }
+}
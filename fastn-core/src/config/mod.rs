@@ -35,17 +35,27 @@ pub struct Config {
     pub original_directory: camino::Utf8PathBuf,
     pub all_packages: std::cell::RefCell<std::collections::BTreeMap<String, fastn_core::Package>>,
     pub downloaded_assets: std::collections::BTreeMap<String, String>,
+    /// Images processed by `fastn.responsive-images` in this build, keyed by `<package>/<path>`
+    pub processed_images:
+        std::collections::BTreeMap<String, Option<fastn_core::responsive_image::ProcessedImage>>,
     pub global_ids: std::collections::HashMap<String, String>,
     pub named_parameters: Vec<(String, ftd::Value)>,
     pub extra_data: std::collections::BTreeMap<String, String>,
     pub current_document: Option<String>,
     pub dependencies_during_render: Vec<String>,
     pub request: Option<fastn_core::http::Request>, // TODO: It should only contain reference
+    /// JSON returned by the end-point of a `fastn.proxy-template`, read by `proxy-data` processor
+    pub proxy_data: Option<serde_json::Value>,
     pub ftd_edition: FTDEdition,
     pub ftd_external_js: Vec<String>,
     pub ftd_inline_js: Vec<String>,
     pub ftd_external_css: Vec<String>,
     pub ftd_inline_css: Vec<String>,
+    /// Emit minified JS with source maps, `fastn build --minify`, else the readable JS
+    pub minify_js: bool,
+    /// Fragment of the page rendered last, when `spa` of `fastn.package` is on, see
+    /// `fastn_core::package::package_doc::SpaFragment`
+    pub spa_fragment: Option<String>,
 }
 
 impl Config {
@@ -1175,9 +1185,8 @@ impl Config {
         let fastn_manifest_path = match utils::find_root_for_file(directory, "fastn.manifest.ftd") {
             Some(fastn_manifest_path) => fastn_manifest_path,
             None => {
-                return Err(fastn_core::Error::UsageError {
-                    message: "FASTN.ftd or fastn.manifest.ftd not found in any parent directory"
-                        .to_string(),
+                return Err(fastn_core::Error::FastnFtdNotFound {
+                    directory: directory.to_string(),
                 });
             }
         };
@@ -1246,6 +1255,12 @@ impl Config {
         config
     }
 
+    pub fn add_minify_js(self, minify_js: bool) -> Self {
+        let mut config = self;
+        config.minify_js = minify_js;
+        config
+    }
+
     /// `read()` is the way to read a Config.
     #[tracing::instrument(name = "Config::read", skip_all)]
     pub async fn read(
@@ -1283,15 +1298,19 @@ impl Config {
             current_document: None,
             all_packages: Default::default(),
             downloaded_assets: Default::default(),
+            processed_images: Default::default(),
             extra_data: Default::default(),
             global_ids: Default::default(),
             request: req.map(ToOwned::to_owned),
+            proxy_data: None,
             named_parameters: vec![],
             ftd_edition: FTDEdition::default(),
             ftd_external_js: Default::default(),
             ftd_inline_js: Default::default(),
             ftd_external_css: Default::default(),
             ftd_inline_css: Default::default(),
+            minify_js: false,
+            spa_fragment: None,
             dependencies_during_render: Default::default(),
         };
 
@@ -36,12 +36,19 @@ pub async fn interpret_helper<'a>(
 ) -> ftd::interpreter::Result<ftd::interpreter::Document> {
     tracing::info!(document = name);
     let doc = cached_parse(name, source, line_number)?;
-    let mut s = ftd::interpreter::interpret_with_line_number(name, doc, line_number)?;
+    // the JS of the imported modules is split out of the pages, it has all their things
+    let mut s = if lib.config.package.split_js {
+        ftd::interpreter::interpret_with_all_imported_things(name, doc, line_number)?
+    } else {
+        ftd::interpreter::interpret_with_line_number(name, doc, line_number)?
+    };
     lib.module_package_map.insert(
         name.trim_matches('/').to_string(),
         lib.config.package.name.to_string(),
     );
-    let document;
+    let mut document;
+    // the variables processed by `page-toc`, they get the headings of the interpreted document
+    let mut page_tocs = vec![];
     loop {
         match s {
             ftd::interpreter::Interpreter::Done { document: doc } => {
@@ -81,6 +88,9 @@ pub async fn interpret_helper<'a>(
                     },
                 )?;
                 let line_number = ast.line_number();
+                if processor.eq("page-toc") {
+                    page_tocs.push((doc.to_string(), ast.clone()));
+                }
                 let value = lib
                     .process(
                         ast.clone(),
@@ -109,6 +119,7 @@ pub async fn interpret_helper<'a>(
             }
         }
     }
+    fastn_core::library2022::processor::page_toc::set_headings(&mut document, page_tocs)?;
     Ok(document)
 }
 
@@ -158,6 +169,7 @@ pub async fn resolve_import<'a>(
 }
 
 // source, foreign_variable, foreign_function
+#[tracing::instrument(skip(lib, _state))]
 pub async fn resolve_import_2022<'a>(
     lib: &'a mut fastn_core::Library2022,
     _state: &mut ftd::interpreter::InterpreterState,
@@ -185,6 +197,7 @@ pub async fn resolve_import_2022<'a>(
                 "http".to_string(),
                 "get-data".to_string(),
                 "toc".to_string(),
+                "page-toc".to_string(),
                 "sitemap".to_string(),
                 "full-sitemap".to_string(),
                 "request-data".to_string(),
@@ -205,6 +218,7 @@ pub async fn resolve_import_2022<'a>(
                 "package-tree".to_string(),
                 "fetch-file".to_string(),
                 "query".to_string(),
+                "proxy-data".to_string(),
             ],
             0,
         )
@@ -254,6 +268,7 @@ pub async fn resolve_import_2022<'a>(
                 "package-query".to_string(),
                 "pg".to_string(),
                 "toc".to_string(),
+                "page-toc".to_string(),
                 "include".to_string(),
                 "get-data".to_string(),
                 "sitemap".to_string(),
@@ -487,6 +502,42 @@ pub async fn resolve_foreign_variable2022(
                         text: dark_mode.trim_start_matches('/').to_string(),
                     });
                 }
+
+                let (light_processed, dark_processed) = if download_assets {
+                    let light_processed = fastn_core::responsive_image::for_asset(
+                        &mut lib.config,
+                        package,
+                        light_path.as_str(),
+                    )
+                    .await
+                    .map_err(|e| ftd::ftd2021::p1::Error::ParseError {
+                        message: e.to_string(),
+                        doc_id: lib.document_id.to_string(),
+                        line_number: 0,
+                    })?;
+                    let dark_processed = if dark_mode == light_mode {
+                        light_processed.clone()
+                    } else {
+                        fastn_core::responsive_image::for_asset(
+                            &mut lib.config,
+                            package,
+                            dark_path.as_str(),
+                        )
+                        .await
+                        .map_err(|e| {
+                            ftd::ftd2021::p1::Error::ParseError {
+                                message: e.to_string(),
+                                doc_id: lib.document_id.to_string(),
+                                line_number: 0,
+                            }
+                        })?
+                    };
+                    (light_processed, dark_processed)
+                } else {
+                    (None, None)
+                };
+                let prefix = format!("-/{}", package.name);
+
                 #[allow(deprecated)]
                 Ok(ftd::interpreter::Value::Record {
                     name: "ftd#image-src".to_string(),
@@ -507,6 +558,26 @@ pub async fn resolve_foreign_variable2022(
                                 line_number: 0,
                             },
                         ),
+                        (
+                            "srcset".to_string(),
+                            optional_string(light_processed.as_ref().map(|p| p.srcset(&prefix))),
+                        ),
+                        (
+                            "dark-srcset".to_string(),
+                            optional_string(dark_processed.as_ref().map(|p| p.srcset(&prefix))),
+                        ),
+                        (
+                            "width".to_string(),
+                            optional_integer(light_processed.as_ref().map(|p| p.width as i64)),
+                        ),
+                        (
+                            "height".to_string(),
+                            optional_integer(light_processed.as_ref().map(|p| p.height as i64)),
+                        ),
+                        (
+                            "placeholder".to_string(),
+                            optional_string(light_processed.and_then(|p| p.placeholder)),
+                        ),
                     ])
                     .collect(),
                 })
@@ -533,6 +604,28 @@ pub async fn resolve_foreign_variable2022(
     }
 }
 
+fn optional_string(text: Option<String>) -> ftd::interpreter::PropertyValue {
+    ftd::interpreter::PropertyValue::Value {
+        value: ftd::interpreter::Value::Optional {
+            data: Box::new(text.map(|text| ftd::interpreter::Value::String { text })),
+            kind: ftd::interpreter::Kind::string().into_kind_data(),
+        },
+        is_mutable: false,
+        line_number: 0,
+    }
+}
+
+fn optional_integer(value: Option<i64>) -> ftd::interpreter::PropertyValue {
+    ftd::interpreter::PropertyValue::Value {
+        value: ftd::interpreter::Value::Optional {
+            data: Box::new(value.map(|value| ftd::interpreter::Value::Integer { value })),
+            kind: ftd::interpreter::Kind::integer().into_kind_data(),
+        },
+        is_mutable: false,
+        line_number: 0,
+    }
+}
+
 async fn download(
     lib: &mut fastn_core::Library2022,
     download_assets: bool,
@@ -72,6 +72,20 @@ pub enum Error {
     #[error("GenericError: {}", _0)]
     GenericError(String),
 
+    #[error("FastnFtdNotFound: FASTN.ftd or fastn.manifest.ftd not found in {directory} or any parent directory")]
+    FastnFtdNotFound { directory: String },
+
+    #[error(
+        "DependencyNotFound: Unable to find the FASTN.ftd for the dependency package: {package}"
+    )]
+    DependencyNotFound { package: String },
+
+    #[error("PortNotAvailable: {bind}:{port:?}")]
+    PortNotAvailable { bind: String, port: Option<u16> },
+
+    #[error("TlsError: {message}")]
+    TlsError { message: String },
+
     #[error("GroupNotFound: id: {id}, {message}")]
     GroupNotFound { id: String, message: String },
 
@@ -98,4 +112,226 @@ impl Error {
     pub fn generic_err<T: AsRef<str> + ToString, O>(error: T) -> fastn_core::Result<O> {
         Err(Self::generic(error))
     }
+
+    /// The friendly version of this error, shown by the `fastn` cli.
+    pub fn report(&self) -> fastn_issues::reporter::Report {
+        use fastn_issues::reporter::Report;
+
+        let report = match self {
+            Error::FastnFtdNotFound { directory } => Report::new(
+                "no FASTN.ftd file",
+                format!(
+                    "We could not find FASTN.ftd or fastn.manifest.ftd in {} or any of its parent \
+                    directories, so this does not look like a fastn package.",
+                    directory
+                ),
+            )
+            .with_recommendation(
+                "Run `fastn create-package <name>` to create a new fastn package, or `cd` into \
+                an existing one.",
+            ),
+            Error::DependencyNotFound { package } => Report::new(
+                "unknown dependency",
+                format!(
+                    "We could not download FASTN.ftd of the dependency `{}`, it is neither \
+                    available over https nor over http.",
+                    package
+                ),
+            )
+            .with_file("FASTN.ftd", None)
+            .with_recommendation(
+                "Check the spelling of the package name in `fastn.dependency`, and that you are \
+                connected to the internet.",
+            ),
+            Error::PortNotAvailable {
+                bind,
+                port: Some(port),
+            } => Report::new(
+                "port in use",
+                format!(
+                    "Port {} on {} is being used by some other program.",
+                    port, bind
+                ),
+            )
+            .with_recommendation(
+                "Stop the other program, or run `fastn serve` without `--port`, it will \
+                automatically pick an unused port.",
+            ),
+            Error::PortNotAvailable { bind, port: None } => Report::new(
+                "port in use",
+                format!(
+                    "We tried every port between 8000 and 9000 on {}, none of them are available.",
+                    bind
+                ),
+            )
+            .with_recommendation("Pass an available port with `fastn serve --port <port>`."),
+            Error::TlsError { message } => Report::new("tls error", message.as_str())
+                .with_recommendation(
+                    "Check `--tls-cert` and `--tls-key` (or `fastn.tls` in FASTN.ftd), both \
+                    must be PEM files.",
+                ),
+            Error::UsageError { message } => Report::new("usage error", message.as_str()),
+            Error::PackageError { message } => Report::new("package error", message.as_str()),
+            Error::NotFound(message) => Report::new("not found", message.as_str()),
+            Error::HttpError(e) => Report::new("network error", e.to_string())
+                .with_recommendation("Check that you are connected to the internet."),
+            Error::IoError(e) => Report::new("io error", e.to_string()),
+            _ => Report::new("error", self.to_string()),
+        };
+
+        match self.ftd_location() {
+            Some((doc_id, line_number)) => Report::new("ftd error", self.to_string())
+                .with_file(doc_id, Some(line_number))
+                .with_recommendation(
+                    "Fix the ftd syntax at the line mentioned above, the ftd documentation is \
+                    at https://fastn.com/ftd/.",
+                ),
+            None => report,
+        }
+    }
+
+    /// Document and line number, for errors raised while processing a ftd document.
+    fn ftd_location(&self) -> Option<(String, usize)> {
+        match self {
+            Error::FTDError(e) => ftd2021_p1_location(e),
+            Error::FTDP1Error(e) => p1_location(e),
+            Error::FTDAstError(e) => ast_location(e),
+            Error::FTDInterpreterError(e) => interpreter_location(e),
+            Error::FTDExecError(ftd::executor::Error::InterpreterError(e))
+            | Error::FTDHtmlError(ftd::html::Error::InterpreterError(e)) => interpreter_location(e),
+            Error::FTDExecError(ftd::executor::Error::ParseError {
+                doc_id,
+                line_number,
+                ..
+            })
+            | Error::FTDHtmlError(ftd::html::Error::ParseError {
+                doc_id,
+                line_number,
+                ..
+            }) => Some((doc_id.to_string(), *line_number)),
+            _ => None,
+        }
+    }
+}
+
+fn p1_location(e: &ftd::p1::Error) -> Option<(String, usize)> {
+    use ftd::p1::Error;
+
+    match e {
+        Error::SectionNotFound {
+            doc_id,
+            line_number,
+        }
+        | Error::MoreThanOneCaption {
+            doc_id,
+            line_number,
+        }
+        | Error::ParseError {
+            doc_id,
+            line_number,
+            ..
+        }
+        | Error::MoreThanOneHeader {
+            doc_id,
+            line_number,
+            ..
+        }
+        | Error::HeaderNotFound {
+            doc_id,
+            line_number,
+            ..
+        }
+        | Error::ForbiddenUsage {
+            doc_id,
+            line_number,
+            ..
+        }
+        | Error::NotFound {
+            doc_id,
+            line_number,
+            ..
+        }
+        | Error::MoreThanOneSubSections {
+            doc_id,
+            line_number,
+            ..
+        } => Some((doc_id.to_string(), *line_number)),
+        Error::Serde { .. } | Error::Syntect { .. } => None,
+    }
+}
+
+fn ftd2021_p1_location(e: &ftd::ftd2021::p1::Error) -> Option<(String, usize)> {
+    use ftd::ftd2021::p1::Error;
+
+    match e {
+        Error::ParseError {
+            doc_id,
+            line_number,
+            ..
+        }
+        | Error::UnknownData {
+            doc_id,
+            line_number,
+            ..
+        }
+        | Error::MissingData {
+            doc_id,
+            line_number,
+            ..
+        }
+        | Error::ForbiddenUsage {
+            doc_id,
+            line_number,
+            ..
+        }
+        | Error::NotFound {
+            doc_id,
+            line_number,
+            ..
+        }
+        | Error::MoreThanOneSubSections {
+            doc_id,
+            line_number,
+            ..
+        } => Some((doc_id.to_string(), *line_number)),
+        Error::Serde { .. } | Error::Syntect { .. } => None,
+    }
+}
+
+fn ast_location(e: &ftd::ast::Error) -> Option<(String, usize)> {
+    match e {
+        ftd::ast::Error::P1(e) => p1_location(e),
+        ftd::ast::Error::Parse {
+            doc_id,
+            line_number,
+            ..
+        } => Some((doc_id.to_string(), *line_number)),
+        ftd::ast::Error::ParseBool(_) => None,
+    }
+}
+
+fn interpreter_location(e: &ftd::interpreter::Error) -> Option<(String, usize)> {
+    use ftd::interpreter::Error;
+
+    match e {
+        Error::P1Error(e) => p1_location(e),
+        Error::OldP1Error(e) => ftd2021_p1_location(e),
+        Error::ASTError(e) => ast_location(e),
+        Error::InvalidKind {
+            doc_id,
+            line_number,
+            ..
+        }
+        | Error::ValueNotFound {
+            doc_id,
+            line_number,
+            ..
+        }
+        | Error::ParseError {
+            doc_id,
+            line_number,
+            ..
+        } => Some((doc_id.to_string(), *line_number)),
+        _ => None,
+    }
 }
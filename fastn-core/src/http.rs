@@ -73,6 +73,94 @@ pub fn ok_with_content_type(
         .body(data)
 }
 
+/// Adds a weak, content hash based `ETag` and a `Cache-Control` header to successful responses,
+/// and replaces them with `304 Not Modified` if the client already has the same content.
+///
+/// The `ETag` is weak because the compression middleware may change the encoding of the body.
+/// `If-Modified-Since` is only looked at when the client sends no `If-None-Match`, and only for
+/// the responses with a `Last-Modified`, see `with_last_modified()`.
+pub fn with_cache_headers(
+    response: fastn_core::http::Response,
+    if_none_match: Option<&str>,
+    if_modified_since: Option<&str>,
+    cache_control: &str,
+) -> fastn_core::http::Response {
+    use actix_web::http::header;
+
+    if response.status() != actix_web::http::StatusCode::OK
+        || response.headers().contains_key(header::CACHE_CONTROL)
+    {
+        return response;
+    }
+
+    let last_modified = response.headers().get(header::LAST_MODIFIED).cloned();
+    let (mut response, body) = response.into_parts();
+    let body = match body.try_into_bytes() {
+        Ok(body) => body,
+        // streaming bodies can not be hashed without buffering them
+        Err(body) => return response.set_body(body),
+    };
+
+    let etag = format!("W/\"{}\"", fastn_core::utils::generate_hash(&body));
+    let not_modified = match if_none_match {
+        Some(v) => v
+            .split(',')
+            .map(|t| t.trim())
+            .any(|t| t == "*" || t.trim_start_matches("W/") == &etag[2..]),
+        None => last_modified
+            .as_ref()
+            .and_then(|v| http_date(v.to_str().ok()?))
+            .zip(if_modified_since.and_then(http_date))
+            .map(|(last_modified, since)| last_modified <= since)
+            .unwrap_or(false),
+    };
+
+    if not_modified {
+        let mut not_modified = actix_web::HttpResponse::NotModified();
+        not_modified
+            .insert_header((header::ETAG, etag))
+            .insert_header((header::CACHE_CONTROL, cache_control));
+        if let Some(last_modified) = last_modified {
+            not_modified.insert_header((header::LAST_MODIFIED, last_modified));
+        }
+        return not_modified.finish();
+    }
+
+    let headers = response.headers_mut();
+    if let Ok(etag) = header::HeaderValue::from_str(etag.as_str()) {
+        headers.insert(header::ETAG, etag);
+    }
+    if let Ok(cache_control) = header::HeaderValue::from_str(cache_control) {
+        headers.insert(header::CACHE_CONTROL, cache_control);
+    }
+    response.set_body(actix_web::body::BoxBody::new(body))
+}
+
+/// Adds the modification time of the static file at `path` as `Last-Modified`, so clients that
+/// do not keep the `ETag` can still revalidate with `If-Modified-Since`.
+pub fn with_last_modified(
+    mut response: fastn_core::http::Response,
+    path: &camino::Utf8Path,
+) -> fastn_core::http::Response {
+    use actix_web::http::header;
+
+    if let Ok(modified) = path.metadata().and_then(|m| m.modified()) {
+        let modified = header::HttpDate::from(modified).to_string();
+        if let Ok(modified) = header::HeaderValue::from_str(modified.as_str()) {
+            response
+                .headers_mut()
+                .insert(header::LAST_MODIFIED, modified);
+        }
+    }
+    response
+}
+
+fn http_date(v: &str) -> Option<std::time::SystemTime> {
+    v.parse::<actix_web::http::header::HttpDate>()
+        .ok()
+        .map(Into::into)
+}
+
 #[derive(Debug, Clone)]
 pub struct Request {
     method: String,
@@ -243,7 +331,21 @@ pub(crate) struct ResponseBuilder {
     // remaining
 }
 
-// We will no do stream, data is going to less from services
+/// Headers which only apply to a single connection, and must not be forwarded by proxies, see
+/// https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Connection#Directives
+const HOP_BY_HOP_HEADERS: &[&str] = &[
+    "connection",
+    "keep-alive",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+];
+
+// Responses from services are streamed to the client as they arrive, so long polling and
+// Server-Sent Events work through fastn
 impl ResponseBuilder {
     // chain implementation
     // .build
@@ -255,14 +357,11 @@ impl ResponseBuilder {
     ) -> fastn_core::http::Response {
         let status = response.status();
 
-        // Remove `Connection` as per
-        // https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Connection#Directives
+        // the body is streamed, so actix-web decides the framing
         let mut response_builder = actix_web::HttpResponse::build(status);
-        for header in response
-            .headers()
-            .iter()
-            .filter(|(h, _)| *h != "connection")
-        {
+        for header in response.headers().iter().filter(|(h, _)| {
+            !HOP_BY_HOP_HEADERS.contains(&h.as_str()) && *h != reqwest::header::CONTENT_LENGTH
+        }) {
             response_builder.insert_header(header);
         }
         if status == actix_web::http::StatusCode::FOUND {
@@ -284,18 +383,35 @@ impl ResponseBuilder {
             }
         }
 
-        let content = match response.bytes().await {
-            Ok(b) => b,
-            Err(e) => {
-                return actix_web::HttpResponse::from(actix_web::error::ErrorInternalServerError(
-                    fastn_core::Error::HttpError(e),
-                ))
+        if is_event_stream(response.headers()) {
+            // events must reach the client as soon as they are sent: keep the compression
+            // middleware and any proxy in front of us from buffering them
+            response_builder.insert_header((
+                actix_web::http::header::CONTENT_ENCODING,
+                actix_web::http::header::ContentEncoding::Identity,
+            ));
+            response_builder.insert_header(("X-Accel-Buffering", "no"));
+            if !response
+                .headers()
+                .contains_key(reqwest::header::CACHE_CONTROL)
+            {
+                response_builder
+                    .insert_header((actix_web::http::header::CACHE_CONTROL, "no-cache"));
             }
-        };
-        response_builder.body(content)
+        }
+
+        response_builder.streaming(response.bytes_stream())
     }
 }
 
+fn is_event_stream(headers: &reqwest::header::HeaderMap) -> bool {
+    headers
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.starts_with("text/event-stream"))
+        .unwrap_or(false)
+}
+
 pub(crate) fn url_regex() -> regex::Regex {
     regex::Regex::new(
         r"((([A-Za-z]{3,9}:(?://)?)(?:[-;:&=\+\$,\w]+@)?[A-Za-z0-9.-]+|(?:www.|[-;:&=\+\$,\w]+@)[A-Za-z0-9.-]+)((?:/[\+~%/.\w_]*)?\??(?:[-\+=&;%@.\w_]*)\#?(?:[\w]*))?)"
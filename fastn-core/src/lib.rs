@@ -23,15 +23,19 @@ mod error;
 mod i18n;
 pub mod library;
 mod proxy;
+mod rate_limit;
+pub mod responsive_image;
 pub mod sitemap;
 mod snapshot;
 mod sync_utils;
+pub mod tls;
 mod track;
 mod tracker;
 mod translation;
 mod version;
 // mod wasm;
 mod library2022;
+mod metrics;
 mod workspace;
 
 pub(crate) use auto_import::AutoImport;
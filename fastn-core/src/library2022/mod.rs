@@ -236,12 +236,14 @@ impl Library2022 {
             }
             "http" => processor::http::process(value, kind, doc, &self.config).await,
             "toc" => processor::toc::process(value, kind, doc, &self.config),
+            "page-toc" => processor::page_toc::process(value, kind, doc, &self.config),
             "get-data" => processor::get_data::process(value, kind, doc, &self.config),
             "sitemap" => processor::sitemap::process(value, kind, doc, &self.config),
             "full-sitemap" => {
                 processor::sitemap::full_sitemap_process(value, kind, doc, &self.config)
             }
             "request-data" => processor::request_data::process(value, kind, doc, &self.config),
+            "proxy-data" => processor::proxy_data::process(value, kind, doc, &self.config),
             "document-readers" => processor::document::process_readers(
                 value,
                 kind,
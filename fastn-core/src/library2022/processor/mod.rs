@@ -6,7 +6,9 @@ pub(crate) mod figma_typography_tokens;
 pub(crate) mod get_data;
 pub(crate) mod http;
 pub(crate) mod package_tree;
+pub(crate) mod page_toc;
 pub(crate) mod pg;
+pub(crate) mod proxy_data;
 pub(crate) mod query;
 pub(crate) mod request_data;
 pub(crate) mod sitemap;
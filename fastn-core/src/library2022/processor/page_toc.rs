@@ -0,0 +1,49 @@
+/// Headings of the current document, for building "On this page" sidebars.
+///
+/// ```ftd
+/// -- pr.page-heading list headings:
+/// $processor$: pr.page-toc
+/// ```
+///
+/// Headings are listed in the order they appear, with their `title`, the `id` of the heading
+/// element, `url` (`#<id>`) and `level`, see `ftd::executor::headings::from_document()`.
+///
+/// The headings are only known once the whole document is interpreted: the processor gives an
+/// empty list, and `set_headings()` sets the headings once the document is interpreted.
+pub fn process(
+    value: ftd::ast::VariableValue,
+    kind: ftd::interpreter::Kind,
+    doc: &ftd::interpreter::TDoc,
+    _config: &fastn_core::Config,
+) -> ftd::interpreter::Result<ftd::interpreter::Value> {
+    doc.from_json(
+        &Vec::<ftd::executor::headings::Heading>::new(),
+        &kind,
+        &value,
+    )
+}
+
+/// Sets the headings of the interpreted `document` to the variables processed by `page-toc`,
+/// `page_tocs` has the module and the variable definition of each of them.
+pub fn set_headings(
+    document: &mut ftd::interpreter::Document,
+    page_tocs: Vec<(String, ftd::ast::AST)>,
+) -> ftd::interpreter::Result<()> {
+    if page_tocs.is_empty() {
+        return Ok(());
+    }
+    let headings = ftd::executor::headings::from_document(document);
+    for (module, ast) in page_tocs {
+        let variable_definition = ast.get_variable_definition(module.as_str())?;
+        let doc = ftd::interpreter::TDoc::new(module.as_str(), &document.aliases, &document.data);
+        let name = doc.resolve_name(variable_definition.name.as_str());
+        let variable = doc.get_variable(name.as_str(), variable_definition.line_number)?;
+        let value = doc
+            .from_json(&headings, &variable.kind.kind, &variable_definition.value)?
+            .into_property_value(variable.mutable, variable.line_number);
+        if let Some(ftd::interpreter::Thing::Variable(v)) = document.data.get_mut(name.as_str()) {
+            v.value = value;
+        }
+    }
+    Ok(())
+}
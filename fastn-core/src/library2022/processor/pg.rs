@@ -34,6 +34,22 @@ async fn pool() -> &'static Result<deadpool_postgres::Pool, deadpool_postgres::C
     POOL_RESULT.get_or_init(create_pool).await
 }
 
+/// Create the connection pool and check the database is reachable, so `fastn serve` is only
+/// ready once `pg` processor can be used. Does nothing if `FASTN_PG_URL` is not set.
+pub(crate) async fn warmup() -> Result<(), String> {
+    if std::env::var("FASTN_PG_URL").is_err() {
+        return Ok(());
+    }
+
+    let pool = pool().await.as_ref().map_err(|e| e.to_string())?;
+    let client = pool.get().await.map_err(|e| e.to_string())?;
+    client
+        .simple_query("SELECT 1")
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
 pub async fn process(
     value: ftd::ast::VariableValue,
     kind: ftd::interpreter::Kind,
@@ -115,6 +131,15 @@ fn resolve_variable_from_doc(
         (&postgres_types::Type::BOOL, ftd::interpreter::Value::Boolean { value, .. }) => {
             Box::new(value)
         }
+        (&postgres_types::Type::TIMESTAMPTZ, ftd::interpreter::Value::DateTime { value }) => {
+            Box::new(parse_datetime(value.as_str(), doc.name, line_number)?)
+        }
+        (&postgres_types::Type::TIMESTAMP, ftd::interpreter::Value::DateTime { value }) => {
+            Box::new(parse_datetime(value.as_str(), doc.name, line_number)?.naive_utc())
+        }
+        (&postgres_types::Type::DATE, ftd::interpreter::Value::DateTime { value }) => {
+            Box::new(parse_datetime(value.as_str(), doc.name, line_number)?.date_naive())
+        }
         (e, a) => {
             return ftd::interpreter::utils::e2(
                 format!("for {} postgresql expected ${:?}, found {:?}", var, e, a),
@@ -286,6 +311,18 @@ async fn execute_query(
     Ok(result)
 }
 
+fn parse_datetime(
+    value: &str,
+    doc_name: &str,
+    line_number: usize,
+) -> ftd::interpreter::Result<fastn_grammar::datetime::DateTime> {
+    fastn_grammar::datetime::parse(value).map_err(|e| ftd::interpreter::Error::ParseError {
+        message: e.to_string(),
+        doc_id: doc_name.to_string(),
+        line_number,
+    })
+}
+
 fn row_to_json(
     r: tokio_postgres::Row,
     doc_name: &str,
@@ -315,6 +352,20 @@ fn row_to_json(
             &postgres_types::Type::CHAR => row.push(serde_json::Value::String(r.get(i))),
             &postgres_types::Type::VARCHAR => row.push(serde_json::Value::String(r.get(i))),
             &postgres_types::Type::JSON => row.push(r.get(i)),
+            &postgres_types::Type::TIMESTAMPTZ => row.push(serde_json::Value::String(
+                r.get::<usize, chrono::DateTime<chrono::Utc>>(i)
+                    .to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true),
+            )),
+            &postgres_types::Type::TIMESTAMP => row.push(serde_json::Value::String(
+                r.get::<usize, chrono::NaiveDateTime>(i)
+                    .and_utc()
+                    .to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true),
+            )),
+            &postgres_types::Type::DATE => row.push(serde_json::Value::String(
+                r.get::<usize, chrono::NaiveDate>(i)
+                    .format("%Y-%m-%d")
+                    .to_string(),
+            )),
 
             t => {
                 return ftd::interpreter::utils::e2(
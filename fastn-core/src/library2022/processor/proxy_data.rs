@@ -0,0 +1,19 @@
+pub fn process(
+    value: ftd::ast::VariableValue,
+    kind: ftd::interpreter::Kind,
+    doc: &ftd::interpreter::TDoc,
+    config: &fastn_core::Config,
+) -> ftd::interpreter::Result<ftd::interpreter::Value> {
+    match config.proxy_data.as_ref() {
+        Some(json) => doc.from_json(json, &kind, &value),
+        None if kind.is_optional() => Ok(ftd::interpreter::Value::Optional {
+            data: Box::new(None),
+            kind: kind.into_kind_data(),
+        }),
+        None => ftd::interpreter::utils::e2(
+            "`proxy-data` processor can only be used in a `fastn.proxy-template` document",
+            doc.name,
+            value.line_number(),
+        ),
+    }
+}
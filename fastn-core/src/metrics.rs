@@ -0,0 +1,164 @@
+// Request counts and latency histograms of `fastn serve`, exposed on `/-/metrics` in the
+// Prometheus text format.
+
+/// Upper bounds of the latency histogram buckets, in seconds, same as the Prometheus client
+/// libraries use by default.
+const BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Routes we report separately, everything else is either a static file or a document. Urls are
+/// never used as labels as is, so the number of time series stays bounded.
+const ROUTES: &[&str] = &[
+    "/-/sync/",
+    "/-/sync2/",
+    "/-/clone/",
+    "/-/view-src/",
+    "/-/edit-src/",
+    "/-/edit/",
+    "/-/revert/",
+    "/-/editor-sync/",
+    "/-/create-cr/",
+    "/-/create-cr-page/",
+    "/-/clear-cache/",
+    "/-/poll/",
+    "/-/debug/requests/",
+    "/-/health",
+    "/-/ready",
+    "/-/metrics",
+    "/auth/",
+];
+
+#[derive(Default)]
+struct RouteMetrics {
+    /// (method, status) -> number of requests
+    requests: std::collections::BTreeMap<(String, u16), u64>,
+    /// number of requests in each bucket, the last one is `+Inf`
+    buckets: [u64; BUCKETS.len() + 1],
+    sum: f64,
+    count: u64,
+}
+
+static METRICS: once_cell::sync::Lazy<
+    std::sync::Mutex<std::collections::BTreeMap<&'static str, RouteMetrics>>,
+> = once_cell::sync::Lazy::new(|| std::sync::Mutex::new(std::collections::BTreeMap::new()));
+
+pub(crate) fn route_label(path: &str) -> &'static str {
+    match ROUTES.iter().find(|r| path.starts_with(*r)) {
+        Some(r) => r,
+        None if path.starts_with("/-/") => "static",
+        None => "document",
+    }
+}
+
+pub(crate) fn observe(
+    route: &'static str,
+    method: &str,
+    status: u16,
+    duration: std::time::Duration,
+) {
+    let seconds = duration.as_secs_f64();
+    let mut metrics = METRICS.lock().unwrap();
+    let m = metrics.entry(route).or_default();
+
+    *m.requests.entry((method.to_string(), status)).or_default() += 1;
+    let bucket = BUCKETS
+        .iter()
+        .position(|le| seconds <= *le)
+        .unwrap_or(BUCKETS.len());
+    m.buckets[bucket] += 1;
+    m.sum += seconds;
+    m.count += 1;
+}
+
+pub(crate) fn render() -> String {
+    use std::fmt::Write;
+
+    let metrics = METRICS.lock().unwrap();
+    let mut out = String::new();
+
+    out.push_str(
+        "# HELP fastn_http_requests_total Number of HTTP requests handled.\n\
+        # TYPE fastn_http_requests_total counter\n",
+    );
+    for (route, m) in metrics.iter() {
+        for ((method, status), count) in m.requests.iter() {
+            writeln!(
+                out,
+                "fastn_http_requests_total{{route=\"{}\",method=\"{}\",status=\"{}\"}} {}",
+                route, method, status, count
+            )
+            .unwrap();
+        }
+    }
+
+    out.push_str(
+        "# HELP fastn_http_request_duration_seconds Time taken to handle HTTP requests.\n\
+        # TYPE fastn_http_request_duration_seconds histogram\n",
+    );
+    for (route, m) in metrics.iter() {
+        let mut cumulative = 0;
+        for (i, count) in m.buckets.iter().enumerate() {
+            cumulative += count;
+            let le = match BUCKETS.get(i) {
+                Some(le) => le.to_string(),
+                None => "+Inf".to_string(),
+            };
+            writeln!(
+                out,
+                "fastn_http_request_duration_seconds_bucket{{route=\"{}\",le=\"{}\"}} {}",
+                route, le, cumulative
+            )
+            .unwrap();
+        }
+        writeln!(
+            out,
+            "fastn_http_request_duration_seconds_sum{{route=\"{}\"}} {}",
+            route, m.sum
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "fastn_http_request_duration_seconds_count{{route=\"{}\"}} {}",
+            route, m.count
+        )
+        .unwrap();
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn route_label() {
+        assert_eq!(super::route_label("/-/edit/"), "/-/edit/");
+        assert_eq!(super::route_label("/-/edit-src/a.ftd"), "/-/edit-src/");
+        assert_eq!(super::route_label("/-/sync2/"), "/-/sync2/");
+        assert_eq!(super::route_label("/-/fastn.com/default.css"), "static");
+        assert_eq!(super::route_label("/blog/hello/"), "document");
+    }
+
+    #[test]
+    fn render() {
+        super::observe("/-/poll/", "GET", 200, std::time::Duration::from_millis(30));
+        super::observe("/-/poll/", "GET", 200, std::time::Duration::from_secs(20));
+
+        let rendered = super::render();
+        assert!(rendered.contains(
+            "fastn_http_requests_total{route=\"/-/poll/\",method=\"GET\",status=\"200\"} 2\n"
+        ));
+        assert!(rendered.contains(
+            "fastn_http_request_duration_seconds_bucket{route=\"/-/poll/\",le=\"0.025\"} 0\n"
+        ));
+        assert!(rendered.contains(
+            "fastn_http_request_duration_seconds_bucket{route=\"/-/poll/\",le=\"0.05\"} 1\n"
+        ));
+        assert!(rendered.contains(
+            "fastn_http_request_duration_seconds_bucket{route=\"/-/poll/\",le=\"+Inf\"} 2\n"
+        ));
+        assert!(
+            rendered.contains("fastn_http_request_duration_seconds_count{route=\"/-/poll/\"} 2\n")
+        );
+    }
+}
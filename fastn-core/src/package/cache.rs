@@ -0,0 +1,99 @@
+/// `Cache-Control` header used by `fastn serve` for the urls starting with `path`.
+///
+/// ```ftd
+/// -- fastn.cache: /blog/
+/// policy: public, max-age=3600
+/// ```
+#[derive(Debug, serde::Deserialize, Clone, PartialEq)]
+pub struct CachePolicy {
+    pub path: String,
+    pub policy: String,
+}
+
+/// Assets whose name contains the hash of their content never change, so browsers can keep them
+/// forever.
+pub const IMMUTABLE: &str = "public, max-age=31536000, immutable";
+
+/// Everything else is revalidated on every request, which is cheap thanks to `ETag` and, for the
+/// static files, `Last-Modified`.
+pub const REVALIDATE: &str = "no-cache";
+
+/// Find the `Cache-Control` header for `path`: content hashed `/-/` assets are immutable,
+/// otherwise the most specific matching `fastn.cache` wins.
+pub fn find_cache_policy<'a>(policies: &'a [CachePolicy], path: &str) -> &'a str {
+    if is_hashed_asset(path) {
+        return IMMUTABLE;
+    }
+
+    policies
+        .iter()
+        .filter(|p| path.starts_with(p.path.as_str()))
+        .max_by_key(|p| p.path.len())
+        .map(|p| p.policy.as_str())
+        .unwrap_or(REVALIDATE)
+}
+
+/// `/-/fastn-<sha256>.js`, `/-/<package>/default-<sha256>.css` and the like, see `fastn_core::utils::generate_hash()`.
+fn is_hashed_asset(path: &str) -> bool {
+    if !path.starts_with("/-/") {
+        return false;
+    }
+
+    let file_name = path.rsplit('/').next().unwrap_or_default();
+    let stem = match file_name.rsplit_once('.') {
+        Some((stem, _extension)) => stem,
+        None => return false,
+    };
+
+    match stem.rsplit_once('-') {
+        Some((_, hash)) => hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit()),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    fn policy(path: &str, policy: &str) -> super::CachePolicy {
+        super::CachePolicy {
+            path: path.to_string(),
+            policy: policy.to_string(),
+        }
+    }
+
+    #[test]
+    fn find_cache_policy() {
+        let policies = vec![
+            policy("/", "public, max-age=60"),
+            policy("/blog/", "public, max-age=3600"),
+        ];
+        let hash = fastn_core::utils::generate_hash("body { color: red; }");
+
+        assert_eq!(
+            super::find_cache_policy(
+                &policies,
+                format!("/-/fastn.com/default-{}.css", hash).as_str()
+            ),
+            super::IMMUTABLE
+        );
+        assert_eq!(
+            super::find_cache_policy(
+                &policies,
+                format!("/{}", fastn_core::utils::hashed_default_ftd_js()).as_str()
+            ),
+            super::IMMUTABLE
+        );
+        assert_eq!(
+            super::find_cache_policy(&policies, "/blog/hello/"),
+            "public, max-age=3600"
+        );
+        assert_eq!(
+            super::find_cache_policy(&policies, "/about/"),
+            "public, max-age=60"
+        );
+        assert_eq!(
+            super::find_cache_policy(&policies, "/-/fastn.com/default.css"),
+            "public, max-age=60"
+        );
+        assert_eq!(super::find_cache_policy(&[], "/about/"), super::REVALIDATE);
+    }
+}
@@ -292,11 +292,8 @@ impl fastn_core::Package {
             {
                 Ok(response_fastn)
             } else {
-                Err(fastn_core::Error::UsageError {
-                    message: format!(
-                        "Unable to find the FASTN.ftd for the dependency package: {}",
-                        name
-                    ),
+                Err(fastn_core::Error::DependencyNotFound {
+                    package: name.to_string(),
                 })
             }
         }
@@ -413,11 +410,8 @@ impl fastn_core::Package {
             {
                 Ok(response_fastn)
             } else {
-                Err(fastn_core::Error::UsageError {
-                    message: format!(
-                        "Unable to find the FASTN.ftd for the dependency package: {}",
-                        name
-                    ),
+                Err(fastn_core::Error::DependencyNotFound {
+                    package: name.to_string(),
                 })
             }
         }
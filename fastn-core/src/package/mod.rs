@@ -1,6 +1,8 @@
 pub mod app;
+pub mod cache;
 pub mod dependency;
 pub mod package_doc;
+pub mod proxy_template;
 pub mod redirects;
 pub mod user_group;
 
@@ -70,6 +72,35 @@ pub struct Package {
 
     /// Redirect URLs
     pub redirects: Option<ftd::Map<String>>,
+
+    /// User group which can access admin only pages, like `/-/debug/requests/`
+    pub admin_group: Option<String>,
+
+    /// Emit the JS of each imported ftd module as its own, cacheable, file instead of inlining
+    /// it in every page
+    pub split_js: bool,
+
+    /// Navigate between the pages on the client, by swapping the page with its fragment, see
+    /// `fastn_core::package::package_doc::SpaFragment`, instead of loading it
+    pub spa: bool,
+
+    /// Global mutable variables which keep their value across the client-side navigations
+    pub persistent_variables: Vec<String>,
+
+    /// `Cache-Control` policies for `fastn serve`, see `fastn_core::package::cache`
+    pub cache: Vec<cache::CachePolicy>,
+
+    /// Certificate and key for `fastn serve` to serve HTTPS
+    pub tls: Option<fastn_core::tls::Tls>,
+
+    /// Rate and body size limits for `fastn serve`, per route prefix
+    pub rate_limits: Vec<fastn_core::rate_limit::RateLimit>,
+
+    /// Urls rendered with an ftd document from the JSON returned by an end-point
+    pub proxy_templates: Vec<proxy_template::ProxyTemplate>,
+
+    /// Resized variants of images generated by `fastn build`
+    pub responsive_images: Option<fastn_core::responsive_image::ResponsiveImages>,
 }
 
 impl Package {
@@ -103,6 +134,15 @@ impl Package {
             apps: vec![],
             icon: None,
             redirects: None,
+            admin_group: None,
+            split_js: false,
+            spa: false,
+            persistent_variables: vec![],
+            cache: vec![],
+            tls: None,
+            rate_limits: vec![],
+            proxy_templates: vec![],
+            responsive_images: None,
         }
     }
 
@@ -614,9 +654,22 @@ impl Package {
             .collect();
 
         package.ignored_paths = fastn_doc.get::<Vec<String>>("fastn#ignore")?;
+        package.persistent_variables = fastn_doc.get::<Vec<String>>("fastn#persistent-variable")?;
         package.fonts = fastn_doc.get("fastn#font")?;
         package.sitemap_temp = fastn_doc.get("fastn#sitemap")?;
         package.dynamic_urls_temp = fastn_doc.get("fastn#dynamic-urls")?;
+        package.cache = fastn_doc.get("fastn#cache")?;
+        package.tls = fastn_doc
+            .get::<Option<fastn_core::tls::Tls>>("fastn#tls")?
+            .map(|tls| tls.relative_to(root));
+        package.rate_limits = fastn_doc.get("fastn#rate-limit")?;
+        package.proxy_templates = fastn_doc.get("fastn#proxy-template")?;
+        package.responsive_images = fastn_doc
+            .get::<Option<fastn_core::responsive_image::ResponsiveImagesTemp>>(
+                "fastn#responsive-images",
+            )?
+            .map(|r| r.into_responsive_images())
+            .transpose()?;
 
         // TODO: resolve group dependent packages, there may be imported group from foreign package
         //   We need to make sure to resolve that package as well before moving ahead
@@ -734,6 +787,15 @@ impl PackageTempIntoPackage for fastn_package::old_fastn::PackageTemp {
             apps: vec![],
             icon: self.icon,
             redirects: None,
+            admin_group: self.admin_group,
+            split_js: self.split_js,
+            spa: self.spa,
+            persistent_variables: vec![],
+            cache: vec![],
+            tls: None,
+            rate_limits: vec![],
+            proxy_templates: vec![],
+            responsive_images: None,
         }
     }
 }
@@ -513,21 +513,87 @@ pub(crate) async fn read_ftd_2023(
         return Ok(FTDResult::Redirect { url, code });
     }
 
-    let js_ast_data = ftd::js::document_into_js_ast(main_ftd_doc);
-    let js_document_script = fastn_js::to_js(
-        js_ast_data.asts.as_slice(),
-        true,
-        config.package.name.as_str(),
-    );
-    let js_ftd_script = fastn_js::to_js(
-        ftd::js::default_bag_into_js_ast().as_slice(),
-        false,
-        config.package.name.as_str(),
-    );
-    let ssr_body = fastn_js::ssr_with_js_string(
-        &config.package.name,
-        format!("{js_ftd_script}\n{js_document_script}").as_str(),
+    let document_name = main_ftd_doc.name.to_string();
+    let js_ast_data = tracing::info_span!("document_into_js_ast")
+        .in_scope(|| ftd::js::document_into_js_ast(main_ftd_doc));
+    let package_name = config.package.name.to_string();
+    // the modules split out of the page are shared by the pages, only the document is shaken
+    let (asts, modules) = if config.package.split_js {
+        fastn_js::split_modules(
+            js_ast_data.asts,
+            document_name.as_str(),
+            package_name.as_str(),
+        )
+    } else {
+        (js_ast_data.asts, vec![])
+    };
+    let asts = tracing::info_span!("tree_shake").in_scope(|| {
+        fastn_js::tree_shake(asts, js_ast_data.roots.as_slice(), package_name.as_str())
+    });
+    let minifier = config.minify_js.then(|| {
+        // the persistent variables are looked up by their name on navigation, see `spa`. Only the
+        // things of the document are renamed, the modules are the same for all the pages
+        let mut keep = js_ast_data.roots.clone();
+        keep.extend(config.package.persistent_variables.iter().cloned());
+        fastn_js::Minifier::new(asts.as_slice(), keep.as_slice(), package_name.as_str())
+    });
+    let js_file = JsFile {
+        build_dir: config.build_dir(),
+        package_name: package_name.as_str(),
+        minifier: minifier.as_ref(),
+        locations: &js_ast_data.locations,
+        build: download_assets,
+    };
+    let mut scripts = js_ast_data.scripts;
+    let page_scripts = scripts.join("");
+    let (js_document_script, js_modules_script) = if config.package.split_js {
+        // the modules are loaded before the document, and share `global` with it
+        let global = format!("let {} = {{}};", fastn_js::GLOBAL_VARIABLE_MAP);
+        let mut module_scripts = vec![format!("<script>{global}</script>")];
+        let mut modules_js = global;
+        for module in modules {
+            let js = js_file.module_js(&module).await?;
+            let path = fastn_core::utils::save_js_file(
+                &js_file.build_dir,
+                module.name.as_str(),
+                "js",
+                js.as_str(),
+                download_assets,
+            )
+            .await?;
+            module_scripts.push(format!("<script src=\"{path}\"></script>"));
+            modules_js = format!("{modules_js}\n{js}");
+        }
+        module_scripts.extend(scripts);
+        scripts = module_scripts;
+        let js_document_script = js_file
+            .document_js(asts.as_slice(), false, document_name.as_str())
+            .await?;
+        (js_document_script, modules_js)
+    } else {
+        let js_document_script = js_file
+            .document_js(asts.as_slice(), true, document_name.as_str())
+            .await?;
+        (js_document_script, String::new())
+    };
+    let ssr_js = format!(
+        "{}\n{js_modules_script}\n{js_document_script}",
+        ftd::js::default_bag_js()
     );
+    let is_static = js_ast_data.is_static.unwrap_or_else(|| {
+        ftd::js::is_static(format!("{js_modules_script}\n{js_document_script}").as_str())
+    });
+    let static_html = is_static.then(|| {
+        tracing::info_span!("ssr_static")
+            .in_scope(|| fastn_js::ssr_static_with_js_string(&config.package.name, ssr_js.as_str()))
+    });
+    // the fragment of a static page is still shown with the JS, when navigating to it
+    let ssr_body = if static_html.is_none() || config.package.spa {
+        tracing::info_span!("ssr")
+            .in_scope(|| fastn_js::ssr_with_js_string(&config.package.name, ssr_js.as_str()))
+    } else {
+        String::new()
+    };
 
     all_packages.extend(lib.config.all_packages.into_inner());
     drop(all_packages);
@@ -537,20 +603,156 @@ pub(crate) async fn read_ftd_2023(
         .extend(lib.config.downloaded_assets);
 
     let font_style = config.get_font_style();
-    let file_content = fastn_core::utils::replace_markers_2023(
-        ftd::ftd_js_html(),
-        js_document_script.as_str(),
-        js_ast_data.scripts.join("").as_str(),
-        ssr_body.as_str(),
-        font_style.as_str(),
-        ftd::ftd_js_css(),
-        base_url,
-        config,
-    );
+    let js_document_script = if config.package.spa {
+        let fragment = SpaFragment {
+            js: format!("{js_modules_script}\n{js_document_script}"),
+            html: format!("{ssr_body}{font_style}"),
+            scripts: page_scripts.clone(),
+        };
+        config.spa_fragment = Some(serde_json::to_string(&fragment)?);
+        let persistent = config
+            .package
+            .persistent_variables
+            .iter()
+            .map(|name| fastn_js::utils::name_to_js_(name))
+            .collect::<Vec<_>>();
+        format!(
+            "{js_document_script}\nfastn_spa.init({}, {});",
+            fastn_js::GLOBAL_VARIABLE_MAP,
+            serde_json::to_string(&persistent)?
+        )
+    } else {
+        js_document_script
+    };
+    let file_content = match static_html {
+        Some(html) => fastn_core::utils::replace_markers_static(
+            ftd::ftd_static_html(),
+            &html,
+            page_scripts.as_str(),
+            font_style.as_str(),
+            ftd::ftd_js_css(),
+            base_url,
+            config,
+        ),
+        None => fastn_core::utils::replace_markers_2023(
+            ftd::ftd_js_html(),
+            js_document_script.as_str(),
+            scripts.join("").as_str(),
+            ssr_body.as_str(),
+            font_style.as_str(),
+            ftd::ftd_js_css(),
+            base_url,
+            config,
+        ),
+    };
 
     Ok(FTDResult::Html(file_content.into()))
 }
 
+/// What the client needs to show a page without loading it, when `spa` of `fastn.package` is
+/// on. `fastn build` writes it to `-/fragments/<path>index.json` and `fastn serve` renders it for
+/// the same url.
+#[derive(serde::Serialize)]
+pub struct SpaFragment {
+    /// JS of the page, defines `global` and `main`
+    pub js: String,
+    /// server rendered html of the page
+    pub html: String,
+    /// the external scripts and css of the page
+    pub scripts: String,
+}
+
+/// Renders the JS of a page, minified with a source map when built with `fastn build --minify`.
+struct JsFile<'a> {
+    build_dir: camino::Utf8PathBuf,
+    package_name: &'a str,
+    minifier: Option<&'a fastn_js::Minifier>,
+    locations: &'a std::collections::HashMap<String, fastn_js::SourceLocation>,
+    build: bool,
+}
+
+impl JsFile<'_> {
+    /// JS inlined in the page, see `__js_script__` in `ftd-js.html`.
+    async fn document_js(
+        &self,
+        asts: &[fastn_js::Ast],
+        is_global_need: bool,
+        document_name: &str,
+    ) -> fastn_core::Result<String> {
+        let minifier = match self.minifier {
+            Some(minifier) => minifier,
+            None => {
+                return Ok(tracing::info_span!("to_js")
+                    .in_scope(|| fastn_js::to_js(asts, is_global_need, self.package_name)))
+            }
+        };
+        let minified = tracing::info_span!("to_js").in_scope(|| {
+            minifier.to_js(
+                asts,
+                is_global_need,
+                self.package_name,
+                self.locations,
+                document_name,
+                inline_js_first_line(),
+            )
+        });
+        let map = fastn_core::utils::save_js_file(
+            &self.build_dir,
+            document_name,
+            "map",
+            minified.source_map.as_str(),
+            self.build,
+        )
+        .await?;
+        Ok(format!("{}\n//# sourceMappingURL={}\n", minified.js, map))
+    }
+
+    /// JS of a module split out of the page, loaded from its own file.
+    async fn module_js(&self, module: &fastn_js::Module) -> fastn_core::Result<String> {
+        let minifier = match self.minifier {
+            Some(minifier) => minifier,
+            None => {
+                return Ok(fastn_js::to_js(
+                    module.asts.as_slice(),
+                    false,
+                    self.package_name,
+                ))
+            }
+        };
+        let minified = minifier.to_js(
+            module.asts.as_slice(),
+            false,
+            self.package_name,
+            self.locations,
+            format!("{}.js", fastn_js::utils::name_to_js_(module.name.as_str())).as_str(),
+            0,
+        );
+        let map = fastn_core::utils::save_js_file(
+            &self.build_dir,
+            module.name.as_str(),
+            "map",
+            minified.source_map.as_str(),
+            self.build,
+        )
+        .await?;
+        // the map is next to the module file, in `-/js/`
+        Ok(format!(
+            "{}\n//# sourceMappingURL={}",
+            minified.js,
+            map.trim_start_matches("-/js/")
+        ))
+    }
+}
+
+/// Line of `__js_script__` in the last `<script>` of `ftd-js.html`, the source maps of inline
+/// scripts count the lines from the `<script>` tag.
+fn inline_js_first_line() -> usize {
+    let html = ftd::ftd_js_html();
+    let marker = html.find("__js_script__").unwrap_or_default();
+    let script = html[..marker].rfind("<script>").unwrap_or_default();
+    html[script..marker].matches('\n').count()
+}
+
 pub(crate) async fn process_ftd(
     config: &mut fastn_core::Config,
     main: &fastn_core::Document,
@@ -561,6 +763,14 @@ pub(crate) async fn process_ftd(
 ) -> fastn_core::Result<FTDResult> {
     let response = read_ftd(config, main, base_url, build_static_files, test).await?;
     fastn_core::utils::overwrite(&config.build_dir(), file_path, &response.html()).await?;
+    if let Some(fragment) = config.spa_fragment.take() {
+        fastn_core::utils::overwrite(
+            &config.build_dir(),
+            fastn_core::utils::spa_fragment_path(file_path).as_str(),
+            fragment.as_bytes(),
+        )
+        .await?;
+    }
 
     Ok(response)
 }
@@ -0,0 +1,91 @@
+/// Urls starting with `path` are rendered by `fastn serve` with `document`, from the JSON the
+/// end-point returns for the same request sent to `end-point`.
+///
+/// ```ftd
+/// -- fastn.proxy-template: /movie/
+/// end-point: /-/movie-db/movie/
+/// document: movie.ftd
+/// ```
+///
+/// `/movie/5/?lang=en` is sent to `/-/movie-db/movie/5/?lang=en`, and `movie.ftd` gets the JSON
+/// with the `proxy-data` processor:
+///
+/// ```ftd
+/// -- movie-data movie:
+/// $processor$: pr.proxy-data
+/// ```
+#[derive(Debug, serde::Deserialize, Clone, PartialEq)]
+pub struct ProxyTemplate {
+    pub path: String,
+    /// Url of the end-point, same as in the `http` processor, eg `/-/<app>/<path>/`
+    #[serde(rename = "end-point")]
+    pub end_point: String,
+    pub document: String,
+}
+
+impl ProxyTemplate {
+    /// The end-point url `path` is proxied to, `path` must start with `self.path`.
+    pub fn end_point_path(&self, path: &str) -> String {
+        let remaining = path.strip_prefix(self.path.as_str()).unwrap_or_default();
+        if remaining.is_empty() {
+            return self.end_point.to_string();
+        }
+        format!(
+            "{}/{}",
+            self.end_point.trim_end_matches('/'),
+            remaining.trim_start_matches('/')
+        )
+    }
+}
+
+/// The most specific proxy template matching `path`.
+pub fn find_proxy_template<'a>(
+    templates: &'a [ProxyTemplate],
+    path: &str,
+) -> Option<&'a ProxyTemplate> {
+    templates
+        .iter()
+        .filter(|t| path.starts_with(t.path.as_str()))
+        .max_by_key(|t| t.path.len())
+}
+
+#[cfg(test)]
+mod test {
+    fn template(path: &str, end_point: &str) -> super::ProxyTemplate {
+        super::ProxyTemplate {
+            path: path.to_string(),
+            end_point: end_point.to_string(),
+            document: "movie.ftd".to_string(),
+        }
+    }
+
+    #[test]
+    fn find_proxy_template() {
+        let templates = vec![
+            template("/movie/", "/-/movie-db/movie/"),
+            template("/movie/top/", "/-/movie-db/top/"),
+        ];
+
+        assert_eq!(
+            super::find_proxy_template(&templates, "/movie/top/").map(|t| t.path.as_str()),
+            Some("/movie/top/")
+        );
+        assert_eq!(
+            super::find_proxy_template(&templates, "/movie/5/").map(|t| t.path.as_str()),
+            Some("/movie/")
+        );
+        assert_eq!(super::find_proxy_template(&templates, "/about/"), None);
+    }
+
+    #[test]
+    fn end_point_path() {
+        let t = template("/movie/", "/-/movie-db/movie/");
+
+        assert_eq!(t.end_point_path("/movie/"), "/-/movie-db/movie/");
+        assert_eq!(t.end_point_path("/movie/5/"), "/-/movie-db/movie/5/");
+        assert_eq!(
+            template("/movie/", "/-/movie-db/movie").end_point_path("/movie/5/"),
+            "/-/movie-db/movie/5/"
+        );
+    }
+}
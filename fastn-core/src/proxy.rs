@@ -14,50 +14,462 @@ fn client_builder() -> reqwest::Client {
 static CLIENT: once_cell::sync::Lazy<std::sync::Arc<reqwest::Client>> =
     once_cell::sync::Lazy::new(|| std::sync::Arc::new(client_builder()));
 
+/// The app or package `end-point` a request is proxied to.
+pub(crate) struct Upstream {
+    /// `<scheme>://<host>[:<port>]` of the end-point
+    pub host: String,
+    /// Path on the end-point
+    pub path: String,
+    pub package_name: String,
+    /// App config, sent to the end-point as headers, eg `X-FASTN-USER-ID`
+    pub headers: std::collections::HashMap<String, String>,
+}
+
+/// Find the end-point `path` is served by. If the request is not allowed, the response to send
+/// instead is returned as `Err`.
+pub(crate) async fn upstream(
+    config: &fastn_core::Config,
+    path: &str,
+    req: &fastn_core::http::Request,
+) -> fastn_core::Result<Result<Upstream, fastn_core::http::Response>> {
+    let (package_name, url, mut conf) = fastn_core::config::utils::get_clean_url(config, path)?;
+    let package_name = package_name.unwrap_or_else(|| config.package.name.to_string());
+
+    let host = if let Some(port) = url.port() {
+        format!("{}://{}:{}", url.scheme(), url.host_str().unwrap(), port)
+    } else {
+        format!("{}://{}", url.scheme(), url.host_str().unwrap())
+    };
+
+    // TODO: read app config and send them to service as header
+    // Adjust x-fastn header from based on the platform and the requested field
+    if let Some(user_id) = conf.get("user-id") {
+        match user_id.split_once('-') {
+            Some((platform, requested_field)) => {
+                if let Some(user_data) = fastn_core::auth::get_user_data_from_cookies(
+                    platform,
+                    requested_field,
+                    req.cookies(),
+                )
+                .await?
+                {
+                    conf.insert("X-FASTN-USER-ID".to_string(), user_data);
+                }
+            }
+            _ => return Ok(Err(fastn_core::unauthorised!("invalid user-id provided"))),
+        }
+    }
+
+    Ok(Ok(Upstream {
+        host,
+        path: url.path().to_string(),
+        package_name,
+        headers: conf,
+    }))
+}
+
 // This method will connect client request to the out of the world
 #[tracing::instrument(skip_all)]
 pub(crate) async fn get_out(
-    host: &str,
+    upstream: Upstream,
     req: fastn_core::http::Request,
-    path: &str,
-    package_name: &str,
-    req_headers: &std::collections::HashMap<String, String>,
 ) -> fastn_core::Result<fastn_core::http::Response> {
-    let headers = req.headers();
     // TODO: It should be part of fastn_core::Request::uri()
     // let path = &req.uri().to_string()[1..];
 
-    tracing::info!("proxy_request: {} {} {}", req.method(), path, host);
-
-    let mut proxy_request = reqwest::Request::new(
-        match req.method() {
-            "GET" => reqwest::Method::GET,
-            "POST" => reqwest::Method::POST,
-            "PUT" => reqwest::Method::PUT,
-            "DELETE" => reqwest::Method::DELETE,
-            "PATCH" => reqwest::Method::PATCH,
-            "HEAD" => reqwest::Method::HEAD,
-            "OPTIONS" => reqwest::Method::OPTIONS,
-            "TRACE" => reqwest::Method::TRACE,
-            "CONNECT" => reqwest::Method::CONNECT,
-            _ => reqwest::Method::GET,
-        },
-        reqwest::Url::parse(
-            format!(
-                "{}/{}{}",
-                host.trim_end_matches('/'),
-                path.trim_start_matches('/'),
-                if req.query_string().is_empty() {
-                    "".to_string()
-                } else {
-                    format!("?{}", req.query_string())
-                }
-            )
-            .as_str(),
-        )?,
+    tracing::info!(
+        "proxy_request: {} {} {}",
+        req.method(),
+        upstream.path,
+        upstream.host
     );
 
-    *proxy_request.headers_mut() = headers.to_owned();
+    Ok(fastn_core::http::ResponseBuilder::from_reqwest(
+        send(&upstream, &req).await?,
+        upstream.package_name.as_str(),
+    )
+    .await)
+}
+
+/// Send the request to the end-point of `template`, and render the JSON it returns with the
+/// template's document, see `fastn_core::package::proxy_template`. Anything other than a
+/// successful response is sent to the client as is, so end-points can still redirect or fail.
+#[tracing::instrument(skip_all)]
+pub(crate) async fn render_template(
+    config: &mut fastn_core::Config,
+    template: &fastn_core::package::proxy_template::ProxyTemplate,
+    req: &fastn_core::http::Request,
+) -> fastn_core::Result<fastn_core::http::Response> {
+    // the page has the same readers as a document at its path would have
+    let path = req.path().replacen('/', "", 1);
+    if let Some(response) =
+        fastn_core::commands::serve::read_denied(config, req, path.as_str()).await
+    {
+        return Ok(response);
+    }
+
+    let document = match config
+        .get_file_and_package_by_id(template.document.as_str())
+        .await
+    {
+        Ok(fastn_core::File::Ftd(document)) => document,
+        Err(e) => {
+            return Ok(fastn_core::not_found!(
+                "proxy-template document {} not found: {:?}",
+                template.document,
+                e
+            ))
+        }
+        Ok(_) => {
+            return Ok(fastn_core::server_error!(
+                "proxy-template document {} is not an ftd file",
+                template.document
+            ))
+        }
+    };
+
+    let end_point = template.end_point_path(req.path());
+    let upstream = match upstream(config, end_point.as_str(), req).await? {
+        Ok(upstream) => upstream,
+        Err(response) => return Ok(response),
+    };
+
+    tracing::info!(
+        "proxy_template: {} {} {} -> {}",
+        req.method(),
+        upstream.path,
+        upstream.host,
+        template.document
+    );
+
+    let response = send(&upstream, req).await?;
+    if !response.status().is_success() {
+        return Ok(fastn_core::http::ResponseBuilder::from_reqwest(
+            response,
+            upstream.package_name.as_str(),
+        )
+        .await);
+    }
+
+    let json = match serde_json::from_slice::<serde_json::Value>(&response.bytes().await?) {
+        Ok(json) => json,
+        Err(e) => {
+            tracing::error!("end-point did not return JSON: {}", e);
+            return Ok(actix_web::HttpResponse::BadGateway()
+                .body(format!("{} did not return JSON: {}", end_point, e)));
+        }
+    };
+    config.proxy_data = Some(json);
+    config.current_document = Some(document.id.to_string());
+
+    Ok(
+        fastn_core::package::package_doc::read_ftd(config, &document, "/", false, false)
+            .await?
+            .into(),
+    )
+}
+
+async fn send(
+    upstream: &Upstream,
+    req: &fastn_core::http::Request,
+) -> fastn_core::Result<reqwest::Response> {
+    let mut proxy_request = reqwest::Request::new(method(req.method()), url(upstream, req)?);
+    *proxy_request.headers_mut() = headers(req, &upstream.headers);
+    *proxy_request.body_mut() = Some(req.body().to_vec().into());
+
+    Ok(CLIENT.execute(proxy_request).await?)
+}
+
+/// Requests which can not be buffered: WebSocket upgrades, and requests with a streaming
+/// (chunked) body, eg large uploads. The body is passed along as it arrives when the request goes
+/// to an end-point, and read first when fastn handles the request itself.
+pub(crate) fn is_streaming(req: &actix_web::HttpRequest) -> bool {
+    is_websocket(req)
+        || req
+            .headers()
+            .get(actix_web::http::header::TRANSFER_ENCODING)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_lowercase().contains("chunked"))
+            .unwrap_or(false)
+}
+
+fn is_websocket(req: &actix_web::HttpRequest) -> bool {
+    req.headers()
+        .get(actix_web::http::header::UPGRADE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.eq_ignore_ascii_case("websocket"))
+        .unwrap_or(false)
+}
+
+/// Proxy a streaming request, see `is_streaming()`, without buffering its body. The body is
+/// counted as it is passed along, and the request is aborted with `413 Payload Too Large` once
+/// it is larger than `max_body_size`.
+#[tracing::instrument(skip_all)]
+pub(crate) async fn stream(
+    config: &fastn_core::Config,
+    req: actix_web::HttpRequest,
+    payload: actix_web::web::Payload,
+    max_body_size: Option<usize>,
+) -> fastn_core::Result<fastn_core::http::Response> {
+    let fastn_req = fastn_core::http::Request::from_actix(req.clone(), Default::default());
+    let upstream = match upstream(config, req.path().trim_start_matches('/'), &fastn_req).await {
+        Ok(Ok(upstream)) => upstream,
+        Ok(Err(response)) => return Ok(response),
+        Err(e) => {
+            return Ok(fastn_core::not_found!(
+                "no end-point to stream {} to: {}",
+                req.path(),
+                e
+            ))
+        }
+    };
+
+    tracing::info!(
+        "proxy_stream: {} {} {}",
+        fastn_req.method(),
+        upstream.path,
+        upstream.host
+    );
+
+    if is_websocket(&req) {
+        return websocket(req, payload, &fastn_req, upstream).await;
+    }
+
+    let mut proxy_request =
+        reqwest::Request::new(method(fastn_req.method()), url(&upstream, &fastn_req)?);
+    *proxy_request.headers_mut() = headers(&fastn_req, &upstream.headers);
+    let too_large = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    *proxy_request.body_mut() = Some(reqwest::Body::wrap_stream(forward(
+        payload,
+        max_body_size,
+        too_large.clone(),
+    )));
+
+    let response = CLIENT.execute(proxy_request).await;
+    if let Some(max_body_size) = max_body_size {
+        if too_large.load(std::sync::atomic::Ordering::Relaxed) {
+            return Ok(fastn_core::rate_limit::payload_too_large(max_body_size));
+        }
+    }
+
+    Ok(
+        fastn_core::http::ResponseBuilder::from_reqwest(response?, upstream.package_name.as_str())
+            .await,
+    )
+}
+
+/// Connect to the end-point over WebSocket, and then accept the client's upgrade and pass the
+/// messages along in both directions till either side closes the connection.
+async fn websocket(
+    req: actix_web::HttpRequest,
+    payload: actix_web::web::Payload,
+    fastn_req: &fastn_core::http::Request,
+    upstream: Upstream,
+) -> fastn_core::Result<fastn_core::http::Response> {
+    use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+
+    let mut url = url(&upstream, fastn_req)?;
+    let scheme = if url.scheme() == "https" { "wss" } else { "ws" };
+    // http(s) -> ws(s) is always a valid scheme change
+    url.set_scheme(scheme).ok();
+
+    let mut ws_request = url
+        .as_str()
+        .into_client_request()
+        .map_err(|e| fastn_core::Error::GenericError(e.to_string()))?;
+    for (key, value) in headers(fastn_req, &upstream.headers).iter() {
+        // tungstenite does its own handshake
+        if key == reqwest::header::CONNECTION
+            || key == reqwest::header::UPGRADE
+            || key.as_str().starts_with("sec-websocket-")
+                && key != reqwest::header::SEC_WEBSOCKET_PROTOCOL
+        {
+            continue;
+        }
+        ws_request.headers_mut().insert(key.clone(), value.clone());
+    }
+
+    let (upstream_ws, upstream_response) = match tokio_tungstenite::connect_async(ws_request).await
+    {
+        Ok(v) => v,
+        Err(e) => {
+            tracing::error!("websocket connection to end-point failed: {}", e);
+            return Ok(actix_web::HttpResponse::BadGateway()
+                .body(format!("could not connect to end-point: {}", e)));
+        }
+    };
+
+    let (mut response, session, client_ws) = actix_ws::handle(&req, payload)
+        .map_err(|e| fastn_core::Error::GenericError(e.to_string()))?;
+    if let Some(protocol) = upstream_response
+        .headers()
+        .get(reqwest::header::SEC_WEBSOCKET_PROTOCOL)
+    {
+        response.headers_mut().insert(
+            actix_web::http::header::SEC_WEBSOCKET_PROTOCOL,
+            protocol.clone(),
+        );
+    }
+
+    actix_web::rt::spawn(pump(session, client_ws, upstream_ws));
+    Ok(response)
+}
+
+async fn pump(
+    mut session: actix_ws::Session,
+    mut client: actix_ws::MessageStream,
+    upstream: tokio_tungstenite::WebSocketStream<
+        tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+    >,
+) {
+    use futures::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::protocol::frame::CloseFrame;
+    use tokio_tungstenite::tungstenite::Message;
+
+    let (mut upstream_tx, mut upstream_rx) = upstream.split();
+
+    loop {
+        tokio::select! {
+            message = client.next() => {
+                let message = match message {
+                    Some(Ok(actix_ws::Message::Text(t))) => Message::Text(t.to_string()),
+                    Some(Ok(actix_ws::Message::Binary(b))) => Message::Binary(b.to_vec()),
+                    Some(Ok(actix_ws::Message::Ping(b))) => Message::Ping(b.to_vec()),
+                    Some(Ok(actix_ws::Message::Pong(b))) => Message::Pong(b.to_vec()),
+                    Some(Ok(actix_ws::Message::Close(reason))) => {
+                        let frame = reason.map(|r| CloseFrame {
+                            code: u16::from(r.code).into(),
+                            reason: r.description.unwrap_or_default().into(),
+                        });
+                        let _ = upstream_tx.send(Message::Close(frame)).await;
+                        break;
+                    }
+                    Some(Ok(_)) => continue,
+                    Some(Err(_)) | None => {
+                        let _ = upstream_tx.send(Message::Close(None)).await;
+                        break;
+                    }
+                };
+                if upstream_tx.send(message).await.is_err() {
+                    break;
+                }
+            }
+            message = upstream_rx.next() => {
+                let sent = match message {
+                    Some(Ok(Message::Text(t))) => session.text(t).await,
+                    Some(Ok(Message::Binary(b))) => session.binary(b).await,
+                    Some(Ok(Message::Ping(b))) => session.ping(&b).await,
+                    Some(Ok(Message::Pong(b))) => session.pong(&b).await,
+                    Some(Ok(Message::Frame(_))) => Ok(()),
+                    Some(Ok(Message::Close(frame))) => {
+                        let reason = frame.map(|f| actix_ws::CloseReason {
+                            code: u16::from(f.code).into(),
+                            description: Some(f.reason.to_string()),
+                        });
+                        let _ = session.close(reason).await;
+                        return;
+                    }
+                    Some(Err(_)) | None => break,
+                };
+                if sent.is_err() {
+                    let _ = upstream_tx.send(Message::Close(None)).await;
+                    return;
+                }
+            }
+        }
+    }
+
+    let _ = session.close(None).await;
+}
+
+/// actix-web request bodies can not be sent across threads, so we forward them to reqwest over
+/// a channel. Once more than `max_body_size` bytes have been read, `too_large` is set and the
+/// body ends with an error, which makes reqwest abort the request.
+fn forward(
+    mut payload: actix_web::web::Payload,
+    max_body_size: Option<usize>,
+    too_large: std::sync::Arc<std::sync::atomic::AtomicBool>,
+) -> Chunks {
+    let (tx, rx) = tokio::sync::mpsc::channel(16);
+    actix_web::rt::spawn(async move {
+        use futures::StreamExt;
+
+        let mut size = 0;
+        while let Some(chunk) = payload.next().await {
+            let chunk = match chunk {
+                Ok(chunk) => {
+                    size += chunk.len();
+                    match max_body_size {
+                        Some(max_body_size) if size > max_body_size => {
+                            too_large.store(true, std::sync::atomic::Ordering::Relaxed);
+                            Err(std::io::Error::new(
+                                std::io::ErrorKind::InvalidData,
+                                format!("request body is larger than {} bytes", max_body_size),
+                            ))
+                        }
+                        _ => Ok(chunk),
+                    }
+                }
+                Err(e) => Err(std::io::Error::new(std::io::ErrorKind::Other, e)),
+            };
+            let failed = chunk.is_err();
+            if tx.send(chunk).await.is_err() || failed {
+                break;
+            }
+        }
+    });
+    Chunks(rx)
+}
+
+struct Chunks(tokio::sync::mpsc::Receiver<std::io::Result<actix_web::web::Bytes>>);
+
+impl futures::Stream for Chunks {
+    type Item = std::io::Result<actix_web::web::Bytes>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.0.poll_recv(cx)
+    }
+}
+
+fn method(method: &str) -> reqwest::Method {
+    match method {
+        "GET" => reqwest::Method::GET,
+        "POST" => reqwest::Method::POST,
+        "PUT" => reqwest::Method::PUT,
+        "DELETE" => reqwest::Method::DELETE,
+        "PATCH" => reqwest::Method::PATCH,
+        "HEAD" => reqwest::Method::HEAD,
+        "OPTIONS" => reqwest::Method::OPTIONS,
+        "TRACE" => reqwest::Method::TRACE,
+        "CONNECT" => reqwest::Method::CONNECT,
+        _ => reqwest::Method::GET,
+    }
+}
+
+fn url(upstream: &Upstream, req: &fastn_core::http::Request) -> fastn_core::Result<reqwest::Url> {
+    Ok(reqwest::Url::parse(
+        format!(
+            "{}/{}{}",
+            upstream.host.trim_end_matches('/'),
+            upstream.path.trim_start_matches('/'),
+            if req.query_string().is_empty() {
+                "".to_string()
+            } else {
+                format!("?{}", req.query_string())
+            }
+        )
+        .as_str(),
+    )?)
+}
+
+fn headers(
+    req: &fastn_core::http::Request,
+    req_headers: &std::collections::HashMap<String, String>,
+) -> reqwest::header::HeaderMap {
+    let mut headers = req.headers().to_owned();
 
     // TODO: Some extra headers, possibly Authentication header
     // Authentication header can come from system environment variable
@@ -76,40 +488,110 @@ pub(crate) async fn get_out(
     // headers
 
     for (header_key, header_value) in req_headers {
-        proxy_request.headers_mut().insert(
+        headers.insert(
             reqwest::header::HeaderName::from_bytes(header_key.as_bytes()).unwrap(),
             reqwest::header::HeaderValue::from_str(header_value.as_str()).unwrap(),
         );
     }
 
-    proxy_request.headers_mut().insert(
+    headers.insert(
         reqwest::header::USER_AGENT,
         reqwest::header::HeaderValue::from_static("fastn"),
     );
 
     if let Some(ip) = req.get_ip() {
-        proxy_request.headers_mut().insert(
+        headers.insert(
             reqwest::header::FORWARDED,
             reqwest::header::HeaderValue::from_str(ip.as_str()).unwrap(),
         );
     }
 
     if let Some(cookies) = req.cookies_string() {
-        proxy_request.headers_mut().insert(
+        headers.insert(
             reqwest::header::COOKIE,
             reqwest::header::HeaderValue::from_str(cookies.as_str()).unwrap(),
         );
     }
 
     for header in fastn_core::utils::ignore_headers() {
-        proxy_request.headers_mut().remove(header);
+        headers.remove(header);
     }
 
-    *proxy_request.body_mut() = Some(req.body().to_vec().into());
+    headers
+}
 
-    Ok(fastn_core::http::ResponseBuilder::from_reqwest(
-        CLIENT.execute(proxy_request).await?,
-        package_name,
-    )
-    .await)
+#[cfg(test)]
+mod test {
+    /// A package whose `/movie/` page is read by `admins` only, and whose `/about/` page is
+    /// rendered with a document that does not exist. Nothing listens on the end-points, the
+    /// requests are answered before they would be sent.
+    async fn config(path: &str) -> (fastn_core::Config, fastn_core::http::Request) {
+        let root =
+            std::env::temp_dir().join(format!("fastn-proxy-template-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(
+            root.join("FASTN.ftd"),
+            indoc::indoc! {"
+                -- import: fastn
+
+                -- fastn.package: proxy-template-test
+
+                -- fastn.proxy-template: /movie/
+                end-point: http://127.0.0.1:9/movie/
+                document: movie.ftd
+
+                -- fastn.proxy-template: /about/
+                end-point: http://127.0.0.1:9/about/
+                document: about.ftd
+
+                -- fastn.user-group: admins
+                email: admin@example.com
+
+                -- fastn.sitemap:
+
+                # Movies: /movie/
+                readers: admins
+            "},
+        )
+        .unwrap();
+        std::fs::write(root.join("movie.ftd"), "-- ftd.text: movie\n").unwrap();
+
+        let req = fastn_core::http::Request::from_actix(
+            actix_web::test::TestRequest::get()
+                .uri(path)
+                .to_http_request(),
+            Default::default(),
+        );
+        let config =
+            fastn_core::Config::read(Some(root.to_string_lossy().to_string()), false, Some(&req))
+                .await
+                .unwrap();
+        (config, req)
+    }
+
+    async fn render(path: &str) -> actix_web::http::StatusCode {
+        let (mut config, req) = config(path).await;
+        let template = fastn_core::package::proxy_template::find_proxy_template(
+            config.package.proxy_templates.as_slice(),
+            path,
+        )
+        .cloned()
+        .unwrap();
+        super::render_template(&mut config, &template, &req)
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn render_template() {
+        assert_eq!(
+            render("/movie/").await,
+            actix_web::http::StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            render("/about/").await,
+            actix_web::http::StatusCode::NOT_FOUND
+        );
+    }
 }
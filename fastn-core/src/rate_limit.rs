@@ -0,0 +1,212 @@
+/// Request rate and body size limits for urls starting with `path`, which can be any route
+/// prefix, eg an app mount point or `/-/edit/`.
+///
+/// ```ftd
+/// -- fastn.rate-limit: /api/
+/// requests-per-minute: 60
+/// burst: 10
+/// max-body-size: 1048576
+/// ```
+///
+/// Rate limits are applied per logged in identity, and per IP address for anonymous requests,
+/// using an in-memory token bucket: a client can make `burst` requests at once, after which
+/// it gets a new request every `60 / requests-per-minute` seconds.
+#[derive(Debug, serde::Deserialize, Clone, PartialEq)]
+pub struct RateLimit {
+    pub path: String,
+    #[serde(rename = "requests-per-minute")]
+    pub requests_per_minute: Option<u32>,
+    /// Defaults to `requests-per-minute`
+    pub burst: Option<u32>,
+    /// In bytes
+    #[serde(rename = "max-body-size")]
+    pub max_body_size: Option<usize>,
+}
+
+/// Once we are tracking this many clients, forget the ones whose buckets have refilled.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// A bucket keeps the rate of the rule it was created for, so buckets of different rules can be
+/// refilled together when the idle ones are forgotten.
+struct Bucket {
+    tokens: f64,
+    updated: std::time::Instant,
+    per_second: f64,
+    capacity: f64,
+}
+
+impl Bucket {
+    fn full(per_second: f64, capacity: f64, now: std::time::Instant) -> Bucket {
+        Bucket {
+            tokens: capacity,
+            updated: now,
+            per_second,
+            capacity,
+        }
+    }
+
+    fn refill(&mut self, now: std::time::Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.per_second).min(self.capacity);
+        self.updated = now;
+    }
+
+    fn is_full(&self) -> bool {
+        self.tokens >= self.capacity
+    }
+
+    /// Take a token, or return how long to wait for the next one.
+    fn take(&mut self, now: std::time::Instant) -> Result<(), std::time::Duration> {
+        self.refill(now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return Ok(());
+        }
+        Err(std::time::Duration::from_secs_f64(
+            (1.0 - self.tokens) / self.per_second,
+        ))
+    }
+}
+
+/// (rate-limit path, client) -> bucket
+static BUCKETS: once_cell::sync::Lazy<
+    std::sync::Mutex<std::collections::HashMap<(String, String), Bucket>>,
+> = once_cell::sync::Lazy::new(|| std::sync::Mutex::new(std::collections::HashMap::new()));
+
+/// The most specific rate limit matching `path`.
+pub(crate) fn find_rate_limit<'a>(limits: &'a [RateLimit], path: &str) -> Option<&'a RateLimit> {
+    limits
+        .iter()
+        .filter(|l| path.starts_with(l.path.as_str()))
+        .max_by_key(|l| l.path.len())
+}
+
+/// Returns `413 Payload Too Large` or `429 Too Many Requests` if the request is over `limit`.
+pub(crate) async fn check(
+    limit: &RateLimit,
+    req: &fastn_core::http::Request,
+) -> Option<fastn_core::http::Response> {
+    if let Some(max_body_size) = limit.max_body_size {
+        if req.body().len() > max_body_size {
+            return Some(payload_too_large(max_body_size));
+        }
+    }
+
+    let requests_per_minute = limit.requests_per_minute?;
+    if requests_per_minute == 0 {
+        return Some(too_many_requests(std::time::Duration::from_secs(60)));
+    }
+    let per_second = requests_per_minute as f64 / 60.0;
+    let capacity = limit.burst.unwrap_or(requests_per_minute).max(1) as f64;
+    let client = client(req).await;
+    let now = std::time::Instant::now();
+
+    let mut buckets = BUCKETS.lock().unwrap();
+    if buckets.len() >= MAX_TRACKED_CLIENTS {
+        buckets.retain(|_, b| {
+            b.refill(now);
+            !b.is_full()
+        });
+    }
+
+    let bucket = buckets
+        .entry((limit.path.to_string(), client))
+        .or_insert_with(|| Bucket::full(per_second, capacity, now));
+    // the rule may have changed since the bucket was created
+    bucket.per_second = per_second;
+    bucket.capacity = capacity;
+    match bucket.take(now) {
+        Ok(()) => None,
+        Err(retry_after) => Some(too_many_requests(retry_after)),
+    }
+}
+
+/// Logged in users are identified by their verified session, everyone else, including clients
+/// sending auth cookies this server did not issue, by their IP address.
+async fn client(req: &fastn_core::http::Request) -> String {
+    match fastn_core::auth::verified_user(req.cookies()).await {
+        Some(user) => format!("user:{}", user),
+        None => format!("ip:{}", req.get_ip().unwrap_or_default()),
+    }
+}
+
+pub(crate) fn payload_too_large(max_body_size: usize) -> fastn_core::http::Response {
+    actix_web::HttpResponse::PayloadTooLarge().body(format!(
+        "request body is larger than {} bytes",
+        max_body_size
+    ))
+}
+
+fn too_many_requests(retry_after: std::time::Duration) -> fastn_core::http::Response {
+    // Retry-After is in whole seconds, round up so the client does not retry too early
+    let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+    actix_web::HttpResponse::TooManyRequests()
+        .insert_header((actix_web::http::header::RETRY_AFTER, seconds.to_string()))
+        .body("too many requests")
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn bucket() {
+        let start = std::time::Instant::now();
+        // 60 requests per minute, bursts of 2
+        let mut bucket = super::Bucket::full(1.0, 2.0, start);
+
+        assert!(bucket.take(start).is_ok());
+        assert!(bucket.take(start).is_ok());
+        assert_eq!(bucket.take(start), Err(std::time::Duration::from_secs(1)));
+
+        let later = start + std::time::Duration::from_millis(1500);
+        assert!(bucket.take(later).is_ok());
+        assert_eq!(
+            bucket.take(later),
+            Err(std::time::Duration::from_millis(500))
+        );
+
+        // never refills beyond the burst size
+        let much_later = start + std::time::Duration::from_secs(3600);
+        assert!(bucket.take(much_later).is_ok());
+        assert!(bucket.take(much_later).is_ok());
+        assert!(bucket.take(much_later).is_err());
+    }
+
+    #[test]
+    fn refill_at_own_rate() {
+        let start = std::time::Instant::now();
+        // a slow rule and a fast rule, refilled together when idle buckets are forgotten
+        let mut slow = super::Bucket::full(0.1, 10.0, start);
+        let mut fast = super::Bucket::full(10.0, 10.0, start);
+        for _ in 0..10 {
+            assert!(slow.take(start).is_ok());
+            assert!(fast.take(start).is_ok());
+        }
+
+        let later = start + std::time::Duration::from_secs(1);
+        slow.refill(later);
+        fast.refill(later);
+        assert!(!slow.is_full());
+        assert!(fast.is_full());
+    }
+
+    #[test]
+    fn find_rate_limit() {
+        let limit = |path: &str| super::RateLimit {
+            path: path.to_string(),
+            requests_per_minute: Some(60),
+            burst: None,
+            max_body_size: None,
+        };
+        let limits = vec![limit("/"), limit("/-/edit/")];
+
+        assert_eq!(
+            super::find_rate_limit(&limits, "/-/edit/").map(|l| l.path.as_str()),
+            Some("/-/edit/")
+        );
+        assert_eq!(
+            super::find_rate_limit(&limits, "/blog/").map(|l| l.path.as_str()),
+            Some("/")
+        );
+        assert_eq!(super::find_rate_limit(&limits[1..], "/blog/"), None);
+    }
+}
@@ -0,0 +1,420 @@
+/// Resized variants, in a modern format, of the images used by `ftd.image`, generated by
+/// `fastn build`.
+///
+/// ```ftd
+/// -- fastn.responsive-images:
+/// widths: 480, 960, 1440
+/// format: avif
+/// placeholder: true
+/// ```
+///
+/// For every local image referred as `$assets.files.<path>`, a variant is generated for each
+/// width smaller than the image, and one with the width of the image. `ftd.image` gets them as
+/// `srcset`, along with the `width` and `height` of the image, so the browser can reserve the
+/// space before the image is downloaded. With `placeholder`, a tiny blurred version of the image
+/// is inlined as the background of the `img` till the image loads.
+///
+/// Images that can not be decoded, and animated GIFs, are used as they are, without variants.
+/// `webp` and `avif` need the `image-encoders` feature of `fastn-core`, which is on by default.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponsiveImages {
+    pub widths: Vec<u32>,
+    pub format: Format,
+    /// 0 to 100, used by the lossy formats
+    pub quality: u8,
+    pub placeholder: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    /// Same format as the image
+    Original,
+    WebP,
+    Avif,
+}
+
+#[derive(Debug, serde::Deserialize, Clone)]
+pub(crate) struct ResponsiveImagesTemp {
+    pub widths: String,
+    pub format: String,
+    pub quality: i64,
+    pub placeholder: bool,
+}
+
+impl ResponsiveImagesTemp {
+    pub(crate) fn into_responsive_images(self) -> fastn_core::Result<ResponsiveImages> {
+        let mut widths = self
+            .widths
+            .split(',')
+            .map(|w| {
+                w.trim()
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|w| *w > 0)
+                    .ok_or_else(|| fastn_core::Error::PackageError {
+                        message: format!("fastn.responsive-images: invalid width `{}`", w.trim()),
+                    })
+            })
+            .collect::<fastn_core::Result<Vec<u32>>>()?;
+        widths.sort_unstable();
+        widths.dedup();
+
+        let format = match self.format.trim() {
+            "original" => Format::Original,
+            "webp" => Format::WebP,
+            "avif" => Format::Avif,
+            t => {
+                return Err(fastn_core::Error::PackageError {
+                    message: format!(
+                        "fastn.responsive-images: unknown format `{}`, expected one of \
+                        `original`, `webp` or `avif`",
+                        t
+                    ),
+                })
+            }
+        };
+
+        if format != Format::Original && !cfg!(feature = "image-encoders") {
+            return Err(fastn_core::Error::PackageError {
+                message: format!(
+                    "fastn.responsive-images: format `{}` needs fastn built with the \
+                    `image-encoders` feature, use `original` instead",
+                    self.format.trim()
+                ),
+            });
+        }
+
+        if !(0..=100).contains(&self.quality) {
+            return Err(fastn_core::Error::PackageError {
+                message: format!(
+                    "fastn.responsive-images: quality must be between 0 and 100, found {}",
+                    self.quality
+                ),
+            });
+        }
+
+        Ok(ResponsiveImages {
+            widths,
+            format,
+            quality: self.quality as u8,
+            placeholder: self.placeholder,
+        })
+    }
+}
+
+/// What `ftd.image-src` gets for an image processed by `ResponsiveImages::process()`.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+pub struct ProcessedImage {
+    pub width: u32,
+    pub height: u32,
+    /// Width and path, relative to the package, of the generated variants, smallest first
+    pub variants: Vec<(u32, String)>,
+    /// `data:` url of the blurred placeholder
+    pub placeholder: Option<String>,
+}
+
+impl ProcessedImage {
+    /// `srcset` attribute, `prefix` is the url the package is served at, eg `-/<package>`.
+    pub fn srcset(&self, prefix: &str) -> String {
+        self.variants
+            .iter()
+            .map(|(width, path)| format!("{}/{} {}w", prefix.trim_end_matches('/'), path, width))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl ResponsiveImages {
+    /// Widths of the variants generated for an image `width` pixels wide, images are never
+    /// scaled up.
+    fn variant_widths(&self, width: u32) -> Vec<u32> {
+        let mut widths: Vec<u32> = self.widths.iter().copied().filter(|w| *w < width).collect();
+        widths.push(width);
+        widths
+    }
+
+    fn extension(&self, original: image::ImageFormat) -> &'static str {
+        match self.format {
+            Format::WebP => "webp",
+            Format::Avif => "avif",
+            Format::Original => original.extensions_str().first().copied().unwrap_or("png"),
+        }
+    }
+
+    /// Generates the variants of the image at `path` of the package in `build_dir`.
+    ///
+    /// Returns `None` for images that can not be resized, eg svg, animated GIFs or images that
+    /// fail to decode, they are used as they are. Results are cached by the hash of the image and
+    /// the settings, so unchanged images are not encoded again on the next build.
+    pub async fn process(
+        &self,
+        build_dir: &camino::Utf8Path,
+        path: &str,
+        content: Vec<u8>,
+    ) -> fastn_core::Result<Option<ProcessedImage>> {
+        let original = match image::guess_format(content.as_slice()) {
+            Ok(f) if f != image::ImageFormat::Ico => f,
+            _ => return Ok(None),
+        };
+
+        let hash = fastn_core::utils::generate_hash(
+            [content.as_slice(), format!("{:?}", self).as_bytes()].concat(),
+        );
+        let (stem, _) = path.rsplit_once('.').unwrap_or((path, ""));
+        let extension = self.extension(original);
+
+        let processed = match fastn_core::utils::get_cached(&cache_id(&hash, "json")) {
+            Some(p) => p,
+            None => {
+                let (settings, h) = (self.clone(), hash.clone());
+                let processed: Option<ProcessedImage> = tokio::task::spawn_blocking(move || {
+                    settings.encode(&h, original, content.as_slice())
+                })
+                .await
+                .map_err(|e| fastn_core::Error::GenericError(e.to_string()))??;
+                fastn_core::utils::cache_it(&cache_id(&hash, "json"), &processed)?;
+                processed
+            }
+        };
+        let processed = match processed {
+            Some(processed) => processed,
+            None => return Ok(None),
+        };
+
+        let mut variants = vec![];
+        for (width, _) in processed.variants.iter() {
+            let variant_path = format!("{}-{}w.{}", stem, width, extension);
+            let cached = fastn_core::utils::get_cache_file(&cache_id(
+                &hash,
+                format!("{}.{}", width, extension).as_str(),
+            ))
+            .ok_or_else(|| fastn_core::Error::GenericError("cache dir not found".to_string()))?;
+            fastn_core::utils::update(
+                build_dir.join(variant_path.as_str()),
+                &std::fs::read(cached)?,
+            )
+            .await?;
+            variants.push((*width, variant_path));
+        }
+
+        Ok(Some(ProcessedImage {
+            variants,
+            ..processed
+        }))
+    }
+
+    /// Encodes the variants into the cache dir, paths of the variants in the result are empty.
+    fn encode(
+        &self,
+        hash: &str,
+        original: image::ImageFormat,
+        content: &[u8],
+    ) -> fastn_core::Result<Option<ProcessedImage>> {
+        if original == image::ImageFormat::Gif && is_animated_gif(content) {
+            return Ok(None);
+        }
+        let img = match image::load_from_memory_with_format(content, original) {
+            Ok(img) => img,
+            Err(e) => {
+                fastn_core::warning!("Could not decode image, variants not generated: {}", e);
+                return Ok(None);
+            }
+        };
+        let (width, height) = (img.width(), img.height());
+        let extension = self.extension(original);
+
+        let mut variants = vec![];
+        for w in self.variant_widths(width) {
+            let h = ((height as u64 * w as u64) / width as u64).max(1) as u32;
+            let resized = if w == width {
+                img.clone()
+            } else {
+                img.resize_exact(w, h, image::imageops::FilterType::Lanczos3)
+            };
+            let bytes = self.encode_one(&resized, original)?;
+            let file = fastn_core::utils::get_cache_file(&cache_id(
+                hash,
+                format!("{}.{}", w, extension).as_str(),
+            ))
+            .ok_or_else(|| fastn_core::Error::GenericError("cache dir not found".to_string()))?;
+            if let Some(parent) = file.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(file, bytes)?;
+            variants.push((w, String::new()));
+        }
+
+        let placeholder = if self.placeholder {
+            use base64::Engine;
+
+            let mut bytes = std::io::Cursor::new(vec![]);
+            img.thumbnail(16, 16)
+                .blur(1.0)
+                .write_to(&mut bytes, image::ImageFormat::Png)
+                .map_err(|e| fastn_core::Error::GenericError(e.to_string()))?;
+            Some(format!(
+                "data:image/png;base64,{}",
+                base64::engine::general_purpose::STANDARD.encode(bytes.into_inner())
+            ))
+        } else {
+            None
+        };
+
+        Ok(Some(ProcessedImage {
+            width,
+            height,
+            variants,
+            placeholder,
+        }))
+    }
+
+    fn encode_one(
+        &self,
+        img: &image::DynamicImage,
+        original: image::ImageFormat,
+    ) -> fastn_core::Result<Vec<u8>> {
+        let mut bytes = std::io::Cursor::new(vec![]);
+        match self.format {
+            #[cfg(feature = "image-encoders")]
+            Format::WebP => {
+                let rgba = img.to_rgba8();
+                let encoder = webp::Encoder::from_rgba(rgba.as_raw(), rgba.width(), rgba.height());
+                return Ok(encoder.encode(self.quality as f32).to_vec());
+            }
+            #[cfg(feature = "image-encoders")]
+            Format::Avif => {
+                img.write_with_encoder(image::codecs::avif::AvifEncoder::new_with_speed_quality(
+                    &mut bytes,
+                    6,
+                    self.quality,
+                ))
+            }
+            // rejected by `ResponsiveImagesTemp::into_responsive_images()`
+            #[cfg(not(feature = "image-encoders"))]
+            Format::WebP | Format::Avif => {
+                return Err(fastn_core::Error::GenericError(
+                    "fastn is built without the `image-encoders` feature".to_string(),
+                ))
+            }
+            Format::Original if original == image::ImageFormat::Jpeg => img.write_with_encoder(
+                image::codecs::jpeg::JpegEncoder::new_with_quality(&mut bytes, self.quality),
+            ),
+            Format::Original => img.write_to(&mut bytes, original),
+        }
+        .map_err(|e| fastn_core::Error::GenericError(e.to_string()))?;
+        Ok(bytes.into_inner())
+    }
+}
+
+/// The variants of an animated GIF would only have its first frame.
+fn is_animated_gif(content: &[u8]) -> bool {
+    use image::AnimationDecoder;
+
+    image::codecs::gif::GifDecoder::new(std::io::Cursor::new(content))
+        .map(|decoder| decoder.into_frames().take(2).count() > 1)
+        .unwrap_or(false)
+}
+
+fn cache_id(hash: &str, name: &str) -> String {
+    format!("images/{}-{}", hash, name)
+}
+
+/// Variants of the image at `path` of `package`, processed once per build. `None` if
+/// `fastn.responsive-images` is not set in FASTN.ftd, or the image can not be resized.
+pub(crate) async fn for_asset(
+    config: &mut fastn_core::Config,
+    package: &fastn_core::Package,
+    path: &str,
+) -> fastn_core::Result<Option<ProcessedImage>> {
+    let settings = match config.package.responsive_images.clone() {
+        Some(settings) => settings,
+        None => return Ok(None),
+    };
+
+    let key = format!("{}/{}", package.name, path);
+    if let Some(processed) = config.processed_images.get(&key) {
+        return Ok(processed.clone());
+    }
+
+    let content = package.resolve_by_file_name(path, None, false).await?;
+    let build_dir = config.build_dir().join("-").join(package.name.as_str());
+    let processed = settings.process(&build_dir, path, content).await?;
+    config.processed_images.insert(key, processed.clone());
+    Ok(processed)
+}
+
+#[cfg(test)]
+mod test {
+    fn temp(widths: &str, format: &str) -> super::ResponsiveImagesTemp {
+        super::ResponsiveImagesTemp {
+            widths: widths.to_string(),
+            format: format.to_string(),
+            quality: 80,
+            placeholder: false,
+        }
+    }
+
+    #[test]
+    fn into_responsive_images() {
+        let r = temp("960, 480,960", "original")
+            .into_responsive_images()
+            .unwrap();
+        assert_eq!(r.widths, vec![480, 960]);
+        assert_eq!(r.format, super::Format::Original);
+        assert_eq!(
+            temp("480", "avif").into_responsive_images().is_ok(),
+            cfg!(feature = "image-encoders")
+        );
+
+        assert!(temp("480, wide", "original")
+            .into_responsive_images()
+            .is_err());
+        assert!(temp("480", "gif").into_responsive_images().is_err());
+    }
+
+    #[test]
+    fn variant_widths() {
+        let r = temp("480, 960, 1440", "original")
+            .into_responsive_images()
+            .unwrap();
+        assert_eq!(r.variant_widths(1200), vec![480, 960, 1200]);
+        assert_eq!(r.variant_widths(300), vec![300]);
+    }
+
+    #[test]
+    fn not_resized() {
+        let r = temp("480", "original").into_responsive_images().unwrap();
+        assert_eq!(
+            r.encode("hash", image::ImageFormat::Png, b"not a png")
+                .unwrap(),
+            None
+        );
+
+        let gif = |frames: usize| {
+            let mut bytes = vec![];
+            image::codecs::gif::GifEncoder::new(&mut bytes)
+                .encode_frames((0..frames).map(|_| image::Frame::new(image::RgbaImage::new(2, 2))))
+                .unwrap();
+            bytes
+        };
+        assert!(super::is_animated_gif(&gif(2)));
+        assert!(!super::is_animated_gif(&gif(1)));
+    }
+
+    #[test]
+    fn srcset() {
+        let p = super::ProcessedImage {
+            width: 1200,
+            height: 600,
+            variants: vec![
+                (480, "images/hero-480w.webp".to_string()),
+                (1200, "images/hero-1200w.webp".to_string()),
+            ],
+            placeholder: None,
+        };
+        assert_eq!(
+            p.srcset("-/fastn.com/"),
+            "-/fastn.com/images/hero-480w.webp 480w, -/fastn.com/images/hero-1200w.webp 1200w"
+        );
+    }
+}
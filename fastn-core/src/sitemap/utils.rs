@@ -74,6 +74,16 @@ pub fn url_match(
                 let value = value.parse::<bool>()?;
                 Ok(ftd::Value::Boolean { value })
             }
+            "datetime" => {
+                // `2024-01-31`, `2024-01-31T10:30:00+05:30` or epoch seconds, passed on as
+                // normalised RFC 3339 text which `datetime` variables parse again
+                let value = fastn_grammar::datetime::parse(value)
+                    .map_err(|e| fastn_core::Error::GenericError(e.to_string()))?;
+                Ok(ftd::Value::String {
+                    text: fastn_grammar::datetime::to_string(&value),
+                    source: ftd::TextSource::Default,
+                })
+            }
             _ => unimplemented!(),
         }
     }
@@ -787,7 +787,7 @@ pub fn replace_markers_2023(
                 "#,
                 hashed_markdown_js(),
                 hashed_prism_js(),
-                hashed_default_ftd_js(config.package.name.as_str()),
+                hashed_default_ftd_js(),
                 hashed_prism_css(),
                 scripts,
             )
@@ -799,6 +799,40 @@ pub fn replace_markers_2023(
     )
 }
 
+/// `replace_markers_2023()` for the pages shown without JS, `ftd::ftd_static_html()`, which load
+/// none of the JS of fastn.
+pub fn replace_markers_static(
+    s: &str,
+    html: &fastn_js::StaticHtml,
+    scripts: &str,
+    font_style: &str,
+    default_css: &str,
+    base_url: &str,
+    config: &fastn_core::Config,
+) -> String {
+    ftd::html::utils::trim_all_lines(
+        s.replace(
+            "__html_body__",
+            format!("{}{}", html.body, font_style).as_str(),
+        )
+        .replace("__html_head__", html.head.as_str())
+        .replace(
+            "__favicon_html_tag__",
+            resolve_favicon(
+                config.root.as_str(),
+                config.package.name.as_str(),
+                &config.package.favicon,
+            )
+            .unwrap_or_default()
+            .as_str(),
+        )
+        .replace("__script_file__", scripts)
+        .replace("__default_css__", default_css)
+        .replace("__base_url__", base_url)
+        .as_str(),
+    )
+}
+
 pub fn is_test() -> bool {
     cfg!(test) || std::env::args().any(|e| e == "--test")
 }
@@ -996,15 +1030,17 @@ pub fn hashed_default_js_name() -> &'static str {
     &JS_HASH
 }
 
-static FTD_JS_HASH: once_cell::sync::OnceCell<String> = once_cell::sync::OnceCell::new();
+static FTD_JS_HASH: once_cell::sync::Lazy<String> = once_cell::sync::Lazy::new(|| {
+    format!(
+        "-/fastn-{}.js",
+        generate_hash(ftd::js::all_js_without_test())
+    )
+});
 
-pub fn hashed_default_ftd_js(package_name: &str) -> &'static str {
-    FTD_JS_HASH.get_or_init(|| {
-        format!(
-            "default-{}.js",
-            generate_hash(ftd::js::all_js_without_test(package_name).as_str())
-        )
-    })
+/// Path, relative to the base url, of the runtime and default bag JS shared by all the pages. It
+/// only changes with the fastn version, so it is served as immutable.
+pub fn hashed_default_ftd_js() -> &'static str {
+    &FTD_JS_HASH
 }
 
 static MARKDOWN_HASH: once_cell::sync::Lazy<String> =
@@ -1014,6 +1050,54 @@ pub fn hashed_markdown_js() -> &'static str {
     &MARKDOWN_HASH
 }
 
+/// Saves a JS file generated for the pages, the JS of an ftd module split out of the pages, see
+/// `split-js` of `fastn.package`, or a source map, see `fastn build --minify`, as
+/// `<name>-<hash>.<extension>`, and returns its path relative to the base url. `fastn build`
+/// writes it to the build dir, for `fastn serve` it is kept in the cache dir.
+pub async fn save_js_file(
+    build_dir: &camino::Utf8Path,
+    name: &str,
+    extension: &str,
+    content: &str,
+    build: bool,
+) -> fastn_core::Result<String> {
+    let file_name = format!(
+        "{}-{}.{}",
+        fastn_js::utils::name_to_js_(name),
+        generate_hash(content),
+        extension
+    );
+    if build {
+        let file = build_dir.join("-").join("js").join(file_name.as_str());
+        if !file.exists() {
+            update(file, content.as_bytes()).await?;
+        }
+    } else if cached_js_file(file_name.as_str()).is_none() {
+        cache_it(format!("js/{}", file_name).as_str(), content)?;
+    }
+    Ok(format!("-/js/{}", file_name))
+}
+
+/// A file saved by `save_js_file()` for `fastn serve`.
+pub fn cached_js_file(file_name: &str) -> Option<String> {
+    get_cached(format!("js/{}", file_name).as_str())
+}
+
+/// Where `fastn build` writes the fragment of the page built to `file_path`, eg
+/// `-/fragments/foo/index.json` for `foo/index.html`, see `spa` of `fastn.package`.
+pub fn spa_fragment_path(file_path: &str) -> String {
+    format!(
+        "-/fragments/{}",
+        replace_last_n(file_path.trim_start_matches('/'), 1, ".html", ".json")
+    )
+}
+
+/// Path of the page whose fragment is requested, `foo/` for `-/fragments/foo/index.json`.
+pub fn spa_fragment_page(path: &str) -> Option<&str> {
+    path.strip_prefix("-/fragments/")?
+        .strip_suffix("index.json")
+}
+
 static PRISM_JS_HASH: once_cell::sync::Lazy<String> =
     once_cell::sync::Lazy::new(|| format!("prism-{}.js", generate_hash(ftd::prism_js().as_str()),));
 
@@ -1065,6 +1149,24 @@ mod tests {
             ]
         )
     }
+
+    #[test]
+    fn spa_fragment() {
+        assert_eq!(
+            super::spa_fragment_path("foo/index.html"),
+            "-/fragments/foo/index.json"
+        );
+        assert_eq!(
+            super::spa_fragment_path("index.html"),
+            "-/fragments/index.json"
+        );
+        assert_eq!(
+            super::spa_fragment_page("-/fragments/foo/index.json"),
+            Some("foo/")
+        );
+        assert_eq!(super::spa_fragment_page("-/fragments/index.json"), Some(""));
+        assert_eq!(super::spa_fragment_page("foo/index.json"), None);
+    }
 }
 
 pub fn ignore_headers() -> Vec<&'static str> {
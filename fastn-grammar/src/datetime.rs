@@ -0,0 +1,644 @@
+// Helpers behind the `datetime` kind of ftd.
+//
+// A datetime travels through the interpreter, the evaluator and the generated JS as an RFC 3339
+// string that keeps the offset it was parsed with, e.g. `2023-10-18T09:30:00+05:30`. All the
+// calendar functions (start of day, adding days etc.) work in that offset.
+
+pub type DateTime = chrono::DateTime<chrono::FixedOffset>;
+
+/// Month and weekday names, and the words of [relative], in a language. The generated JS has
+/// the same tables, see [locales_json], so the server rendered text does not change on hydration.
+#[derive(serde::Serialize)]
+pub struct Locale {
+    pub language: &'static str,
+    pub months: [&'static str; 12],
+    pub months_short: [&'static str; 12],
+    /// Monday first.
+    pub weekdays: [&'static str; 7],
+    pub weekdays_short: [&'static str; 7],
+    pub am_pm: [&'static str; 2],
+    pub just_now: &'static str,
+    /// `{}` is the count and the unit, `{} ago`.
+    pub past: &'static str,
+    pub future: &'static str,
+    /// The singular and the plural of minute, hour, day, month and year.
+    pub units: [[&'static str; 2]; 5],
+}
+
+/// English comes first, it is used for the languages that are not here.
+pub const LOCALES: [Locale; 6] = [
+    Locale {
+        language: "en",
+        months: [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ],
+        months_short: [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ],
+        weekdays: [
+            "Monday",
+            "Tuesday",
+            "Wednesday",
+            "Thursday",
+            "Friday",
+            "Saturday",
+            "Sunday",
+        ],
+        weekdays_short: ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"],
+        am_pm: ["AM", "PM"],
+        just_now: "just now",
+        past: "{} ago",
+        future: "in {}",
+        units: [
+            ["minute", "minutes"],
+            ["hour", "hours"],
+            ["day", "days"],
+            ["month", "months"],
+            ["year", "years"],
+        ],
+    },
+    Locale {
+        language: "fr",
+        months: [
+            "janvier",
+            "février",
+            "mars",
+            "avril",
+            "mai",
+            "juin",
+            "juillet",
+            "août",
+            "septembre",
+            "octobre",
+            "novembre",
+            "décembre",
+        ],
+        months_short: [
+            "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.",
+            "nov.", "déc.",
+        ],
+        weekdays: [
+            "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche",
+        ],
+        weekdays_short: ["lun.", "mar.", "mer.", "jeu.", "ven.", "sam.", "dim."],
+        am_pm: ["AM", "PM"],
+        just_now: "à l’instant",
+        past: "il y a {}",
+        future: "dans {}",
+        units: [
+            ["minute", "minutes"],
+            ["heure", "heures"],
+            ["jour", "jours"],
+            ["mois", "mois"],
+            ["an", "ans"],
+        ],
+    },
+    Locale {
+        language: "de",
+        months: [
+            "Januar",
+            "Februar",
+            "März",
+            "April",
+            "Mai",
+            "Juni",
+            "Juli",
+            "August",
+            "September",
+            "Oktober",
+            "November",
+            "Dezember",
+        ],
+        months_short: [
+            "Jan.", "Feb.", "März", "Apr.", "Mai", "Juni", "Juli", "Aug.", "Sept.", "Okt.", "Nov.",
+            "Dez.",
+        ],
+        weekdays: [
+            "Montag",
+            "Dienstag",
+            "Mittwoch",
+            "Donnerstag",
+            "Freitag",
+            "Samstag",
+            "Sonntag",
+        ],
+        weekdays_short: ["Mo.", "Di.", "Mi.", "Do.", "Fr.", "Sa.", "So."],
+        am_pm: ["AM", "PM"],
+        just_now: "gerade eben",
+        past: "vor {}",
+        future: "in {}",
+        units: [
+            ["Minute", "Minuten"],
+            ["Stunde", "Stunden"],
+            ["Tag", "Tagen"],
+            ["Monat", "Monaten"],
+            ["Jahr", "Jahren"],
+        ],
+    },
+    Locale {
+        language: "es",
+        months: [
+            "enero",
+            "febrero",
+            "marzo",
+            "abril",
+            "mayo",
+            "junio",
+            "julio",
+            "agosto",
+            "septiembre",
+            "octubre",
+            "noviembre",
+            "diciembre",
+        ],
+        months_short: [
+            "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic",
+        ],
+        weekdays: [
+            "lunes",
+            "martes",
+            "miércoles",
+            "jueves",
+            "viernes",
+            "sábado",
+            "domingo",
+        ],
+        weekdays_short: ["lun", "mar", "mié", "jue", "vie", "sáb", "dom"],
+        am_pm: ["a. m.", "p. m."],
+        just_now: "justo ahora",
+        past: "hace {}",
+        future: "dentro de {}",
+        units: [
+            ["minuto", "minutos"],
+            ["hora", "horas"],
+            ["día", "días"],
+            ["mes", "meses"],
+            ["año", "años"],
+        ],
+    },
+    Locale {
+        language: "pt",
+        months: [
+            "janeiro",
+            "fevereiro",
+            "março",
+            "abril",
+            "maio",
+            "junho",
+            "julho",
+            "agosto",
+            "setembro",
+            "outubro",
+            "novembro",
+            "dezembro",
+        ],
+        months_short: [
+            "jan", "fev", "mar", "abr", "mai", "jun", "jul", "ago", "set", "out", "nov", "dez",
+        ],
+        weekdays: [
+            "segunda-feira",
+            "terça-feira",
+            "quarta-feira",
+            "quinta-feira",
+            "sexta-feira",
+            "sábado",
+            "domingo",
+        ],
+        weekdays_short: ["seg", "ter", "qua", "qui", "sex", "sáb", "dom"],
+        am_pm: ["AM", "PM"],
+        just_now: "agora mesmo",
+        past: "há {}",
+        future: "em {}",
+        units: [
+            ["minuto", "minutos"],
+            ["hora", "horas"],
+            ["dia", "dias"],
+            ["mês", "meses"],
+            ["ano", "anos"],
+        ],
+    },
+    Locale {
+        language: "it",
+        months: [
+            "gennaio",
+            "febbraio",
+            "marzo",
+            "aprile",
+            "maggio",
+            "giugno",
+            "luglio",
+            "agosto",
+            "settembre",
+            "ottobre",
+            "novembre",
+            "dicembre",
+        ],
+        months_short: [
+            "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+        ],
+        weekdays: [
+            "lunedì",
+            "martedì",
+            "mercoledì",
+            "giovedì",
+            "venerdì",
+            "sabato",
+            "domenica",
+        ],
+        weekdays_short: ["lun", "mar", "mer", "gio", "ven", "sab", "dom"],
+        am_pm: ["AM", "PM"],
+        just_now: "proprio ora",
+        past: "{} fa",
+        future: "tra {}",
+        units: [
+            ["minuto", "minuti"],
+            ["ora", "ore"],
+            ["giorno", "giorni"],
+            ["mese", "mesi"],
+            ["anno", "anni"],
+        ],
+    },
+];
+
+/// The tables of a BCP 47 language tag, `pt-BR` is `pt`, English for the languages that are not
+/// in [LOCALES].
+pub fn locale(tag: Option<&str>) -> &'static Locale {
+    let language = tag
+        .and_then(|tag| tag.split(['-', '_']).next())
+        .unwrap_or_default()
+        .to_lowercase();
+    LOCALES
+        .iter()
+        .find(|l| l.language == language)
+        .unwrap_or(&LOCALES[0])
+}
+
+/// [LOCALES] as JSON, for `ftd_datetime` of the generated JS.
+pub fn locales_json() -> String {
+    serde_json::to_string(&LOCALES).unwrap()
+}
+
+fn error<T>(message: String) -> fastn_grammar::evalexpr::EvalexprResult<T> {
+    Err(fastn_grammar::evalexpr::EvalexprError::CustomMessage(
+        message,
+    ))
+}
+
+/// Parses ISO-8601 / RFC 3339 timestamps, postgres `timestamptz` text output (`2023-10-18
+/// 09:30:00.123+05:30`), timestamps without an offset (taken as UTC), plain dates, and
+/// integer epochs (see [from_epoch]).
+pub fn parse(value: &str) -> fastn_grammar::evalexpr::EvalexprResult<DateTime> {
+    let value = value.trim();
+    if let Ok(epoch) = value.parse::<i64>() {
+        return from_epoch(epoch);
+    }
+    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Ok(dt);
+    }
+
+    let with_t = value.replacen(' ', "T", 1);
+    // postgres writes offsets as `+05` or `+0530`
+    for pattern in ["%Y-%m-%dT%H:%M:%S%.f%#z", "%Y-%m-%dT%H:%M:%S%.f%z"] {
+        if let Ok(dt) = chrono::DateTime::parse_from_str(with_t.as_str(), pattern) {
+            return Ok(dt);
+        }
+    }
+    for pattern in ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%dT%H:%M"] {
+        if let Ok(dt) = chrono::NaiveDateTime::parse_from_str(with_t.as_str(), pattern) {
+            return Ok(utc(dt));
+        }
+    }
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(utc(date.and_time(chrono::NaiveTime::MIN)));
+    }
+
+    error(format!("Can't parse `{}` as datetime", value))
+}
+
+/// Epoch in seconds, values too large to be seconds (after year 5138) are taken as milliseconds,
+/// which is what `Date.now()` and most JSON APIs hand out.
+pub fn from_epoch(value: i64) -> fastn_grammar::evalexpr::EvalexprResult<DateTime> {
+    use chrono::TimeZone;
+
+    let (seconds, millis) = if value.abs() >= 100_000_000_000 {
+        (value.div_euclid(1000), value.rem_euclid(1000))
+    } else {
+        (value, 0)
+    };
+    match chrono::Utc.timestamp_opt(seconds, (millis * 1_000_000) as u32) {
+        chrono::LocalResult::Single(dt) => Ok(dt.fixed_offset()),
+        _ => error(format!("Epoch `{}` is out of range", value)),
+    }
+}
+
+fn utc(dt: chrono::NaiveDateTime) -> DateTime {
+    chrono::DateTime::<chrono::Utc>::from_naive_utc_and_offset(dt, chrono::Utc).fixed_offset()
+}
+
+/// The canonical string form of a datetime.
+pub fn to_string(dt: &DateTime) -> String {
+    dt.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
+}
+
+pub fn now() -> DateTime {
+    use chrono::TimeZone;
+
+    let since_epoch = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    chrono::Utc
+        .timestamp_opt(since_epoch.as_secs() as i64, since_epoch.subsec_nanos())
+        .unwrap()
+        .fixed_offset()
+}
+
+/// Formats with a strftime like `pattern` (`%Y-%m-%d %H:%M`, `%e %B %Y`...), the names of months
+/// and weekdays, and AM/PM, are in the language of `locale`, see [locale].
+pub fn format(
+    dt: &DateTime,
+    pattern: &str,
+    locale: Option<&str>,
+) -> fastn_grammar::evalexpr::EvalexprResult<String> {
+    use chrono::{Datelike, Timelike};
+    use std::fmt::Write;
+
+    let locale = self::locale(locale);
+    let mut output = String::new();
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            output.push(c);
+            continue;
+        }
+        let month = dt.month0() as usize;
+        let weekday = dt.weekday().num_days_from_monday() as usize;
+        let hour12 = match dt.hour() % 12 {
+            0 => 12,
+            h => h,
+        };
+        // writing to a `String` does not fail
+        let _ = match chars.next() {
+            Some('Y') => write!(output, "{}", dt.year()),
+            Some('y') => write!(output, "{:02}", dt.year() % 100),
+            Some('m') => write!(output, "{:02}", dt.month()),
+            Some('d') => write!(output, "{:02}", dt.day()),
+            Some('e') => write!(output, "{}", dt.day()),
+            Some('H') => write!(output, "{:02}", dt.hour()),
+            Some('I') => write!(output, "{:02}", hour12),
+            Some('l') => write!(output, "{}", hour12),
+            Some('M') => write!(output, "{:02}", dt.minute()),
+            Some('S') => write!(output, "{:02}", dt.second()),
+            Some('p') => write!(output, "{}", locale.am_pm[(dt.hour() >= 12) as usize]),
+            Some('B') => write!(output, "{}", locale.months[month]),
+            Some('b') => write!(output, "{}", locale.months_short[month]),
+            Some('A') => write!(output, "{}", locale.weekdays[weekday]),
+            Some('a') => write!(output, "{}", locale.weekdays_short[weekday]),
+            Some('j') => write!(output, "{:03}", dt.ordinal()),
+            Some('z') => write!(output, "{}", dt.format("%z")),
+            Some(':') if chars.next() == Some('z') => write!(output, "{}", dt.format("%:z")),
+            Some('%') => write!(output, "%"),
+            Some(c) => return error(format!("Unknown datetime pattern `%{}`", c)),
+            None => return error("Datetime pattern can't end with `%`".to_string()),
+        };
+    }
+    Ok(output)
+}
+
+/// "just now", "5 minutes ago", "in 3 days" etc, relative to `now`, in the language of `locale`.
+///
+/// The generated JS has the same rules in `ftd_datetime.relative()`, and the same words, see
+/// [Locale], so the server rendered text does not change on hydration.
+pub fn relative(dt: &DateTime, now: &DateTime, locale: Option<&str>) -> String {
+    let locale = self::locale(locale);
+    let seconds = dt.signed_duration_since(*now).num_seconds();
+    let abs = seconds.abs();
+    if abs < 60 {
+        return locale.just_now.to_string();
+    }
+    let (count, unit) = match abs {
+        a if a < 3600 => (a / 60, 0),
+        a if a < 86400 => (a / 3600, 1),
+        a if a < 30 * 86400 => (a / 86400, 2),
+        a if a < 365 * 86400 => (a / (30 * 86400), 3),
+        a => (a / (365 * 86400), 4),
+    };
+    let unit = format!("{} {}", count, locale.units[unit][(count != 1) as usize]);
+    if seconds < 0 {
+        locale.past.replace("{}", unit.as_str())
+    } else {
+        locale.future.replace("{}", unit.as_str())
+    }
+}
+
+/// Adds `amount` of `unit` (`second`, `minute`, `hour`, `day`, `week`, `month` or `year`,
+/// plural forms are accepted too). Months and years keep the day of month, clamped to the
+/// length of the target month.
+pub fn add(
+    dt: &DateTime,
+    amount: i64,
+    unit: &str,
+) -> fastn_grammar::evalexpr::EvalexprResult<DateTime> {
+    let seconds = |n: i64| dt.checked_add_signed(chrono::Duration::seconds(n));
+    let months = |n: i64| {
+        let months = chrono::Months::new(n.unsigned_abs() as u32);
+        if n < 0 {
+            dt.checked_sub_months(months)
+        } else {
+            dt.checked_add_months(months)
+        }
+    };
+    let result = match unit.trim_end_matches('s') {
+        "second" => seconds(amount),
+        "minute" => seconds(amount * 60),
+        "hour" => seconds(amount * 3600),
+        "day" => seconds(amount * 86400),
+        "week" => seconds(amount * 7 * 86400),
+        "month" => months(amount),
+        "year" => months(amount * 12),
+        _ => return error(format!("Unknown datetime unit `{}`", unit)),
+    };
+    match result {
+        Some(dt) => Ok(dt),
+        None => error(format!("Adding {} {} overflows datetime", amount, unit)),
+    }
+}
+
+/// Whole `unit`s from `b` to `a`, negative when `a` is before `b`.
+pub fn diff(
+    a: &DateTime,
+    b: &DateTime,
+    unit: &str,
+) -> fastn_grammar::evalexpr::EvalexprResult<i64> {
+    use chrono::Datelike;
+
+    let seconds = a.signed_duration_since(*b).num_seconds();
+    let months = || {
+        let months = (a.year() - b.year()) as i64 * 12 + a.month() as i64 - b.month() as i64;
+        // a partial last month does not count
+        match add(b, months, "month") {
+            Ok(end) if months > 0 && end > *a => months - 1,
+            Ok(end) if months < 0 && end < *a => months + 1,
+            _ => months,
+        }
+    };
+    Ok(match unit.trim_end_matches('s') {
+        "second" => seconds,
+        "minute" => seconds / 60,
+        "hour" => seconds / 3600,
+        "day" => seconds / 86400,
+        "week" => seconds / (7 * 86400),
+        "month" => months(),
+        "year" => months() / 12,
+        _ => return error(format!("Unknown datetime unit `{}`", unit)),
+    })
+}
+
+/// Midnight of the same day, in the offset of `dt`.
+pub fn start_of_day(dt: &DateTime) -> DateTime {
+    let midnight = dt.date_naive().and_time(chrono::NaiveTime::MIN);
+    chrono::DateTime::from_naive_utc_and_offset(midnight - *dt.offset(), *dt.offset())
+}
+
+/// The same instant seen from another offset, `offset` is `Z`, `+05:30`, `-0800` or `+05`.
+pub fn with_offset(
+    dt: &DateTime,
+    offset: &str,
+) -> fastn_grammar::evalexpr::EvalexprResult<DateTime> {
+    let offset = offset.trim();
+    if offset.eq("Z") || offset.eq_ignore_ascii_case("utc") {
+        return Ok(dt.with_timezone(&chrono::FixedOffset::east_opt(0).unwrap()));
+    }
+    let (sign, rest) = match offset.split_at(offset.len().min(1)) {
+        ("+", rest) => (1, rest),
+        ("-", rest) => (-1, rest),
+        _ => return error(format!("Invalid offset `{}`", offset)),
+    };
+    let digits = rest.replace(':', "");
+    let (hours, minutes) = match digits.len() {
+        2 => (digits.parse::<i32>().ok(), Some(0)),
+        4 => (
+            digits[..2].parse::<i32>().ok(),
+            digits[2..].parse::<i32>().ok(),
+        ),
+        _ => (None, None),
+    };
+    match (hours, minutes) {
+        (Some(hours), Some(minutes)) if hours < 24 && minutes < 60 => {
+            let offset = chrono::FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+                .expect("offset is within a day");
+            Ok(dt.with_timezone(&offset))
+        }
+        _ => error(format!("Invalid offset `{}`", offset)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[track_caller]
+    fn p(s: &str) -> super::DateTime {
+        super::parse(s).unwrap_or_else(|e| panic!("{:?}", e))
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            super::to_string(&p("2023-10-18T09:30:00Z")),
+            "2023-10-18T09:30:00Z"
+        );
+        assert_eq!(
+            super::to_string(&p("2023-10-18 09:30:00.5+05:30")),
+            "2023-10-18T09:30:00.500+05:30"
+        );
+        assert_eq!(
+            super::to_string(&p("2023-10-18 09:30:00+05")),
+            "2023-10-18T09:30:00+05:00"
+        );
+        assert_eq!(super::to_string(&p("2023-10-18")), "2023-10-18T00:00:00Z");
+        assert_eq!(super::to_string(&p("1697621400")), "2023-10-18T09:30:00Z");
+        assert_eq!(
+            super::to_string(&p("1697621400000")),
+            "2023-10-18T09:30:00Z"
+        );
+        assert!(super::parse("yesterday").is_err());
+    }
+
+    #[test]
+    fn format() {
+        let dt = p("2023-10-08T21:05:09+05:30");
+        assert_eq!(
+            super::format(&dt, "%a, %e %b %Y %I:%M %p %:z", None).unwrap(),
+            "Sun, 8 Oct 2023 09:05 PM +05:30"
+        );
+        assert_eq!(
+            super::format(&dt, "%A %B %d %%", None).unwrap(),
+            "Sunday October 08 %"
+        );
+        assert!(super::format(&dt, "%Q", None).is_err());
+        assert_eq!(
+            super::format(&dt, "%A %e %B %Y, %a %b", Some("pt-BR")).unwrap(),
+            "domingo 8 outubro 2023, dom out"
+        );
+        assert_eq!(
+            super::format(&dt, "%I:%M %p", Some("es")).unwrap(),
+            "09:05 p. m."
+        );
+        assert_eq!(
+            super::format(&dt, "%A", Some("xx")).unwrap(),
+            super::format(&dt, "%A", None).unwrap()
+        );
+    }
+
+    #[test]
+    fn relative() {
+        let now = p("2023-10-18T09:30:00Z");
+        let r = |s: &str| super::relative(&p(s), &now, None);
+        assert_eq!(r("2023-10-18T09:29:30Z"), "just now");
+        assert_eq!(r("2023-10-18T09:25:00Z"), "5 minutes ago");
+        assert_eq!(r("2023-10-18T10:30:00Z"), "in 1 hour");
+        assert_eq!(r("2023-10-15T09:30:00Z"), "3 days ago");
+        assert_eq!(r("2021-10-18T09:30:00Z"), "2 years ago");
+
+        let r = |s: &str, locale: &str| super::relative(&p(s), &now, Some(locale));
+        assert_eq!(r("2023-10-15T09:30:00Z", "de"), "vor 3 Tagen");
+        assert_eq!(r("2023-10-18T10:30:00Z", "fr-CA"), "dans 1 heure");
+        assert_eq!(r("2021-10-18T09:30:00Z", "it"), "2 anni fa");
+        assert_eq!(r("2023-10-18T09:29:30Z", "es"), "justo ahora");
+    }
+
+    #[test]
+    fn arithmetic() {
+        let dt = p("2023-01-31T22:00:00-02:00");
+        assert_eq!(
+            super::to_string(&super::add(&dt, 1, "month").unwrap()),
+            "2023-02-28T22:00:00-02:00"
+        );
+        assert_eq!(
+            super::to_string(&super::add(&dt, -2, "days").unwrap()),
+            "2023-01-29T22:00:00-02:00"
+        );
+        assert_eq!(
+            super::to_string(&super::start_of_day(&dt)),
+            "2023-01-31T00:00:00-02:00"
+        );
+        assert_eq!(
+            super::to_string(&super::with_offset(&dt, "+05:30").unwrap()),
+            "2023-02-01T05:30:00+05:30"
+        );
+        let later = p("2023-03-30T22:00:00-02:00");
+        assert_eq!(super::diff(&later, &dt, "months").unwrap(), 1);
+        assert_eq!(super::diff(&later, &dt, "day").unwrap(), 58);
+        assert_eq!(super::diff(&dt, &later, "month").unwrap(), -1);
+        assert!(super::add(&dt, 1, "fortnight").is_err());
+    }
+}
@@ -43,7 +43,18 @@ impl fmt::Display for EvalexprError {
                 "Expected a Value::Tuple of len {}, but got {:?}.",
                 expected_len, actual
             ),
+            ExpectedRecord { actual } => {
+                write!(f, "Expected a Value::Record, but got {:?}.", actual)
+            }
             ExpectedEmpty { actual } => write!(f, "Expected a Value::Empty, but got {:?}.", actual),
+            IndexOutOfBounds { index, length } => write!(
+                f,
+                "Index {} is out of bounds for a tuple of length {}.",
+                index, length
+            ),
+            FieldNotFound { field, actual } => {
+                write!(f, "Field {:?} not found in {:?}.", field, actual)
+            }
             AppendedToLeafNode => write!(f, "Tried to append a node to a leaf node."),
             PrecedenceViolation => write!(
                 f,
@@ -69,6 +80,23 @@ impl fmt::Display for EvalexprError {
             ),
             UnmatchedLBrace => write!(f, "Found an unmatched opening parenthesis '('."),
             UnmatchedRBrace => write!(f, "Found an unmatched closing parenthesis ')'."),
+            MismatchedBrace { opening, closing } => write!(
+                f,
+                "Found a closing '{}' for an opening '{}'.",
+                closing, opening
+            ),
+            UnmatchedInterpolation => write!(
+                f,
+                "Found an unmatched opening brace '{{' in a string literal, use '\\{{' for a brace."
+            ),
+            InvalidLambdaParameters => write!(
+                f,
+                "The parameters of a lambda must be an identifier or identifiers in parenthesis."
+            ),
+            UnexpectedLambda => write!(
+                f,
+                "A lambda can only be an argument of `map`, `filter`, `reduce` or `sort`."
+            ),
             MissingOperatorOutsideOfBrace { .. } => write!(
                 f,
                 "Found an opening parenthesis that is preceded by something that does not take \
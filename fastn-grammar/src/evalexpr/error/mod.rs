@@ -5,7 +5,10 @@
 //! The module also contains some helper functions starting with `expect_` that check for a condition and return `Err(_)` if the condition is not fulfilled.
 //! They are meant as shortcuts to not write the same error checking code everywhere.
 
-use fastn_grammar::evalexpr::{token::PartialToken, value::value_type::ValueType};
+use fastn_grammar::evalexpr::{
+    token::{PartialToken, Token},
+    value::{value_type::ValueType, IntType},
+};
 
 use fastn_grammar::evalexpr::{operator::Operator, value::Value};
 
@@ -85,12 +88,34 @@ pub enum EvalexprError {
         actual: Value,
     },
 
+    /// A record value was expected.
+    ExpectedRecord {
+        /// The actual value.
+        actual: Value,
+    },
+
     /// An empty value was expected.
     ExpectedEmpty {
         /// The actual value.
         actual: Value,
     },
 
+    /// An item of a tuple was accessed with an index outside of it, `(1, 2)[2]`.
+    IndexOutOfBounds {
+        /// The index accessed.
+        index: IntType,
+        /// The length of the tuple.
+        length: usize,
+    },
+
+    /// A field that the record does not have was accessed.
+    FieldNotFound {
+        /// The name of the field.
+        field: String,
+        /// The record accessed.
+        actual: Value,
+    },
+
     /// Tried to append a child to a leaf node.
     /// Leaf nodes cannot have children.
     AppendedToLeafNode,
@@ -129,6 +154,23 @@ pub enum EvalexprError {
     /// A closing brace without a matching opening brace was found.
     UnmatchedRBrace,
 
+    /// A group is closed by the other kind of bracket, like `(a]`.
+    MismatchedBrace {
+        /// The bracket that opened the group.
+        opening: Token,
+        /// The bracket that closed the group.
+        closing: Token,
+    },
+
+    /// An interpolation in a string literal, `f"a {b}"`, is not closed by a brace.
+    UnmatchedInterpolation,
+
+    /// The parameters of a lambda are not an identifier or a parenthesised list of identifiers.
+    InvalidLambdaParameters,
+
+    /// A lambda was found outside of an argument of `map`, `filter`, `reduce` or `sort`.
+    UnexpectedLambda,
+
     /// Left of an opening brace or right of a closing brace is a token that does not expect the brace next to it.
     /// For example, writing `4(5)` would yield this error, as the `4` does not have any operands.
     MissingOperatorOutsideOfBrace,
@@ -269,6 +311,11 @@ impl EvalexprError {
         }
     }
 
+    /// Constructs `EvalexprError::ExpectedRecord{actual}`.
+    pub fn expected_record(actual: Value) -> Self {
+        EvalexprError::ExpectedRecord { actual }
+    }
+
     /// Constructs `EvalexprError::ExpectedEmpty{actual}`.
     pub fn expected_empty(actual: Value) -> Self {
         EvalexprError::ExpectedEmpty { actual }
@@ -282,6 +329,7 @@ impl EvalexprError {
             ValueType::Float => Self::expected_float(actual),
             ValueType::Boolean => Self::expected_boolean(actual),
             ValueType::Tuple => Self::expected_tuple(actual),
+            ValueType::Record => Self::expected_record(actual),
             ValueType::Empty => Self::expected_empty(actual),
         }
     }
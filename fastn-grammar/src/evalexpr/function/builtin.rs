@@ -2,6 +2,7 @@
 use regex::Regex;
 
 use fastn_grammar::evalexpr::{
+    tree::lambda::sort_order,
     value::{FloatType, IntType},
     EvalexprError, Function, Value, ValueType,
 };
@@ -45,6 +46,14 @@ macro_rules! int_function {
     };
 }
 
+/// The string of `value` in a `concat` or a `join`, the strings are not quoted.
+fn to_string(value: &Value) -> String {
+    match value {
+        Value::String(string) => string.clone(),
+        value => value.to_string(),
+    }
+}
+
 pub fn builtin_function(identifier: &str) -> Option<Function> {
     match identifier {
         // Log
@@ -95,6 +104,7 @@ pub fn builtin_function(identifier: &str) -> Option<Function> {
                 Value::Int(_) => "int",
                 Value::Boolean(_) => "boolean",
                 Value::Tuple(_) => "tuple",
+                Value::Record(_) => "record",
                 Value::Empty => "empty",
             }
             .into())
@@ -160,6 +170,45 @@ pub fn builtin_function(identifier: &str) -> Option<Function> {
                 ))
             }
         })),
+        // List functions, `map`, `filter`, `reduce` and `sort` with a lambda are evaluated by
+        // `ExprNode::eval_lambda_call()`
+        "sort" => Some(Function::new(|argument| {
+            let list = argument.as_tuple()?;
+            let order = sort_order(list.as_slice())?;
+            Ok(Value::Tuple(
+                order.into_iter().map(|i| list[i].clone()).collect(),
+            ))
+        })),
+        "join" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            let separator = arguments[1].as_string()?;
+            let list = arguments[0]
+                .as_tuple()?
+                .iter()
+                .map(to_string)
+                .collect::<Vec<_>>();
+            Ok(Value::String(list.join(separator.as_str())))
+        })),
+        "contains" => Some(Function::new(|argument| {
+            let arguments = argument.as_fixed_len_tuple(2)?;
+            match (&arguments[0], &arguments[1]) {
+                (Value::Tuple(list), item) => Ok(Value::Boolean(list.contains(item))),
+                (Value::String(string), Value::String(substring)) => {
+                    Ok(Value::Boolean(string.contains(substring.as_str())))
+                }
+                (value, _) => Err(EvalexprError::type_error(
+                    value.clone(),
+                    vec![ValueType::Tuple, ValueType::String],
+                )),
+            }
+        })),
+        // The interpolated strings, `f"Hello {name}"`, are parsed into a call of `concat`
+        "concat" => Some(Function::new(|argument| {
+            Ok(Value::String(match argument {
+                Value::Tuple(arguments) => arguments.iter().map(to_string).collect(),
+                argument => to_string(argument),
+            }))
+        })),
         // String functions
         #[cfg(feature = "regex_support")]
         "str::regex_matches" => Some(Function::new(|argument| {
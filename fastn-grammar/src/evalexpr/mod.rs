@@ -113,6 +113,7 @@
 //!
 //! | Operator | Precedence | Description |
 //! |----------|------------|-------------|
+//! | [] | 150 | Item of a tuple, `a[0]`, or field of a record, `a[0].name` |
 //! | ^ | 120 | Exponentiation |
 //! | * | 100 | Product |
 //! | / | 100 | Division (integer if both arguments are integers, otherwise float) |
@@ -136,6 +137,7 @@
 //! | ^= | 50 | Exponentiation-Assignment |
 //! | &&= | 50 | Logical-And-Assignment |
 //! | &#124;&#124;= | 50 | Logical-Or-Assignment |
+//! | -> | 45 | Lambda, `(acc, x) -> acc + x`, an argument of `map`, `filter`, `reduce` or `sort` |
 //! | , | 40 | Aggregation |
 //! | ; | 0 | Expression Chaining |
 //!
@@ -328,7 +330,7 @@
 //! | `round`              | 1               | Numeric                | Returns the nearest integer to a number. Rounds half-way cases away from 0.0 |
 //! | `ceil`               | 1               | Numeric                | Returns the smallest integer greater than or equal to a number |
 //! | `if`                 | 3               | Boolean, Any, Any      | If the first argument is true, returns the second argument, otherwise, returns the third  |
-//! | `typeof`             | 1               | Any                    | returns "string", "float", "int", "boolean", "tuple", or "empty" depending on the type of the argument  |
+//! | `typeof`             | 1               | Any                    | returns "string", "float", "int", "boolean", "tuple", "record", or "empty" depending on the type of the argument  |
 //! | `math::is_nan`       | 1               | Numeric                | Returns true if the argument is the floating-point value NaN, false if it is another floating-point value, and throws an error if it is not a number  |
 //! | `math::is_finite`    | 1               | Numeric                | Returns true if the argument is a finite floating-point number, false otherwise  |
 //! | `math::is_infinite`  | 1               | Numeric                | Returns true if the argument is an infinite floating-point number, false otherwise  |
@@ -362,6 +364,13 @@
 //! | `str::to_uppercase`  | 1               | String                 | Returns the upper-case version of the string |
 //! | `str::trim`          | 1               | String                 | Strips whitespace from the start and the end of the string |
 //! | `str::from`          | >= 0            | Any                    | Returns passed value as string |
+//! | `map`                | 2               | Tuple, Lambda          | Returns the results of the lambda, called with each element and its index |
+//! | `filter`             | 2               | Tuple, Lambda          | Returns the elements the lambda, called with each element and its index, returns true for |
+//! | `reduce`             | 3               | Tuple, Any, Lambda     | Folds the elements into the second argument with the lambda, called with the accumulator and each element |
+//! | `sort`               | 1 or 2          | Tuple, Lambda          | Returns the elements sorted, by the key the lambda returns for them if given |
+//! | `join`               | 2               | Tuple, String          | Returns the elements joined with the separator |
+//! | `contains`           | 2               | Tuple/String, Any      | Returns true if the tuple has the element, or the string has the substring |
+//! | `concat`             | >= 0            | Any                    | Returns the arguments concatenated as strings, interpolated strings are calls of `concat` |
 //! | `bitand`             | 2               | Int                    | Computes the bitwise and of the given integers |
 //! | `bitor`              | 2               | Int                    | Computes the bitwise or of the given integers |
 //! | `bitxor`             | 2               | Int                    | Computes the bitwise xor of the given integers |
@@ -376,6 +385,21 @@
 //!
 //! The regex functions require the feature flag `regex_support`.
 //!
+//! The functions taking a lambda are evaluated with the parameters of the lambda bound to the values it is called with.
+//! An expression in braces in a string literal prefixed with `f` is interpolated, `f"Hello {name}"`, a brace is escaped with a backslash.
+//! The braces of the other string literals are kept as is, `"{"` is a brace.
+//! Interpolation needs the prefix, and not every string literal is interpolated, because strings of ftd documents often hold JSON, CSS or code with braces in them.
+//!
+//! ```rust
+//! use fastn_grammar::evalexpr::*;
+//!
+//! let mut context = HashMapContext::new();
+//! context.set_value("names".into(), Value::from(vec!["b".into(), "a".into()])).unwrap(); // Do proper error handling here
+//! assert_eq!(eval_with_context("map(names, (n, i) -> f\"{i}: {n}\")[1]", &context), Ok(Value::from("1: a")));
+//! assert_eq!(eval_with_context("join(sort(names), \", \")", &context), Ok(Value::from("a, b")));
+//! assert_eq!(eval_int("reduce((1, 2, 3), 0, (sum, x) -> sum + x)"), Ok(6));
+//! ```
+//!
 //! ### Values
 //!
 //! Operators take values as arguments and produce values as results.
@@ -389,6 +413,7 @@
 //! | `Value::Int` | `3`, `-9`, `0`, `135412` |
 //! | `Value::Float` | `3.`, `.35`, `1.00`, `0.5`, `123.554`, `23e4`, `-2e-3`, `3.54e+2` |
 //! | `Value::Tuple` | `(3, 55.0, false, ())`, `(1, 2)` |
+//! | `Value::Record` | set in the context only |
 //! | `Value::Empty` | `()` |
 //!
 //! Integers are internally represented as `i64`, and floating point numbers are represented as `f64`.
@@ -544,7 +569,10 @@ pub use fastn_grammar::evalexpr::{
     operator::Operator,
     token::PartialToken,
     tree::ExprNode,
-    value::{value_type::ValueType, EmptyType, FloatType, IntType, TupleType, Value, EMPTY_VALUE},
+    value::{
+        value_type::ValueType, EmptyType, FloatType, IntType, RecordType, TupleType, Value,
+        EMPTY_VALUE,
+    },
 };
 
 mod context;
@@ -37,6 +37,9 @@ impl Display for Operator {
             AndAssign => write!(f, " &&= "),
             OrAssign => write!(f, " ||= "),
 
+            Index => write!(f, "[]"),
+            Lambda => write!(f, " -> "),
+
             Tuple => write!(f, ", "),
             Chain => write!(f, "; "),
 
@@ -66,6 +66,13 @@ pub enum Operator {
     /// A binary or-assign operator.
     OrAssign,
 
+    /// A binary access operator, the item of a tuple at an index, `a[0]`, or the field of a
+    /// record, `a[0].name`.
+    Index,
+    /// A binary lambda constructor, `x -> x * 2`, its first argument are the parameters.
+    /// Lambdas are arguments of the `map`, `filter`, `reduce` and `sort` builtin functions.
+    Lambda,
+
     /// An n-ary tuple constructor.
     Tuple,
     /// An n-ary subexpression chain.
@@ -135,6 +142,9 @@ impl Operator {
             Assign | AddAssign | SubAssign | MulAssign | DivAssign | ModAssign | ExpAssign
             | AndAssign | OrAssign => 50,
 
+            Index => 150,
+            Lambda => 45,
+
             Tuple => 40,
             Chain => 0,
 
@@ -149,7 +159,7 @@ impl Operator {
     /// Left-to-right chaining has priority if operators with different order but same precedence are chained.
     pub(crate) const fn is_left_to_right(&self) -> bool {
         use fastn_grammar::evalexpr::operator::Operator::*;
-        !matches!(self, Assign | FunctionIdentifier { .. })
+        !matches!(self, Assign | Lambda | FunctionIdentifier { .. })
     }
 
     /// Returns true if chains of this operator should be flattened into one operator with many arguments.
@@ -170,7 +180,7 @@ impl Operator {
         match self {
             Add | Sub | Mul | Div | Mod | Exp | Eq | Neq | Gt | Lt | Geq | Leq | And | Or
             | Assign | AddAssign | SubAssign | MulAssign | DivAssign | ModAssign | ExpAssign
-            | AndAssign | OrAssign => Some(2),
+            | AndAssign | OrAssign | Index | Lambda => Some(2),
             Tuple | Chain => None,
             Not | Neg | RootNode => Some(1),
             Const { .. } => Some(0),
@@ -424,6 +434,12 @@ impl Operator {
             }
             Assign | AddAssign | SubAssign | MulAssign | DivAssign | ModAssign | ExpAssign
             | AndAssign | OrAssign => Err(EvalexprError::ContextNotMutable),
+            Index => {
+                expect_operator_argument_amount(arguments.len(), 2)?;
+
+                arguments[0].get(&arguments[1])
+            }
+            Lambda => Err(EvalexprError::UnexpectedLambda),
             Tuple => Ok(Value::Tuple(arguments.into())),
             Chain => {
                 if arguments.is_empty() {
@@ -446,12 +462,17 @@ impl Operator {
                 expect_operator_argument_amount(arguments.len(), 0)?;
 
                 if let Some(value) = context.get_value(identifier).cloned() {
-                    Ok(value)
-                } else {
-                    Err(EvalexprError::VariableIdentifierNotFound(
-                        identifier.clone(),
-                    ))
+                    return Ok(value);
                 }
+                // `person.name` is the field `name` of the variable `person`
+                for (end, _) in identifier.rmatch_indices('.') {
+                    if let Some(value) = context.get_value(&identifier[..end]) {
+                        return value.get_path(&identifier[end + 1..]);
+                    }
+                }
+                Err(EvalexprError::VariableIdentifierNotFound(
+                    identifier.clone(),
+                ))
             }
             FunctionIdentifier { identifier } => {
                 expect_operator_argument_amount(arguments.len(), 1)?;
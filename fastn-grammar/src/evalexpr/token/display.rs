@@ -28,6 +28,14 @@ impl fmt::Display for Token {
             LBrace => write!(f, "("),
             RBrace => write!(f, ")"),
 
+            // Access
+            LBracket => write!(f, "["),
+            RBracket => write!(f, "]"),
+            Dot => write!(f, "."),
+
+            // Lambda
+            Arrow => write!(f, "->"),
+
             // Assignment
             Assign => write!(f, "="),
             PlusAssign => write!(f, "+="),
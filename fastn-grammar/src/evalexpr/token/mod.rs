@@ -30,6 +30,14 @@ pub enum Token {
     LBrace,
     RBrace,
 
+    // Access
+    LBracket,
+    RBracket,
+    Dot,
+
+    // Lambda
+    Arrow,
+
     // Assignment
     Assign,
     PlusAssign,
@@ -100,6 +108,8 @@ fn char_to_partial_token(c: char) -> PartialToken {
 
         '(' => PartialToken::Token(Token::LBrace),
         ')' => PartialToken::Token(Token::RBrace),
+        '[' => PartialToken::Token(Token::LBracket),
+        ']' => PartialToken::Token(Token::RBracket),
 
         ',' => PartialToken::Token(Token::Comma),
         ';' => PartialToken::Token(Token::Semicolon),
@@ -145,6 +155,12 @@ impl Token {
             Token::LBrace => true,
             Token::RBrace => false,
 
+            Token::LBracket => false,
+            Token::RBracket => false,
+            Token::Dot => false,
+
+            Token::Arrow => false,
+
             Token::Comma => false,
             Token::Semicolon => false,
 
@@ -189,6 +205,12 @@ impl Token {
             Token::LBrace => false,
             Token::RBrace => true,
 
+            Token::LBracket => false,
+            Token::RBracket => true,
+            Token::Dot => false,
+
+            Token::Arrow => false,
+
             Token::Comma => false,
             Token::Semicolon => false,
 
@@ -233,6 +255,8 @@ fn parse_escape_sequence<Iter: Iterator<Item = char>>(iter: &mut Iter) -> Evalex
     match iter.next() {
         Some('"') => Ok('"'),
         Some('\\') => Ok('\\'),
+        Some('{') => Ok('{'),
+        Some('}') => Ok('}'),
         Some(c) => Err(EvalexprError::IllegalEscapeSequence(format!("\\{}", c))),
         None => Err(EvalexprError::IllegalEscapeSequence("\\".to_string())),
     }
@@ -242,22 +266,87 @@ fn parse_escape_sequence<Iter: Iterator<Item = char>>(iter: &mut Iter) -> Evalex
 ///
 /// The first character from the iterator is interpreted as first character of the string.
 /// The string is terminated by a double quote `"`.
-/// Occurrences of `"` within the string can be escaped with `\`.
+/// Occurrences of `"`, `{` and `}` within the string can be escaped with `\`.
 /// The backslash needs to be escaped with another backslash `\`.
+///
+/// If the string is `interpolated`, `f"Hello {name}!"`, an expression in braces is interpolated,
+/// the string is parsed as `concat("Hello ", (name), "!")`. The braces of the other strings are
+/// kept as is.
 fn parse_string_literal<Iter: Iterator<Item = char>>(
     mut iter: &mut Iter,
-) -> EvalexprResult<PartialToken> {
+    interpolated: bool,
+) -> EvalexprResult<Vec<PartialToken>> {
+    let mut parts = vec![];
     let mut result = String::new();
 
     while let Some(c) = iter.next() {
         match c {
             '"' => break,
             '\\' => result.push(parse_escape_sequence(&mut iter)?),
+            '{' if interpolated => {
+                parts.push(PartialToken::Token(Token::String(std::mem::take(
+                    &mut result,
+                ))));
+                parts.push(PartialToken::Token(Token::Comma));
+                parts.push(PartialToken::Token(Token::LBrace));
+                parts.extend(str_to_partial_tokens(
+                    parse_interpolated_expression(&mut iter)?.as_str(),
+                )?);
+                parts.push(PartialToken::Token(Token::RBrace));
+                parts.push(PartialToken::Token(Token::Comma));
+            }
             c => result.push(c),
         }
     }
 
-    Ok(PartialToken::Token(Token::String(result)))
+    if parts.is_empty() {
+        return Ok(vec![PartialToken::Token(Token::String(result))]);
+    }
+    parts.insert(
+        0,
+        PartialToken::Token(Token::Identifier(INTERPOLATION_FUNCTION.to_string())),
+    );
+    parts.insert(1, PartialToken::Token(Token::LBrace));
+    parts.push(PartialToken::Token(Token::String(result)));
+    parts.push(PartialToken::Token(Token::RBrace));
+    Ok(parts)
+}
+
+/// The builtin function the interpolated strings are parsed into a call of.
+pub(crate) const INTERPOLATION_FUNCTION: &str = "concat";
+
+/// Returns the expression of an interpolation up to its closing brace, the braces and the string
+/// literals in the expression are skipped over.
+fn parse_interpolated_expression<Iter: Iterator<Item = char>>(
+    iter: &mut Iter,
+) -> EvalexprResult<String> {
+    let mut expression = String::new();
+    let mut depth = 0;
+    let mut in_string = false;
+
+    while let Some(c) = iter.next() {
+        match c {
+            '\\' if in_string => {
+                expression.push(c);
+                if let Some(c) = iter.next() {
+                    expression.push(c);
+                }
+                continue;
+            }
+            '"' => in_string = !in_string,
+            '{' if !in_string => depth += 1,
+            '}' if !in_string => {
+                if depth == 0 {
+                    return Ok(expression);
+                }
+                depth -= 1;
+            }
+            _ => {}
+        }
+        expression.push(c);
+    }
+
+    Err(EvalexprError::UnmatchedInterpolation)
 }
 
 /// Converts a string to a vector of partial tokens.
@@ -267,11 +356,25 @@ fn str_to_partial_tokens(string: &str) -> EvalexprResult<Vec<PartialToken>> {
 
     while let Some(c) = iter.next() {
         if c == '"' {
-            result.push(parse_string_literal(&mut iter)?);
+            // the prefix of the interpolated strings, `f"Hello {name}"`
+            let interpolated =
+                matches!(result.last(), Some(PartialToken::Literal(literal)) if literal == "f");
+            if interpolated {
+                result.pop();
+            }
+            result.extend(parse_string_literal(&mut iter, interpolated)?);
+        } else if c == '.'
+            && matches!(
+                result.last(),
+                Some(PartialToken::Token(Token::RBrace | Token::RBracket))
+            )
+        {
+            // Field access on a parenthesised expression or an item, `(a).b` or `a[0].b`
+            result.push(PartialToken::Token(Token::Dot));
         } else {
             let mut partial_token = char_to_partial_token(c);
             if let Some(PartialToken::Literal(..)) = result.last() {
-                if partial_token == PartialToken::Minus {
+                if partial_token == PartialToken::Minus && iter.peek() != Some(&'>') {
                     partial_token = PartialToken::Literal('-'.to_string())
                 }
             }
@@ -303,8 +406,23 @@ fn partial_tokens_to_tokens(mut tokens: &[PartialToken]) -> EvalexprResult<Vec<T
         let third = tokens.get(2).cloned();
         let mut cutoff = 2;
 
+        // `a[0].b.c` is `a[0]["b"]["c"]`
+        if let (PartialToken::Token(Token::Dot), Some(PartialToken::Literal(fields))) =
+            (&first, &second)
+        {
+            for field in fields.split('.') {
+                result.push(Token::Dot);
+                result.push(Token::String(field.to_string()));
+            }
+            tokens = &tokens[cutoff..];
+            continue;
+        }
+
         result.extend(
             match first {
+                PartialToken::Token(Token::Dot) => {
+                    return Err(EvalexprError::unmatched_partial_token(first, second))
+                }
                 PartialToken::Token(token) => {
                     cutoff = 1;
                     Some(token)
@@ -318,6 +436,7 @@ fn partial_tokens_to_tokens(mut tokens: &[PartialToken]) -> EvalexprResult<Vec<T
                 },
                 PartialToken::Minus => match second {
                     Some(PartialToken::Eq) => Some(Token::MinusAssign),
+                    Some(PartialToken::Gt) => Some(Token::Arrow),
                     _ => {
                         cutoff = 1;
                         Some(Token::Minus)
@@ -492,4 +611,31 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn interpolation_and_lambda() {
+        assert_eq!(
+            tokenize("f\"a{x[0].b}\" x->x").unwrap(),
+            [
+                Token::Identifier("concat".to_string()),
+                Token::LBrace,
+                Token::String("a".to_string()),
+                Token::Comma,
+                Token::LBrace,
+                Token::Identifier("x".to_string()),
+                Token::LBracket,
+                Token::Int(0),
+                Token::RBracket,
+                Token::Dot,
+                Token::String("b".to_string()),
+                Token::RBrace,
+                Token::Comma,
+                Token::String("".to_string()),
+                Token::RBrace,
+                Token::Identifier("x".to_string()),
+                Token::Arrow,
+                Token::Identifier("x".to_string()),
+            ]
+        );
+    }
 }
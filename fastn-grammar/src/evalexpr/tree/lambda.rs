@@ -0,0 +1,413 @@
+use fastn_grammar::evalexpr::{
+    error::{EvalexprError, EvalexprResult},
+    operator::Operator,
+    value::{value_type::ValueType, Value},
+    Context, ExprNode,
+};
+use std::cmp::Ordering;
+
+/// The builtin functions that take a lambda as an argument.
+pub(crate) const LAMBDA_FUNCTIONS: [&str; 4] = ["map", "filter", "reduce", "sort"];
+
+/// A lambda argument, `(acc, x) -> acc + x`, called by the builtin functions with the items of a
+/// tuple.
+struct Lambda<'a> {
+    parameters: Vec<String>,
+    body: &'a ExprNode,
+}
+
+enum Argument<'a> {
+    Value(Value),
+    Lambda(Lambda<'a>),
+}
+
+/// The context the body of a lambda is evaluated in, its parameters shadow the variables of the
+/// context of the call.
+struct LambdaContext<'a> {
+    parameters: Vec<(&'a str, Value)>,
+    // not generic, the lambdas in the body would instantiate ever nested contexts
+    context: &'a dyn Context,
+}
+
+impl Context for LambdaContext<'_> {
+    fn get_value(&self, identifier: &str) -> Option<&Value> {
+        self.parameters
+            .iter()
+            .rev()
+            .find(|(name, _)| *name == identifier)
+            .map(|(_, value)| value)
+            .or_else(|| self.context.get_value(identifier))
+    }
+
+    fn call_function(&self, identifier: &str, argument: &Value) -> EvalexprResult<Value> {
+        self.context.call_function(identifier, argument)
+    }
+}
+
+impl Lambda<'_> {
+    fn call(&self, arguments: Vec<Value>, context: &dyn Context) -> EvalexprResult<Value> {
+        if self.parameters.len() > arguments.len() {
+            return Err(EvalexprError::wrong_function_argument_amount(
+                arguments.len(),
+                self.parameters.len(),
+            ));
+        }
+        let context = LambdaContext {
+            parameters: self
+                .parameters
+                .iter()
+                .map(String::as_str)
+                .zip(arguments)
+                .collect(),
+            context,
+        };
+        self.body.eval_with_context(&context)
+    }
+}
+
+impl ExprNode {
+    /// Returns the name and the argument nodes of a call of one of the `LAMBDA_FUNCTIONS` with a
+    /// lambda argument, the call is evaluated by `eval_lambda_call()`.
+    pub(crate) fn lambda_call(&self) -> Option<(&str, Vec<&ExprNode>)> {
+        let identifier = match self.operator() {
+            Operator::FunctionIdentifier { identifier }
+                if LAMBDA_FUNCTIONS.contains(&identifier.as_str()) =>
+            {
+                identifier.as_str()
+            }
+            _ => return None,
+        };
+        let arguments = match self.children().first().map(ExprNode::unwrap_root) {
+            Some(node) if node.operator() == &Operator::Tuple => {
+                node.children().iter().map(ExprNode::unwrap_root).collect()
+            }
+            Some(node) => vec![node],
+            None => vec![],
+        };
+        arguments
+            .iter()
+            .any(|node| node.operator() == &Operator::Lambda)
+            .then_some((identifier, arguments))
+    }
+
+    /// Evaluates the call of `identifier`, one of the `LAMBDA_FUNCTIONS`, the `values` are the
+    /// arguments, `None` for a lambda.
+    pub(crate) fn eval_lambda_call<C: Context>(
+        identifier: &str,
+        arguments: &[&ExprNode],
+        values: Vec<Option<Value>>,
+        context: &C,
+    ) -> EvalexprResult<Value> {
+        let arguments = arguments
+            .iter()
+            .zip(values)
+            .map(|(node, value)| match value {
+                Some(value) => Ok(Argument::Value(value)),
+                None => Ok(Argument::Lambda(node.lambda()?)),
+            })
+            .collect::<EvalexprResult<Vec<_>>>()?;
+
+        match (identifier, arguments.as_slice()) {
+            ("map", [Argument::Value(list), Argument::Lambda(f)]) => {
+                let mut result = vec![];
+                for (index, item) in list.as_tuple()?.into_iter().enumerate() {
+                    result.push(f.call(vec![item, Value::Int(index as i64)], context)?);
+                }
+                Ok(Value::Tuple(result))
+            }
+            ("filter", [Argument::Value(list), Argument::Lambda(f)]) => {
+                let mut result = vec![];
+                for (index, item) in list.as_tuple()?.into_iter().enumerate() {
+                    if f.call(vec![item.clone(), Value::Int(index as i64)], context)?
+                        .as_boolean()?
+                    {
+                        result.push(item);
+                    }
+                }
+                Ok(Value::Tuple(result))
+            }
+            ("reduce", [Argument::Value(list), Argument::Value(init), Argument::Lambda(f)]) => {
+                let mut result = init.clone();
+                for item in list.as_tuple()? {
+                    result = f.call(vec![result, item], context)?;
+                }
+                Ok(result)
+            }
+            ("sort", [Argument::Value(list), Argument::Lambda(f)]) => {
+                let list = list.as_tuple()?;
+                let mut keys = vec![];
+                for item in list.iter() {
+                    keys.push(f.call(vec![item.clone()], context)?);
+                }
+                let order = sort_order(keys.as_slice())?;
+                Ok(Value::Tuple(
+                    order.into_iter().map(|i| list[i].clone()).collect(),
+                ))
+            }
+            (_, arguments) => Err(EvalexprError::wrong_function_argument_amount(
+                arguments.len(),
+                if identifier == "reduce" { 3 } else { 2 },
+            )),
+        }
+    }
+
+    fn lambda(&self) -> EvalexprResult<Lambda<'_>> {
+        match (self.operator(), self.children()) {
+            (Operator::Lambda, [parameters, body]) => Ok(Lambda {
+                parameters: parameters
+                    .lambda_parameters()?
+                    .into_iter()
+                    .map(ToString::to_string)
+                    .collect(),
+                body,
+            }),
+            _ => Err(EvalexprError::UnexpectedLambda),
+        }
+    }
+
+    /// The names of the parameters of a lambda, `x` or `(acc, x)`.
+    fn lambda_parameters(&self) -> EvalexprResult<Vec<&str>> {
+        let node = self.unwrap_root();
+        let parameters = if node.operator() == &Operator::Tuple {
+            node.children().iter().map(ExprNode::unwrap_root).collect()
+        } else {
+            vec![node]
+        };
+        parameters
+            .into_iter()
+            .map(|parameter| match parameter.operator() {
+                Operator::VariableIdentifierRead { identifier }
+                | Operator::VariableIdentifierWrite { identifier }
+                    if !identifier.contains('.') =>
+                {
+                    Ok(identifier.as_str())
+                }
+                _ => Err(EvalexprError::InvalidLambdaParameters),
+            })
+            .collect()
+    }
+
+    /// Marks the parameters of the lambdas in the tree as variable writes, so they are not
+    /// reported by `iter_read_variable_identifiers()`.
+    pub(crate) fn mark_lambda_parameters(&mut self) -> EvalexprResult<()> {
+        if self.operator() == &Operator::Lambda {
+            let parameters = self
+                .children_mut()
+                .first_mut()
+                .ok_or(EvalexprError::InvalidLambdaParameters)?;
+            parameters.lambda_parameters()?;
+            parameters.mark_as_written();
+        }
+        for child in self.children_mut() {
+            child.mark_lambda_parameters()?;
+        }
+        Ok(())
+    }
+
+    fn mark_as_written(&mut self) {
+        if let Operator::VariableIdentifierRead { identifier } = self.operator() {
+            *self.operator_mut() = Operator::VariableIdentifierWrite {
+                identifier: identifier.clone(),
+            };
+        }
+        for child in self.children_mut() {
+            child.mark_as_written();
+        }
+    }
+
+    /// The node in the root nodes of parenthesis, `x` of `((x))`.
+    fn unwrap_root(&self) -> &ExprNode {
+        match (self.operator(), self.children()) {
+            (Operator::RootNode, [child]) => child.unwrap_root(),
+            _ => self,
+        }
+    }
+}
+
+/// The indices of `keys` in the sorted order, the keys are numbers, strings or booleans.
+pub(crate) fn sort_order(keys: &[Value]) -> EvalexprResult<Vec<usize>> {
+    for key in keys {
+        if !(key.is_number() || key.is_string() || key.is_boolean()) {
+            return Err(EvalexprError::type_error(
+                key.clone(),
+                vec![
+                    ValueType::Int,
+                    ValueType::Float,
+                    ValueType::String,
+                    ValueType::Boolean,
+                ],
+            ));
+        }
+    }
+    let mut order = (0..keys.len()).collect::<Vec<_>>();
+    let mut error = None;
+    order.sort_by(|a, b| match compare(&keys[*a], &keys[*b]) {
+        Some(ordering) => ordering,
+        None => {
+            error.get_or_insert_with(|| {
+                EvalexprError::wrong_type_combination(
+                    Operator::Lt,
+                    vec![(&keys[*a]).into(), (&keys[*b]).into()],
+                )
+            });
+            Ordering::Equal
+        }
+    });
+    match error {
+        Some(error) => Err(error),
+        None => Ok(order),
+    }
+}
+
+fn compare(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Int(a), Value::Int(b)) => Some(a.cmp(b)),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        (Value::Boolean(a), Value::Boolean(b)) => Some(a.cmp(b)),
+        (a, b) if a.is_number() && b.is_number() => Some(
+            a.as_number()
+                .ok()?
+                .partial_cmp(&b.as_number().ok()?)
+                .unwrap_or(Ordering::Equal),
+        ),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fastn_grammar::evalexpr::*;
+
+    fn people() -> HashMapContext {
+        let person = |name: &str, age: i64| {
+            Value::from(RecordType::from([
+                ("name".to_string(), Value::from(name)),
+                ("age".to_string(), Value::from(age)),
+            ]))
+        };
+        let mut context = HashMapContext::new();
+        context
+            .set_value(
+                "people".to_string(),
+                Value::from(vec![person("Bob", 40), person("Alice", 15)]),
+            )
+            .unwrap();
+        context
+    }
+
+    #[test]
+    fn list_operations() {
+        let context = people();
+        assert_eq!(
+            eval_with_context("map(people, p -> p.name)", &context),
+            Ok(Value::from(vec![Value::from("Bob"), Value::from("Alice")]))
+        );
+        assert_eq!(
+            eval_with_context("len(filter(people, p -> p.age >= 18))", &context),
+            Ok(Value::from(1))
+        );
+        assert_eq!(
+            eval_with_context("reduce(people, 0, (sum, p) -> sum + p.age)", &context),
+            Ok(Value::from(55))
+        );
+        assert_eq!(
+            eval_with_context("sort(people, p -> p.age)[0].name", &context),
+            Ok(Value::from("Alice"))
+        );
+        assert_eq!(
+            eval_with_context("join(sort(map(people, p -> p.name)), \", \")", &context),
+            Ok(Value::from("Alice, Bob"))
+        );
+        assert_eq!(
+            eval_with_context("contains(map(people, (p, i) -> i), 1)", &context),
+            Ok(Value::from(true))
+        );
+        assert_eq!(eval("x -> x"), Err(EvalexprError::UnexpectedLambda));
+        assert_eq!(
+            build_operator_tree("map((1, 2), (x + 1) -> x)"),
+            Err(EvalexprError::InvalidLambdaParameters)
+        );
+    }
+
+    #[test]
+    fn lambda_parameters_are_not_read() {
+        let tree = build_operator_tree("filter(people, p -> p.age > min_age)").unwrap();
+        assert_eq!(
+            tree.iter_read_variable_identifiers().collect::<Vec<_>>(),
+            vec!["people", "p.age", "min_age"]
+        );
+        assert_eq!(
+            tree.iter_write_variable_identifiers().collect::<Vec<_>>(),
+            vec!["p"]
+        );
+    }
+
+    #[test]
+    fn access() {
+        let context = people();
+        assert_eq!(
+            eval_with_context("people[1].name", &context),
+            Ok(Value::from("Alice"))
+        );
+        assert_eq!(
+            eval_with_context("people.0.age + people[len(people) - 1].age", &context),
+            Ok(Value::from(55))
+        );
+        assert_eq!(
+            eval_with_context("people[2]", &context),
+            Err(EvalexprError::IndexOutOfBounds {
+                index: 2,
+                length: 2
+            })
+        );
+        assert_eq!(
+            eval_with_context("people[(1])", &context),
+            Err(EvalexprError::MismatchedBrace {
+                opening: token::Token::LBrace,
+                closing: token::Token::RBracket
+            })
+        );
+        assert_eq!(
+            eval("(1]"),
+            Err(EvalexprError::MismatchedBrace {
+                opening: token::Token::LBrace,
+                closing: token::Token::RBracket
+            })
+        );
+        assert_eq!(eval("1]"), Err(EvalexprError::UnmatchedRBrace));
+        assert_eq!(
+            eval_with_context("people[1)", &context),
+            Err(EvalexprError::MismatchedBrace {
+                opening: token::Token::LBracket,
+                closing: token::Token::RBrace
+            })
+        );
+    }
+
+    #[test]
+    fn interpolation() {
+        let context = people();
+        assert_eq!(
+            eval_with_context(
+                "f\"{people[0].name} is {people[0].age} and {str::to_lowercase(f\"A{1}\")}\"",
+                &context
+            ),
+            Ok(Value::from("Bob is 40 and a1"))
+        );
+        assert_eq!(eval("f\"\\{a}\""), Ok(Value::from("{a}")));
+        assert_eq!(eval("f\"{1\""), Err(EvalexprError::UnmatchedInterpolation));
+    }
+
+    #[test]
+    fn braces_in_string_literals() {
+        assert_eq!(eval("\"{\""), Ok(Value::from("{")));
+        assert_eq!(eval("\"}\""), Ok(Value::from("}")));
+        assert_eq!(eval("\"{a}\""), Ok(Value::from("{a}")));
+        assert_eq!(eval("\"\\{a\\}\""), Ok(Value::from("{a}")));
+        assert_eq!(
+            eval("\"{\\\"a\\\": [1, {\\\"b\\\": 2}]}\""),
+            Ok(Value::from("{\"a\": [1, {\"b\": 2}]}"))
+        );
+        assert_eq!(eval("str::to_uppercase(\"{a}\")"), Ok(Value::from("{A}")));
+    }
+}
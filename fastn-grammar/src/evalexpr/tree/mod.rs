@@ -15,6 +15,7 @@ use std::mem;
 #[cfg(not(tarpaulin_include))]
 mod display;
 mod iter;
+pub(crate) mod lambda;
 
 /// A node in the operator tree.
 /// The operator tree is created by the crate-level `build_operator_tree` method.
@@ -176,6 +177,19 @@ impl ExprNode {
     ///
     /// Fails, if one of the operators in the expression tree fails.
     pub fn eval_with_context<C: Context>(&self, context: &C) -> EvalexprResult<Value> {
+        if self.operator() == &Operator::Lambda {
+            return Err(EvalexprError::UnexpectedLambda);
+        }
+        if let Some((identifier, arguments)) = self.lambda_call() {
+            let mut values = Vec::new();
+            for argument in arguments.iter() {
+                values.push(match argument.operator() {
+                    Operator::Lambda => None,
+                    _ => Some(argument.eval_with_context(context)?),
+                });
+            }
+            return ExprNode::eval_lambda_call(identifier, &arguments, values, context);
+        }
         let mut arguments = Vec::new();
         for child in self.children() {
             arguments.push(child.eval_with_context(context)?);
@@ -190,6 +204,19 @@ impl ExprNode {
         &self,
         context: &mut C,
     ) -> EvalexprResult<Value> {
+        if self.operator() == &Operator::Lambda {
+            return Err(EvalexprError::UnexpectedLambda);
+        }
+        if let Some((identifier, arguments)) = self.lambda_call() {
+            let mut values = Vec::new();
+            for argument in arguments.iter() {
+                values.push(match argument.operator() {
+                    Operator::Lambda => None,
+                    _ => Some(argument.eval_with_context_mut(context)?),
+                });
+            }
+            return ExprNode::eval_lambda_call(identifier, &arguments, values, context);
+        }
         let mut arguments = Vec::new();
         for child in self.children() {
             arguments.push(child.eval_with_context_mut(context)?);
@@ -623,6 +650,8 @@ fn collapse_all_sequences(root_stack: &mut Vec<ExprNode>) -> EvalexprResult<()>
 
 pub(crate) fn tokens_to_operator_tree(tokens: Vec<Token>) -> EvalexprResult<ExprNode> {
     let mut root_stack = vec![ExprNode::root_node()];
+    // The opening `(` or `[` of every open group, so a group is closed by its own bracket
+    let mut openers = Vec::new();
     let mut last_token_is_rightsided_value = false;
     let mut token_iter = tokens.iter().peekable();
 
@@ -654,17 +683,25 @@ pub(crate) fn tokens_to_operator_tree(tokens: Vec<Token>) -> EvalexprResult<Expr
             Token::Not => Some(ExprNode::new(Operator::Not)),
 
             Token::LBrace => {
+                openers.push(Token::LBrace);
                 root_stack.push(ExprNode::root_node());
                 None
             }
-            Token::RBrace => {
-                if root_stack.len() <= 1 {
-                    return Err(EvalexprError::UnmatchedRBrace);
-                } else {
+            Token::RBrace | Token::RBracket => match (openers.pop(), &token) {
+                (Some(Token::LBrace), Token::RBrace) | (Some(Token::LBracket), Token::RBracket)
+                    if root_stack.len() > 1 =>
+                {
                     collapse_all_sequences(&mut root_stack)?;
                     root_stack.pop()
                 }
-            }
+                (Some(opening), _) if root_stack.len() > 1 => {
+                    return Err(EvalexprError::MismatchedBrace {
+                        opening,
+                        closing: token,
+                    });
+                }
+                _ => return Err(EvalexprError::UnmatchedRBrace),
+            },
 
             Token::Assign => Some(ExprNode::new(Operator::Assign)),
             Token::PlusAssign => Some(ExprNode::new(Operator::AddAssign)),
@@ -676,6 +713,11 @@ pub(crate) fn tokens_to_operator_tree(tokens: Vec<Token>) -> EvalexprResult<Expr
             Token::AndAssign => Some(ExprNode::new(Operator::AndAssign)),
             Token::OrAssign => Some(ExprNode::new(Operator::OrAssign)),
 
+            // The index is a subexpression, `a[i + 1]`
+            Token::LBracket => Some(ExprNode::new(Operator::Index)),
+            Token::Dot => Some(ExprNode::new(Operator::Index)),
+            Token::Arrow => Some(ExprNode::new(Operator::Lambda)),
+
             Token::Comma => Some(ExprNode::new(Operator::Tuple)),
             Token::Semicolon => Some(ExprNode::new(Operator::Chain)),
 
@@ -759,6 +801,11 @@ pub(crate) fn tokens_to_operator_tree(tokens: Vec<Token>) -> EvalexprResult<Expr
             }
         }
 
+        if token == Token::LBracket {
+            openers.push(Token::LBracket);
+            root_stack.push(ExprNode::root_node());
+        }
+
         last_token_is_rightsided_value = token.is_rightsided_value();
     }
 
@@ -767,7 +814,8 @@ pub(crate) fn tokens_to_operator_tree(tokens: Vec<Token>) -> EvalexprResult<Expr
 
     if root_stack.len() > 1 {
         Err(EvalexprError::UnmatchedLBrace)
-    } else if let Some(root) = root_stack.pop() {
+    } else if let Some(mut root) = root_stack.pop() {
+        root.mark_lambda_parameters()?;
         Ok(root)
     } else {
         Err(EvalexprError::UnmatchedRBrace)
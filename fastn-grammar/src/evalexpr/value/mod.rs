@@ -1,4 +1,5 @@
 use fastn_grammar::evalexpr::error::{EvalexprError, EvalexprResult};
+use fastn_grammar::evalexpr::operator::Operator;
 use std::convert::TryFrom;
 
 mod display;
@@ -13,6 +14,9 @@ pub type FloatType = f64;
 /// The type used to represent tuples in `Value::Tuple`.
 pub type TupleType = Vec<Value>;
 
+/// The type used to represent records in `Value::Record`, the values of the fields by their name.
+pub type RecordType = std::collections::BTreeMap<String, Value>;
+
 /// The type used to represent empty values in `Value::Empty`.
 pub type EmptyType = ();
 
@@ -34,6 +38,8 @@ pub enum Value {
     Boolean(bool),
     /// A tuple value.
     Tuple(TupleType),
+    /// A record value, its fields are read with `record.field`.
+    Record(RecordType),
     /// An empty value.
     Empty,
 }
@@ -68,6 +74,11 @@ impl Value {
         matches!(self, Value::Tuple(_))
     }
 
+    /// Returns true if `self` is a `Value::Record`.
+    pub fn is_record(&self) -> bool {
+        matches!(self, Value::Record(_))
+    }
+
     /// Returns true if `self` is a `Value::Empty`.
     pub fn is_empty(&self) -> bool {
         matches!(self, Value::Empty)
@@ -137,6 +148,56 @@ impl Value {
         }
     }
 
+    /// Clones the value stored in `self` as `RecordType`, or returns `Err` if `self` is not a `Value::Record`.
+    pub fn as_record(&self) -> EvalexprResult<RecordType> {
+        match self {
+            Value::Record(record) => Ok(record.clone()),
+            value => Err(EvalexprError::expected_record(value.clone())),
+        }
+    }
+
+    /// Returns the item of a `Value::Tuple` at the `Value::Int` index `key`, or the field of a
+    /// `Value::Record` named by the `Value::String` `key`.
+    pub fn get(&self, key: &Value) -> EvalexprResult<Value> {
+        match (self, key) {
+            (Value::Tuple(tuple), Value::Int(index)) => usize::try_from(*index)
+                .ok()
+                .and_then(|i| tuple.get(i))
+                .cloned()
+                .ok_or(EvalexprError::IndexOutOfBounds {
+                    index: *index,
+                    length: tuple.len(),
+                }),
+            (Value::Record(record), Value::String(field)) => {
+                record
+                    .get(field)
+                    .cloned()
+                    .ok_or_else(|| EvalexprError::FieldNotFound {
+                        field: field.clone(),
+                        actual: self.clone(),
+                    })
+            }
+            _ => Err(EvalexprError::wrong_type_combination(
+                Operator::Index,
+                vec![self.into(), key.into()],
+            )),
+        }
+    }
+
+    /// Returns the value at the dotted `path` in `self`, `"name"` of a record or `"0.name"` of a
+    /// tuple of records.
+    pub fn get_path(&self, path: &str) -> EvalexprResult<Value> {
+        let mut value = self.clone();
+        for key in path.split('.') {
+            let key = match (&value, key.parse::<IntType>()) {
+                (Value::Tuple(_), Ok(index)) => Value::Int(index),
+                _ => Value::String(key.to_string()),
+            };
+            value = value.get(&key)?;
+        }
+        Ok(value)
+    }
+
     /// Returns `()`, or returns`Err` if `self` is not a `Value::Tuple`.
     pub fn as_empty(&self) -> EvalexprResult<()> {
         match self {
@@ -182,6 +243,12 @@ impl From<TupleType> for Value {
     }
 }
 
+impl From<RecordType> for Value {
+    fn from(record: RecordType) -> Self {
+        Value::Record(record)
+    }
+}
+
 impl From<Value> for EvalexprResult<Value> {
     fn from(value: Value) -> Self {
         Ok(value)
@@ -254,6 +321,18 @@ impl TryFrom<Value> for TupleType {
     }
 }
 
+impl TryFrom<Value> for RecordType {
+    type Error = EvalexprError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        if let Value::Record(value) = value {
+            Ok(value)
+        } else {
+            Err(EvalexprError::ExpectedRecord { actual: value })
+        }
+    }
+}
+
 impl TryFrom<Value> for () {
     type Error = EvalexprError;
 
@@ -268,7 +347,8 @@ impl TryFrom<Value> for () {
 
 #[cfg(test)]
 mod tests {
-    use fastn_grammar::evalexpr::value::{TupleType, Value};
+    use fastn_grammar::evalexpr::value::{RecordType, TupleType, Value};
+    use fastn_grammar::evalexpr::EvalexprError;
 
     #[test]
     fn test_value_conversions() {
@@ -283,6 +363,33 @@ mod tests {
             Value::from(TupleType::new()).as_tuple(),
             Ok(TupleType::new())
         );
+        assert_eq!(
+            Value::from(RecordType::new()).as_record(),
+            Ok(RecordType::new())
+        );
+    }
+
+    #[test]
+    fn test_value_get() {
+        let record = Value::from(RecordType::from([(
+            "items".to_string(),
+            Value::from(vec![Value::from(1), Value::from(2)]),
+        )]));
+        assert_eq!(record.get_path("items.1"), Ok(Value::from(2)));
+        assert_eq!(
+            record.get(&Value::from("name")),
+            Err(EvalexprError::FieldNotFound {
+                field: "name".to_string(),
+                actual: record.clone()
+            })
+        );
+        assert_eq!(
+            record.get_path("items.2"),
+            Err(EvalexprError::IndexOutOfBounds {
+                index: 2,
+                length: 2
+            })
+        );
     }
 
     #[test]
@@ -292,5 +399,6 @@ mod tests {
         assert!(Value::from(3.3).is_float());
         assert!(Value::from(true).is_boolean());
         assert!(Value::from(TupleType::new()).is_tuple());
+        assert!(Value::from(RecordType::new()).is_record());
     }
 }
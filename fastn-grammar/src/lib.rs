@@ -1,5 +1,6 @@
 extern crate self as fastn_grammar;
 
+pub mod datetime;
 pub mod evalexpr;
 
 pub fn add(left: usize, right: usize) -> usize {
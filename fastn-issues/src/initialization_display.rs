@@ -1,34 +1,121 @@
 use fastn_issues::initialization::*;
+use fastn_issues::reporter::Report;
 
-pub fn display_initialisation_error(e: &InitializePackageError) {
+pub fn display_initialisation_error(e: &InitializePackageError, json: bool) {
+    fastn_issues::reporter::report(&initialisation_report(e), json)
+}
+
+pub fn initialisation_report(e: &InitializePackageError) -> Report {
     match e {
-        InitializePackageError::FastnFTDError { source } => display_fastn_ftd_error(source),
-        InitializePackageError::InitializeDBError { source } => display_initialize_db_error(source),
+        InitializePackageError::FastnFTDError { source } => fastn_ftd_report(source),
+        InitializePackageError::InitializeDBError { source } => initialize_db_report(source),
     }
 }
 
-fn display_fastn_ftd_error(e: &FastnFTDError) {
+fn fastn_ftd_report(e: &FastnFTDError) -> Report {
     match e {
         FastnFTDError::ReadFTDFile { source } => match source {
-            FileAsStringError::FileDoesNotExist { .. } => {}
-            _ => todo!(),
+            FileAsStringError::FileDoesNotExist { name, .. } => Report::new(
+                "no FASTN.ftd file",
+                format!("We could not find {}, every fastn package needs one.", name),
+            )
+            .with_recommendation(
+                "Run `fastn create-package <name>` to create a new fastn package, or `cd` into \
+                an existing one.",
+            ),
+            FileAsStringError::PathIsNotAFile { name, .. } => Report::new(
+                "FASTN.ftd is not a file",
+                format!("{} exists, but it is not a regular file.", name),
+            )
+            .with_recommendation("Remove or rename it, and create FASTN.ftd as a file."),
+            FileAsStringError::CantReadFile { name, source } => Report::new(
+                "can not read FASTN.ftd",
+                format!("We could not read {}: {}.", name, source),
+            )
+            .with_recommendation("Check the permissions of the file."),
+            FileAsStringError::ContentIsNotUTF8 { name, .. } => Report::new(
+                "FASTN.ftd is not utf-8",
+                format!("{} contains bytes which are not valid utf-8.", name),
+            )
+            .with_recommendation("Save the file with utf-8 encoding."),
+        },
+        FastnFTDError::ParseFASTNFile { source } => parse_report(source),
+        FastnFTDError::StorePackageName {
+            source: StoreNameError::CantGetPackageName { source },
+        } => match source {
+            GetNameError::CantFindPackage { source } => Report::new(
+                "no fastn.package in FASTN.ftd",
+                format!("We could not find `fastn.package` in FASTN.ftd: {}", source),
+            )
+            .with_file("FASTN.ftd", None)
+            .with_recommendation("Add `-- fastn.package: <package-name>` to FASTN.ftd."),
+            GetNameError::PackageIsNone => Report::new(
+                "no fastn.package in FASTN.ftd",
+                "FASTN.ftd does not declare a package, so we do not know its name.",
+            )
+            .with_file("FASTN.ftd", None)
+            .with_recommendation("Add `-- fastn.package: <package-name>` to FASTN.ftd."),
         },
-        FastnFTDError::ParseFASTNFile { .. } => {
-            todo!()
-        }
-        FastnFTDError::StorePackageName { .. } => {
-            todo!()
-        }
     }
 }
 
-fn display_initialize_db_error(e: &InitializeDBError) {
+fn parse_report(e: &OldFastnParseError) -> Report {
     match e {
-        InitializeDBError::OpenDBConnection { .. } => {
-            todo!()
-        }
-        InitializeDBError::CreateTables { .. } => {
-            todo!()
+        OldFastnParseError::FTDError { source } => {
+            Report::new("invalid FASTN.ftd", source.to_string())
+                .with_file("FASTN.ftd", line_number(source))
+                .with_recommendation("Fix the ftd syntax at the line mentioned above.")
         }
+        OldFastnParseError::InvalidImport { module } => Report::new(
+            "invalid import in FASTN.ftd",
+            format!(
+                "FASTN.ftd imports `{}`, but it can only import `fastn`.",
+                module
+            ),
+        )
+        .with_file("FASTN.ftd", None)
+        .with_recommendation(format!("Remove `-- import: {}` from FASTN.ftd.", module)),
+        OldFastnParseError::ProcessorUsed { processor } => Report::new(
+            "processor used in FASTN.ftd",
+            format!(
+                "FASTN.ftd uses the processor `{}`, processors are not allowed in FASTN.ftd.",
+                processor
+            ),
+        )
+        .with_file("FASTN.ftd", None)
+        .with_recommendation("Remove the `$processor$` line from FASTN.ftd."),
+    }
+}
+
+fn initialize_db_report(e: &InitializeDBError) -> Report {
+    match e {
+        InitializeDBError::OpenDBConnection { source } => Report::new(
+            "can not open database",
+            format!(
+                "We could not open the in-memory package database: {}.",
+                source
+            ),
+        ),
+        InitializeDBError::CreateTables { source } => Report::new(
+            "can not create tables",
+            format!(
+                "We could not create the package database tables: {}.",
+                source
+            ),
+        ),
+    }
+}
+
+fn line_number(e: &ftd::ftd2021::p1::Error) -> Option<usize> {
+    use ftd::ftd2021::p1::Error;
+
+    match e {
+        Error::ParseError { line_number, .. }
+        | Error::UnknownData { line_number, .. }
+        | Error::MissingData { line_number, .. }
+        | Error::ForbiddenUsage { line_number, .. }
+        | Error::NotFound { line_number, .. }
+        | Error::MoreThanOneSubSections { line_number, .. } => Some(*line_number),
+        Error::Serde { .. } | Error::Syntect { .. } => None,
     }
 }
@@ -1,23 +1,127 @@
-// use dioxus::prelude::*;
-//
-// pub(crate) fn report(_brief: &str, _message_1: &str, _recommendation: &str, _message_2: &str) {
-//     dioxus_tui::launch(app);
-// }
-//
-// // create a component that renders a div with the text "Hello, world!"
-// #[allow(non_snake_case)]
-// fn app(cx: Scope) -> Element {
-//     cx.render(rsx! {
-//         div {
-//             "hello world"
-//         }
-//     })
-// }
-
-// -- NO elm.json FILE ------------------------------------------------------------
-//
-// It looks like you are starting a new Elm project. Very exciting! Try running:
-//
-//      elm init
-//
-// It will help you get set up. It is really simple!
+/// Width of the report, the headline is padded with `-` to this width.
+const WIDTH: usize = 80;
+
+/// A friendly, actionable description of an error, shown to the user by the `fastn` cli.
+///
+/// Inspired by the error reports of the elm compiler:
+///
+/// ```txt
+/// -- NO FASTN.ftd FILE -----------------------------------------------------------
+///
+/// We could not find FASTN.ftd in /home/amitu or any of its parent directories.
+///
+/// Hint: Run `fastn create-package <name>` to create a new fastn package here.
+/// ```
+///
+/// With `--json` the same report is printed as a single JSON object, for editors and CI.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct Report {
+    pub headline: String,
+    pub file: Option<String>,
+    pub line_number: Option<usize>,
+    pub explanation: String,
+    pub recommendation: Option<String>,
+}
+
+impl Report {
+    pub fn new(headline: impl Into<String>, explanation: impl Into<String>) -> Report {
+        Report {
+            headline: headline.into(),
+            file: None,
+            line_number: None,
+            explanation: explanation.into(),
+            recommendation: None,
+        }
+    }
+
+    pub fn with_file(mut self, file: impl Into<String>, line_number: Option<usize>) -> Report {
+        self.file = Some(file.into());
+        self.line_number = line_number;
+        self
+    }
+
+    pub fn with_recommendation(mut self, recommendation: impl Into<String>) -> Report {
+        self.recommendation = Some(recommendation.into());
+        self
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("report is always serializable")
+    }
+
+    fn location(&self) -> Option<String> {
+        let file = self.file.as_ref()?;
+        Some(match self.line_number {
+            Some(line_number) => format!("{}:{}", file, line_number),
+            None => file.to_string(),
+        })
+    }
+}
+
+impl std::fmt::Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let headline = format!("-- {} ", self.headline.to_uppercase());
+        let location = self
+            .location()
+            .map(|l| format!(" {}", l))
+            .unwrap_or_default();
+        let dashes = WIDTH.saturating_sub(headline.chars().count() + location.chars().count());
+
+        writeln!(f, "{}{}{}", headline, "-".repeat(dashes), location)?;
+        writeln!(f)?;
+        writeln!(f, "{}", self.explanation.trim())?;
+        if let Some(ref recommendation) = self.recommendation {
+            writeln!(f)?;
+            writeln!(f, "Hint: {}", recommendation.trim())?;
+        }
+        Ok(())
+    }
+}
+
+/// Print the report on stderr, as JSON if `json` is true.
+pub fn report(report: &Report, json: bool) {
+    if json {
+        eprintln!("{}", report.to_json());
+    } else {
+        eprintln!("{}", report);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn display() {
+        let report = fastn_issues::reporter::Report::new(
+            "unknown dependency",
+            "We could not download FASTN.ftd of the dependency `foo.com`.",
+        )
+        .with_file("FASTN.ftd", Some(4))
+        .with_recommendation("Check the spelling of the package name.");
+
+        assert_eq!(
+            report.to_string(),
+            indoc::indoc!(
+                "
+                -- UNKNOWN DEPENDENCY ---------------------------------------------- FASTN.ftd:4
+
+                We could not download FASTN.ftd of the dependency `foo.com`.
+
+                Hint: Check the spelling of the package name.
+                "
+            )
+        );
+        assert_eq!(
+            report.to_string().lines().next().unwrap().chars().count(),
+            80
+        );
+    }
+
+    #[test]
+    fn json() {
+        let report = fastn_issues::reporter::Report::new("port in use", "Port 8000 is in use.");
+        assert_eq!(
+            report.to_json(),
+            r#"{"headline":"port in use","file":null,"line_number":null,"explanation":"Port 8000 is in use.","recommendation":null}"#
+        );
+    }
+}
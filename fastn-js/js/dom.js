@@ -17,11 +17,14 @@ fastn_dom.commentMessage = "***FASTN***";
 fastn_dom.webComponentArgument = "args";
 
 fastn_dom.classes = { }
+// `@keyframes` rules of `ftd.animation`s used so far, by animation name
+fastn_dom.keyframes = { }
 fastn_dom.unsanitised_classes = {}
 fastn_dom.class_count = 0;
 fastn_dom.propertyMap = {
     "align-items": "ali",
     "align-self": "as",
+    "animation": "an",
     "background-color": "bgc",
     "background-image": "bgi",
     "background-position": "bgp",
@@ -49,6 +52,7 @@ fastn_dom.propertyMap = {
     "border-width": "bw",
     "bottom": "b",
     "color": "c",
+    "column-gap": "cg",
     "shadow": "sh",
     "text-shadow": "tsh",
     "cursor": "cur",
@@ -57,6 +61,10 @@ fastn_dom.propertyMap = {
     "font-style": "fst",
     "font-weight": "fwt",
     "gap": "g",
+    "grid-area": "ga",
+    "grid-template-areas": "gta",
+    "grid-template-columns": "gtc",
+    "grid-template-rows": "gtr",
     "height": "h",
     "justify-content": "jc",
     "left": "l",
@@ -88,12 +96,15 @@ fastn_dom.propertyMap = {
     "position": "pos",
     "resize": "res",
     "role": "rl",
+    "row-gap": "rg",
     "right": "r",
     "sticky": "s",
     "text-align": "ta",
     "text-decoration": "td",
     "text-transform": "tt",
     "top": "t",
+    "transform": "tf",
+    "transition": "tn",
     "width": "w",
     "z-index": "z",
     "-webkit-box-orient": "wbo",
@@ -101,19 +112,39 @@ fastn_dom.propertyMap = {
 };
 
 // dynamic-class-css.md
-fastn_dom.getClassesAsString = function() {
+/**
+ * With `asMediaQueries`, for the pages shown without JS, the classes of the dark mode and of the
+ * mobile, which are switched by the JS with a class on the body, apply by the media queries.
+ */
+fastn_dom.getClassesAsString = function(asMediaQueries) {
     let classes = Object.entries(fastn_dom.classes).map(entry => {
-        return getClassAsString(entry[0], entry[1]);
+        let css = getClassAsString(entry[0], entry[1]);
+        return asMediaQueries ? getClassAsMediaQuery(entry[0], css) : css;
     });
 
     /*.ft_text {
         padding: 0;
     }*/
+    let keyframes = Object.values(fastn_dom.keyframes);
+
     return `<style id="styles">
-    ${classes.join("\n\t")}
+    ${classes.concat(keyframes).join("\n\t")}
     </style>`;
 }
 
+function getClassAsMediaQuery(className, css) {
+    let query;
+    if (className.startsWith("body.dark ")) {
+        query = "(prefers-color-scheme: dark)";
+    } else if (className.startsWith("body.mobile ")) {
+        let breakpoint = fastn_utils.getStaticValue(ftd.breakpoint_width.get("mobile"));
+        query = `(max-width: ${breakpoint}px)`;
+    } else {
+        return css;
+    }
+    return `@media ${query} { ${css.replace(/^body\.\w+\s+/, "")} }`;
+}
+
 function getClassAsString(className, obj) {
     if (typeof obj.value === 'object' && obj.value !== null) {
         let value = "";
@@ -154,6 +185,7 @@ fastn_dom.ElementKind = {
     // internal usage in js functions.
     WebComponent: (webcomponent, args) => { return [17, [webcomponent, args]]; },
     Video: 18,
+    Grid: 19,
 };
 
 fastn_dom.PropertyKind = {
@@ -277,6 +309,19 @@ fastn_dom.PropertyKind = {
     Muted: 115,
     LinkColor: 116,
     TextShadow: 117,
+    Transform: 118,
+    Transition: 119,
+    Animation: 120,
+    GridColumns: 121,
+    GridRows: 122,
+    GridAreas: 123,
+    GridTemplate: 124,
+    GridArea: 125,
+    Gap: 126,
+    RowGap: 127,
+    ColumnGap: 128,
+    ImageSizes: 129,
+    HeadingAnchors: 130,
 };
 
 
@@ -422,6 +467,28 @@ fastn_dom.TextAlign = {
     Justify: "justify",
 }
 
+fastn_dom.Easing = {
+    Linear: "linear",
+    Ease: "ease",
+    EaseIn: "ease-in",
+    EaseOut: "ease-out",
+    EaseInOut: "ease-in-out",
+}
+
+fastn_dom.GridTrack = {
+    Fixed: (value) => { return value; },
+    Fr: (value) => {
+        if (value instanceof fastn.mutableClass) {
+            return fastn.formula([value], function () { return `${value.get()}fr`})
+        }
+        return `${value}fr`;
+    },
+    Auto: "auto",
+    Minmax: (value) => { return [1, value]; },
+    AutoFill: (value) => { return [2, value]; },
+    AutoFit: (value) => { return [3, value]; },
+}
+
 fastn_dom.Cursor = {
     None: "none",
     Default: "default",
@@ -486,6 +553,15 @@ fastn_dom.BackgroundStyle = {
     LinearGradient: (value) => {
         return [3, value];
     },
+    RadialGradient: (value) => {
+        return [4, value];
+    },
+    ConicGradient: (value) => {
+        return [5, value];
+    },
+    Layers: (value) => {
+        return [6, value];
+    },
 }
 
 fastn_dom.BackgroundRepeat = {
@@ -533,6 +609,11 @@ fastn_dom.LinearGradientDirection = {
     BottomRight: "135deg",
 }
 
+fastn_dom.RadialGradientShape = {
+    Circle: "circle",
+    Ellipse: "ellipse",
+}
+
 fastn_dom.FontSize = {
     Px: (value) => {
         if (value instanceof fastn.mutableClass) {
@@ -657,6 +738,7 @@ class Node2 {
     #parent;
     #tagName;
     #rawInnerValue;
+    #headingAnchors;
     /**
      * This is where we store all the attached closures, so we can free them
      * when we are done.
@@ -670,6 +752,7 @@ class Node2 {
     #extraData;
     #children;
     constructor(parentOrSibiling, kind) {
+        fastn_spa.track(this);
         this.#kind = kind;
         this.#parent = parentOrSibiling;
         this.#children = [];
@@ -735,12 +818,15 @@ class Node2 {
     }
 
     setFavicon(url) {
+        if (url instanceof fastn.recordInstanceClass) url = url.get('src');
+        while (true) {
+            if (url instanceof fastn.mutableClass) url = url.get();
+            else break;
+        }
+        if (ssr) {
+            fastn_virtual.addToHead(`<link rel="shortcut icon" href="${fastn_utils.escapeHtmlAttribute(url)}">`);
+        }
         if (hydrating) {
-            if (url instanceof fastn.recordInstanceClass) url = url.get('src');
-            while (true) {
-                if (url instanceof fastn.mutableClass) url = url.get();
-                else break;
-            }
 
             let link_element = document.createElement("link");
             link_element.rel = "shortcut icon";
@@ -822,6 +908,9 @@ class Node2 {
         }
     }
     updateMetaTitle(value) {
+        if (ssr && !fastn_utils.isNull(value)) {
+            fastn_virtual.addToHead(`<title>${fastn_utils.escapeHtmlAttribute(value)}</title>`);
+        }
         if (!ssr && hydrating) {
             if (!fastn_utils.isNull(value)) window.document.title = value;
         }
@@ -831,10 +920,15 @@ class Node2 {
             this.removeMetaTagByName(name);
             return;
         }
+        if (ssr) {
+            fastn_virtual.addToHead(`<meta name="${fastn_utils.escapeHtmlAttribute(name)}" content="${fastn_utils.escapeHtmlAttribute(value)}">`);
+        }
         if (!ssr && hydrating) {
             const metaTag = window.document.createElement('meta');
             metaTag.setAttribute('name', name);
             metaTag.setAttribute('content', value);
+            // removed when navigating to another page, see `fastn_spa.show()`
+            metaTag.setAttribute('data-fastn', '');
             document.head.appendChild(metaTag);
         }
     }
@@ -843,10 +937,15 @@ class Node2 {
             this.removeMetaTagByProperty(property);
             return;
         }
+        if (ssr) {
+            fastn_virtual.addToHead(`<meta property="${fastn_utils.escapeHtmlAttribute(property)}" content="${fastn_utils.escapeHtmlAttribute(value)}">`);
+        }
         if (!ssr && hydrating) {
             const metaTag = window.document.createElement('meta');
             metaTag.setAttribute('property', property);
             metaTag.setAttribute('content', value);
+            // removed when navigating to another page, see `fastn_spa.show()`
+            metaTag.setAttribute('data-fastn', '');
             document.head.appendChild(metaTag);
         }
     }
@@ -1006,58 +1105,106 @@ class Node2 {
             this.attachCss("text-shadow", darkShadowCss, true, `body.dark .${lightClass}`);
         }
     }
-    attachLinearGradientCss(value) {
+    attachTransform(value) {
         if (fastn_utils.isNull(value)) {
-            this.attachCss("background-image", value);
+            this.attachCss("transform", value);
+            return;
+        }
+        this.attachCss("transform", fastn_utils.getTransformCss(value));
+    }
+    attachGridTemplate(value) {
+        if (fastn_utils.isNull(value)) {
+            this.attachCss("grid-template-columns", value);
+            this.attachCss("grid-template-rows", value);
+            this.attachCss("grid-template-areas", value);
             return;
         }
-        var lightGradientString = "";
-        var darkGradientString = "";
 
-        let colorsList = value.get("colors").get().getList();
-        let direction = fastn_utils.getStaticValue(value.get("direction"));
-        colorsList.map(function (element) {
-            // LinearGradient RecordInstance
-            let lg_color = element.item;
+        // `mobile` defaults to `desktop` in ftd.responsive-grid-template
+        const device = ftd.device.get() === "desktop" ? "desktop" : "mobile";
+        const template = fastn_utils.getStaticValue(value.get(device));
+        const field = (name) => fastn_utils.getStaticValue(template.get(name));
 
-            let color = lg_color.get("color").get();
-            let lightColor = fastn_utils.getStaticValue(color.get("light"));
-            let darkColor = fastn_utils.getStaticValue(color.get("dark"));
+        this.attachCss("grid-template-columns", fastn_utils.getGridTracksCss(field("columns")));
+        this.attachCss("grid-template-rows", fastn_utils.getGridTracksCss(field("rows")));
+        this.attachCss("grid-template-areas", fastn_utils.getGridAreasCss(field("areas")));
+    }
+    attachTransition(value) {
+        if (fastn_utils.isNull(value)) {
+            this.attachCss("transition", value);
+            return;
+        }
 
-            lightGradientString = `${lightGradientString} ${lightColor}`;
-            darkGradientString = `${darkGradientString} ${darkColor}`;
+        const property = fastn_utils.getStaticValue(value.get("property"));
+        const duration = fastn_utils.getStaticValue(value.get("duration"));
+        const easing = fastn_utils.getStaticValue(value.get("easing"));
+        const delay = fastn_utils.getStaticValue(value.get("delay"));
 
-            let start = fastn_utils.getStaticValue(lg_color.get("start"));
-            if (start !== undefined && start !== null ) {
-                lightGradientString = `${lightGradientString} ${start}`;
-                darkGradientString = `${darkGradientString} ${start}`;
-            }
+        this.attachCss("transition", `${property} ${duration}s ${easing} ${delay}s`);
+    }
+    attachAnimation(value) {
+        if (fastn_utils.isNull(value)) {
+            this.attachCss("animation", value);
+            return;
+        }
 
-            let end = fastn_utils.getStaticValue(lg_color.get("end"));
-            if (end !== undefined && end !== null ) {
-                lightGradientString = `${lightGradientString} ${end}`;
-                darkGradientString = `${darkGradientString} ${end}`;
-            }
+        const name = fastn_utils.getStaticValue(value.get("name"));
+        const duration = fastn_utils.getStaticValue(value.get("duration"));
+        const easing = fastn_utils.getStaticValue(value.get("easing"));
+        const delay = fastn_utils.getStaticValue(value.get("delay"));
+        const iterations = fastn_utils.getStaticValue(value.get("iterations"));
+        const alternate = fastn_utils.getStaticValue(value.get("alternate"));
 
-            let stop_position = fastn_utils.getStaticValue(lg_color.get("stop_position"));
-            if (stop_position !== undefined && stop_position !== null ) {
-                lightGradientString = `${lightGradientString}, ${stop_position}`;
-                darkGradientString = `${darkGradientString}, ${stop_position}`;
+        if (!fastn_dom.keyframes[name]) {
+            const keyframes = fastn_utils.getStaticValue(value.get("keyframes"))
+                .map(obj => fastn_utils.getStaticValue(obj.item));
+            fastn_dom.keyframes[name] = fastn_utils.getKeyframesCss(name, keyframes);
+            if (!ssr && !hydrating) {
+                let styles = document.getElementById('styles');
+                styles.innerHTML = `${styles.innerHTML}${fastn_dom.keyframes[name]}\n`;
             }
+        }
 
-            lightGradientString = `${lightGradientString},`
-            darkGradientString = `${darkGradientString},`
-        });
+        const count = fastn_utils.isNull(iterations) ? "infinite" : iterations;
+        const direction = alternate ? " alternate" : "";
+        this.attachCss("animation", `${name} ${duration}s ${easing} ${delay}s ${count}${direction}`);
+    }
+    attachGradientCss(kind, value) {
+        if (fastn_utils.isNull(value)) {
+            this.attachCss("background-image", value);
+            return;
+        }
+        let [lightGradient, darkGradient] = fastn_utils.getGradientCss(kind, value);
+        if (lightGradient === darkGradient) {
+            this.attachCss("background-image", lightGradient, false);
+        } else {
+            let lightClass = this.attachCss("background-image", lightGradient, true);
+            this.attachCss("background-image", darkGradient, true, `body.dark .${lightClass}`);
+        }
+    }
+    attachLayeredBackgroundCss(value) {
+        let layers = fastn_utils.isNull(value) ? [] : fastn_utils.getStaticValue(value.get("layers"));
+        if (layers.length === 0) {
+            this.attachBackgroundImageCss(null);
+            this.attachColorCss("background-color", null);
+            return;
+        }
+        layers = layers.map(obj => fastn_utils.getBackgroundLayerCss(fastn_utils.getStaticValue(obj.item)));
+        const join = (f) => layers.map(f).join(", ");
 
-        lightGradientString = lightGradientString.trim().slice(0, -1);
-        darkGradientString = darkGradientString.trim().slice(0, -1);
+        this.attachCss("background-repeat", join(l => l.repeat));
+        this.attachCss("background-position", join(l => l.position));
+        this.attachCss("background-size", join(l => l.size));
 
-        if (lightGradientString === darkGradientString) {
-            this.attachCss("background-image", `linear-gradient(${direction}, ${lightGradientString})`, false);
+        let lightValue = join(l => l.image[0]);
+        let darkValue = join(l => l.image[1]);
+        if (lightValue === darkValue) {
+            this.attachCss("background-image", lightValue, false);
         } else {
-            let lightClass = this.attachCss("background-image", `linear-gradient(${direction}, ${lightGradientString})`,true);
-            this.attachCss("background-image", `linear-gradient(${direction}, ${darkGradientString})`, true, `body.dark .${lightClass}`);
+            let lightClass = this.attachCss("background-image", lightValue, true);
+            this.attachCss("background-image", darkValue, true, `body.dark .${lightClass}`);
         }
+        this.attachColorCss("background-color", fastn_utils.getStaticValue(value.get("color")));
     }
     attachBackgroundImageCss(value) {
         if (fastn_utils.isNull(value)) {
@@ -1072,33 +1219,9 @@ class Node2 {
         let lightValue = fastn_utils.getStaticValue(src.get("light"));
         let darkValue = fastn_utils.getStaticValue(src.get("dark"));
 
-        let position = fastn_utils.getStaticValue(value.get("position"));
-        let positionX = null;
-        let positionY = null;
-        if (position !== null && position instanceof Object) {
-            positionX = fastn_utils.getStaticValue(position.get("x"));
-            positionY = fastn_utils.getStaticValue(position.get("y"));
-
-            if (positionX !== null) position = `${positionX}`;
-            if (positionY !== null) {
-                if (positionX === null) position = `0px ${positionY}`;
-                else position = `${position} ${positionY}`;
-            }
-        }
+        let position = fastn_utils.getLengthPairCss(fastn_utils.getStaticValue(value.get("position")));
         let repeat = fastn_utils.getStaticValue(value.get("repeat"));
-        let size = fastn_utils.getStaticValue(value.get("size"));
-        let sizeX = null;
-        let sizeY = null;
-        if (size !== null && size instanceof Object) {
-            sizeX = fastn_utils.getStaticValue(size.get("x"));
-            sizeY = fastn_utils.getStaticValue(size.get("y"));
-
-            if (sizeX !== null) size = `${sizeX}`;
-            if (sizeY !== null) {
-                if (sizeX === null) size = `0px ${sizeY}`;
-                else size = `${size} ${sizeY}`;
-            }
-        }
+        let size = fastn_utils.getLengthPairCss(fastn_utils.getStaticValue(value.get("size")));
 
         if (repeat !== null) this.attachCss("background-repeat", repeat);
         if (position !== null) this.attachCss("background-position", position);
@@ -1356,6 +1479,9 @@ class Node2 {
             }
         } else if (kind === fastn_dom.PropertyKind.Id) {
             this.#node.id = staticValue;
+            if (this.#kind === fastn_dom.ElementKind.Text && !fastn_utils.isNull(staticValue)) {
+                fastn_utils.private.reserveHeadingId(staticValue);
+            }
         } else if (kind === fastn_dom.PropertyKind.Css) {
             let css_list = staticValue.map(obj => fastn_utils.getStaticValue(obj.item));
             css_list.forEach((css) => {
@@ -1446,6 +1572,28 @@ class Node2 {
             this.attachShadow(staticValue);
         } else if (kind === fastn_dom.PropertyKind.TextShadow) {
             this.attachTextShadow(staticValue);
+        } else if (kind === fastn_dom.PropertyKind.Transform) {
+            this.attachTransform(staticValue);
+        } else if (kind === fastn_dom.PropertyKind.Transition) {
+            this.attachTransition(staticValue);
+        } else if (kind === fastn_dom.PropertyKind.Animation) {
+            this.attachAnimation(staticValue);
+        } else if (kind === fastn_dom.PropertyKind.GridColumns) {
+            this.attachCss("grid-template-columns", fastn_utils.getGridTracksCss(staticValue));
+        } else if (kind === fastn_dom.PropertyKind.GridRows) {
+            this.attachCss("grid-template-rows", fastn_utils.getGridTracksCss(staticValue));
+        } else if (kind === fastn_dom.PropertyKind.GridAreas) {
+            this.attachCss("grid-template-areas", fastn_utils.getGridAreasCss(staticValue));
+        } else if (kind === fastn_dom.PropertyKind.GridTemplate) {
+            this.attachGridTemplate(staticValue);
+        } else if (kind === fastn_dom.PropertyKind.GridArea) {
+            this.attachCss("grid-area", staticValue);
+        } else if (kind === fastn_dom.PropertyKind.Gap) {
+            this.attachCss("gap", staticValue);
+        } else if (kind === fastn_dom.PropertyKind.RowGap) {
+            this.attachCss("row-gap", staticValue);
+        } else if (kind === fastn_dom.PropertyKind.ColumnGap) {
+            this.attachCss("column-gap", staticValue);
         } else if (kind === fastn_dom.PropertyKind.Classes) {
             fastn_utils.removeNonFastnClasses(this);
             if (!fastn_utils.isNull(staticValue)) {
@@ -1590,7 +1738,7 @@ class Node2 {
             if (fastn_utils.isNull(staticValue)) {
                 this.attachColorCss("background-color", staticValue);
                 this.attachBackgroundImageCss(staticValue);
-                this.attachLinearGradientCss(staticValue);
+                this.attachGradientCss("linear", staticValue);
                 return;
             }
 
@@ -1603,7 +1751,16 @@ class Node2 {
                     this.attachBackgroundImageCss(staticValue[1]);
                     break;
                 case fastn_dom.BackgroundStyle.LinearGradient()[0]:
-                    this.attachLinearGradientCss(staticValue[1]);
+                    this.attachGradientCss("linear", staticValue[1]);
+                    break;
+                case fastn_dom.BackgroundStyle.RadialGradient()[0]:
+                    this.attachGradientCss("radial", staticValue[1]);
+                    break;
+                case fastn_dom.BackgroundStyle.ConicGradient()[0]:
+                    this.attachGradientCss("conic", staticValue[1]);
+                    break;
+                case fastn_dom.BackgroundStyle.Layers()[0]:
+                    this.attachLayeredBackgroundCss(staticValue[1]);
                     break;
             }
         } else if (kind === fastn_dom.PropertyKind.Display) {
@@ -1678,8 +1835,8 @@ class Node2 {
             this.attachTextStyles(styles);
         } else if (kind === fastn_dom.PropertyKind.Region) {
             this.updateTagName(staticValue);
-            if (this.#node.innerHTML) {
-                this.#node.id = fastn_utils.slugify(this.#rawInnerValue);
+            if (this.#node.innerHTML && !this.#node.id) {
+                this.#node.id = fastn_utils.private.nextHeadingId(fastn_utils.slugify(this.#rawInnerValue));
             }
         } else if (kind === fastn_dom.PropertyKind.AlignContent) {
             let node_kind = this.#kind;
@@ -1696,6 +1853,13 @@ class Node2 {
                 }
                 const is_dark_mode = ftd.dark_mode.get();
                 const src = staticValue.get(is_dark_mode ? 'dark' : 'light');
+                // srcset, width and height are set by `fastn build` for the images of the package
+                const attributes = {
+                    src: fastn_utils.getStaticValue(src),
+                    srcset: fastn_utils.getStaticValue(staticValue.get(is_dark_mode ? 'dark_srcset' : 'srcset')),
+                    width: fastn_utils.getStaticValue(staticValue.get('width')),
+                    height: fastn_utils.getStaticValue(staticValue.get('height')),
+                };
                 if (!ssr) {
                     let image_node = this.#node;
                     if( image_node.nodeName.toLowerCase() === "a" ) {
@@ -1705,15 +1869,34 @@ class Node2 {
                                 image_node = child;
                         });
                     }
-                    image_node.setAttribute("src", fastn_utils.getStaticValue(src));
+                    for (const [name, value] of Object.entries(attributes)) {
+                        if (fastn_utils.isNull(value)) {
+                            image_node.removeAttribute(name);
+                        } else {
+                            image_node.setAttribute(name, value);
+                        }
+                    }
                 }
                 else {
-                    this.attachAttribute("src", fastn_utils.getStaticValue(src));
+                    for (const [name, value] of Object.entries(attributes)) {
+                        if (!fastn_utils.isNull(value)) {
+                            this.attachAttribute(name, value);
+                        }
+                    }
                 }
             }).addNodeProperty(this, null, inherited));
             this.#mutables.push(ftd.dark_mode);
+            const placeholder = fastn_utils.isNull(staticValue) ? null : fastn_utils.getStaticValue(staticValue.get('placeholder'));
+            if (!fastn_utils.isNull(placeholder)) {
+                this.attachCss("background-image", `url("${placeholder}")`);
+                this.attachCss("background-size", "cover");
+            }
         } else if (kind === fastn_dom.PropertyKind.Alt) {
             this.attachAttribute("alt", staticValue);
+        } else if (kind === fastn_dom.PropertyKind.ImageSizes) {
+            this.attachAttribute("sizes", staticValue);
+        } else if (kind === fastn_dom.PropertyKind.HeadingAnchors) {
+            this.#headingAnchors = staticValue;
         } else if (kind === fastn_dom.PropertyKind.VideoSrc) {
             ftd.dark_mode.addClosure(fastn.closure(() => {
                 if (fastn_utils.isNull(staticValue)) {
@@ -1878,9 +2061,17 @@ class Node2 {
         } else if (kind === fastn_dom.PropertyKind.StringValue) {
             this.#rawInnerValue = staticValue;
             if (!hydrating || this.#node.innerHTML === "undefined") {
-                staticValue = fastn_utils.markdown_inline(fastn_utils.escapeHtmlInMarkdown(staticValue));
+                staticValue = fastn_utils.markdown_inline(
+                    fastn_utils.escapeHtmlInMarkdown(staticValue),
+                    this.#headingAnchors,
+                    fastn_utils.private.renderedMarkdown.get(staticValue),
+                );
             } else {
                 staticValue = this.#node.innerHTML;
+                // the headings rendered on the server keep their ids
+                for (const [, id] of staticValue.matchAll(/<h[1-6] id="([^"]*)"/g)) {
+                    fastn_utils.private.reserveHeadingId(id);
+                }
             }
             staticValue = fastn_utils.process_post_markdown(this.#node, staticValue);
             this.#node.innerHTML = staticValue;
@@ -1889,7 +2080,12 @@ class Node2 {
         }
     }
     setProperty(kind, value, inherited) {
-        if (value instanceof fastn.mutableClass) {
+        if (kind === fastn_dom.PropertyKind.GridTemplate) {
+            // the template in use depends on the device, re-apply it when that changes
+            this.setDynamicProperty(kind, [ftd.device, value], () => {
+                return value instanceof fastn.mutableClass ? value.get() : value;
+            }, inherited);
+        } else if (value instanceof fastn.mutableClass) {
             this.setDynamicProperty(kind, [value], () => { return value.get(); }, inherited);
         } else if (value instanceof PropertyValueAsClosure) {
             this.setDynamicProperty(kind, value.deps, value.closureFunction, inherited);
@@ -1976,8 +2172,11 @@ class ConditionalDom {
     #condition;
     #mutables;
     #conditionUI;
+    #deps;
+    #closure;
 
     constructor(parent, deps, condition, node_constructor) {
+        fastn_spa.track(this);
         this.#marker = fastn_dom.createKernel(parent, fastn_dom.ElementKind.Comment);
         this.#parent = parent;
 
@@ -2015,6 +2214,18 @@ class ConditionalDom {
         this.#node_constructor = node_constructor;
         this.#condition = condition;
         this.#mutables = [];
+        this.#deps = deps;
+        this.#closure = closure;
+    }
+
+    // the nodes of the condition are destroyed on their own, see `fastn_spa.teardown()`
+    destroy() {
+        this.#deps.forEach(dep => {
+            if (!fastn_utils.isNull(dep) && dep.removeClosure) {
+                dep.removeClosure(this.#closure);
+            }
+        });
+        this.#deps = [];
     }
 
     getParent() {
@@ -2056,6 +2267,7 @@ class ForLoop {
     #parent;
     #nodes;
     constructor(parent, node_constructor, list) {
+        fastn_spa.track(this);
         this.#wrapper = fastn_dom.createKernel(parent, fastn_dom.ElementKind.Comment);
         this.#parent = parent;
         this.#node_constructor = node_constructor;
@@ -2076,7 +2288,7 @@ class ForLoop {
         if (index !== 0) {
             parentWithSibiling = new ParentNodeWithSibiling(this.#parent, this.#nodes[index-1]);
         }
-        let v = this.#list.get(index);
+        let v = this.#list.getList()[index];
         let node = this.#node_constructor(parentWithSibiling, v.item, v.index);
         this.#nodes.splice(index, 0, node);
         if (resizeBodyHeight) {
@@ -2115,6 +2327,12 @@ class ForLoop {
     getParent() {
         return this.#parent;
     }
+    // the nodes of the loop are destroyed on their own, see `fastn_spa.teardown()`
+    destroy() {
+        if (this.#list.removeForLoop) {
+            this.#list.removeForLoop(this);
+        }
+    }
 }
 
 fastn_dom.forLoop = function (parent, node_constructor, list) {
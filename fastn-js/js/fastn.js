@@ -175,6 +175,9 @@ class MutableList {
     addClosure(closure) {
         this.#closures.push(closure);
     }
+    removeClosure(closure) {
+        this.#closures = this.#closures.filter(c => c !== closure);
+    }
     unlinkNode(node) {
         this.#closures = this.#closures.filter(closure => closure.getNode() !== node);
     }
@@ -183,6 +186,9 @@ class MutableList {
         this.#watchers.push(l);
         return l;
     }
+    removeForLoop(l) {
+        this.#watchers = this.#watchers.filter(watcher => watcher !== l);
+    }
     getList() {
         return this.#list;
     }
@@ -341,6 +347,9 @@ class RecordInstance {
     addClosure(closure) {
         this.#closures.push(closure);
     }
+    removeClosure(closure) {
+        this.#closures = this.#closures.filter(c => c !== closure);
+    }
     unlinkNode(node) {
         this.#closures = this.#closures.filter(closure => closure.getNode() !== node);
     }
@@ -360,6 +369,8 @@ class RecordInstance {
             }
 
             this.#fields = fields;
+            this.#closures.forEach((closure) => closure.update());
+            return;
         }
         if (this.#fields[key] === undefined) {
             this.#fields[key] = fastn.mutable(null);
@@ -402,6 +413,91 @@ class RecordInstance {
     }
 }
 
+// `map<string, T>` values. Entries are iterated in key order, the loop
+// counter of a `for` over a map is the key of the entry.
+//
+// The keys are kept as they are. References like `$map.some-key` reach the map
+// with the key mangled like any other name, as `some_key`, so an entry is found
+// by its key, else by the mangled key. The interpreter rejects the maps with
+// two keys that are the same name, like `a-b` and `a_b`.
+class MutableMap extends RecordInstance {
+    #watchers;
+    constructor(obj) {
+        super(obj);
+        this.#watchers = [];
+    }
+    // The key as `fastn_js::utils::name_to_js_()` mangles it.
+    static #normalise(key) {
+        key = String(key);
+        if (/^[0-9]/.test(key)) {
+            key = `_${key}`;
+        }
+        return key
+            .replace(/#/g, "__")
+            .replace(/-/g, "_")
+            .replace(/:/g, "___")
+            .replace(/,/g, "$")
+            .replace(/\\/g, "/")
+            .replace(/[\/.]/g, "_");
+    }
+    #find(key) {
+        let fields = this.getAllFields();
+        if (Object.prototype.hasOwnProperty.call(fields, key)) {
+            return key;
+        }
+        let normalised = MutableMap.#normalise(key);
+        return Object.keys(fields).find((k) => MutableMap.#normalise(k) === normalised);
+    }
+    forLoop(root, dom_constructor) {
+        let l = fastn_dom.forLoop(root, dom_constructor, this);
+        this.#watchers.push(l);
+        return l;
+    }
+    removeForLoop(l) {
+        this.#watchers = this.#watchers.filter(watcher => watcher !== l);
+    }
+    getList() {
+        let fields = this.getAllFields();
+        return Object.keys(fields).sort().map((key) => ({ item: fields[key], index: new Mutable(key) }));
+    }
+    getLength() {
+        return Object.keys(this.getAllFields()).length;
+    }
+    get(key) {
+        if (fastn_utils.isNull(key)) {
+            return undefined;
+        }
+        let found = this.#find(key);
+        return found === undefined ? undefined : super.get(found);
+    }
+    set(key, value) {
+        if (value === undefined) {
+            super.set(key instanceof MutableMap ? key : new MutableMap(key));
+        } else {
+            let found = this.#find(key);
+            super.set(found === undefined ? key : found, value);
+            if (found !== undefined) {
+                return;
+            }
+        }
+        for (let i in this.#watchers) {
+            this.#watchers[i].createAllNode();
+        }
+    }
+    getClone() {
+        let current_fields = this.getAllFields();
+        let cloned_fields = {};
+        for (let key in current_fields) {
+            let value = fastn_utils.clone(current_fields[key]);
+            if (value instanceof fastn.mutableClass) {
+                value = value.get();
+            }
+            cloned_fields[key] = value;
+        }
+        return new MutableMap(cloned_fields);
+    }
+}
+
 class Module {
     #name;
     #global;
@@ -419,6 +515,10 @@ fastn.recordInstance = function (obj) {
     return new RecordInstance(obj);
 }
 
+fastn.mutableMap = function (obj) {
+    return new MutableMap(obj);
+}
+
 fastn.color = function (r, g, b) {
     return `rgb(${r},${g},${b})`;
 }
@@ -426,6 +526,7 @@ fastn.color = function (r, g, b) {
 fastn.mutableClass = Mutable;
 fastn.mutableListClass = MutableList;
 fastn.recordInstanceClass = RecordInstance;
+fastn.mutableMapClass = MutableMap;
 fastn.module = function (name, global) {
     return new Module(name, global);
 }
@@ -16,7 +16,7 @@ let ftd = {
             let inner_data = data.get();
             return ftd.len(inner_data);
         }
-        if (!!data && data.length) {
+        if (!!data && data.length !== undefined) {
             return data.length;
         }
         return -2;
@@ -44,6 +44,62 @@ let ftd = {
         return args.a;
     },
 
+    // datetime values are RFC 3339 strings, the offset they carry is kept
+    // around so formatting shows the wall clock time of that offset
+    format_datetime(args) {
+        let dt = ftd_datetime.parse(fastn_utils.getStaticValue(args.value));
+        let pattern = fastn_utils.getStaticValue(args.pattern);
+        let locale = fastn_utils.getStaticValue(args.locale);
+        return ftd_datetime.format(dt, pattern, locale);
+    },
+
+    relative_datetime(args) {
+        let dt = ftd_datetime.parse(fastn_utils.getStaticValue(args.value));
+        let locale = fastn_utils.getStaticValue(args.locale);
+        return ftd_datetime.relative(dt, ftd_datetime.make(new Date(), 0), locale);
+    },
+
+    add_to_datetime(args) {
+        let dt = ftd_datetime.parse(fastn_utils.getStaticValue(args.value));
+        let amount = fastn_utils.getStaticValue(args.amount);
+        let unit = fastn_utils.getStaticValue(args.unit);
+        return ftd_datetime.toString(ftd_datetime.add(dt, amount, unit));
+    },
+
+    datetime_diff(args) {
+        let a = ftd_datetime.parse(fastn_utils.getStaticValue(args.value));
+        let b = ftd_datetime.parse(fastn_utils.getStaticValue(args.other));
+        let unit = fastn_utils.getStaticValue(args.unit).replace(/s$/, "");
+        let seconds = Math.trunc((a.date.getTime() - b.date.getTime()) / 1000);
+        let sizes = { second: 1, minute: 60, hour: 3600, day: 86400, week: 7 * 86400 };
+        if (sizes[unit] !== undefined) {
+            return Math.trunc(seconds / sizes[unit]);
+        }
+        let wallA = a.wall(), wallB = b.wall();
+        let months = (wallA.getUTCFullYear() - wallB.getUTCFullYear()) * 12
+            + wallA.getUTCMonth() - wallB.getUTCMonth();
+        // a partial last month does not count
+        let end = ftd_datetime.add(b, months, "month").date.getTime();
+        if (months > 0 && end > a.date.getTime()) months -= 1;
+        if (months < 0 && end < a.date.getTime()) months += 1;
+        if (unit === "month") return months;
+        if (unit === "year") return Math.trunc(months / 12);
+        throw new Error(`Unknown datetime unit \`${unit}\``);
+    },
+
+    start_of_day(args) {
+        let dt = ftd_datetime.parse(fastn_utils.getStaticValue(args.value));
+        let wall = dt.wall();
+        wall.setUTCHours(0, 0, 0, 0);
+        return ftd_datetime.toString(ftd_datetime.fromWall(wall, dt.offset));
+    },
+
+    datetime_in_offset(args) {
+        let dt = ftd_datetime.parse(fastn_utils.getStaticValue(args.value));
+        let offset = ftd_datetime.parseOffset(fastn_utils.getStaticValue(args.offset));
+        return ftd_datetime.toString(ftd_datetime.make(dt.date, offset));
+    },
+
     set_rive_boolean(args, node) {
         if (!!args.rive) {
             let riveNode = ftd.riveNodes[`${args.rive}__${ftd.device.get()}`];
@@ -154,6 +210,60 @@ ftd.clear_all = function (list) { list.clearAll() }
 ftd.clear = ftd.clear_all;
 ftd.set_list = function (list, value) { list.set(value) }
 
+// The list functions of the expressions, `map(list, x -> …)`, … The lambdas are called with
+// the values of the items, the records are kept for their fields to be read with `.get()`.
+ftd.items = function (list) {
+    list = fastn_utils.getter(list);
+    if (list instanceof fastn.mutableListClass) {
+        return list.getList().map(item => fastn_utils.getter(item.item));
+    }
+    return Array.isArray(list) ? list.map(fastn_utils.getter) : [];
+}
+// `list[0]` and `record.field`
+ftd.index = function (value, key) {
+    value = fastn_utils.getter(value);
+    key = fastn_utils.getStaticValue(key);
+    if (value instanceof fastn.mutableListClass) {
+        let item = value.get(key);
+        return item === undefined ? null : fastn_utils.getter(item.item);
+    }
+    if (value instanceof fastn.recordInstanceClass) {
+        return fastn_utils.getter(value.get(key));
+    }
+    return fastn_utils.isNull(value) ? null : fastn_utils.getter(value[key]);
+}
+ftd.map = function (list, f) { return ftd.items(list).map((item, index) => f(item, index)) }
+ftd.filter = function (list, f) { return ftd.items(list).filter((item, index) => f(item, index)) }
+ftd.reduce = function (list, init, f) {
+    return ftd.items(list).reduce((acc, item) => f(acc, item), fastn_utils.getter(init));
+}
+ftd.sort = function (list, f) {
+    let items = ftd.items(list);
+    let keys = items.map(item => fastn_utils.getStaticValue(f ? f(item) : item));
+    return items
+        .map((_, index) => index)
+        .sort((a, b) => keys[a] < keys[b] ? -1 : keys[a] > keys[b] ? 1 : 0)
+        .map(index => items[index]);
+}
+ftd.join = function (list, separator) {
+    return ftd.items(list).map(item => ftd.concat(item)).join(fastn_utils.getStaticValue(separator));
+}
+ftd.contains = function (value, item) {
+    let string = fastn_utils.getStaticValue(value);
+    item = fastn_utils.getStaticValue(item);
+    if (typeof string === "string") {
+        return string.includes(item);
+    }
+    return ftd.items(value).some(v => fastn_utils.getStaticValue(v) === item);
+}
+// `"Hello {name}"` is `concat("Hello ", name, "")`
+ftd.concat = function (...values) {
+    return values.map(value => {
+        value = fastn_utils.getStaticValue(value);
+        return fastn_utils.isNull(value) ? "" : String(value);
+    }).join("");
+}
+
 ftd.http = function (url, method, body, headers) {
     if (url instanceof fastn.mutableClass) url = url.get();
     if (method instanceof fastn.mutableClass) method = method.get();
@@ -198,10 +308,11 @@ ftd.navigate = function(url, request_data) {
     }
     let query_string = query_parameters.toString();
     if (query_string) {
-        let get_url = url + "?" + query_parameters.toString();
-        window.location.href = get_url;
+        url = url + "?" + query_parameters.toString();
     }
-    else {
+    if (fastn_spa.enabled) {
+        fastn_spa.navigate(url, true);
+    } else {
         window.location.href = url;
     }
 }
@@ -254,3 +365,141 @@ ftd.local_storage = {
         localStorage.removeItem(key);
     }
 }
+
+// datetime helpers backing `ftd.format_datetime` and friends, a parsed value is
+// `{date, offset}` where `offset` is minutes east of UTC
+const ftd_datetime = {
+    parse(value) {
+        if (typeof value === "number") {
+            // same rule as the rust side: large values are milliseconds
+            let date = new Date(Math.abs(value) >= 1e11 ? value : value * 1000);
+            return this.make(date, 0);
+        }
+        let match = /(Z|[+-]\d{2}:?\d{2})$/i.exec(value.trim());
+        let offset = match ? this.parseOffset(match[1]) : 0;
+        let date = new Date(match ? value : `${value.trim()}Z`);
+        if (isNaN(date.getTime())) {
+            throw new Error(`Invalid datetime \`${value}\``);
+        }
+        return this.make(date, offset);
+    },
+
+    make(date, offset) {
+        return {
+            date,
+            offset,
+            // a `Date` whose UTC fields show the wall clock time at `offset`
+            wall() { return new Date(this.date.getTime() + this.offset * 60000); },
+        };
+    },
+
+    fromWall(wall, offset) {
+        return this.make(new Date(wall.getTime() - offset * 60000), offset);
+    },
+
+    parseOffset(offset) {
+        offset = offset.trim();
+        if (offset === "Z" || offset.toLowerCase() === "utc") return 0;
+        let match = /^([+-])(\d{2}):?(\d{2})?$/.exec(offset);
+        if (!match) {
+            throw new Error(`Invalid offset \`${offset}\``);
+        }
+        let minutes = parseInt(match[2]) * 60 + parseInt(match[3] || "0");
+        return match[1] === "-" ? -minutes : minutes;
+    },
+
+    formatOffset(offset, colon = true) {
+        let sign = offset < 0 ? "-" : "+";
+        let abs = Math.abs(offset);
+        let hours = String(Math.floor(abs / 60)).padStart(2, "0");
+        let minutes = String(abs % 60).padStart(2, "0");
+        return `${sign}${hours}${colon ? ":" : ""}${minutes}`;
+    },
+
+    toString(dt) {
+        let iso = dt.wall().toISOString();
+        if (iso.endsWith(".000Z")) iso = iso.replace(".000Z", "Z");
+        return dt.offset === 0 ? iso : iso.replace("Z", this.formatOffset(dt.offset));
+    },
+
+    add(dt, amount, unit) {
+        let sizes = { second: 1, minute: 60, hour: 3600, day: 86400, week: 7 * 86400 };
+        unit = unit.replace(/s$/, "");
+        if (sizes[unit] !== undefined) {
+            return this.make(new Date(dt.date.getTime() + amount * sizes[unit] * 1000), dt.offset);
+        }
+        let months = unit === "month" ? amount : unit === "year" ? amount * 12 : null;
+        if (months === null) {
+            throw new Error(`Unknown datetime unit \`${unit}\``);
+        }
+        let wall = dt.wall();
+        let day = wall.getUTCDate();
+        wall.setUTCDate(1);
+        wall.setUTCMonth(wall.getUTCMonth() + months);
+        // keep the day of month, clamped to the length of the target month
+        let last = new Date(Date.UTC(wall.getUTCFullYear(), wall.getUTCMonth() + 1, 0)).getUTCDate();
+        wall.setUTCDate(Math.min(day, last));
+        return this.fromWall(wall, dt.offset);
+    },
+
+    // the tables of `fastn_grammar::datetime::LOCALES`, not `Intl`, so the
+    // server and the browser render the same text
+    locale(tag) {
+        let language = String(tag || "").split(/[-_]/)[0].toLowerCase();
+        return ftd_datetime_locales.find((l) => l.language === language) || ftd_datetime_locales[0];
+    },
+
+    // same rules and wording as `fastn_grammar::datetime::relative`
+    relative(dt, now, locale) {
+        locale = this.locale(locale);
+        let seconds = Math.trunc((dt.date.getTime() - now.date.getTime()) / 1000);
+        let abs = Math.abs(seconds);
+        if (abs < 60) {
+            return locale.just_now;
+        }
+        let units = [
+            [60, 3600], [3600, 86400], [86400, 30 * 86400],
+            [30 * 86400, 365 * 86400], [365 * 86400, Infinity],
+        ];
+        let unit = units.findIndex(([, below]) => abs < below);
+        let count = Math.floor(abs / units[unit][0]);
+        let text = `${count} ${locale.units[unit][count === 1 ? 0 : 1]}`;
+        return (seconds < 0 ? locale.past : locale.future).replace("{}", text);
+    },
+
+    format(dt, pattern, locale) {
+        locale = this.locale(locale);
+        let wall = dt.wall();
+        let weekday = (wall.getUTCDay() + 6) % 7;
+        let pad = (n, width = 2) => String(n).padStart(width, "0");
+        let hour12 = wall.getUTCHours() % 12 || 12;
+        let start = Date.UTC(wall.getUTCFullYear(), 0, 1);
+        let fields = {
+            Y: () => wall.getUTCFullYear(),
+            y: () => pad(wall.getUTCFullYear() % 100),
+            m: () => pad(wall.getUTCMonth() + 1),
+            d: () => pad(wall.getUTCDate()),
+            e: () => wall.getUTCDate(),
+            H: () => pad(wall.getUTCHours()),
+            I: () => pad(hour12),
+            l: () => hour12,
+            M: () => pad(wall.getUTCMinutes()),
+            S: () => pad(wall.getUTCSeconds()),
+            p: () => locale.am_pm[wall.getUTCHours() < 12 ? 0 : 1],
+            B: () => locale.months[wall.getUTCMonth()],
+            b: () => locale.months_short[wall.getUTCMonth()],
+            A: () => locale.weekdays[weekday],
+            a: () => locale.weekdays_short[weekday],
+            j: () => pad(Math.floor((wall.getTime() - start) / 86400000) + 1, 3),
+            z: () => this.formatOffset(dt.offset, false),
+            ":z": () => this.formatOffset(dt.offset),
+            "%": () => "%",
+        };
+        return pattern.replace(/%(:z|.)/g, (all, key) => {
+            if (fields[key] === undefined) {
+                throw new Error(`Unknown datetime pattern \`${all}\``);
+            }
+            return fields[key]();
+        });
+    },
+};
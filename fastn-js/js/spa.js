@@ -0,0 +1,204 @@
+/**
+ * Client-side navigation, for the packages with `spa: true` in `fastn.package`.
+ *
+ * Instead of loading the page, its fragment, `-/fragments/<path>index.json`, is fetched: the JS
+ * of the page is run, the server rendered html replaces the body and is hydrated, like it is
+ * done when the page is loaded.
+ */
+let fastn_spa = {
+    enabled: false,
+    // `global` of the page shown
+    global: null,
+    // JS identifiers of the variables which keep their value across the pages
+    persistent: [],
+    // values of the persistent variables, by their JS identifier
+    persisted: {},
+    // path and query of the page shown, a change of the hash only is not a navigation
+    current: null,
+    // the nodes, conditions and loops of the page shown, held weakly, see `fastn_spa.teardown()`
+    parts: [],
+};
+
+fastn_spa.init = function (global, persistent) {
+    fastn_spa.enabled = true;
+    fastn_spa.global = global;
+    fastn_spa.persistent = persistent;
+    fastn_spa.current = fastn_spa.pageOf(window.location);
+    window.history.replaceState({ fastn_spa: true }, "", window.location.href);
+    document.addEventListener("click", fastn_spa.onClick);
+    window.addEventListener("popstate", function () {
+        if (fastn_spa.pageOf(window.location) !== fastn_spa.current) {
+            fastn_spa.navigate(window.location.href, false);
+        }
+    });
+}
+
+/**
+ * Keeps `part`, a node, a condition or a loop of the page, to be destroyed when the page is
+ * replaced.
+ */
+fastn_spa.track = function (part) {
+    if (fastn_spa.enabled) {
+        fastn_spa.parts.push(new WeakRef(part));
+    }
+}
+
+/**
+ * Destroys the parts of the page shown which are still alive: the closures they added to the
+ * mutables which outlive the page, like `ftd.dark_mode`, `ftd.device` or the persistent
+ * variables, would keep them, and the detached nodes, in memory and update them.
+ */
+fastn_spa.teardown = function () {
+    for (let part of fastn_spa.parts) {
+        let alive = part.deref();
+        if (alive) {
+            alive.destroy();
+        }
+    }
+    fastn_spa.parts = [];
+}
+
+fastn_spa.pageOf = function (url) {
+    return `${url.pathname}${url.search}`;
+}
+
+fastn_spa.onClick = function (event) {
+    if (event.defaultPrevented || event.button !== 0 || event.metaKey || event.ctrlKey
+        || event.shiftKey || event.altKey) {
+        return;
+    }
+    let anchor = event.target.closest ? event.target.closest("a") : null;
+    if (!anchor || !anchor.href || anchor.hasAttribute("download")
+        || (anchor.target && anchor.target !== "_self")) {
+        return;
+    }
+    let url = new URL(anchor.href, document.baseURI);
+    if (!fastn_spa.isPage(url)) {
+        return;
+    }
+    event.preventDefault();
+    fastn_spa.navigate(url.href, true);
+}
+
+/**
+ * Links to the other origins, to the files, like `/-/foo/image.png`, and to an anchor of the
+ * page shown are left to the browser.
+ */
+fastn_spa.isPage = function (url) {
+    return url.origin === window.location.origin
+        && !url.pathname.includes("/-/")
+        && !/\.[a-zA-Z0-9]+$/.test(url.pathname)
+        && !(fastn_spa.pageOf(url) === fastn_spa.current && url.hash !== "");
+}
+
+fastn_spa.fragmentUrl = function (url) {
+    let base = new URL(document.baseURI).pathname;
+    let path = url.pathname.startsWith(base)
+        ? url.pathname.slice(base.length)
+        : url.pathname.replace(/^\//, "");
+    if (path !== "" && !path.endsWith("/")) {
+        path = `${path}/`;
+    }
+    return new URL(`-/fragments/${path}index.json${url.search}`, document.baseURI).href;
+}
+
+/**
+ * Shows the page at `url`, `push` adds it to the history, it is already there for the back and
+ * forward buttons. The page is loaded if its fragment can not be fetched.
+ */
+fastn_spa.navigate = async function (url, push) {
+    let target = new URL(url, document.baseURI);
+    let fragment;
+    try {
+        let response = await fetch(fastn_spa.fragmentUrl(target));
+        if (!response.ok) {
+            throw new Error(`status ${response.status}`);
+        }
+        fragment = await response.json();
+    } catch (e) {
+        console.log("[spa]: loading the page", target.href, e);
+        window.location.href = target.href;
+        return;
+    }
+    if (push) {
+        window.history.pushState({ fastn_spa: true }, "", target.href);
+    }
+    fastn_spa.current = fastn_spa.pageOf(target);
+    fastn_spa.show(fragment);
+
+    let anchor = target.hash ? document.getElementById(decodeURIComponent(target.hash.slice(1))) : null;
+    if (anchor) {
+        anchor.scrollIntoView();
+    } else if (push) {
+        window.scrollTo(0, 0);
+    }
+}
+
+fastn_spa.show = function (fragment) {
+    fastn_spa.teardown();
+    for (let name of fastn_spa.persistent) {
+        if (name in fastn_spa.global) {
+            fastn_spa.persisted[name] = fastn_spa.global[name];
+        }
+    }
+    let [global, main] = new Function(`${fragment.js}\nreturn [global, main];`)();
+    for (let name in fastn_spa.persisted) {
+        if (name in global) {
+            global[name] = fastn_spa.persisted[name];
+        }
+    }
+    fastn_spa.global = global;
+    fastn_spa.loadScripts(fragment.scripts);
+
+    let body = new DOMParser().parseFromString(fragment.html, "text/html").body;
+    document.body.replaceChildren(...body.childNodes);
+    document.body.querySelectorAll("script").forEach(script => script.replaceWith(fastn_spa.runnable(script)));
+    document.querySelectorAll("meta[data-fastn]").forEach(meta => meta.remove());
+    // the classes are numbered from the start again when the page is rendered on the server
+    fastn_dom.classes = {};
+    fastn_dom.unsanitised_classes = {};
+    fastn_dom.class_count = 0;
+    ftd.clickOutsideEvents = [];
+    ftd.globalKeyEvents = [];
+    ftd.globalKeySeqEvents = [];
+
+    fastn_virtual.hydrate(function (parent) {
+        let parenti0 = fastn_dom.createKernel(parent, fastn_dom.ElementKind.Column);
+        parenti0.setProperty(fastn_dom.PropertyKind.Width, fastn_dom.Resizing.FillContainer, inherited);
+        parenti0.setProperty(fastn_dom.PropertyKind.Height, fastn_dom.Resizing.FillContainer, inherited);
+        main(parenti0);
+    });
+}
+
+/**
+ * Adds the external scripts and css of the page which the pages shown so far did not have, and
+ * runs its inline scripts.
+ */
+fastn_spa.loadScripts = function (scripts) {
+    let parsed = new DOMParser().parseFromString(scripts, "text/html");
+    for (let script of parsed.querySelectorAll("script")) {
+        let src = script.getAttribute("src");
+        if (src === null || !document.querySelector(`script[src="${src}"]`)) {
+            document.head.appendChild(fastn_spa.runnable(script));
+        }
+    }
+    for (let link of parsed.querySelectorAll("link[rel=stylesheet][href]")) {
+        let href = link.getAttribute("href");
+        if (!document.querySelector(`link[rel=stylesheet][href="${href}"]`)) {
+            document.head.appendChild(link);
+        }
+    }
+}
+
+/**
+ * A copy of the parsed `script`, the browser does not run the scripts parsed with `DOMParser`
+ * once they are added to the document, it runs the ones it creates.
+ */
+fastn_spa.runnable = function (script) {
+    let tag = document.createElement("script");
+    for (let attribute of script.attributes) {
+        tag.setAttribute(attribute.name, attribute.value);
+    }
+    tag.textContent = script.textContent;
+    return tag;
+}
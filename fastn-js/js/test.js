@@ -39,6 +39,38 @@ function assertKindIdIsUniqueForValue(value, ids) {
 
 assertKindIdIsUnique();
 
+function assertMapKeysAreKept() {
+    let assertEqual = (got, expected) => {
+        if (got !== expected) {
+            throw `map: expected ${expected}, got ${got}`;
+        }
+    };
+
+    // the keys are kept, a reference reaches an entry with the mangled key
+    let map = fastn.mutableMap({ "a-b": 1, c: 2, "d.e": 3 });
+    assertEqual(map.getLength(), 3);
+    assertEqual(map.get("a-b").get(), 1);
+    assertEqual(map.get("a_b").get(), 1);
+    assertEqual(map.get("d_e").get(), 3);
+    assertEqual(map.get("f"), undefined);
+    assertEqual(map.get("toString"), undefined);
+
+    // a loop goes over the entries in key order, the counter is the key
+    let entries = map.getList().map((e) => `${e.index.get()}=${e.item.get()}`);
+    assertEqual(entries.join(","), "a-b=1,c=2,d.e=3");
+
+    // setting by the mangled key updates the entry, a new key adds one
+    map.set("a_b", 5);
+    assertEqual(map.get("a-b").get(), 5);
+    assertEqual(map.getLength(), 3);
+    map.set("g-h", 6);
+    assertEqual(map.get("g_h").get(), 6);
+    entries = map.getList().map((e) => `${e.index.get()}=${e.item.get()}`);
+    assertEqual(entries.join(","), "a-b=5,c=2,d.e=3,g-h=6");
+}
+
+assertMapKeysAreKept();
+
 
 function flattenObject(obj) {
     let result = {};
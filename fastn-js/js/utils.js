@@ -10,6 +10,8 @@ let fastn_utils = {
             css.push("full");
         } else if (kind === fastn_dom.ElementKind.Row) {
             css.push("ft_row");
+        } else if (kind === fastn_dom.ElementKind.Grid) {
+            css.push("ft_grid");
         } else if (kind === fastn_dom.ElementKind.IFrame) {
             node = "iframe";
             // To allow fullscreen support
@@ -58,6 +60,155 @@ let fastn_utils = {
            return obj;
         }
     },
+    // `transform` css for a `ftd.transform` record, unset fields are skipped
+    getTransformCss(value) {
+        const field = (name) => fastn_utils.getStaticValue(value.get(name));
+        const functions = [
+            ["translateX", field("translate_x"), ""],
+            ["translateY", field("translate_y"), ""],
+            ["rotate", field("rotate"), "deg"],
+            ["scale", field("scale"), ""],
+            ["scaleX", field("scale_x"), ""],
+            ["scaleY", field("scale_y"), ""],
+            ["skewX", field("skew_x"), "deg"],
+            ["skewY", field("skew_y"), "deg"],
+        ];
+        let css = functions
+            .filter(([_, v]) => !fastn_utils.isNull(v))
+            .map(([f, v, unit]) => `${f}(${v}${unit})`);
+        return css.length === 0 ? "none" : css.join(" ");
+    },
+    // `x y` css of a `ftd.length-pair`, a missing `x` falls back to `0px`
+    getLengthPairCss(pair) {
+        if (fastn_utils.isNull(pair) || !(pair instanceof Object)) {
+            return pair;
+        }
+        const x = fastn_utils.getStaticValue(pair.get("x"));
+        const y = fastn_utils.getStaticValue(pair.get("y"));
+        if (fastn_utils.isNull(y)) {
+            return fastn_utils.isNull(x) ? null : `${x}`;
+        }
+        return `${fastn_utils.isNull(x) ? "0px" : x} ${y}`;
+    },
+    // `[light, dark]` css of the color stops of a gradient
+    getGradientColorsCss(colors) {
+        let light = [];
+        let dark = [];
+        fastn_utils.getStaticValue(colors).map(obj => {
+            const stop = fastn_utils.getStaticValue(obj.item);
+            const color = fastn_utils.getStaticValue(stop.get("color"));
+            const suffix = ["start", "end"]
+                .map(name => fastn_utils.getStaticValue(stop.get(name)))
+                .filter(v => !fastn_utils.isNull(v))
+                .map(v => ` ${v}`)
+                .join("");
+            light.push(`${fastn_utils.getStaticValue(color.get("light"))}${suffix}`);
+            dark.push(`${fastn_utils.getStaticValue(color.get("dark"))}${suffix}`);
+            const stopPosition = fastn_utils.getStaticValue(stop.get("stop_position"));
+            if (!fastn_utils.isNull(stopPosition)) {
+                light.push(`${stopPosition}`);
+                dark.push(`${stopPosition}`);
+            }
+        });
+        return [light.join(", "), dark.join(", ")];
+    },
+    // `[light, dark]` css of a `linear`, `radial` or `conic` gradient record
+    getGradientCss(kind, value) {
+        const field = (name) => fastn_utils.getStaticValue(value.get(name));
+        const [light, dark] = fastn_utils.getGradientColorsCss(value.get("colors"));
+        let prefix;
+        if (kind === "linear") {
+            prefix = field("direction");
+        } else {
+            prefix = kind === "radial" ? field("shape") : `from ${field("from")}deg`;
+            const position = fastn_utils.getLengthPairCss(field("position"));
+            if (!fastn_utils.isNull(position)) {
+                prefix = `${prefix} at ${position}`;
+            }
+        }
+        return [`${kind}-gradient(${prefix}, ${light})`, `${kind}-gradient(${prefix}, ${dark})`];
+    },
+    // css of one `ftd.background-layer`, gradients use the css initial values
+    // for position, size and repeat
+    getBackgroundLayerCss(layer) {
+        const field = (name) => fastn_utils.getStaticValue(layer.get(name));
+        for (const kind of ["linear", "radial", "conic"]) {
+            const gradient = field(`${kind}_gradient`);
+            if (!fastn_utils.isNull(gradient)) {
+                return {
+                    image: fastn_utils.getGradientCss(kind, gradient),
+                    position: "0% 0%",
+                    size: "auto",
+                    repeat: "repeat",
+                };
+            }
+        }
+        const image = field("image");
+        const imageField = (name) => fastn_utils.getStaticValue(image.get(name));
+        const src = imageField("src");
+        const orDefault = (v, d) => fastn_utils.isNull(v) ? d : v;
+        return {
+            image: [
+                `url(${fastn_utils.getStaticValue(src.get("light"))})`,
+                `url(${fastn_utils.getStaticValue(src.get("dark"))})`,
+            ],
+            position: orDefault(fastn_utils.getLengthPairCss(imageField("position")), "0% 0%"),
+            size: orDefault(fastn_utils.getLengthPairCss(imageField("size")), "auto"),
+            repeat: orDefault(imageField("repeat"), "repeat"),
+        };
+    },
+    getKeyframesCss(name, keyframes) {
+        let steps = keyframes.map(keyframe => {
+            const at = fastn_utils.getStaticValue(keyframe.get("at"));
+            const opacity = fastn_utils.getStaticValue(keyframe.get("opacity"));
+            const transform = fastn_utils.getStaticValue(keyframe.get("transform"));
+            let css = "";
+            if (!fastn_utils.isNull(opacity)) {
+                css = `${css} opacity: ${opacity};`;
+            }
+            if (!fastn_utils.isNull(transform)) {
+                css = `${css} transform: ${fastn_utils.getTransformCss(transform)};`;
+            }
+            return `${at}% {${css} }`;
+        });
+        return `@keyframes ${name} { ${steps.join(" ")} }`;
+    },
+    getGridTrackCss(track) {
+        if (track instanceof PropertyValueAsClosure) {
+            return track.closureFunction();
+        }
+        if (!Array.isArray(track)) {
+            return fastn_utils.getStaticValue(track);
+        }
+        const [kind, minmax] = track;
+        const field = (name) => fastn_utils.getGridTrackCss(minmax.get(name));
+        const max = fastn_utils.isNull(field("max")) ? `${field("max_fr")}fr` : field("max");
+        const css = `minmax(${field("min")}, ${max})`;
+        switch (kind) {
+            case 2:
+                return `repeat(auto-fill, ${css})`;
+            case 3:
+                return `repeat(auto-fit, ${css})`;
+            default:
+                return css;
+        }
+    },
+    getGridTracksCss(tracks) {
+        if (fastn_utils.isNull(tracks) || tracks.length === 0) {
+            return undefined;
+        }
+        return tracks
+            .map(obj => fastn_utils.getGridTrackCss(fastn_utils.getStaticValue(obj.item)))
+            .join(" ");
+    },
+    getGridAreasCss(areas) {
+        if (fastn_utils.isNull(areas) || areas.length === 0) {
+            return undefined;
+        }
+        return areas
+            .map(obj => `"${fastn_utils.getStaticValue(obj.item).trim()}"`)
+            .join(" ");
+    },
     getInheritedValues(default_args, inherited, function_args) {
         let record_fields = {
             "colors": ftd.default_colors.getClone().setAndReturn("is-root", true),
@@ -252,13 +403,25 @@ let fastn_utils = {
      * adjusts spaces around the content.
      *
      * @param {string} i - The input string to be processed as inline markdown.
+     * @param {boolean} headingAnchors - Whether to add a `#` link to the headings.
+     * @param {string} [html] - The html of the input rendered on the server, see
+     * `fastn_utils.renderedMarkdown()`, used instead of `marked`.
      * @returns {string} - The processed string with inline markdown.
      */
-    markdown_inline(i) {
+    markdown_inline(i, headingAnchors, html) {
         if (fastn_utils.isNull(i)) return;
         const { space_before, space_after } = fastn_utils.private.spaces(i);
         const o = (() => {
-            let g = fastn_utils.private.replace_last_occurrence(marked.parse(i), "<p>", "");
+            let g = fastn_utils.private.replace_last_occurrence(
+                fastn_utils.isNull(html)
+                    ? marked.parse(i, {
+                        renderer: fastn_utils.private.markdownRenderer(headingAnchors),
+                        headerIds: false,
+                    })
+                    : fastn_utils.private.addHeadingIds(html, headingAnchors),
+                "<p>",
+                "",
+            );
             g = fastn_utils.private.replace_last_occurrence(g, "</p>", "");
             return g;
         })();
@@ -410,17 +573,31 @@ let fastn_utils = {
         }
     },
 
-    //Taken from: https://byby.dev/js-slugify-string
+    /**
+     * Keeps the html of the markdown `text`, rendered on the server as it has
+     * math, footnotes or definition lists, which `marked` can not render, see
+     * `ftd::executor::markup::browser_markup`. The texts with the string are
+     * rendered with the html.
+     *
+     * @param {string} text - The string.
+     * @param {string} html - The html of the string.
+     * @returns {string} - The string.
+     */
+    renderedMarkdown(text, html) {
+        fastn_utils.private.renderedMarkdown.set(text, html);
+        return text;
+    },
+
+    /**
+     * Slug of a heading, used as its id. Same as `ftd::executor::headings::slugify`, which
+     * the `page-toc` processor uses.
+     */
     slugify(str) {
         return String(str)
-            .normalize('NFKD') // split accented characters into their base characters and diacritical marks
-            .replace('.', '-')
-            .replace(/[\u0300-\u036f]/g, '') // remove all the accents, which happen to be all in the \u03xx UNICODE block.
-            .trim() // trim leading or trailing whitespace
-            .toLowerCase() // convert to lowercase
-            .replace(/[^a-z0-9 -]/g, '') // remove non-alphanumeric characters
-            .replace(/\s+/g, '-') // replace spaces with hyphens
-            .replace(/-+/g, '-'); // remove consecutive hyphens
+            .trim()
+            .toLowerCase()
+            .replace(/[\u2000-\u206F\u2E00-\u2E7F\\'!"#$%&()*+,./:;<=>?@[\]^`{|}~]/g, '')
+            .replace(/\s/g, '-');
     },
 
     getEventListeners(node) {
@@ -490,6 +667,16 @@ let fastn_utils = {
         return result;
     },
 
+    // Used for the values of the tags of the head of the page rendered on the server
+    escapeHtmlAttribute(str) {
+        return String(str)
+            .replace(/&/g, "&amp;")
+            .replace(/</g, "&lt;")
+            .replace(/>/g, "&gt;")
+            .replace(/"/g, "&quot;")
+            .replace(/'/g, "&#39;");
+    },
+
     // Used to initialize __args__ inside component and UDF js functions
     getArgs(default_args, passed_args) {
         // Note: arguments as variable name not allowed in strict mode
@@ -514,6 +701,90 @@ fastn_utils.private = {
             return acc.concat(Array.isArray(item) ? fastn_utils.private.flattenArray(item) : item);
         }, []);
     },
+    /**
+     * The html of the strings rendered on the server, see
+     * `fastn_utils.renderedMarkdown()`.
+     */
+    renderedMarkdown: new Map(),
+    /**
+     * Ids of the headings of the document, the second `install` heading gets
+     * `install-1`. Same as `ftd::executor::headings::HeadingIds`, emptied
+     * before the document is rendered.
+     */
+    headingIds: {},
+    /**
+     * Returns the id for a heading with the `slug`, unique in the document.
+     *
+     * @param {string} slug - The slug of the heading.
+     * @returns {string} - The id.
+     */
+    nextHeadingId(slug) {
+        const seen = fastn_utils.private.headingIds;
+        let id = slug;
+        let count = 0;
+        if (seen.hasOwnProperty(slug)) {
+            count = seen[slug];
+            do {
+                count++;
+                id = `${slug}-${count}`;
+            } while (seen.hasOwnProperty(id));
+        }
+        seen[slug] = count;
+        seen[id] = 0;
+        return id;
+    },
+    /**
+     * Marks an id, set on a text or by the server, as taken.
+     *
+     * @param {string} id - The id.
+     */
+    reserveHeadingId(id) {
+        if (!fastn_utils.private.headingIds.hasOwnProperty(id)) {
+            fastn_utils.private.headingIds[id] = 0;
+        }
+    },
+    /**
+     * Helper function for `fastn_utils.markdown_inline` to render the headings
+     * with ids, unique within the document, and optionally a `#` link to the
+     * heading.
+     *
+     * @param {boolean} headingAnchors - Whether to add the `#` link.
+     * @returns {object} - The `marked` renderer.
+     */
+    markdownRenderer(headingAnchors) {
+        const renderer = new marked.Renderer();
+        renderer.heading = (text, level, raw) =>
+            `${fastn_utils.private.heading(text, level, raw, headingAnchors)}\n`;
+        return renderer;
+    },
+    /**
+     * Helper function for `fastn_utils.markdown_inline` to give the ids to
+     * the headings of the html rendered on the server, which has none.
+     *
+     * @param {string} html - The html rendered on the server.
+     * @param {boolean} headingAnchors - Whether to add the `#` link.
+     * @returns {string} - The html with the ids of the headings.
+     */
+    addHeadingIds(html, headingAnchors) {
+        return html.replace(/<h([1-6])>([\s\S]*?)<\/h\1>/g, (m, level, text) =>
+            fastn_utils.private.heading(text, level, text.replace(/<[^>]*>/g, ""), headingAnchors));
+    },
+    /**
+     * The html of a heading, with an id unique in the document.
+     *
+     * @param {string} text - The html of the heading text.
+     * @param {number} level - The level of the heading.
+     * @param {string} raw - The plain text of the heading, to slugify.
+     * @param {boolean} headingAnchors - Whether to add the `#` link.
+     * @returns {string} - The html of the heading.
+     */
+    heading(text, level, raw, headingAnchors) {
+        const id = fastn_utils.private.nextHeadingId(fastn_utils.slugify(raw));
+        const anchor = headingAnchors
+            ? `<a class="ft-heading-anchor" href="#${id}" aria-hidden="true">#</a>`
+            : "";
+        return `<h${level} id="${id}">${text}${anchor}</h${level}>`;
+    },
     /**
      * Helper function for `fastn_utils.markdown_inline` to find the number of
      * spaces before and after the content.
@@ -150,17 +150,47 @@ fastn_virtual.document = new Document2();
 
 fastn_virtual.hydrate = function(main) {
     hydrating = true;
+    fastn_utils.private.headingIds = {};
     let body = fastn_virtual.document.createElement("body");
     main(body);
     id_counter = 0;
     hydrating = false;
 }
 
+// the tags of the `<head>`, like `<title>`, of the page rendered on the server
+fastn_virtual.head = [];
+// separates the head from the body in the html of `fastn_virtual.ssrStatic()`
+fastn_virtual.HEAD_END = "<!-- fastn-head-end -->";
+
+fastn_virtual.addToHead = function(tag) {
+    fastn_virtual.head.push(tag);
+}
+
 fastn_virtual.ssr = function(main) {
     ssr = true;
+    fastn_virtual.head = [];
+    fastn_utils.private.headingIds = {};
     let body = fastn_virtual.document.createElement("body");
     main(body)
     ssr = false;
     id_counter = 0;
     return body.toHtmlAsString() + fastn_dom.getClassesAsString();
 }
+
+/**
+ * Renders the page shown without JS: the tags of the `<head>`, then `HEAD_END` and the body, see
+ * `fastn_js::ssr_static_with_js_string()`.
+ */
+fastn_virtual.ssrStatic = function(main) {
+    ssr = true;
+    fastn_virtual.head = [];
+    fastn_utils.private.headingIds = {};
+    let body = fastn_virtual.document.createElement("body");
+    main(body)
+    ssr = false;
+    id_counter = 0;
+    return fastn_virtual.head.join("")
+        + fastn_virtual.HEAD_END
+        + body.toHtmlAsString()
+        + fastn_dom.getClassesAsString(true);
+}
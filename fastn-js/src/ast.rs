@@ -19,4 +19,17 @@ impl Ast {
             _ => None,
         }
     }
+
+    /// Name of the thing the ast defines, `main` for the document tree.
+    pub fn get_name(&self) -> &str {
+        match self {
+            Ast::Component(component) => component.name.as_str(),
+            Ast::UDF(udf) => udf.name.as_str(),
+            Ast::StaticVariable(static_variable) => static_variable.name.as_str(),
+            Ast::MutableVariable(mutable_variable) => mutable_variable.name.as_str(),
+            Ast::MutableList(mutable_list) => mutable_list.name.as_str(),
+            Ast::RecordInstance(record_instance) => record_instance.name.as_str(),
+            Ast::Export { to, .. } => to.as_str(),
+        }
+    }
 }
@@ -25,6 +25,7 @@ pub enum ElementKind {
     Row,
     Column,
     ContainerElement,
+    Grid,
     Integer,
     Decimal,
     Boolean,
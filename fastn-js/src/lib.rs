@@ -9,12 +9,14 @@ mod constants;
 mod device;
 mod event;
 mod loop_component;
+mod minify;
 mod mutable_variable;
 mod property;
 mod record;
 mod ssr;
 mod static_variable;
 mod to_js;
+mod tree_shake;
 mod udf;
 mod udf_statement;
 pub mod utils;
@@ -30,14 +32,16 @@ pub use constants::*;
 pub use device::{DeviceBlock, DeviceType};
 pub use event::{Event, EventHandler, Function, FunctionData};
 pub use loop_component::ForLoop;
+pub use minify::{minify, Minified, Minifier, SourceLocation};
 pub use mutable_variable::{mutable_integer, mutable_string, MutableList, MutableVariable};
 pub use property::{
     ConditionalValue, Formula, FormulaType, PropertyKind, SetProperty, SetPropertyValue, Value,
 };
 pub use record::RecordInstance;
-pub use ssr::{ssr, ssr_str, ssr_with_js_string};
+pub use ssr::{ssr, ssr_static_with_js_string, ssr_str, ssr_with_js_string, StaticHtml};
 pub use static_variable::{static_integer, static_string, StaticVariable};
 pub use to_js::to_js;
+pub use tree_shake::{split_modules, tree_shake, Module};
 pub use udf::{udf_with_arguments, UDF};
 pub use udf_statement::UDFStatement;
 
@@ -50,7 +54,12 @@ pub fn all_js_without_test_and_ftd_langugage_js() -> String {
     let ftd_js = include_str!("../js/ftd.js");
     let web_component_js = include_str!("../js/web-component.js");
     let post_init_js = include_str!("../js/postInit.js");
-    format!("{markdown_js}{fastn_js}{dom_js}{utils_js}{virtual_js}{ftd_js}{web_component_js}{post_init_js}")
+    let spa_js = include_str!("../js/spa.js");
+    let datetime_locales = format!(
+        "const ftd_datetime_locales = {};\n",
+        fastn_grammar::datetime::locales_json()
+    );
+    format!("{markdown_js}{fastn_js}{dom_js}{utils_js}{virtual_js}{datetime_locales}{ftd_js}{web_component_js}{post_init_js}{spa_js}")
 }
 
 pub fn all_js_without_test() -> String {
@@ -0,0 +1,429 @@
+/// Where the ftd thing an ast is generated from is defined, for the source map.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceLocation {
+    /// the ftd file, eg `fastn-community.github.io/doc-site/page.ftd`
+    pub source: String,
+    /// 1-based line number of the thing in the ftd file
+    pub line: usize,
+}
+
+/// Minified JS and its source map, see `Minifier::to_js()`.
+#[derive(Debug, PartialEq)]
+pub struct Minified {
+    pub js: String,
+    /// source map, version 3, mapping each line of the JS to the ftd line it comes from
+    pub source_map: String,
+}
+
+/// Emits the JS for production: the JS identifiers of the things, `name_to_js` of their names,
+/// are replaced by short ones and the whitespace is stripped.
+///
+/// A `Minifier` is created for the asts of a document, without the modules split out of it, see
+/// `split_modules()`: the things of the modules keep their JS identifier, so the JS of a module
+/// is the same for all the pages.
+#[derive(Debug, Default)]
+pub struct Minifier {
+    /// JS identifier -> short identifier
+    renames: std::collections::HashMap<String, String>,
+}
+
+impl Minifier {
+    /// The things named in `keep`, and the things of the modules looked up by name with
+    /// `fastn.module()`, keep their JS identifier, the JS of the document may refer to them.
+    pub fn new(asts: &[fastn_js::Ast], keep: &[String], package_name: &str) -> Minifier {
+        let rendered = asts
+            .iter()
+            .map(|ast| fastn_js::tree_shake::render(ast, package_name))
+            .collect::<Vec<_>>();
+        let mut looked_up = std::collections::HashSet::new();
+        let mut tokens = std::collections::HashSet::new();
+        for js in rendered.iter() {
+            for (i, m) in js.match_indices("fastn.module(\"") {
+                let module = js[i + m.len()..].split('"').next().unwrap_or_default();
+                looked_up.insert(module.to_string());
+            }
+            tokens.extend(identifiers(js.as_str()).map(ToString::to_string));
+        }
+        let keep = keep
+            .iter()
+            .map(|name| fastn_js::tree_shake::ident(name))
+            .collect::<std::collections::HashSet<_>>();
+
+        let mut renames = std::collections::HashMap::new();
+        let mut counter = 0;
+        for ast in asts {
+            let name = ast.get_name();
+            let module = match fastn_js::tree_shake::module_name(name) {
+                Some(module) => fastn_js::utils::name_to_js_(module),
+                None => continue,
+            };
+            let ident = fastn_js::tree_shake::ident(name);
+            if looked_up.contains(&module) || keep.contains(&ident) || renames.contains_key(&ident)
+            {
+                continue;
+            }
+            let short = loop {
+                let short = short_name(counter);
+                counter += 1;
+                if !tokens.contains(&short) {
+                    break short;
+                }
+            };
+            renames.insert(ident, short);
+        }
+        Minifier { renames }
+    }
+
+    /// Minified JS of the `asts`, one line for the JS of each ast, with the source map of `file`,
+    /// the name of the JS file, or of the page if the JS is inlined in it.
+    ///
+    /// `locations` are the locations of the things by their name, the JS of the asts with no
+    /// location is not mapped. `first_line` is the line the JS starts at in `file`, 0 for a JS
+    /// file.
+    pub fn to_js(
+        &self,
+        asts: &[fastn_js::Ast],
+        is_global_need: bool,
+        package_name: &str,
+        locations: &std::collections::HashMap<String, SourceLocation>,
+        file: &str,
+        first_line: usize,
+    ) -> Minified {
+        let mut lines = vec![];
+        if is_global_need {
+            lines.push((format!("let {}={{}};", fastn_js::GLOBAL_VARIABLE_MAP), None));
+        }
+        for ast in asts {
+            let js = self.rename(fastn_js::tree_shake::render(ast, package_name).as_str());
+            lines.push((minify(js.as_str()), locations.get(ast.get_name())));
+        }
+
+        let mut source_map = SourceMap::default();
+        source_map.skip_lines(first_line);
+        for (js, location) in lines.iter() {
+            for _ in js.split('\n') {
+                source_map.add_line(*location);
+            }
+        }
+        Minified {
+            js: lines
+                .into_iter()
+                .map(|(js, _)| js)
+                .collect::<Vec<_>>()
+                .join("\n"),
+            source_map: source_map.to_json(file),
+        }
+    }
+
+    /// Replaces the identifiers of the things in `js`. The strings are left as is, but for the
+    /// names of the variables, created with `fastn_utils.createNestedObject(global, "<identifier>",
+    /// …)`, and of the things set with `global["<identifier>"] = …`.
+    fn rename(&self, js: &str) -> String {
+        let mut o = String::with_capacity(js.len());
+        let mut chars = js.char_indices().peekable();
+        while let Some((start, c)) = chars.next() {
+            if matches!(c, '"' | '\'' | '`') {
+                let mut end = js.len();
+                let mut escaped = false;
+                for (index, next) in chars.by_ref() {
+                    if escaped {
+                        escaped = false;
+                    } else if next == '\\' {
+                        escaped = true;
+                    } else if next == c {
+                        end = index;
+                        break;
+                    }
+                }
+                let literal = &js[start + c.len_utf8()..end];
+                // `foo__x.field` sets a field of `foo__x`
+                let (name, field) = literal.split_at(literal.find('.').unwrap_or(literal.len()));
+                let before = o.trim_end();
+                match self.renames.get(name) {
+                    Some(short)
+                        if before.ends_with("global[")
+                            || before.ends_with("createNestedObject(global,") =>
+                    {
+                        o.push(c);
+                        o.push_str(short);
+                        o.push_str(field);
+                        o.push(c);
+                    }
+                    _ => o.push_str(&js[start..(end + c.len_utf8()).min(js.len())]),
+                }
+            } else if is_identifier_char(c) {
+                let mut end = js.len();
+                while let Some((index, next)) = chars.peek() {
+                    if !is_identifier_char(*next) {
+                        end = *index;
+                        break;
+                    }
+                    chars.next();
+                }
+                let token = &js[start..end];
+                o.push_str(self.renames.get(token).map_or(token, String::as_str));
+            } else {
+                o.push(c);
+            }
+        }
+        o
+    }
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '$'
+}
+
+fn identifiers(js: &str) -> impl Iterator<Item = &str> {
+    js.split(|c: char| !is_identifier_char(c))
+        .filter(|t| !t.is_empty())
+}
+
+/// `$0`, `$1`, …, `$z`, `$10`, …
+fn short_name(mut counter: usize) -> String {
+    let mut digits = vec![];
+    loop {
+        digits.push(std::char::from_digit((counter % 36) as u32, 36).unwrap());
+        counter /= 36;
+        if counter == 0 {
+            break;
+        }
+    }
+    digits.push('$');
+    digits.into_iter().rev().collect()
+}
+
+/// Strips the whitespace of the generated `js`, the strings are left as is.
+///
+/// A space is kept where the tokens would merge, eg `let x`, and a new line where a statement
+/// may end without a `;`, as automatic semicolon insertion depends on it.
+pub fn minify(js: &str) -> String {
+    let chars = js.chars().collect::<Vec<_>>();
+    let mut o = String::with_capacity(js.len());
+    // the whitespace skipped since the last char, `Some(true)` if it has a new line
+    let mut whitespace: Option<bool> = None;
+    let mut idx = 0;
+    while idx < chars.len() {
+        let c = chars[idx];
+        if c.is_whitespace() {
+            whitespace = Some(whitespace.unwrap_or_default() || c == '\n');
+            idx += 1;
+            continue;
+        }
+        if let Some(new_line) = whitespace.take() {
+            if let Some(last) = o.chars().last() {
+                if new_line && ends_statement(last) && starts_statement(c) {
+                    o.push('\n');
+                } else if is_identifier_char(last) && is_identifier_char(c)
+                    || (last == c && matches!(c, '+' | '-'))
+                    || (last == '/' && matches!(c, '/' | '*'))
+                {
+                    o.push(' ');
+                }
+            }
+        }
+        o.push(c);
+        idx += 1;
+        if matches!(c, '"' | '\'' | '`') {
+            while idx < chars.len() {
+                o.push(chars[idx]);
+                idx += 1;
+                if chars[idx - 1] == '\\' && idx < chars.len() {
+                    o.push(chars[idx]);
+                    idx += 1;
+                } else if chars[idx - 1] == c {
+                    break;
+                }
+            }
+        }
+    }
+    o
+}
+
+fn ends_statement(c: char) -> bool {
+    is_identifier_char(c) || matches!(c, ')' | ']' | '}' | '"' | '\'' | '`' | '+' | '-')
+}
+
+fn starts_statement(c: char) -> bool {
+    is_identifier_char(c) || matches!(c, '{' | '"' | '\'' | '`' | '!' | '~' | '+' | '-')
+}
+
+/// Builds a source map, <https://sourcemaps.info/spec.html>, one segment per generated line.
+#[derive(Debug, Default)]
+struct SourceMap {
+    sources: Vec<String>,
+    mappings: Vec<String>,
+    previous_source: i64,
+    previous_line: i64,
+}
+
+impl SourceMap {
+    fn skip_lines(&mut self, count: usize) {
+        self.mappings
+            .resize(self.mappings.len() + count, String::new());
+    }
+
+    fn add_line(&mut self, location: Option<&SourceLocation>) {
+        let location = match location {
+            Some(location) => location,
+            None => return self.mappings.push(String::new()),
+        };
+        let source = match self.sources.iter().position(|s| s.eq(&location.source)) {
+            Some(source) => source,
+            None => {
+                self.sources.push(location.source.to_string());
+                self.sources.len() - 1
+            }
+        } as i64;
+        let line = location.line.saturating_sub(1) as i64;
+        // generated column, source index, source line and source column, all relative to the
+        // previous segment but the generated column, which is relative to the line
+        let mut segment = String::new();
+        vlq(0, &mut segment);
+        vlq(source - self.previous_source, &mut segment);
+        vlq(line - self.previous_line, &mut segment);
+        vlq(0, &mut segment);
+        self.previous_source = source;
+        self.previous_line = line;
+        self.mappings.push(segment);
+    }
+
+    fn to_json(&self, file: &str) -> String {
+        format!(
+            "{{\"version\":3,\"file\":{},\"sources\":[{}],\"names\":[],\"mappings\":{}}}",
+            json_string(file),
+            self.sources
+                .iter()
+                .map(|s| json_string(s))
+                .collect::<Vec<_>>()
+                .join(","),
+            json_string(self.mappings.join(";").as_str())
+        )
+    }
+}
+
+/// Base64 VLQ of `value`, as used in the `mappings` of a source map.
+fn vlq(value: i64, o: &mut String) {
+    const BASE64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut value = if value < 0 {
+        ((-value as u64) << 1) | 1
+    } else {
+        (value as u64) << 1
+    };
+    loop {
+        let mut digit = value & 31;
+        value >>= 5;
+        if value > 0 {
+            digit |= 32;
+        }
+        o.push(BASE64[digit as usize] as char);
+        if value == 0 {
+            break;
+        }
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut o = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => o.push_str("\\\""),
+            '\\' => o.push_str("\\\\"),
+            c if (c as u32) < 0x20 => o.push_str(format!("\\u{:04x}", c as u32).as_str()),
+            c => o.push(c),
+        }
+    }
+    o.push('"');
+    o
+}
+
+#[cfg(test)]
+mod test {
+    fn reference(name: &str, to: &str) -> fastn_js::Ast {
+        fastn_js::Ast::StaticVariable(fastn_js::StaticVariable {
+            name: name.to_string(),
+            value: fastn_js::SetPropertyValue::Reference(to.to_string()),
+            prefix: Some(fastn_js::GLOBAL_VARIABLE_MAP.to_string()),
+        })
+    }
+
+    #[test]
+    fn minify() {
+        assert_eq!(
+            super::minify(
+                "let x = a +  +b;\nlet s = \"a  b\\\" c\";\nreturn x\n(y)\nlet z = x\nz++"
+            ),
+            "let x=a+ +b;let s=\"a  b\\\" c\";return x(y)\nlet z=x\nz++"
+        );
+    }
+
+    #[test]
+    fn vlq() {
+        for (value, expected) in [(0, "A"), (1, "C"), (-1, "D"), (16, "gB"), (-17, "jB")] {
+            let mut o = String::new();
+            super::vlq(value, &mut o);
+            assert_eq!(o, expected);
+        }
+    }
+
+    #[test]
+    fn to_js() {
+        let asts = vec![
+            reference("index#x", "global.foo#title"),
+            reference("foo#title", "global.foo#other"),
+            reference("foo#other", "global.index#x"),
+        ];
+        let minifier = super::Minifier::new(asts.as_slice(), &["foo#other".to_string()], "foo");
+        let locations = [
+            ("index#x", "index.ftd", 3),
+            ("foo#title", "foo.ftd", 1),
+            ("foo#other", "foo.ftd", 5),
+        ]
+        .into_iter()
+        .map(|(name, source, line)| {
+            (
+                name.to_string(),
+                super::SourceLocation {
+                    source: source.to_string(),
+                    line,
+                },
+            )
+        })
+        .collect();
+        let minified = minifier.to_js(asts.as_slice(), true, "foo", &locations, "index.html", 2);
+        let lines = minified.js.split('\n').collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert!(!minified.js.contains("index__x"));
+        assert!(!minified.js.contains("foo__title"));
+        assert!(lines[3].contains("foo__other"));
+        // the things are named in `createNestedObject()`
+        assert!(lines[1].contains("createNestedObject(global,\"$"));
+        assert_eq!(
+            minified.source_map,
+            "{\"version\":3,\"file\":\"index.html\",\"sources\":[\"index.ftd\",\"foo.ftd\"],\
+             \"names\":[],\"mappings\":\";;;AAEA;ACFA;AAIA\"}"
+        );
+    }
+
+    #[test]
+    fn rename() {
+        let asts = vec![fastn_js::Ast::StaticVariable(fastn_js::StaticVariable {
+            name: "index#x".to_string(),
+            value: fastn_js::SetPropertyValue::Value(fastn_js::Value::String(
+                "index__x and index#x".to_string(),
+            )),
+            prefix: Some(fastn_js::GLOBAL_VARIABLE_MAP.to_string()),
+        })];
+        let minifier = super::Minifier::new(asts.as_slice(), &[], "foo");
+        assert_eq!(
+            minifier.rename(
+                "fastn_utils.createNestedObject(global, \"index__x\", \"index__x\");\n\
+                 fastn_utils.createNestedObject(global, \"index__x.a_b\", \"index__x.a_b\");\n\
+                 global[\"index__x\"] = index__x;\nlet s = 'index__x';"
+            ),
+            "fastn_utils.createNestedObject(global, \"$0\", \"index__x\");\n\
+             fastn_utils.createNestedObject(global, \"$0.a_b\", \"index__x.a_b\");\n\
+             global[\"$0\"] = $0;\nlet s = 'index__x';"
+        );
+    }
+}
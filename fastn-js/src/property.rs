@@ -202,12 +202,21 @@ pub enum Value {
         fields: Vec<(String, SetPropertyValue)>,
         other_references: Vec<String>,
     },
+    Map {
+        value: Vec<(String, SetPropertyValue)>,
+    },
     UI {
         value: Vec<fastn_js::ComponentStatement>,
     },
     Module {
         name: String,
     },
+    /// A string, with the html of its markdown which `marked` can not render, see
+    /// `fastn_utils.renderedMarkdown()`
+    RenderedMarkdown {
+        text: String,
+        html: String,
+    },
     Null,
     Undefined,
 }
@@ -264,6 +273,17 @@ impl Value {
                     ))
                     .join(", ")
             ),
+            Value::Map { value } => format!(
+                "fastn.mutableMap({{{}}})",
+                value
+                    .iter()
+                    .map(|(k, v)| format!(
+                        "{}: {}",
+                        Value::String(k.to_string()).to_js(element_name),
+                        v.to_js_with_element_name(element_name)
+                    ))
+                    .join(", ")
+            ),
             Value::UI { value } => format!(
                 "function({}, {}){{{}}}",
                 fastn_js::FUNCTION_PARENT,
@@ -277,6 +297,11 @@ impl Value {
                     })
                     .join("")
             ),
+            Value::RenderedMarkdown { text, html } => format!(
+                "fastn_utils.renderedMarkdown({}, {})",
+                Value::String(text.to_string()).to_js(element_name),
+                Value::String(html.to_string()).to_js(element_name)
+            ),
             Value::Null => "null".to_string(),
             Value::Undefined => "undefined".to_string(),
             Value::Module { name } => {
@@ -295,7 +320,9 @@ impl Value {
                 .map(|v| v.is_local_value_dependent())
                 .unwrap_or_default(),
             Value::List { value } => value.iter().any(|v| v.is_local_value_dependent()),
-            Value::Record { fields, .. } => fields.iter().any(|v| v.1.is_local_value_dependent()),
+            Value::Record { fields, .. } | Value::Map { value: fields } => {
+                fields.iter().any(|v| v.1.is_local_value_dependent())
+            }
             Value::UI { .. } => {
                 //Todo: Check for UI
                 false
@@ -403,6 +430,8 @@ pub enum PropertyKind {
     Src,
     Fit,
     ImageSrc,
+    ImageSizes,
+    HeadingAnchors,
     VideoSrc,
     LoopVideo,
     Controls,
@@ -411,6 +440,17 @@ pub enum PropertyKind {
     Poster,
     YoutubeSrc,
     Shadow,
+    Transform,
+    Transition,
+    Animation,
+    GridColumns,
+    GridRows,
+    GridAreas,
+    GridTemplate,
+    GridArea,
+    Gap,
+    RowGap,
+    ColumnGap,
     Code,
     CodeTheme,
     CodeLanguage,
@@ -533,9 +573,22 @@ impl PropertyKind {
             PropertyKind::Controls => "fastn_dom.PropertyKind.Controls",
             PropertyKind::Poster => "fastn_dom.PropertyKind.Poster",
             PropertyKind::Alt => "fastn_dom.PropertyKind.Alt",
+            PropertyKind::ImageSizes => "fastn_dom.PropertyKind.ImageSizes",
+            PropertyKind::HeadingAnchors => "fastn_dom.PropertyKind.HeadingAnchors",
             PropertyKind::Fit => "fastn_dom.PropertyKind.Fit",
             PropertyKind::YoutubeSrc => "fastn_dom.PropertyKind.YoutubeSrc",
             PropertyKind::Shadow => "fastn_dom.PropertyKind.Shadow",
+            PropertyKind::Transform => "fastn_dom.PropertyKind.Transform",
+            PropertyKind::Transition => "fastn_dom.PropertyKind.Transition",
+            PropertyKind::Animation => "fastn_dom.PropertyKind.Animation",
+            PropertyKind::GridColumns => "fastn_dom.PropertyKind.GridColumns",
+            PropertyKind::GridRows => "fastn_dom.PropertyKind.GridRows",
+            PropertyKind::GridAreas => "fastn_dom.PropertyKind.GridAreas",
+            PropertyKind::GridTemplate => "fastn_dom.PropertyKind.GridTemplate",
+            PropertyKind::GridArea => "fastn_dom.PropertyKind.GridArea",
+            PropertyKind::Gap => "fastn_dom.PropertyKind.Gap",
+            PropertyKind::RowGap => "fastn_dom.PropertyKind.RowGap",
+            PropertyKind::ColumnGap => "fastn_dom.PropertyKind.ColumnGap",
             PropertyKind::Code => "fastn_dom.PropertyKind.Code",
             PropertyKind::CodeTheme => "fastn_dom.PropertyKind.CodeTheme",
             PropertyKind::CodeShowLineNumber => "fastn_dom.PropertyKind.CodeShowLineNumber",
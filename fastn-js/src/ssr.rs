@@ -36,7 +36,37 @@ pub fn ssr(ast: &[fastn_js::Ast]) -> String {
 }
 
 pub fn ssr_with_js_string(package_name: &str, js: &str) -> String {
-    let js = format!("
+    ssr_str(&ssr_js(package_name, js, "fastn_virtual.ssr"))
+}
+
+/// The server rendered html of a page shown without JS, see `fastn_virtual.ssrStatic()`.
+#[derive(Debug, Default, PartialEq)]
+pub struct StaticHtml {
+    /// the `<title>`, `<meta>` and favicon tags set by `ftd.document`
+    pub head: String,
+    /// the body, with the css of the classes in a `<style>`
+    pub body: String,
+}
+
+pub fn ssr_static_with_js_string(package_name: &str, js: &str) -> StaticHtml {
+    let html = ssr_str(&ssr_js(package_name, js, "fastn_virtual.ssrStatic"));
+    match html.split_once(SSR_HEAD_END) {
+        Some((head, body)) => StaticHtml {
+            head: head.to_string(),
+            body: body.to_string(),
+        },
+        None => StaticHtml {
+            head: String::new(),
+            body: html,
+        },
+    }
+}
+
+/// `fastn_virtual.HEAD_END`
+const SSR_HEAD_END: &str = "<!-- fastn-head-end -->";
+
+fn ssr_js(package_name: &str, js: &str, render: &str) -> String {
+    format!("
         let __fastn_package_name__ = \"{}\";\n{}
         let main_wrapper = function(parent) {{
             let parenti0 = fastn_dom.createKernel(parent, fastn_dom.ElementKind.Column);
@@ -44,7 +74,5 @@ pub fn ssr_with_js_string(package_name: &str, js: &str) -> String {
             parenti0.setProperty(fastn_dom.PropertyKind.Height, fastn_dom.Resizing.FillContainer, inherited);
             main(parenti0);
         }};
-        fastn_virtual.ssr(main_wrapper);", package_name, js);
-
-    ssr_str(&js)
+        {}(main_wrapper);", package_name, js, render)
 }
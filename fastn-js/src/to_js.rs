@@ -177,6 +177,7 @@ impl fastn_js::ElementKind {
                 "fastn_dom.ElementKind.ContainerElement".to_string()
             }
             fastn_js::ElementKind::Column => "fastn_dom.ElementKind.Column".to_string(),
+            fastn_js::ElementKind::Grid => "fastn_dom.ElementKind.Grid".to_string(),
             fastn_js::ElementKind::Integer => "fastn_dom.ElementKind.Integer".to_string(),
             fastn_js::ElementKind::Decimal => "fastn_dom.ElementKind.Decimal".to_string(),
             fastn_js::ElementKind::Boolean => "fastn_dom.ElementKind.Boolean".to_string(),
@@ -930,6 +931,29 @@ impl ExpressionGenerator {
             return format!("[{}]", result.join(","));
         }
 
+        if let Some((parameters, body)) = self.lambda(node) {
+            // the parameters are in scope in the body, without a prefix
+            let mut arguments = arguments.to_vec();
+            arguments.extend(parameters.iter().map(|p| (p.to_string(), None)));
+            return format!(
+                "({}) => {}",
+                parameters
+                    .iter()
+                    .map(|p| fastn_js::utils::name_to_js_(p))
+                    .join(","),
+                self.to_js_(body, false, arguments.as_slice(), false)
+            );
+        }
+
+        if matches!(node.operator(), fastn_grammar::evalexpr::Operator::Index) {
+            let result = node
+                .children()
+                .iter()
+                .map(|children| self.to_js_(children, false, arguments, false))
+                .collect_vec();
+            return format!("ftd.index({})", result.join(","));
+        }
+
         if let Some(function_name) = self.function_name(node.operator()) {
             let mut result = vec![];
             if let Some(child) = node.children().first() {
@@ -941,7 +965,11 @@ impl ExpressionGenerator {
                     result.push(value);
                 }
             }
-            return format!("{}({})", function_name, result.join(","));
+            return if LIST_FUNCTIONS.contains(&function_name.as_str()) {
+                format!("ftd.{}({})", function_name, result.join(","))
+            } else {
+                format!("{}({})", function_name, result.join(","))
+            };
         }
 
         if self.is_assignment(node.operator()) {
@@ -1015,19 +1043,19 @@ impl ExpressionGenerator {
             "null".to_string()
         } else {
             let value = node.operator().to_string();
-            let prefix = arguments
-                .iter()
-                .find_map(|v| {
-                    if value.to_string().eq(&v.0) || value.starts_with(format!("{}.", v.0).as_str())
-                    {
-                        v.1.clone()
-                    } else {
-                        None
-                    }
-                })
+            let argument = arguments.iter().rev().find(|v| {
+                value.to_string().eq(&v.0) || value.starts_with(format!("{}.", v.0).as_str())
+            });
+            let prefix = argument
+                .and_then(|v| v.1.clone())
                 .map(|v| format!("{}.", v))
                 .unwrap_or_default();
-            format!("{}{}", prefix, value)
+            if argument.is_some() && value.contains('.') {
+                // the fields of a record argument, `person.name`, or its items, `list.0`
+                fastn_js::utils::reference_to_js(format!("{}{}", prefix, value).as_str())
+            } else {
+                format!("{}{}", prefix, value)
+            }
         };
 
         if node.operator().get_variable_identifier_read().is_some() && !no_getter {
@@ -1088,6 +1116,11 @@ impl ExpressionGenerator {
 
     pub fn has_operator(&self, operator: &fastn_grammar::evalexpr::Operator) -> Option<String> {
         if self.has_value(operator).is_none()
+            && !matches!(
+                operator,
+                fastn_grammar::evalexpr::Operator::Index
+                    | fastn_grammar::evalexpr::Operator::Lambda
+            )
             && self.has_function(operator).is_none()
             && !self.is_chain(operator)
             && !self.is_root(operator)
@@ -1103,8 +1136,31 @@ impl ExpressionGenerator {
     pub fn is_root(&self, operator: &fastn_grammar::evalexpr::Operator) -> bool {
         matches!(operator, fastn_grammar::evalexpr::Operator::RootNode)
     }
+
+    /// The parameters and the body of a lambda, `(acc, x) -> acc + x`.
+    pub fn lambda<'a>(
+        &self,
+        node: &'a fastn_grammar::evalexpr::ExprNode,
+    ) -> Option<(Vec<String>, &'a fastn_grammar::evalexpr::ExprNode)> {
+        match (node.operator(), node.children()) {
+            (fastn_grammar::evalexpr::Operator::Lambda, [parameters, body]) => Some((
+                std::iter::once(parameters)
+                    .chain(parameters.iter())
+                    .filter_map(|p| p.operator().get_variable_identifier_write())
+                    .collect(),
+                body,
+            )),
+            _ => None,
+        }
+    }
 }
 
+/// The builtin functions of the expressions which are implemented in `ftd.js`, called as
+/// `ftd.<name>(…)`, the names are common in JS.
+const LIST_FUNCTIONS: [&str; 7] = [
+    "map", "filter", "reduce", "sort", "join", "contains", "concat",
+];
+
 pub(crate) fn get_variable_declaration(variable: &str) -> pretty::RcDoc<'static> {
     text("let")
         .append(space())
@@ -1280,4 +1336,28 @@ mod tests {
         // )
         // .unwrap();
     }
+
+    #[test]
+    fn list_expressions() {
+        let expression = |s: &str| {
+            let node = fastn_grammar::evalexpr::build_operator_tree(s).unwrap();
+            fastn_js::to_js::ExpressionGenerator.to_js_(
+                &node,
+                true,
+                &[(
+                    "people".to_string(),
+                    Some(fastn_js::LOCAL_VARIABLE_MAP.to_string()),
+                )],
+                false,
+            )
+        };
+        assert_eq!(
+            expression("reduce(filter(people, p -> p.age >= 18), 0, (sum, p) -> sum + p.age)"),
+            "return (ftd.reduce((ftd.filter(__args__.people,((p) => fastn_utils.getter(p.get(\"age\"))>=18))),0,((sum,p) => fastn_utils.getter(sum)+fastn_utils.getter(p.get(\"age\")))));"
+        );
+        assert_eq!(
+            expression("f\"{people[0].name} and {len(people) - 1} more\""),
+            "return (ftd.concat(\"\",((ftd.index(ftd.index(fastn_utils.getter(__args__.people),0),\"name\"))),\" and \",((len(__args__.people)-1)),\" more\"));"
+        );
+    }
 }
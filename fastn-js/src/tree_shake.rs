@@ -0,0 +1,361 @@
+/// The asts with the JS identifier each one defines and the identifiers its JS refers to.
+struct Things {
+    asts: Vec<fastn_js::Ast>,
+    /// identifier, `name_to_js` of the name, -> index of the ast defining it
+    by_ident: std::collections::HashMap<String, usize>,
+    /// dependencies of each ast, indices of the asts its JS refers to
+    deps: Vec<Vec<usize>>,
+}
+
+impl Things {
+    fn new(asts: Vec<fastn_js::Ast>, package_name: &str) -> Things {
+        let by_ident = asts
+            .iter()
+            .enumerate()
+            .map(|(index, ast)| (ident(ast.get_name()), index))
+            .collect::<std::collections::HashMap<_, _>>();
+        // module identifier, eg `foo__bar` for `foo/bar`, -> indices of the asts of the module
+        let mut by_module: std::collections::HashMap<String, Vec<usize>> = Default::default();
+        for (index, ast) in asts.iter().enumerate() {
+            if let Some(module) = module_name(ast.get_name()) {
+                by_module
+                    .entry(fastn_js::utils::name_to_js_(module))
+                    .or_default()
+                    .push(index);
+            }
+        }
+
+        let deps = asts
+            .iter()
+            .enumerate()
+            .map(|(index, ast)| {
+                let js = render(ast, package_name);
+                let mut deps = std::collections::BTreeSet::new();
+                for token in
+                    js.split(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '$'))
+                {
+                    if let Some(dep) = by_ident.get(token) {
+                        deps.insert(*dep);
+                    }
+                }
+                // a thing may be looked up by its name, eg `ftd.get_value("foo#x")`
+                for literal in string_literals(js.as_str()) {
+                    if let Some(dep) = by_ident.get(ident(literal).as_str()) {
+                        deps.insert(*dep);
+                    }
+                }
+                // `fastn.module("<module>", global)` finds the things of the module by name
+                for (i, m) in js.match_indices("fastn.module(\"") {
+                    let module = js[i + m.len()..].split('"').next().unwrap_or_default();
+                    deps.extend(by_module.get(module).into_iter().flatten().copied());
+                }
+                deps.remove(&index);
+                deps.into_iter().collect::<Vec<usize>>()
+            })
+            .collect::<Vec<_>>();
+
+        Things {
+            asts,
+            by_ident,
+            deps,
+        }
+    }
+
+    /// Adds `index` to `sorted` after the things of `among` it depends on, a cycle is broken where
+    /// it is found.
+    fn visit(
+        &self,
+        index: usize,
+        among: &[usize],
+        visiting: &mut Vec<usize>,
+        sorted: &mut Vec<usize>,
+    ) {
+        if sorted.contains(&index) || visiting.contains(&index) {
+            return;
+        }
+        visiting.push(index);
+        let mut deps = self.deps[index]
+            .iter()
+            .filter(|dep| among.contains(dep))
+            .collect::<Vec<_>>();
+        deps.sort_by_key(|dep| self.asts[**dep].get_name());
+        for dep in deps {
+            self.visit(*dep, among, visiting, sorted);
+        }
+        visiting.pop();
+        sorted.push(index);
+    }
+
+    /// Indices of the asts reachable from `roots`.
+    fn reachable(&self, roots: &[usize]) -> std::collections::BTreeSet<usize> {
+        let mut seen = std::collections::BTreeSet::new();
+        let mut stack = roots.to_vec();
+        while let Some(index) = stack.pop() {
+            if seen.insert(index) {
+                stack.extend(self.deps[index].iter().copied());
+            }
+        }
+        seen
+    }
+}
+
+/// Contents of the string literals of `js`, the escapes are left as is.
+fn string_literals(js: &str) -> Vec<&str> {
+    let mut literals = vec![];
+    let mut chars = js.char_indices();
+    while let Some((start, quote)) = chars.next() {
+        if !matches!(quote, '"' | '\'' | '`') {
+            continue;
+        }
+        let mut escaped = false;
+        for (index, c) in chars.by_ref() {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == quote {
+                literals.push(&js[start + 1..index]);
+                break;
+            }
+        }
+    }
+    literals
+}
+
+pub(crate) fn render(ast: &fastn_js::Ast, package_name: &str) -> String {
+    let mut w = Vec::new();
+    ast.to_js(package_name).render(80, &mut w).unwrap();
+    String::from_utf8(w).unwrap()
+}
+
+pub(crate) fn ident(name: &str) -> String {
+    fastn_js::utils::name_to_js_(fastn_js::utils::get_doc_name_and_remaining(name).0.as_str())
+}
+
+/// `foo/bar` for `foo/bar#baz`, `None` for the things without a module, like `main`.
+pub(crate) fn module_name(name: &str) -> Option<&str> {
+    name.split_once('#').map(|(module, _)| module)
+}
+
+/// Drops the components, functions and variables which can not be reached from the `roots`, the
+/// names of the things to keep, eg `main`.
+///
+/// A thing reaches the things its JS refers to, by their JS identifier, and all the things of the
+/// modules it refers to with `fastn.module()`.
+pub fn tree_shake(
+    asts: Vec<fastn_js::Ast>,
+    roots: &[String],
+    package_name: &str,
+) -> Vec<fastn_js::Ast> {
+    let things = Things::new(asts, package_name);
+    let roots = roots
+        .iter()
+        .filter_map(|r| things.by_ident.get(ident(r).as_str()).copied())
+        .collect::<Vec<_>>();
+    let keep = things.reachable(roots.as_slice());
+    things
+        .asts
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, ast)| keep.contains(&index).then_some(ast))
+        .collect()
+}
+
+/// Things of an imported ftd module, their JS is loaded with its own `<script>`.
+#[derive(Debug)]
+pub struct Module {
+    /// name of the ftd module, eg `fastn-community.github.io/doc-site/page`
+    pub name: String,
+    pub asts: Vec<fastn_js::Ast>,
+}
+
+/// Takes out the things of the modules imported by the document `document_name` as one `Module`
+/// each, in the order they have to be loaded: a module comes after the modules it depends on.
+///
+/// The modules share the variables through `global`, which has to be declared before the first
+/// module is loaded. The JS file of a module is shared by the pages importing it, so the modules
+/// are split before `tree_shake()`, which only drops the things of the document, and have all
+/// their things, see `ftd::interpreter::interpret_with_all_imported_things()`.
+pub fn split_modules(
+    asts: Vec<fastn_js::Ast>,
+    document_name: &str,
+    package_name: &str,
+) -> (Vec<fastn_js::Ast>, Vec<Module>) {
+    let things = Things::new(asts, package_name);
+    let module_of = |index: usize| {
+        module_name(things.asts[index].get_name()).filter(|module| !module.eq(&document_name))
+    };
+
+    // modules in the order of their first thing, then sorted so dependencies come first
+    let mut modules: Vec<&str> = vec![];
+    for index in 0..things.asts.len() {
+        if let Some(module) = module_of(index) {
+            if !modules.contains(&module) {
+                modules.push(module);
+            }
+        }
+    }
+    let module_deps = |module: &str| {
+        let mut deps = vec![];
+        for index in (0..things.asts.len()).filter(|i| module_of(*i) == Some(module)) {
+            for dep in things.deps[index].iter() {
+                if let Some(dep) = module_of(*dep) {
+                    if dep != module && !deps.contains(&dep) {
+                        deps.push(dep);
+                    }
+                }
+            }
+        }
+        deps
+    };
+    let mut ordered: Vec<&str> = vec![];
+    let mut visiting: Vec<&str> = vec![];
+    fn visit<'a>(
+        module: &'a str,
+        module_deps: &dyn Fn(&str) -> Vec<&'a str>,
+        ordered: &mut Vec<&'a str>,
+        visiting: &mut Vec<&'a str>,
+    ) {
+        // a cycle is loaded in the order it is found, the JS of a module only refers to the other
+        // modules when it runs
+        if ordered.contains(&module) || visiting.contains(&module) {
+            return;
+        }
+        visiting.push(module);
+        for dep in module_deps(module) {
+            visit(dep, module_deps, ordered, visiting);
+        }
+        visiting.pop();
+        ordered.push(module);
+    }
+    for module in modules {
+        visit(module, &module_deps, &mut ordered, &mut visiting);
+    }
+
+    // the things of a module are sorted by their name, after the things they depend on, so the
+    // JS of the module does not depend on the order the document uses them in
+    let mut module_things: Vec<Vec<usize>> = ordered.iter().map(|_| vec![]).collect();
+    let mut document = vec![];
+    for index in 0..things.asts.len() {
+        match module_of(index).and_then(|m| ordered.iter().position(|o| o.eq(&m))) {
+            Some(position) => module_things[position].push(index),
+            None => document.push(index),
+        }
+    }
+    for indices in module_things.iter_mut() {
+        indices.sort_by_key(|index| things.asts[*index].get_name());
+        let mut sorted = vec![];
+        for index in indices.iter() {
+            things.visit(*index, indices.as_slice(), &mut vec![], &mut sorted);
+        }
+        *indices = sorted;
+    }
+
+    let ordered = ordered
+        .into_iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    let mut asts = things.asts.into_iter().map(Some).collect::<Vec<_>>();
+    let mut take = |indices: Vec<usize>| {
+        indices
+            .into_iter()
+            .filter_map(|index| asts[index].take())
+            .collect::<Vec<_>>()
+    };
+    let document = take(document);
+    let modules = ordered
+        .into_iter()
+        .zip(module_things)
+        .map(|(name, indices)| Module {
+            name,
+            asts: take(indices),
+        })
+        .collect();
+    (document, modules)
+}
+
+#[cfg(test)]
+mod test {
+    fn string(name: &str, value: &str) -> fastn_js::Ast {
+        fastn_js::Ast::StaticVariable(fastn_js::StaticVariable {
+            name: name.to_string(),
+            value: fastn_js::SetPropertyValue::Value(fastn_js::Value::String(value.to_string())),
+            prefix: Some(fastn_js::GLOBAL_VARIABLE_MAP.to_string()),
+        })
+    }
+
+    fn reference(name: &str, to: &str) -> fastn_js::Ast {
+        fastn_js::Ast::StaticVariable(fastn_js::StaticVariable {
+            name: name.to_string(),
+            value: fastn_js::SetPropertyValue::Reference(to.to_string()),
+            prefix: Some(fastn_js::GLOBAL_VARIABLE_MAP.to_string()),
+        })
+    }
+
+    fn names(asts: &[fastn_js::Ast]) -> Vec<&str> {
+        asts.iter().map(|a| a.get_name()).collect()
+    }
+
+    #[test]
+    fn tree_shake() {
+        let asts = vec![
+            reference("main", "global.foo#title"),
+            string("foo#title", "Hello"),
+            string("foo#unused", "Bye"),
+            reference("bar#x", "global.foo#unused"),
+        ];
+        let asts = super::tree_shake(asts, &["main".to_string()], "foo");
+        assert_eq!(names(asts.as_slice()), vec!["main", "foo#title"]);
+
+        // looked up by its name
+        let asts = vec![string("main", "foo#title"), string("foo#title", "Hello")];
+        let asts = super::tree_shake(asts, &["main".to_string()], "foo");
+        assert_eq!(names(asts.as_slice()), vec!["main", "foo#title"]);
+    }
+
+    #[test]
+    fn split_modules() {
+        let asts = vec![
+            reference("index#x", "global.bar#y"),
+            reference("bar#y", "global.foo#z"),
+            string("foo#z", "Hello"),
+        ];
+        let (document, modules) = super::split_modules(asts, "index", "foo");
+        assert_eq!(names(document.as_slice()), vec!["index#x"]);
+        assert_eq!(
+            modules.iter().map(|m| m.name.as_str()).collect::<Vec<_>>(),
+            vec!["foo", "bar"]
+        );
+        assert_eq!(names(modules[0].asts.as_slice()), vec!["foo#z"]);
+    }
+
+    #[test]
+    fn split_modules_is_the_same_for_all_the_pages() {
+        let page = |things: Vec<fastn_js::Ast>| {
+            let mut asts = vec![reference("index#x", "global.bar#b")];
+            asts.extend(things);
+            let (_, modules) = super::split_modules(asts, "index", "foo");
+            names(modules[0].asts.as_slice())
+                .into_iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        };
+        let expected = vec!["bar#a", "bar#b", "bar#c"];
+        assert_eq!(
+            page(vec![
+                reference("bar#b", "global.bar#a"),
+                string("bar#c", "c"),
+                string("bar#a", "a"),
+            ]),
+            expected
+        );
+        assert_eq!(
+            page(vec![
+                string("bar#c", "c"),
+                string("bar#a", "a"),
+                reference("bar#b", "global.bar#a"),
+            ]),
+            expected
+        );
+    }
+}
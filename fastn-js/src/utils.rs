@@ -5,6 +5,7 @@ pub fn is_kernel(s: &str) -> bool {
         "ftd#column",
         "ftd#integer",
         "ftd#container",
+        "ftd#grid",
     ]
     .contains(&s)
 }
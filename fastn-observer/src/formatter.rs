@@ -169,6 +169,123 @@ impl Pretty {
     }
 }
 
+/// Format a tree as nested HTML lists, used by debug pages. Spans are rendered as collapsible
+/// `<details>` elements, showing when they started with respect to their parent, their
+/// duration and the share of the root duration they took.
+#[derive(Debug)]
+pub struct Html;
+
+impl Html {
+    pub fn fmt(&self, tree: &fastn_observer::Tree) -> Result<String, std::fmt::Error> {
+        let mut writer = String::with_capacity(1024);
+
+        writer.push_str("<ul class=\"fastn-observer-tree\">");
+        Html::format_tree(tree, None, &mut writer)?;
+        writer.push_str("</ul>");
+
+        Ok(writer)
+    }
+
+    pub fn fmt_span(&self, span: &fastn_observer::Span) -> Result<String, std::fmt::Error> {
+        let mut writer = String::with_capacity(1024);
+
+        writer.push_str("<ul class=\"fastn-observer-tree\">");
+        Html::format_span(span, None, &mut writer)?;
+        writer.push_str("</ul>");
+
+        Ok(writer)
+    }
+
+    fn format_tree(
+        tree: &fastn_observer::Tree,
+        duration_root: Option<f64>,
+        writer: &mut String,
+    ) -> std::fmt::Result {
+        match tree {
+            fastn_observer::Tree::Event(event) => Html::format_event(event, writer),
+            fastn_observer::Tree::Span(span) => Html::format_span(span, duration_root, writer),
+        }
+    }
+
+    fn format_event(event: &fastn_observer::Event, writer: &mut String) -> std::fmt::Result {
+        use std::fmt::Write;
+
+        write!(
+            writer,
+            "<li class=\"event {}\"><span class=\"on\">{}</span> ",
+            event.shared.level.as_str().to_lowercase(),
+            fastn_observer::DurationDisplay(event.shared.on.as_nanos() as f64),
+        )?;
+        if let Some(ref message) = event.message {
+            writer.push_str(escape(message).as_str());
+        }
+        Html::format_fields(&event.shared.fields, writer)?;
+        writer.push_str("</li>");
+        Ok(())
+    }
+
+    fn format_span(
+        span: &fastn_observer::Span,
+        duration_root: Option<f64>,
+        writer: &mut String,
+    ) -> std::fmt::Result {
+        use std::fmt::Write;
+
+        let total_duration = span.duration.as_nanos() as f64;
+        let root_duration = duration_root.unwrap_or(total_duration);
+        let percent = if root_duration == 0.0 {
+            100.0
+        } else {
+            total_duration * 100.0 / root_duration
+        };
+
+        write!(
+            writer,
+            "<li class=\"span {}\"><details open><summary><span class=\"on\">{}</span> \
+            <b>{}</b> [ {} | {:.2}% ]",
+            span.shared.level.as_str().to_lowercase(),
+            fastn_observer::DurationDisplay(span.shared.on.as_nanos() as f64),
+            escape(span.name),
+            fastn_observer::DurationDisplay(total_duration),
+            percent,
+        )?;
+        Html::format_fields(&span.shared.fields, writer)?;
+        writer.push_str("</summary>");
+
+        if !span.nodes.is_empty() {
+            writer.push_str("<ul>");
+            for node in span.nodes.iter() {
+                Html::format_tree(node, Some(root_duration), writer)?;
+            }
+            writer.push_str("</ul>");
+        }
+        writer.push_str("</details></li>");
+        Ok(())
+    }
+
+    fn format_fields(fields: &fastn_observer::FieldSet, writer: &mut String) -> std::fmt::Result {
+        use std::fmt::Write;
+
+        for field in fields.iter() {
+            write!(
+                writer,
+                " | <i>{}</i>: {}",
+                escape(field.key()),
+                escape(field.value())
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Escapes `s` for the text and the attribute values of the html pages.
+pub fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
 enum Indent {
     Null,
     Line,
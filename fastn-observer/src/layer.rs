@@ -116,14 +116,14 @@ where
                 .expect(OPENED_SPAN_NOT_IN_EXTENSIONS)
                 .record_span(span),
             None => {
+                let tree = fastn_observer::Tree::Span(span);
                 if fastn_observer::is_traced() {
                     println!(
                         "{}",
-                        fastn_observer::formatter::Pretty {}
-                            .fmt(&fastn_observer::Tree::Span(span))
-                            .unwrap()
+                        fastn_observer::formatter::Pretty {}.fmt(&tree).unwrap()
                     );
                 }
+                fastn_observer::recorder::push(tree);
             }
         }
     }
@@ -5,13 +5,15 @@ mod field;
 mod formatter;
 mod layer;
 mod opened_span;
+mod recorder;
 mod tree;
 
 pub(crate) use duration_display::DurationDisplay;
 pub use field::{Field, FieldSet};
-pub use formatter::write_immediate;
+pub use formatter::{escape, write_immediate, Html, Pretty};
 pub use layer::Layer;
 pub use opened_span::OpenedSpan;
+pub use recorder::{get_recorded, is_recording, record, recorded, Recorded};
 pub use tree::{Event, Shared, Span, Tree};
 
 pub fn observe() {
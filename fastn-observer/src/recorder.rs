@@ -0,0 +1,107 @@
+/// Keeps the most recent closed root spans with a given name in memory, so they can be
+/// inspected after the fact, eg `fastn serve --profile` records one span per request and shows
+/// them on `/-/debug/requests/`.
+#[derive(Debug)]
+pub struct Recorded {
+    pub id: u64,
+    pub recorded_on: std::time::SystemTime,
+    pub span: fastn_observer::Span,
+}
+
+struct Recorder {
+    name: &'static str,
+    capacity: usize,
+    next_id: u64,
+    recorded: std::collections::VecDeque<std::sync::Arc<Recorded>>,
+}
+
+static RECORDER: std::sync::Mutex<Option<Recorder>> = std::sync::Mutex::new(None);
+
+/// Start recording root spans named `name`, keeping at most `capacity` of them.
+pub fn record(name: &'static str, capacity: usize) {
+    *RECORDER.lock().unwrap() = Some(Recorder {
+        name,
+        capacity,
+        next_id: 1,
+        recorded: std::collections::VecDeque::with_capacity(capacity),
+    });
+}
+
+pub fn is_recording() -> bool {
+    RECORDER.lock().unwrap().is_some()
+}
+
+/// All recorded spans, most recent first.
+pub fn recorded() -> Vec<std::sync::Arc<Recorded>> {
+    match RECORDER.lock().unwrap().as_ref() {
+        Some(recorder) => recorder.recorded.iter().rev().cloned().collect(),
+        None => vec![],
+    }
+}
+
+pub fn get_recorded(id: u64) -> Option<std::sync::Arc<Recorded>> {
+    RECORDER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|r| r.recorded.iter().find(|v| v.id == id).cloned())
+}
+
+pub(crate) fn push(tree: fastn_observer::Tree) {
+    let span = match tree {
+        fastn_observer::Tree::Span(span) => span,
+        fastn_observer::Tree::Event(_) => return,
+    };
+
+    let mut recorder = RECORDER.lock().unwrap();
+    let recorder = match recorder.as_mut() {
+        Some(r) if r.name == span.name && r.capacity > 0 => r,
+        _ => return,
+    };
+
+    if recorder.recorded.len() == recorder.capacity {
+        recorder.recorded.pop_front();
+    }
+    recorder.recorded.push_back(std::sync::Arc::new(Recorded {
+        id: recorder.next_id,
+        recorded_on: std::time::SystemTime::now(),
+        span,
+    }));
+    recorder.next_id += 1;
+}
+
+#[cfg(test)]
+mod test {
+    #[tracing::instrument]
+    fn request(path: &str) {
+        tracing::info!(path);
+    }
+
+    #[tracing::instrument]
+    fn other() {}
+
+    #[test]
+    fn records_latest_named_root_spans() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let subscriber = tracing_subscriber::registry().with(fastn_observer::Layer::default());
+        fastn_observer::record("request", 2);
+
+        tracing::subscriber::with_default(subscriber, || {
+            request("/a/");
+            other();
+            request("/b/");
+            request("/c/");
+        });
+
+        let recorded = fastn_observer::recorded();
+        assert_eq!(recorded.len(), 2);
+        assert_eq!(recorded[0].id, 3);
+        assert_eq!(recorded[1].id, 2);
+        assert_eq!(recorded[0].span.name(), "request");
+        assert_eq!(recorded[0].span.shared().fields()[0].value(), "\"/c/\"");
+        assert_eq!(recorded[0].span.nodes().len(), 1);
+        assert!(fastn_observer::get_recorded(1).is_none());
+        assert!(fastn_observer::get_recorded(2).is_some());
+    }
+}
@@ -37,4 +37,44 @@ impl Span {
             nodes: Vec::new(),
         }
     }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn duration(&self) -> std::time::Duration {
+        self.duration
+    }
+
+    pub fn nodes(&self) -> &[Tree] {
+        self.nodes.as_slice()
+    }
+
+    pub fn shared(&self) -> &Shared {
+        &self.shared
+    }
+}
+
+impl Event {
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    pub fn shared(&self) -> &Shared {
+        &self.shared
+    }
+}
+
+impl Shared {
+    pub fn level(&self) -> tracing::Level {
+        self.level
+    }
+
+    pub fn fields(&self) -> &fastn_observer::FieldSet {
+        &self.fields
+    }
+
+    pub fn on(&self) -> std::time::Duration {
+        self.on
+    }
 }
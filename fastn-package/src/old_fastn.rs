@@ -95,4 +95,13 @@ pub struct PackageTemp {
     pub backend_headers: Option<Vec<BackendHeader>>,
     #[serde(rename = "icon")]
     pub icon: Option<ftd::ImageSrc>,
+    /// `admin-group` is the id of the user group whose members can access the admin only
+    /// pages, like `/-/debug/requests/`
+    #[serde(rename = "admin-group")]
+    pub admin_group: Option<String>,
+    /// `split-js` emits the JS of each imported ftd module as its own file
+    #[serde(rename = "split-js")]
+    pub split_js: bool,
+    /// `spa` navigates between the pages without reloading them
+    pub spa: bool,
 }
@@ -19,11 +19,7 @@ pub trait DomT {
         parent: fastn_runtime::NodeKey,
         _k: fastn_runtime::ElementKind,
     ) -> fastn_runtime::NodeKey;
-    fn add_child(
-        &mut self,
-        parent_key: fastn_runtime::NodeKey,
-        child_key: fastn_runtime::NodeKey,
-    );
+    fn add_child(&mut self, parent_key: fastn_runtime::NodeKey, child_key: fastn_runtime::NodeKey);
 }
 
 #[cfg(not(feature = "browser"))]
@@ -121,31 +117,62 @@ impl Dom {
     pub fn create_kernel(
         &mut self,
         parent: fastn_runtime::NodeKey,
-        _k: fastn_runtime::ElementKind,
+        k: fastn_runtime::ElementKind,
     ) -> fastn_runtime::NodeKey {
         let taffy_key = self
             .taffy
             .new_leaf(taffy::style::Style::default())
             .expect("this should never fail");
 
-        // TODO: based on k, create different elements
-        let c = fastn_runtime::Element::Container(fastn_runtime::Container {
-            taffy_key,
-            style: fastn_runtime::CommonStyle {
-                // background_color: Some(
-                //     fastn_runtime::Color {
-                //         red: self.memory.create_i32(0),
-                //         green: self.memory.create_i32(100),
-                //         blue: self.memory.create_i32(0),
-                //         alpha: self.memory.create_f32(1.0),
-                //     }
-                //     .into(),
-                // ),
-                background_color: None,
-                padding: None,
-                align: None,
-            },
-        });
+        // TODO: create the image and iframe elements
+        if let fastn_runtime::ElementKind::Column = k {
+            self.taffy
+                .set_style(
+                    taffy_key,
+                    taffy::style::Style {
+                        flex_direction: taffy::style::FlexDirection::Column,
+                        ..Default::default()
+                    },
+                )
+                .expect("this should never fail");
+        }
+
+        let c = match k {
+            fastn_runtime::ElementKind::Text
+            | fastn_runtime::ElementKind::Integer
+            | fastn_runtime::ElementKind::Decimal
+            | fastn_runtime::ElementKind::Boolean => {
+                fastn_runtime::Element::Text(Box::new(fastn_runtime::Text {
+                    taffy: taffy_key,
+                    // not in any frame, the string lives as long as the node
+                    text: self.memory.create_string_constant(vec![]),
+                    role: None,
+                    style: fastn_runtime::CommonStyle {
+                        background_color: None,
+                        padding: None,
+                        align: None,
+                    },
+                }))
+            }
+            _ => fastn_runtime::Element::Container(fastn_runtime::Container {
+                taffy_key,
+                kind: k,
+                style: fastn_runtime::CommonStyle {
+                    // background_color: Some(
+                    //     fastn_runtime::Color {
+                    //         red: self.memory.create_i32(0),
+                    //         green: self.memory.create_i32(100),
+                    //         blue: self.memory.create_i32(0),
+                    //         alpha: self.memory.create_f32(1.0),
+                    //     }
+                    //     .into(),
+                    // ),
+                    background_color: None,
+                    padding: None,
+                    align: None,
+                },
+            }),
+        };
 
         let key = self.nodes.insert(c);
         self.children.insert(key, vec![]);
@@ -171,6 +198,35 @@ impl Dom {
         println!("add_child: {:?} -> {:?}", &parent_key, &child_key);
     }
 
+    /// Shows the value of `ptr` in the text node `key`, the node keeps a copy of it. The guest
+    /// gets a trap if the value can not be shown or `key` is not a text node.
+    pub fn set_text(
+        &mut self,
+        key: fastn_runtime::NodeKey,
+        kind: fastn_runtime::PointerKind,
+        ptr: fastn_runtime::PointerKey,
+    ) -> wasmtime::Result<()> {
+        let text = match self.memory.get_text(ptr, kind) {
+            Some(text) => text,
+            None => {
+                return Err(wasmtime::Error::msg(format!(
+                    "set_text: a {:?} can not be shown as text",
+                    kind
+                )))
+            }
+        };
+        match &self.nodes[key] {
+            fastn_runtime::Element::Text(t) => {
+                self.memory.string[t.text].value.set_value(text);
+                Ok(())
+            }
+            t => Err(wasmtime::Error::msg(format!(
+                "set_text called on a non text node: {:?}",
+                t
+            ))),
+        }
+    }
+
     pub fn set_element_background_solid(
         &mut self,
         _key: fastn_runtime::NodeKey,
@@ -189,6 +245,13 @@ impl Dom {
         self.taffy.set_style(taffy_key, style).unwrap();
     }
 
+    pub fn set_element_width_percent(&mut self, key: fastn_runtime::NodeKey, width: f32) {
+        let taffy_key = self.nodes[key].taffy();
+        let mut style = self.taffy.style(taffy_key).unwrap().to_owned();
+        style.size.width = taffy::prelude::percent(width / 100.0);
+        self.taffy.set_style(taffy_key, style).unwrap();
+    }
+
     pub fn set_element_height_px(&mut self, key: fastn_runtime::NodeKey, height: i32) {
         let taffy_key = self.nodes[key].taffy();
         let mut style = self.taffy.style(taffy_key).unwrap().to_owned();
@@ -199,7 +262,11 @@ impl Dom {
     pub fn set_element_spacing_px(&mut self, key: fastn_runtime::NodeKey, spacing: i32) {
         let taffy_key = self.nodes[key].taffy();
         let mut style = self.taffy.style(taffy_key).unwrap().to_owned();
-        style.gap.height = taffy::prelude::points(spacing as f32);
+        // like the css `gap`, between the rows and between the columns
+        style.gap = taffy::prelude::Size {
+            width: taffy::prelude::points(spacing as f32),
+            height: taffy::prelude::points(spacing as f32),
+        };
         self.taffy.set_style(taffy_key, style).unwrap();
     }
 
@@ -210,10 +277,17 @@ impl Dom {
         self.taffy.set_style(taffy_key, style).unwrap();
     }
 
+    pub fn set_element_padding_px(&mut self, key: fastn_runtime::NodeKey, padding: i32) {
+        let taffy_key = self.nodes[key].taffy();
+        let mut style = self.taffy.style(taffy_key).unwrap().to_owned();
+        style.padding = taffy::prelude::points(padding as f32);
+        self.taffy.set_style(taffy_key, style).unwrap();
+    }
+
     fn set_element_height_percent(&mut self, key: fastn_runtime::NodeKey, height: f32) {
         let taffy_key = self.nodes[key].taffy();
         let mut style = self.taffy.style(taffy_key).unwrap().to_owned();
-        style.size.height = taffy::prelude::points(height);
+        style.size.height = taffy::prelude::percent(height / 100.0);
         self.taffy.set_style(taffy_key, style).unwrap();
     }
 
@@ -242,6 +316,12 @@ impl Dom {
                 self.set_element_margin_px(key, value.i32())
             }
             fastn_runtime::UIProperty::Event => {}
+            fastn_runtime::UIProperty::WidthFixedPercentage => {
+                self.set_element_width_percent(key, value.f32())
+            }
+            fastn_runtime::UIProperty::PaddingFixedPx => {
+                self.set_element_padding_px(key, value.i32())
+            }
         }
     }
 
@@ -331,7 +411,7 @@ impl Value {
 mod test {
     #[test]
     fn ui_dependency() {
-        let mut d = super::Dom::default();
+        let mut d = super::Dom::new(0, 0);
         println!("1** {:#?}", d.memory());
         d.memory().assert_empty();
         d.memory_mut().create_frame();
@@ -362,4 +442,33 @@ mod test {
             .memory
             .is_pointer_valid(i32_pointer2.into_integer_pointer()));
     }
+
+    #[test]
+    fn set_text() {
+        let mut d = super::Dom::new(0, 0);
+        d.memory_mut().create_frame();
+        let text_node = d.create_kernel(d.root, fastn_runtime::ElementKind::Integer);
+        let column_node = d.create_kernel(d.root, fastn_runtime::ElementKind::Column);
+        let i32_pointer = d.memory_mut().create_i32(42);
+        let list_pointer = d.memory_mut().create_list();
+
+        d.set_text(text_node, fastn_runtime::PointerKind::Integer, i32_pointer)
+            .unwrap();
+        match &d.nodes[text_node] {
+            fastn_runtime::Element::Text(t) => assert_eq!(d.memory.get_string(t.text), "42"),
+            t => panic!("not a text node: {:?}", t),
+        }
+        // a list can not be shown, and a column does not show a text
+        assert!(d
+            .set_text(text_node, fastn_runtime::PointerKind::List, list_pointer)
+            .is_err());
+        assert!(d
+            .set_text(
+                column_node,
+                fastn_runtime::PointerKind::Integer,
+                i32_pointer
+            )
+            .is_err());
+        d.memory_mut().end_frame();
+    }
 }
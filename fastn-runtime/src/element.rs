@@ -5,7 +5,7 @@ pub enum Element {
     Image(Image),
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 pub enum ElementKind {
     Column,
     Row,
@@ -73,6 +73,8 @@ pub struct CommonStyle {
 pub struct Container {
     #[cfg(not(feature = "browser"))]
     pub taffy_key: taffy::node::Node,
+    /// `Column`, `Row` or `Container`
+    pub kind: ElementKind,
     pub style: CommonStyle,
 }
 
@@ -83,13 +85,14 @@ impl Container {
             taffy_key: taffy
                 .new_leaf(taffy::style::Style {
                     size: taffy::prelude::Size {
-                        width: taffy::prelude::percent(100.0),
-                        height: taffy::prelude::percent(100.0),
+                        width: taffy::prelude::percent(1.0),
+                        height: taffy::prelude::percent(1.0),
                     },
                     gap: taffy::prelude::points(20.0),
                     ..Default::default()
                 })
                 .expect("this should never fail"),
+            kind: ElementKind::Column,
             style: CommonStyle {
                 // background_color: Some(
                 //     fastn_runtime::Color {
@@ -112,8 +115,9 @@ impl Container {
 pub struct Text {
     #[cfg(not(feature = "browser"))]
     pub taffy: taffy::node::Node,
+    /// the string shown, owned by the node, `Dom::set_text()` updates it
     pub text: fastn_runtime::PointerKey,
-    pub role: fastn_runtime::ResponsiveProperty<fastn_runtime::PointerKey>,
+    pub role: Option<fastn_runtime::ResponsiveProperty<fastn_runtime::PointerKey>>,
     pub style: CommonStyle,
 }
 
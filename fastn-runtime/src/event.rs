@@ -24,6 +24,7 @@ pub enum DomEventKind {
     OnMouseEnter,
     OnMouseLeave,
     OnGlobalKey, /*(Vec<VirtualKeyCode>)*/
+    OnClick,
 }
 
 impl DomEventKind {
@@ -38,6 +39,7 @@ impl From<i32> for DomEventKind {
             0 => DomEventKind::OnMouseEnter,
             1 => DomEventKind::OnMouseLeave,
             2 => DomEventKind::OnGlobalKey,
+            3 => DomEventKind::OnClick,
             _ => panic!("Unknown UIProperty: {}", i),
         }
     }
@@ -49,6 +51,7 @@ impl From<DomEventKind> for i32 {
             DomEventKind::OnMouseEnter => 0,
             DomEventKind::OnMouseLeave => 1,
             DomEventKind::OnGlobalKey => 2,
+            DomEventKind::OnClick => 3,
         }
     }
 }
@@ -1,5 +1,3 @@
-extern crate self as fastn_runtime;
-
 #[cfg(feature = "browser")]
 fn main() {}
 
@@ -154,7 +152,7 @@ pub fn create_module() -> Vec<u8> {
 
 // source: columns.clj (derived from columns.ftd)
 #[cfg(not(feature = "browser"))]
-fn create_columns() -> Vec<u8> {
+pub fn create_columns() -> Vec<u8> {
     let mut m: Vec<fastn_wasm::Ast> = fastn_runtime::Dom::imports();
 
     // Note: can not add these till the functions are defined
@@ -12,6 +12,18 @@ impl fastn_runtime::Memory {
         None
     }
 
+    /// If a handler of `event_kind` is attached to `node`.
+    pub(crate) fn has_event_handler(
+        &self,
+        node: fastn_runtime::NodeKey,
+        event_kind: fastn_runtime::DomEventKind,
+    ) -> bool {
+        self.event_handler
+            .get(&event_kind)
+            .map(|v| v.iter().any(|e| e.node == node))
+            .unwrap_or(false)
+    }
+
     pub(crate) fn get_heapdata_from_pointer(&self, _pointer: fastn_runtime::Pointer) {
         /* match pointer.kind {
             fastn_runtime::PointerKind::Boolean => self.boolean.get()
@@ -154,7 +154,7 @@ impl Memory {
             .expect("Expected color vec")
             .value
             .value();
-        let r_pointer = vec_value.get(0).expect("Expected r pointer");
+        let r_pointer = vec_value.first().expect("Expected r pointer");
         let r_value = self
             .i32
             .get(r_pointer.pointer)
@@ -307,6 +307,48 @@ impl Memory {
         pointer
     }
 
+    pub fn list_push(
+        &mut self,
+        list: fastn_runtime::PointerKey,
+        kind: fastn_runtime::PointerKind,
+        ptr: fastn_runtime::PointerKey,
+    ) {
+        let item = fastn_runtime::Pointer { pointer: ptr, kind };
+        self.vec[list].value.mut_value().push(item);
+        self.add_parent(item, list.into_list_pointer());
+    }
+
+    pub fn list_len(&self, list: fastn_runtime::PointerKey) -> i32 {
+        self.vec[list].value.value().len() as i32
+    }
+
+    pub fn concat_string(
+        &mut self,
+        a: fastn_runtime::PointerKey,
+        b: fastn_runtime::PointerKey,
+    ) -> fastn_runtime::PointerKey {
+        let s = format!("{}{}", self.get_string(a), self.get_string(b));
+        self.create_string(s)
+    }
+
+    pub fn copy_string(&mut self, ptr: fastn_runtime::PointerKey) -> fastn_runtime::PointerKey {
+        let s = self.get_string(ptr);
+        self.create_string(s)
+    }
+
+    pub fn set_string(&mut self, ptr: fastn_runtime::PointerKey, value: fastn_runtime::PointerKey) {
+        let s = self.get_string(value);
+        self.string[ptr].value.set_value(s)
+    }
+
+    fn create_string(&mut self, s: String) -> fastn_runtime::PointerKey {
+        let pointer = self
+            .string
+            .insert(fastn_runtime::HeapValue::new(s).into_heap_data());
+        self.insert_in_frame(pointer, fastn_runtime::PointerKind::String);
+        pointer
+    }
+
     pub fn create_boolean(&mut self, value: bool) -> fastn_runtime::PointerKey {
         let pointer = self
             .boolean
@@ -531,6 +573,22 @@ impl Memory {
     pub(crate) fn get_string(&self, ptr: fastn_runtime::PointerKey) -> String {
         self.string[ptr].value.value().to_string()
     }
+
+    /// The value as it is shown by the text kernels, `ftd.text`, `ftd.integer` etc., `None` for
+    /// the kinds they can not show.
+    pub(crate) fn get_text(
+        &self,
+        ptr: fastn_runtime::PointerKey,
+        kind: fastn_runtime::PointerKind,
+    ) -> Option<String> {
+        Some(match kind {
+            fastn_runtime::PointerKind::String => self.get_string(ptr),
+            fastn_runtime::PointerKind::Integer => self.get_i32(ptr).to_string(),
+            fastn_runtime::PointerKind::Decimal => self.get_f32(ptr).to_string(),
+            fastn_runtime::PointerKind::Boolean => self.get_boolean(ptr).to_string(),
+            _ => return None,
+        })
+    }
 }
 
 #[cfg(test)]
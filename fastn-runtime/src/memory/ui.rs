@@ -61,6 +61,8 @@ pub enum UIProperty {
     SpacingFixedPx,
     MarginFixedPx,
     Event,
+    WidthFixedPercentage,
+    PaddingFixedPx,
 }
 
 impl From<i32> for UIProperty {
@@ -73,6 +75,8 @@ impl From<i32> for UIProperty {
             4 => UIProperty::SpacingFixedPx,
             5 => UIProperty::MarginFixedPx,
             6 => UIProperty::Event,
+            7 => UIProperty::WidthFixedPercentage,
+            8 => UIProperty::PaddingFixedPx,
             _ => panic!("Unknown UIProperty: {}", i),
         }
     }
@@ -88,6 +92,8 @@ impl From<UIProperty> for i32 {
             UIProperty::SpacingFixedPx => 4,
             UIProperty::MarginFixedPx => 5,
             UIProperty::Event => 6,
+            UIProperty::WidthFixedPercentage => 7,
+            UIProperty::PaddingFixedPx => 8,
         }
     }
 }
@@ -39,6 +39,54 @@ pub fn leaf(
         .append(">")
 }
 
+pub fn text(
+    tag: &'static str,
+    node_key: fastn_runtime::NodeKey,
+    attrs: Option<pretty::RcDoc<'static>>,
+    text: &str,
+) -> pretty::RcDoc<'static> {
+    let g1 = pretty::RcDoc::text("<")
+        .append(tag)
+        .append(pretty::RcDoc::space())
+        .append("data-id=")
+        .append(fastn_runtime::dom::node_key_to_id(node_key));
+    let g1 = match attrs {
+        Some(v) => g1.append(pretty::RcDoc::space()).append(v),
+        None => g1,
+    };
+    g1.append(">")
+        .append(escape(text))
+        .append("</")
+        .append(tag)
+        .append(">")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// The `class` and `style` attributes of a node, `None` if it has neither. The class is the one
+/// `fastn_js` gives to the kernel, the style has the css of the properties set on the node.
+pub fn attrs(class: Option<&'static str>, style: Vec<String>) -> Option<pretty::RcDoc<'static>> {
+    let mut attrs = vec![];
+    if let Some(class) = class {
+        attrs.push(pretty::RcDoc::text(format!("class=\"{}\"", class)));
+    }
+    if !style.is_empty() {
+        attrs.push(pretty::RcDoc::text(format!(
+            "style=\"{}\"",
+            escape(style.join("; ").as_str()).replace('"', "&quot;")
+        )));
+    }
+    if attrs.is_empty() {
+        None
+    } else {
+        Some(pretty::RcDoc::intersperse(attrs, pretty::RcDoc::space()))
+    }
+}
+
 pub fn initial(dom: &fastn_runtime::Dom) -> String {
     let mut w = Vec::new();
     let o = dom.html(dom.root);
@@ -51,6 +99,102 @@ impl fastn_runtime::Dom {
         let root = self.nodes.get(node_key).unwrap();
         root.html(node_key, self)
     }
+
+    /// The css of the layout style of the node, the properties left to their default are not
+    /// in it. A node with a click handler gets the pointer cursor, like in `fastn_js`.
+    fn style(&self, node_key: fastn_runtime::NodeKey) -> Vec<String> {
+        let style = self.taffy.style(self.nodes[node_key].taffy()).unwrap();
+        let mut css = vec![];
+        if let Some(width) = dimension(style.size.width) {
+            css.push(format!("width: {}", width));
+        }
+        if let Some(height) = dimension(style.size.height) {
+            css.push(format!("height: {}", height));
+        }
+        css.extend(sides(
+            "margin",
+            [
+                style.margin.top,
+                style.margin.right,
+                style.margin.bottom,
+                style.margin.left,
+            ]
+            .map(length_percentage_auto),
+        ));
+        css.extend(sides(
+            "padding",
+            [
+                style.padding.top,
+                style.padding.right,
+                style.padding.bottom,
+                style.padding.left,
+            ]
+            .map(length_percentage),
+        ));
+        match (
+            length_percentage(style.gap.height),
+            length_percentage(style.gap.width),
+        ) {
+            (Some(row), Some(column)) if row == column => css.push(format!("gap: {}", row)),
+            (row, column) => {
+                css.extend(row.map(|v| format!("row-gap: {}", v)));
+                css.extend(column.map(|v| format!("column-gap: {}", v)));
+            }
+        }
+        if self
+            .memory
+            .has_event_handler(node_key, fastn_runtime::DomEventKind::OnClick)
+        {
+            css.push("cursor: pointer".to_string());
+        }
+        css
+    }
+}
+
+fn dimension(d: taffy::style::Dimension) -> Option<String> {
+    match d {
+        taffy::style::Dimension::Points(v) => Some(px(v)),
+        taffy::style::Dimension::Percent(v) => Some(percent(v)),
+        taffy::style::Dimension::Auto => None,
+    }
+}
+
+/// `None` for zero, the default of the margins, the paddings and the gaps
+fn length_percentage(l: taffy::style::LengthPercentage) -> Option<String> {
+    match l {
+        taffy::style::LengthPercentage::Points(v) => (v != 0.0).then(|| px(v)),
+        taffy::style::LengthPercentage::Percent(v) => Some(percent(v)),
+    }
+}
+
+fn length_percentage_auto(l: taffy::style::LengthPercentageAuto) -> Option<String> {
+    match l {
+        taffy::style::LengthPercentageAuto::Points(v) => (v != 0.0).then(|| px(v)),
+        taffy::style::LengthPercentageAuto::Percent(v) => Some(percent(v)),
+        taffy::style::LengthPercentageAuto::Auto => Some("auto".to_string()),
+    }
+}
+
+/// `margin: 10px` if all the sides, top, right, bottom and left, are the same, `margin-top: 10px`
+/// etc. otherwise.
+fn sides(name: &str, sides: [Option<String>; 4]) -> Vec<String> {
+    if sides.iter().all(|v| v.is_some() && v.eq(&sides[0])) {
+        return vec![format!("{}: {}", name, sides[0].as_ref().unwrap())];
+    }
+    ["top", "right", "bottom", "left"]
+        .iter()
+        .zip(sides)
+        .filter_map(|(side, v)| v.map(|v| format!("{}-{}: {}", name, side, v)))
+        .collect()
+}
+
+fn px(v: f32) -> String {
+    format!("{}px", v)
+}
+
+/// taffy keeps the fraction, `0.4` is `40%`
+fn percent(v: f32) -> String {
+    format!("{}%", (v * 100_000.0).round() / 1000.0)
 }
 
 impl fastn_runtime::Element {
@@ -77,13 +221,19 @@ impl fastn_runtime::Container {
             .iter()
             .map(|v| dom.html(*v))
             .collect::<Vec<_>>();
+        let class = match self.kind {
+            fastn_runtime::ElementKind::Column => Some("ft_column"),
+            fastn_runtime::ElementKind::Row => Some("ft_row"),
+            _ => None,
+        };
+        let attrs = fastn_runtime::server::html::attrs(class, dom.style(node_key));
         if children.is_empty() {
-            fastn_runtime::server::html::leaf("div", node_key, None)
+            fastn_runtime::server::html::leaf("div", node_key, attrs)
         } else {
             fastn_runtime::server::html::node(
                 "div",
                 node_key,
-                None,
+                attrs,
                 pretty::RcDoc::intersperse(children, pretty::RcDoc::line()),
             )
         }
@@ -93,10 +243,15 @@ impl fastn_runtime::Container {
 impl fastn_runtime::Text {
     fn html(
         &self,
-        _node_key: fastn_runtime::NodeKey,
-        _dom: &fastn_runtime::Dom,
+        node_key: fastn_runtime::NodeKey,
+        dom: &fastn_runtime::Dom,
     ) -> pretty::RcDoc<'static> {
-        todo!()
+        fastn_runtime::server::html::text(
+            "div",
+            node_key,
+            fastn_runtime::server::html::attrs(None, dom.style(node_key)),
+            dom.memory.get_string(self.text).as_str(),
+        )
     }
 }
 
@@ -423,8 +578,8 @@ mod test {
             fastn_runtime::Document::new(create_columns()),
             indoc::indoc!(
                 r#"
-            <div data-id="1v1" > <div data-id=4294967298 > <div data-id=4294967299 ></div>
-            <div data-id=4294967300 ></div></div></div>"#
+            <div data-id=4294967297 class="ft_column" style="width: 100%; height: 100%; gap: 20px"> <div data-id=4294967298 class="ft_column" style="height: 500px; margin: 140px; gap: 100px"> <div data-id=4294967299 class="ft_column" style="width: 84px; height: 80px"></div>
+            <div data-id=4294967300 class="ft_column" style="width: 84px; height: 80px"></div></div></div>"#
             ),
         )
     }
@@ -435,13 +590,13 @@ mod test {
         let k1 = i32s.insert(10);
         let k2 = i32s.insert(20);
         let k3 = i32s.insert(30);
-        assert_eq!(fastn_runtime::html::node_key_to_id(k1), "4294967297");
-        assert_eq!(fastn_runtime::html::node_key_to_id(k2), "4294967298");
-        assert_eq!(fastn_runtime::html::node_key_to_id(k3), "4294967299");
+        assert_eq!(fastn_runtime::node_key_to_id(k1), "4294967297");
+        assert_eq!(fastn_runtime::node_key_to_id(k2), "4294967298");
+        assert_eq!(fastn_runtime::node_key_to_id(k3), "4294967299");
         let mut bools: slotmap::SlotMap<fastn_runtime::NodeKey, bool> =
             slotmap::SlotMap::with_key();
         assert_eq!(
-            fastn_runtime::html::node_key_to_id(bools.insert(false)),
+            fastn_runtime::node_key_to_id(bools.insert(false)),
             "4294967297"
         );
     }
@@ -61,6 +61,12 @@ impl fastn_runtime::Dom {
                 fastn_wasm::Type::I32.into(),
                 fastn_wasm::Type::ExternRef.into(),
             ),
+            fastn_wasm::import::func3(
+                "set_text",
+                fastn_wasm::Type::ExternRef.into(),
+                fastn_wasm::Type::I32.into(),
+                fastn_wasm::Type::ExternRef.into(),
+            ),
         ]);
         e
     }
@@ -75,6 +81,10 @@ impl fastn_runtime::Dom {
             "create_kernel",
             |dom: &mut fastn_runtime::Dom, parent, kind| dom.create_kernel(parent, kind),
         );
+        linker.func3_result(
+            "set_text",
+            |dom: &mut fastn_runtime::Dom, key, kind, ptr| dom.set_text(key, kind, ptr),
+        );
         linker.func3(
             "set_property_i32",
             |dom: &mut fastn_runtime::Dom, key, property_kind, value| {
@@ -206,6 +216,17 @@ impl fastn_runtime::Memory {
                 fastn_wasm::Type::ExternRef.into(),
                 fastn_wasm::Type::ExternRef,
             ),
+            fastn_wasm::import::func3(
+                "list_push",
+                fastn_wasm::Type::ExternRef.into(),
+                fastn_wasm::Type::I32.into(),
+                fastn_wasm::Type::ExternRef.into(),
+            ),
+            fastn_wasm::import::func1ret(
+                "list_len",
+                fastn_wasm::Type::ExternRef.into(),
+                fastn_wasm::Type::I32,
+            ),
             fastn_wasm::import::func1ret(
                 "get_boolean",
                 fastn_wasm::Type::ExternRef.into(),
@@ -265,6 +286,22 @@ impl fastn_runtime::Memory {
                 fastn_wasm::Type::I32.into(),
                 fastn_wasm::Type::ExternRef,
             ),
+            fastn_wasm::import::func2ret(
+                "concat_string",
+                fastn_wasm::Type::ExternRef.into(),
+                fastn_wasm::Type::ExternRef.into(),
+                fastn_wasm::Type::ExternRef,
+            ),
+            fastn_wasm::import::func1ret(
+                "copy_string",
+                fastn_wasm::Type::ExternRef.into(),
+                fastn_wasm::Type::ExternRef,
+            ),
+            fastn_wasm::import::func2(
+                "set_string",
+                fastn_wasm::Type::ExternRef.into(),
+                fastn_wasm::Type::ExternRef.into(),
+            ),
             fastn_wasm::import::func2ret(
                 "array_i32_2",
                 fastn_wasm::Type::ExternRef.into(),
@@ -313,10 +350,27 @@ impl fastn_runtime::Memory {
                 mem.create_list_2(v1_kind, v1_ptr, v2_kind, v2_ptr)
             },
         );
+        linker.func3(
+            "list_push",
+            |mem: &mut fastn_runtime::Memory, list, kind, ptr| mem.list_push(list, kind, ptr),
+        );
+        linker.func1ret("list_len", |mem: &mut fastn_runtime::Memory, list| {
+            mem.list_len(list)
+        });
+        linker.func2ret("concat_string", |mem: &mut fastn_runtime::Memory, a, b| {
+            mem.concat_string(a, b)
+        });
+        linker.func1ret("copy_string", |mem: &mut fastn_runtime::Memory, ptr| {
+            mem.copy_string(ptr)
+        });
+        linker.func2(
+            "set_string",
+            |mem: &mut fastn_runtime::Memory, ptr, value| mem.set_string(ptr, value),
+        );
         linker.func2ret_caller(
             "create_string_constant",
             |mut caller: wasmtime::Caller<'_, fastn_runtime::Dom>, start: i32, length: i32| {
-                let mut buffer = Vec::with_capacity(length as usize);
+                let mut buffer = vec![0; length as usize];
                 caller
                     .get_export("memory")
                     .unwrap()
@@ -500,6 +554,7 @@ mod test {
         assert_import("create_kernel", "(param externref i32) (result externref)");
         assert_import("set_property_i32", "(param externref i32 i32)");
         assert_import("set_property_f32", "(param externref i32 f32)");
+        assert_import("set_text", "(param externref i32 externref)");
         assert_import(
             "set_dynamic_property_i32",
             "(param externref i32 i32 externref)",
@@ -527,6 +582,8 @@ mod test {
             "create_list_2",
             "(param i32 externref i32 externref) (result externref)",
         );
+        assert_import("list_push", "(param externref i32 externref)");
+        assert_import("list_len", "(param externref) (result i32)");
         assert_import("create_boolean", "(param i32) (result externref)");
         assert_import("get_boolean", "(param externref) (result i32)");
         assert_import("set_boolean", "(param externref i32)");
@@ -540,6 +597,12 @@ mod test {
         assert_import("create_f32", "(param f32) (result externref)");
         assert_import("get_f32", "(param externref) (result f32)");
         assert_import("set_f32", "(param externref f32)");
+        assert_import(
+            "concat_string",
+            "(param externref externref) (result externref)",
+        );
+        assert_import("copy_string", "(param externref) (result externref)");
+        assert_import("set_string", "(param externref externref)");
         assert_import(
             "array_i32_2",
             "(param externref externref) (result externref)",
@@ -7,6 +7,7 @@ pub enum Ast {
     Memory(fastn_wasm::Memory),
     Elem(fastn_wasm::Elem),
     FuncDef(fastn_wasm::FuncDef),
+    Data(fastn_wasm::Data),
 }
 
 impl Ast {
@@ -19,6 +20,7 @@ impl Ast {
             Ast::Memory(m) => m.to_doc(),
             Ast::Elem(g) => g.to_doc(),
             Ast::FuncDef(g) => g.to_doc(),
+            Ast::Data(d) => d.to_doc(),
         }
     }
 }
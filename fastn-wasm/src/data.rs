@@ -0,0 +1,40 @@
+/// An active data segment of the memory 0, the `data` is copied to the memory at the `offset` when
+/// the module is instantiated.
+#[derive(Debug)]
+pub struct Data {
+    pub offset: u32,
+    pub data: Vec<u8>,
+}
+
+impl Data {
+    pub fn to_doc(&self) -> pretty::RcDoc<'static> {
+        let data: String = self.data.iter().map(|b| format!("\\{:02x}", b)).collect();
+        fastn_wasm::group(
+            "data".to_string(),
+            Some(pretty::RcDoc::text(format!("(i32.const {})", self.offset))),
+            pretty::RcDoc::text(format!("\"{}\"", data)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[track_caller]
+    fn e(f: super::Data, s: &str) {
+        let g = fastn_wasm::encode(&[fastn_wasm::Ast::Data(f)]);
+        println!("got: {}", g);
+        println!("expected: {}", s);
+        assert_eq!(g, s);
+    }
+
+    #[test]
+    fn test() {
+        e(
+            super::Data {
+                offset: 16,
+                data: "hi\n".as_bytes().to_vec(),
+            },
+            r#"(module (data (i32.const 16) "\68\69\0a"))"#,
+        );
+    }
+}
@@ -23,7 +23,7 @@ impl Elem {
 mod test {
     #[track_caller]
     fn e(f: super::Elem, s: &str) {
-        let g = fastn_wasm::encode(&vec![fastn_wasm::Ast::Elem(f)]);
+        let g = fastn_wasm::encode(&[fastn_wasm::Ast::Elem(f)]);
         println!("got: {}", g);
         println!("expected: {}", s);
         assert_eq!(g, s);
@@ -0,0 +1,768 @@
+/// Encodes the module to the binary format, <https://webassembly.github.io/spec/core/binary/>,
+/// the module is validated first, see `fastn_wasm::validate()`.
+///
+/// The names of the functions are kept in the `name` custom section, for the stack traces.
+pub fn encode_binary(module: &[fastn_wasm::Ast]) -> Result<Vec<u8>, fastn_wasm::ValidationError> {
+    let m = fastn_wasm::validator::Module::new(module)?;
+    m.validate()?;
+
+    let mut o = b"\0asm".to_vec();
+    o.extend([1, 0, 0, 0]);
+
+    section(&mut o, 1, m.types.len(), |s| {
+        for t in m.types.iter() {
+            s.push(0x60);
+            vec(s, t.params.len(), |s| {
+                t.params.iter().for_each(|p| ty(s, p))
+            });
+            vec(s, t.result.iter().count(), |s| {
+                t.result.iter().for_each(|r| ty(s, r))
+            });
+        }
+    });
+
+    let imports: Vec<_> = module
+        .iter()
+        .filter_map(|a| match a {
+            fastn_wasm::Ast::Import(i) => Some(i),
+            _ => None,
+        })
+        .collect();
+    let mut imported_funcs = 0;
+    section(&mut o, 2, imports.len(), |s| {
+        for i in imports.iter() {
+            name(s, &i.module);
+            name(s, &i.name);
+            match &i.desc {
+                fastn_wasm::ImportDesc::Func(_) => {
+                    s.push(0x00);
+                    u32(s, m.funcs[imported_funcs]);
+                    imported_funcs += 1;
+                }
+                fastn_wasm::ImportDesc::Table(t) => {
+                    s.push(0x01);
+                    table(s, t);
+                }
+                fastn_wasm::ImportDesc::Memory(memory) => {
+                    s.push(0x02);
+                    limits(s, &memory.limits, memory.shared);
+                }
+            }
+        }
+    });
+
+    let defined = &m.funcs[imported_funcs..];
+    section(&mut o, 3, defined.len(), |s| {
+        defined.iter().for_each(|t| u32(s, *t))
+    });
+
+    let tables: Vec<_> = module
+        .iter()
+        .filter_map(|a| match a {
+            fastn_wasm::Ast::Table(t) => Some(t),
+            _ => None,
+        })
+        .collect();
+    section(&mut o, 4, tables.len(), |s| {
+        tables.iter().for_each(|t| table(s, t))
+    });
+
+    let memories: Vec<_> = module
+        .iter()
+        .filter_map(|a| match a {
+            fastn_wasm::Ast::Memory(memory) => Some(memory),
+            _ => None,
+        })
+        .collect();
+    section(&mut o, 5, memories.len(), |s| {
+        memories
+            .iter()
+            .for_each(|memory| limits(s, &memory.limits, memory.shared))
+    });
+
+    let exports = m.exports()?;
+    section(&mut o, 7, exports.len(), |s| {
+        for (export, desc) in exports.iter() {
+            name(s, export);
+            match desc {
+                fastn_wasm::validator::Extern::Func(index) => {
+                    s.push(0x00);
+                    u32(s, *index);
+                }
+                fastn_wasm::validator::Extern::Memory(index) => {
+                    s.push(0x02);
+                    u32(s, *index);
+                }
+            }
+        }
+    });
+
+    let mut elems = vec![];
+    for elem in m.elems() {
+        elems.push((elem.start, m.elem_indices(elem)?));
+    }
+    section(&mut o, 9, elems.len(), |s| {
+        for (start, indices) in elems.iter() {
+            // an active segment of the table 0, at the offset `(i32.const start)`
+            s.push(0x00);
+            s.push(0x41);
+            i64(s, *start as i64);
+            s.push(0x0B);
+            vec(s, indices.len(), |s| {
+                indices.iter().for_each(|i| u32(s, *i))
+            });
+        }
+    });
+
+    let mut code = vec![];
+    for (index, func) in m.defined_funcs() {
+        let context = m.func_context(func, index);
+        let mut body = vec![];
+        let locals = compress_locals(&func.locals);
+        vec(&mut body, locals.len(), |s| {
+            for (count, t) in locals.iter() {
+                u32(s, *count);
+                ty(s, t);
+            }
+        });
+        for e in func.body.iter() {
+            expression(&mut body, &m, func, e, context.as_str())?;
+        }
+        body.push(0x0B);
+        code.push(body);
+    }
+    section(&mut o, 10, code.len(), |s| {
+        for body in code.iter() {
+            u32(s, body.len() as u32);
+            s.extend(body);
+        }
+    });
+
+    let data: Vec<_> = m.data().collect();
+    section(&mut o, 11, data.len(), |s| {
+        for d in data.iter() {
+            // an active segment of the memory 0, at the offset `(i32.const offset)`
+            s.push(0x00);
+            s.push(0x41);
+            i64(s, d.offset as i64);
+            s.push(0x0B);
+            vec(s, d.data.len(), |s| s.extend(d.data.iter()));
+        }
+    });
+
+    if !m.func_names.is_empty() {
+        let mut names = vec![];
+        name(&mut names, "name");
+        names.push(0x01);
+        let mut function_names = vec![];
+        vec(&mut function_names, m.func_names.len(), |s| {
+            for (index, func) in m.func_names.iter() {
+                u32(s, *index);
+                name(s, func);
+            }
+        });
+        u32(&mut names, function_names.len() as u32);
+        names.extend(function_names);
+        o.push(0x00);
+        u32(&mut o, names.len() as u32);
+        o.extend(names);
+    }
+
+    Ok(o)
+}
+
+fn expression(
+    o: &mut Vec<u8>,
+    m: &fastn_wasm::validator::Module,
+    func: &fastn_wasm::Func,
+    e: &fastn_wasm::Expression,
+    context: &str,
+) -> Result<(), fastn_wasm::ValidationError> {
+    match e {
+        fastn_wasm::Expression::GlobalSet { index, .. } => {
+            return Err(fastn_wasm::ValidationError::UnknownGlobal {
+                context: context.to_string(),
+                index: index.to_wat(),
+            })
+        }
+        fastn_wasm::Expression::LocalSet { index, value } => {
+            expression(o, m, func, value, context)?;
+            o.push(0x21);
+            u32(o, m.local(func, index, context)?.0);
+        }
+        fastn_wasm::Expression::LocalGet { index } => {
+            o.push(0x20);
+            u32(o, m.local(func, index, context)?.0);
+        }
+        fastn_wasm::Expression::I32Const(v) => {
+            o.push(0x41);
+            i64(o, *v as i64);
+        }
+        fastn_wasm::Expression::I64Const(v) => {
+            o.push(0x42);
+            i64(o, *v);
+        }
+        fastn_wasm::Expression::F32Const(v) => {
+            o.push(0x43);
+            o.extend(v.to_le_bytes());
+        }
+        fastn_wasm::Expression::F64Const(v) => {
+            o.push(0x44);
+            o.extend(v.to_le_bytes());
+        }
+        fastn_wasm::Expression::Operation { name, values } => {
+            for v in values {
+                expression(o, m, func, v, context)?;
+            }
+            let instruction = fastn_wasm::instruction::instruction(name).ok_or_else(|| {
+                fastn_wasm::ValidationError::UnknownInstruction {
+                    context: context.to_string(),
+                    name: name.to_string(),
+                }
+            })?;
+            o.push(instruction.opcode);
+        }
+        fastn_wasm::Expression::Call { name, params } => {
+            for p in params {
+                expression(o, m, func, p, context)?;
+            }
+            o.push(0x10);
+            u32(
+                o,
+                m.func_index(&fastn_wasm::Index::Variable(name.to_string()), context)?,
+            );
+        }
+        fastn_wasm::Expression::CallIndirect { type_, params } => {
+            for p in params {
+                expression(o, m, func, p, context)?;
+            }
+            o.push(0x11);
+            u32(o, m.type_index(type_, context)?);
+            // the table 0
+            o.push(0x00);
+        }
+        fastn_wasm::Expression::Drop => o.push(0x1A),
+        fastn_wasm::Expression::If {
+            condition,
+            then,
+            else_,
+        } => {
+            expression(o, m, func, condition, context)?;
+            // `if` with the empty block type
+            o.extend([0x04, 0x40]);
+            for e in then {
+                expression(o, m, func, e, context)?;
+            }
+            if !else_.is_empty() {
+                o.push(0x05);
+                for e in else_ {
+                    expression(o, m, func, e, context)?;
+                }
+            }
+            o.push(0x0B);
+        }
+        fastn_wasm::Expression::While { condition, body } => {
+            // block, loop, exit the block if the condition is zero, run the body, back to the loop
+            o.extend([0x02, 0x40, 0x03, 0x40]);
+            expression(o, m, func, condition, context)?;
+            o.extend([0x45, 0x0D, 0x01]);
+            for e in body {
+                expression(o, m, func, e, context)?;
+            }
+            o.extend([0x0C, 0x00, 0x0B, 0x0B]);
+        }
+    }
+    Ok(())
+}
+
+/// The locals are encoded as runs of the same type.
+fn compress_locals(locals: &[fastn_wasm::PL]) -> Vec<(u32, &fastn_wasm::Type)> {
+    let mut runs: Vec<(u32, &fastn_wasm::Type)> = vec![];
+    for local in locals {
+        match runs.last_mut() {
+            Some((count, t)) if *t == &local.ty => *count += 1,
+            _ => runs.push((1, &local.ty)),
+        }
+    }
+    runs
+}
+
+/// Appends the section, unless it is empty, the content is prefixed by its size.
+fn section(o: &mut Vec<u8>, id: u8, count: usize, f: impl FnOnce(&mut Vec<u8>)) {
+    if count == 0 {
+        return;
+    }
+    let mut content = vec![];
+    vec(&mut content, count, f);
+    o.push(id);
+    u32(o, content.len() as u32);
+    o.extend(content);
+}
+
+fn vec(o: &mut Vec<u8>, count: usize, f: impl FnOnce(&mut Vec<u8>)) {
+    u32(o, count as u32);
+    f(o);
+}
+
+fn name(o: &mut Vec<u8>, name: &str) {
+    u32(o, name.len() as u32);
+    o.extend(name.as_bytes());
+}
+
+fn ty(o: &mut Vec<u8>, t: &fastn_wasm::Type) {
+    o.push(match t {
+        fastn_wasm::Type::I32 => 0x7F,
+        fastn_wasm::Type::I64 => 0x7E,
+        fastn_wasm::Type::F32 => 0x7D,
+        fastn_wasm::Type::F64 => 0x7C,
+        fastn_wasm::Type::FuncRef => 0x70,
+        fastn_wasm::Type::ExternRef => 0x6F,
+        // rejected by the validator
+        fastn_wasm::Type::Void | fastn_wasm::Type::EmptyBlockType => 0x40,
+    })
+}
+
+fn table(o: &mut Vec<u8>, t: &fastn_wasm::Table) {
+    o.push(match t.ref_type {
+        fastn_wasm::RefType::Func => 0x70,
+        fastn_wasm::RefType::Extern => 0x6F,
+    });
+    limits(o, &t.limits, false);
+}
+
+fn limits(o: &mut Vec<u8>, limits: &fastn_wasm::Limits, shared: bool) {
+    match limits.max {
+        Some(max) => {
+            o.push(if shared { 0x03 } else { 0x01 });
+            u32(o, limits.min);
+            u32(o, max);
+        }
+        None => {
+            o.push(0x00);
+            u32(o, limits.min);
+        }
+    }
+}
+
+/// Unsigned LEB128.
+fn u32(o: &mut Vec<u8>, mut v: u32) {
+    loop {
+        let byte = (v & 0x7F) as u8;
+        v >>= 7;
+        if v == 0 {
+            o.push(byte);
+            return;
+        }
+        o.push(byte | 0x80);
+    }
+}
+
+/// Signed LEB128, the `i32` constants are encoded the same way.
+fn i64(o: &mut Vec<u8>, mut v: i64) {
+    loop {
+        let byte = (v & 0x7F) as u8;
+        v >>= 7;
+        if (v == 0 && byte & 0x40 == 0) || (v == -1 && byte & 0x40 != 0) {
+            o.push(byte);
+            return;
+        }
+        o.push(byte | 0x80);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    fn add() -> Vec<fastn_wasm::Ast> {
+        vec![
+            fastn_wasm::import::func2ret(
+                "mul",
+                fastn_wasm::Type::I32.into(),
+                fastn_wasm::Type::I32.into(),
+                fastn_wasm::Type::I32,
+            ),
+            fastn_wasm::Ast::Memory(fastn_wasm::Memory {
+                limits: fastn_wasm::Limits {
+                    min: 1,
+                    max: Some(2),
+                },
+                shared: false,
+            }),
+            fastn_wasm::func_def::func2ret(
+                "binary",
+                fastn_wasm::Type::I32.into(),
+                fastn_wasm::Type::I32.into(),
+                fastn_wasm::Type::I32,
+            ),
+            fastn_wasm::Func {
+                name: Some("add".to_string()),
+                export: Some("add".to_string()),
+                params: vec![
+                    fastn_wasm::Type::I32.to_pl("lhs"),
+                    fastn_wasm::Type::I32.to_pl("rhs"),
+                ],
+                result: Some(fastn_wasm::Type::I32),
+                body: vec![fastn_wasm::expression::operation_2(
+                    "i32.add",
+                    fastn_wasm::expression::local("lhs"),
+                    fastn_wasm::expression::local("rhs"),
+                )],
+                ..Default::default()
+            }
+            .to_ast(),
+            fastn_wasm::Func {
+                export: Some("apply".to_string()),
+                params: vec![
+                    fastn_wasm::Type::I32.to_pl("fn_idx"),
+                    fastn_wasm::Type::I64.to_pl("value"),
+                ],
+                locals: vec![
+                    fastn_wasm::Type::I32.to_pl("a"),
+                    fastn_wasm::Type::I32.to_pl("b"),
+                    fastn_wasm::Type::F64.to_pl("c"),
+                ],
+                result: Some(fastn_wasm::Type::I32),
+                body: vec![
+                    fastn_wasm::expression::local_set(
+                        "a",
+                        fastn_wasm::Expression::Operation {
+                            name: "i32.wrap_i64".to_string(),
+                            values: vec![fastn_wasm::expression::local("value")],
+                        },
+                    ),
+                    fastn_wasm::expression::local_set("c", fastn_wasm::Expression::F64Const(-1.5)),
+                    fastn_wasm::expression::local_set(
+                        "b",
+                        fastn_wasm::expression::call2(
+                            "mul",
+                            fastn_wasm::expression::local("a"),
+                            fastn_wasm::expression::i32(-300),
+                        ),
+                    ),
+                    fastn_wasm::Expression::CallIndirect {
+                        type_: "binary".to_string(),
+                        params: vec![
+                            fastn_wasm::expression::local("a"),
+                            fastn_wasm::expression::local("b"),
+                            fastn_wasm::expression::local("fn_idx"),
+                        ],
+                    },
+                ],
+                ..Default::default()
+            }
+            .to_ast(),
+            fastn_wasm::Func {
+                export: Some("sum".to_string()),
+                params: vec![fastn_wasm::Type::I32.to_pl("n")],
+                locals: vec![
+                    fastn_wasm::Type::I32.to_pl("i"),
+                    fastn_wasm::Type::I32.to_pl("acc"),
+                ],
+                result: Some(fastn_wasm::Type::I32),
+                body: vec![
+                    // acc = 0 + 1 + .. + n - 1, doubled if it is more than 10
+                    fastn_wasm::expression::while_(
+                        fastn_wasm::expression::operation_2(
+                            "i32.lt_s",
+                            fastn_wasm::expression::local("i"),
+                            fastn_wasm::expression::local("n"),
+                        ),
+                        vec![
+                            fastn_wasm::expression::local_set(
+                                "acc",
+                                fastn_wasm::expression::operation_2(
+                                    "i32.add",
+                                    fastn_wasm::expression::local("acc"),
+                                    fastn_wasm::expression::local("i"),
+                                ),
+                            ),
+                            fastn_wasm::expression::local_set(
+                                "i",
+                                fastn_wasm::expression::operation_2(
+                                    "i32.add",
+                                    fastn_wasm::expression::local("i"),
+                                    fastn_wasm::expression::i32(1),
+                                ),
+                            ),
+                        ],
+                    ),
+                    fastn_wasm::expression::if_(
+                        fastn_wasm::expression::operation_2(
+                            "i32.gt_s",
+                            fastn_wasm::expression::local("acc"),
+                            fastn_wasm::expression::i32(10),
+                        ),
+                        vec![fastn_wasm::expression::local_set(
+                            "acc",
+                            fastn_wasm::expression::operation_2(
+                                "i32.mul",
+                                fastn_wasm::expression::local("acc"),
+                                fastn_wasm::expression::i32(2),
+                            ),
+                        )],
+                        vec![],
+                    ),
+                    fastn_wasm::expression::local("acc"),
+                ],
+                ..Default::default()
+            }
+            .to_ast(),
+            fastn_wasm::Ast::Export(fastn_wasm::Export {
+                name: "plus".to_string(),
+                desc: fastn_wasm::ExportDesc::Func {
+                    index: "add".into(),
+                },
+            }),
+            fastn_wasm::Ast::Export(fastn_wasm::Export {
+                name: "memory".to_string(),
+                desc: fastn_wasm::ExportDesc::Memory { index: 0 },
+            }),
+            fastn_wasm::Ast::Data(fastn_wasm::Data {
+                offset: 8,
+                data: b"hello".to_vec(),
+            }),
+        ]
+        .into_iter()
+        .chain(fastn_wasm::table_2(fastn_wasm::RefType::Func, "add", "mul"))
+        .collect()
+    }
+
+    fn instantiate(module: &wasmtime::Module) -> (wasmtime::Store<()>, wasmtime::Instance) {
+        let mut store = wasmtime::Store::new(module.engine(), ());
+        let mut linker = wasmtime::Linker::new(module.engine());
+        linker
+            .func_wrap("fastn", "mul", |a: i32, b: i32| a.wrapping_mul(b))
+            .unwrap();
+        let instance = linker.instantiate(&mut store, module).unwrap();
+        (store, instance)
+    }
+
+    #[test]
+    fn round_trip() {
+        let module = add();
+        let engine = wasmtime::Engine::default();
+        let binary =
+            wasmtime::Module::new(&engine, fastn_wasm::encode_binary(&module).unwrap()).unwrap();
+        let text = wasmtime::Module::new(&engine, fastn_wasm::encode(&module)).unwrap();
+
+        let imports = |m: &wasmtime::Module| {
+            m.imports()
+                .map(|i| format!("{} {} {:?}", i.module(), i.name(), i.ty()))
+                .collect::<Vec<_>>()
+        };
+        let exports = |m: &wasmtime::Module| {
+            m.exports()
+                .map(|e| format!("{} {:?}", e.name(), e.ty()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(imports(&binary), imports(&text));
+        assert_eq!(exports(&binary), exports(&text));
+        assert_eq!(
+            binary.exports().map(|e| e.name()).collect::<Vec<_>>(),
+            vec!["add", "apply", "sum", "plus", "memory"]
+        );
+
+        for m in [&binary, &text] {
+            let (mut store, instance) = instantiate(m);
+            let add = instance
+                .get_typed_func::<(i32, i32), i32>(&mut store, "plus")
+                .unwrap();
+            assert_eq!(add.call(&mut store, (2, 3)).unwrap(), 5);
+            let apply = instance
+                .get_typed_func::<(i32, i64), i32>(&mut store, "apply")
+                .unwrap();
+            // the table is [add, mul], apply(fn_idx, value) = fn_idx(value, value * -300)
+            assert_eq!(apply.call(&mut store, (0, 2)).unwrap(), -598);
+            assert_eq!(apply.call(&mut store, (1, 2)).unwrap(), -1200);
+            let sum = instance
+                .get_typed_func::<i32, i32>(&mut store, "sum")
+                .unwrap();
+            assert_eq!(sum.call(&mut store, 0).unwrap(), 0);
+            assert_eq!(sum.call(&mut store, 4).unwrap(), 6);
+            assert_eq!(sum.call(&mut store, 6).unwrap(), 30);
+            let memory = instance.get_memory(&mut store, "memory").unwrap();
+            assert_eq!(&memory.data(&store)[8..13], b"hello");
+        }
+    }
+
+    #[test]
+    fn leb128() {
+        let e = |v: i64| {
+            let mut o = vec![];
+            super::i64(&mut o, v);
+            o
+        };
+        assert_eq!(e(0), vec![0x00]);
+        assert_eq!(e(63), vec![0x3F]);
+        assert_eq!(e(64), vec![0xC0, 0x00]);
+        assert_eq!(e(-1), vec![0x7F]);
+        assert_eq!(e(-64), vec![0x40]);
+        assert_eq!(e(-65), vec![0xBF, 0x7F]);
+        let mut o = vec![];
+        super::u32(&mut o, 624485);
+        assert_eq!(o, vec![0xE5, 0x8E, 0x26]);
+    }
+
+    #[track_caller]
+    fn e(module: Vec<fastn_wasm::Ast>, error: &str) {
+        let g = fastn_wasm::encode_binary(&module).unwrap_err().to_string();
+        println!("got: {}", g);
+        println!("expected: {}", error);
+        assert_eq!(g, error);
+    }
+
+    #[test]
+    fn validation_errors() {
+        let func = |body: Vec<fastn_wasm::Expression>| {
+            fastn_wasm::Func {
+                name: Some("f".to_string()),
+                params: vec![fastn_wasm::Type::I32.to_pl("x")],
+                result: Some(fastn_wasm::Type::I32),
+                body,
+                ..Default::default()
+            }
+            .to_ast()
+        };
+
+        e(
+            vec![func(vec![fastn_wasm::expression::local("y")])],
+            "func $f: unknown local `$y`",
+        );
+        e(
+            vec![func(vec![fastn_wasm::expression::call("g")])],
+            "func $f: unknown function `$g`",
+        );
+        e(
+            vec![func(vec![fastn_wasm::Expression::Operation {
+                name: "i32.plus".to_string(),
+                values: vec![],
+            }])],
+            "func $f: unknown instruction `i32.plus`",
+        );
+        e(
+            vec![func(vec![fastn_wasm::expression::operation_2(
+                "i32.add",
+                fastn_wasm::expression::local("x"),
+                fastn_wasm::Expression::I64Const(1),
+            )])],
+            "func $f: `i32.add` expects `i32`, found `i64`",
+        );
+        e(
+            vec![func(vec![fastn_wasm::Expression::Operation {
+                name: "i32.add".to_string(),
+                values: vec![fastn_wasm::expression::local("x")],
+            }])],
+            "func $f: `i32.add` expects `i32`, the stack is empty",
+        );
+        e(
+            vec![func(vec![
+                fastn_wasm::expression::local("x"),
+                fastn_wasm::expression::local("x"),
+            ])],
+            "func $f: the body leaves [i32 i32] on the stack, the result is [i32]",
+        );
+        e(
+            vec![func(vec![fastn_wasm::Expression::Drop])],
+            "func $f: `drop` expects a value, the stack is empty",
+        );
+        e(
+            vec![func(vec![fastn_wasm::expression::call_indirect2(
+                "binary",
+                fastn_wasm::expression::local("x"),
+                fastn_wasm::expression::local("x"),
+            )])],
+            "func $f: unknown type `$binary`",
+        );
+        e(
+            vec![
+                fastn_wasm::func_def::func1("unary", fastn_wasm::Type::I32.into()),
+                func(vec![fastn_wasm::expression::call_indirect2(
+                    "unary",
+                    fastn_wasm::expression::local("x"),
+                    fastn_wasm::expression::local("x"),
+                )]),
+            ],
+            "func $f: no `funcref` table, declare or import one",
+        );
+        e(
+            vec![func(vec![fastn_wasm::expression::if_(
+                fastn_wasm::expression::local("x"),
+                vec![fastn_wasm::expression::local("x")],
+                vec![],
+            )])],
+            "func $f: `then`: the body leaves [i32] on the stack, the result is []",
+        );
+        e(
+            vec![func(vec![
+                fastn_wasm::expression::while_(fastn_wasm::Expression::F32Const(1.0), vec![]),
+                fastn_wasm::expression::local("x"),
+            ])],
+            "func $f: `while` condition: the body leaves [f32] on the stack, the result is [i32]",
+        );
+        e(vec![func(vec![]), func(vec![])], "duplicate function `$f`");
+        e(
+            vec![fastn_wasm::Func {
+                export: Some("main".to_string()),
+                params: vec![fastn_wasm::Type::Void.into()],
+                ..Default::default()
+            }
+            .to_ast()],
+            "func (export \"main\"): `void` is not a value type",
+        );
+        e(
+            vec![
+                func(vec![fastn_wasm::expression::local("x")]),
+                fastn_wasm::Ast::Export(fastn_wasm::Export {
+                    name: "f".to_string(),
+                    desc: fastn_wasm::ExportDesc::Func { index: 1.into() },
+                }),
+            ],
+            "export \"f\": unknown function `1`",
+        );
+        e(
+            vec![fastn_wasm::Ast::Memory(fastn_wasm::Memory {
+                limits: fastn_wasm::Limits { min: 1, max: None },
+                shared: true,
+            })],
+            "memory: a shared memory must have a max",
+        );
+        e(
+            vec![fastn_wasm::Ast::Memory(fastn_wasm::Memory {
+                limits: fastn_wasm::Limits {
+                    min: 70000,
+                    max: None,
+                },
+                shared: false,
+            })],
+            "memory: 70000 pages, a memory can have at most 65536 pages",
+        );
+        e(
+            vec![fastn_wasm::Ast::Data(fastn_wasm::Data {
+                offset: 0,
+                data: b"hello".to_vec(),
+            })],
+            "data: no memory, declare or import one",
+        );
+        e(
+            vec![fastn_wasm::Ast::Export(fastn_wasm::Export {
+                name: "memory".to_string(),
+                desc: fastn_wasm::ExportDesc::Memory { index: 0 },
+            })],
+            "export \"memory\": unknown memory `0`",
+        );
+        e(
+            fastn_wasm::table_1(fastn_wasm::RefType::Func, "g"),
+            "elem: unknown function `$g`",
+        );
+        e(
+            vec![
+                func(vec![fastn_wasm::expression::local("x")]),
+                fastn_wasm::table(1, fastn_wasm::RefType::Func),
+                fastn_wasm::Ast::Elem(fastn_wasm::Elem {
+                    start: 1,
+                    fns: vec!["f".to_string()],
+                }),
+            ],
+            "elem: 1 functions at 1 do not fit in the table of 1",
+        );
+    }
+}
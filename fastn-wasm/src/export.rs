@@ -30,12 +30,16 @@ impl Export {
 #[derive(Debug)]
 pub enum ExportDesc {
     Func { index: fastn_wasm::Index },
+    Memory { index: u32 },
 }
 
 impl ExportDesc {
     pub fn to_doc(&self) -> pretty::RcDoc<'static> {
         match self {
             ExportDesc::Func { index } => fastn_wasm::named("func", Some(index.to_doc())),
+            ExportDesc::Memory { index } => {
+                fastn_wasm::named("memory", Some(pretty::RcDoc::text(index.to_string())))
+            }
         }
     }
 }
@@ -44,7 +48,7 @@ impl ExportDesc {
 mod test {
     #[track_caller]
     fn e(f: fastn_wasm::Export, s: &str) {
-        let g = fastn_wasm::encode(&vec![fastn_wasm::Ast::Export(f)]);
+        let g = fastn_wasm::encode(&[fastn_wasm::Ast::Export(f)]);
         println!("got: {}", g);
         println!("expected: {}", s);
         assert_eq!(g, s);
@@ -61,5 +65,12 @@ mod test {
             },
             r#"(module (export "add" (func $add)))"#,
         );
+        e(
+            fastn_wasm::Export {
+                name: "memory".to_string(),
+                desc: fastn_wasm::ExportDesc::Memory { index: 0 },
+            },
+            r#"(module (export "memory" (memory 0)))"#,
+        );
     }
 }
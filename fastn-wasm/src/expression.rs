@@ -28,6 +28,18 @@ pub enum Expression {
         params: Vec<Expression>,
     },
     Drop,
+    /// `if` without a result, `else_` is left out of the WAT when it is empty.
+    If {
+        condition: Box<Expression>,
+        then: Vec<Expression>,
+        else_: Vec<Expression>,
+    },
+    /// Runs the `body` as long as the `condition` is not zero, the `condition` is checked before
+    /// each run. It is a `loop` in a `block`, wasm does not have a `while`.
+    While {
+        condition: Box<Expression>,
+        body: Vec<Expression>,
+    },
 }
 
 pub fn call(name: &str) -> fastn_wasm::Expression {
@@ -74,6 +86,28 @@ pub fn call_indirect2(
     }
 }
 
+pub fn if_(
+    condition: fastn_wasm::Expression,
+    then: Vec<fastn_wasm::Expression>,
+    else_: Vec<fastn_wasm::Expression>,
+) -> fastn_wasm::Expression {
+    fastn_wasm::Expression::If {
+        condition: Box::new(condition),
+        then,
+        else_,
+    }
+}
+
+pub fn while_(
+    condition: fastn_wasm::Expression,
+    body: Vec<fastn_wasm::Expression>,
+) -> fastn_wasm::Expression {
+    fastn_wasm::Expression::While {
+        condition: Box::new(condition),
+        body,
+    }
+}
+
 pub fn call1(name: &str, e0: fastn_wasm::Expression) -> fastn_wasm::Expression {
     fastn_wasm::Expression::Call {
         name: name.into(),
@@ -185,10 +219,61 @@ impl Expression {
                 }
             }
             Expression::Drop => pretty::RcDoc::text("(drop)"),
+            Expression::If {
+                condition,
+                then,
+                else_,
+            } => {
+                let mut v = vec![condition.to_doc(), block("then", then)];
+                if !else_.is_empty() {
+                    v.push(block("else", else_));
+                }
+                fastn_wasm::group(
+                    "if".to_string(),
+                    None,
+                    pretty::RcDoc::intersperse(v, pretty::RcDoc::line()),
+                )
+                .nest(4)
+            }
+            Expression::While { condition, body } => {
+                let exit = fastn_wasm::group(
+                    "br_if".to_string(),
+                    Some(pretty::RcDoc::text("1")),
+                    fastn_wasm::group("i32.eqz".to_string(), None, condition.to_doc()),
+                );
+                let body = std::iter::once(exit)
+                    .chain(body.iter().map(|v| v.to_doc()))
+                    .chain(std::iter::once(fastn_wasm::named(
+                        "br",
+                        Some(pretty::RcDoc::text("0")),
+                    )));
+                fastn_wasm::group(
+                    "block".to_string(),
+                    None,
+                    fastn_wasm::group(
+                        "loop".to_string(),
+                        None,
+                        pretty::RcDoc::intersperse(body, pretty::RcDoc::line()),
+                    )
+                    .nest(4),
+                )
+            }
         }
     }
 }
 
+fn block(kind: &'static str, body: &[Expression]) -> pretty::RcDoc<'static> {
+    if body.is_empty() {
+        return fastn_wasm::named(kind, None);
+    }
+    fastn_wasm::group(
+        kind.to_string(),
+        None,
+        pretty::RcDoc::intersperse(body.iter().map(|v| v.to_doc()), pretty::RcDoc::line()),
+    )
+    .nest(4)
+}
+
 #[derive(Debug, Clone)]
 pub enum Index {
     Index(i32),
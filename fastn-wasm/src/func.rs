@@ -110,7 +110,7 @@ mod test {
 
     #[track_caller]
     fn e(f: Func, s: &str) {
-        let g = fastn_wasm::encode(&vec![fastn_wasm::Ast::Func(f)]);
+        let g = fastn_wasm::encode(&[fastn_wasm::Ast::Func(f)]);
         println!("got: {}", g);
         println!("expected: {}", s);
         assert_eq!(g, s);
@@ -345,5 +345,39 @@ mod test {
                         (drop)))"#
             ),
         );
+        e(
+            Func {
+                params: vec![fastn_wasm::Type::I32.to_pl("n")],
+                body: vec![
+                    fastn_wasm::expression::while_(
+                        fastn_wasm::expression::local("n"),
+                        vec![fastn_wasm::expression::local_set(
+                            "n",
+                            fastn_wasm::expression::operation_2(
+                                "i32.sub",
+                                fastn_wasm::expression::local("n"),
+                                fastn_wasm::expression::i32(1),
+                            ),
+                        )],
+                    ),
+                    fastn_wasm::expression::if_(
+                        fastn_wasm::expression::local("n"),
+                        vec![fastn_wasm::expression::call("foo")],
+                        vec![fastn_wasm::expression::call("bar")],
+                    ),
+                ],
+                ..Default::default()
+            },
+            indoc::indoc!(
+                r#"
+                (module (func (param $n i32)
+                        (block (loop (br_if 1 (i32.eqz (local.get $n)))
+                            (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                            (br 0)))
+                        (if (local.get $n)
+                            (then (call $foo))
+                            (else (call $bar)))))"#
+            ),
+        );
     }
 }
@@ -1,7 +1,7 @@
 #[derive(Debug)]
 pub struct FuncDef {
-    name: String,
-    decl: fastn_wasm::FuncDecl,
+    pub name: String,
+    pub decl: fastn_wasm::FuncDecl,
 }
 
 pub fn func_def(
@@ -50,7 +50,7 @@ impl FuncDef {
 mod test {
     #[track_caller]
     fn e(f: fastn_wasm::Ast, s: &str) {
-        let g = fastn_wasm::encode(&vec![f]);
+        let g = fastn_wasm::encode(&[f]);
         println!("got: {}", g);
         println!("expected: {}", s);
         assert_eq!(g, s);
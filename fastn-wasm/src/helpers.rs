@@ -94,6 +94,12 @@ pub trait LinkerExt<S> {
         name: &'static str,
         func: impl Fn(&mut SE, T1, T2, T3) + Send + Sync + 'static,
     );
+    /// `func3` for the functions that can fail, the error is a trap of the guest.
+    fn func3_result<SE: StoreExtractor<Parent = S>, T1: WasmType, T2: WasmType, T3: WasmType>(
+        &mut self,
+        name: &'static str,
+        func: impl Fn(&mut SE, T1, T2, T3) -> wasmtime::Result<()> + Send + Sync + 'static,
+    );
     fn func4<
         SE: StoreExtractor<Parent = S>,
         T1: WasmType,
@@ -246,6 +252,32 @@ impl<S> LinkerExt<S> for wasmtime::Linker<S> {
         )
         .unwrap();
     }
+    fn func3_result<SE: StoreExtractor<Parent = S>, T1: WasmType, T2: WasmType, T3: WasmType>(
+        &mut self,
+        name: &'static str,
+        func: impl Fn(&mut SE, T1, T2, T3) -> wasmtime::Result<()> + Send + Sync + 'static,
+    ) {
+        self.func_new(
+            "fastn",
+            name,
+            wasmtime::FuncType::new(
+                [T1::the_type(), T2::the_type(), T3::the_type()]
+                    .iter()
+                    .cloned(),
+                [].iter().cloned(),
+            ),
+            move |mut caller: wasmtime::Caller<'_, S>, params, _results| {
+                println!("fastn.{}", name);
+                func(
+                    SE::extract(&mut caller),
+                    T1::extract(0, params),
+                    T2::extract(1, params),
+                    T3::extract(2, params),
+                )
+            },
+        )
+        .unwrap();
+    }
     fn func4<
         SE: StoreExtractor<Parent = S>,
         T1: WasmType,
@@ -126,7 +126,7 @@ mod test {
 
     #[track_caller]
     fn e(f: Import, s: &str) {
-        let g = fastn_wasm::encode(&vec![fastn_wasm::Ast::Import(f)]);
+        let g = fastn_wasm::encode(&[fastn_wasm::Ast::Import(f)]);
         println!("got: {}", g);
         println!("expected: {}", s);
         assert_eq!(g, s);
@@ -0,0 +1,155 @@
+use fastn_wasm::Type::{F32, F64, I32, I64};
+
+/// An instruction of `fastn_wasm::Expression::Operation`, by its WAT name.
+#[derive(Debug)]
+pub(crate) struct Instruction {
+    pub(crate) opcode: u8,
+    pub(crate) params: &'static [fastn_wasm::Type],
+    pub(crate) result: Option<fastn_wasm::Type>,
+}
+
+pub(crate) fn instruction(name: &str) -> Option<Instruction> {
+    INSTRUCTIONS
+        .iter()
+        .find(|(n, ..)| n.eq(&name))
+        .map(|(_, opcode, params, result)| Instruction {
+            opcode: *opcode,
+            params,
+            result: result.clone(),
+        })
+}
+
+type Row = (
+    &'static str,
+    u8,
+    &'static [fastn_wasm::Type],
+    Option<fastn_wasm::Type>,
+);
+
+// the numeric instructions, <https://webassembly.github.io/spec/core/binary/instructions.html>
+const INSTRUCTIONS: &[Row] = &[
+    ("nop", 0x01, &[], None),
+    ("i32.eqz", 0x45, &[I32], Some(I32)),
+    ("i32.eq", 0x46, &[I32, I32], Some(I32)),
+    ("i32.ne", 0x47, &[I32, I32], Some(I32)),
+    ("i32.lt_s", 0x48, &[I32, I32], Some(I32)),
+    ("i32.lt_u", 0x49, &[I32, I32], Some(I32)),
+    ("i32.gt_s", 0x4A, &[I32, I32], Some(I32)),
+    ("i32.gt_u", 0x4B, &[I32, I32], Some(I32)),
+    ("i32.le_s", 0x4C, &[I32, I32], Some(I32)),
+    ("i32.le_u", 0x4D, &[I32, I32], Some(I32)),
+    ("i32.ge_s", 0x4E, &[I32, I32], Some(I32)),
+    ("i32.ge_u", 0x4F, &[I32, I32], Some(I32)),
+    ("i64.eqz", 0x50, &[I64], Some(I32)),
+    ("i64.eq", 0x51, &[I64, I64], Some(I32)),
+    ("i64.ne", 0x52, &[I64, I64], Some(I32)),
+    ("i64.lt_s", 0x53, &[I64, I64], Some(I32)),
+    ("i64.lt_u", 0x54, &[I64, I64], Some(I32)),
+    ("i64.gt_s", 0x55, &[I64, I64], Some(I32)),
+    ("i64.gt_u", 0x56, &[I64, I64], Some(I32)),
+    ("i64.le_s", 0x57, &[I64, I64], Some(I32)),
+    ("i64.le_u", 0x58, &[I64, I64], Some(I32)),
+    ("i64.ge_s", 0x59, &[I64, I64], Some(I32)),
+    ("i64.ge_u", 0x5A, &[I64, I64], Some(I32)),
+    ("f32.eq", 0x5B, &[F32, F32], Some(I32)),
+    ("f32.ne", 0x5C, &[F32, F32], Some(I32)),
+    ("f32.lt", 0x5D, &[F32, F32], Some(I32)),
+    ("f32.gt", 0x5E, &[F32, F32], Some(I32)),
+    ("f32.le", 0x5F, &[F32, F32], Some(I32)),
+    ("f32.ge", 0x60, &[F32, F32], Some(I32)),
+    ("f64.eq", 0x61, &[F64, F64], Some(I32)),
+    ("f64.ne", 0x62, &[F64, F64], Some(I32)),
+    ("f64.lt", 0x63, &[F64, F64], Some(I32)),
+    ("f64.gt", 0x64, &[F64, F64], Some(I32)),
+    ("f64.le", 0x65, &[F64, F64], Some(I32)),
+    ("f64.ge", 0x66, &[F64, F64], Some(I32)),
+    ("i32.clz", 0x67, &[I32], Some(I32)),
+    ("i32.ctz", 0x68, &[I32], Some(I32)),
+    ("i32.popcnt", 0x69, &[I32], Some(I32)),
+    ("i32.add", 0x6A, &[I32, I32], Some(I32)),
+    ("i32.sub", 0x6B, &[I32, I32], Some(I32)),
+    ("i32.mul", 0x6C, &[I32, I32], Some(I32)),
+    ("i32.div_s", 0x6D, &[I32, I32], Some(I32)),
+    ("i32.div_u", 0x6E, &[I32, I32], Some(I32)),
+    ("i32.rem_s", 0x6F, &[I32, I32], Some(I32)),
+    ("i32.rem_u", 0x70, &[I32, I32], Some(I32)),
+    ("i32.and", 0x71, &[I32, I32], Some(I32)),
+    ("i32.or", 0x72, &[I32, I32], Some(I32)),
+    ("i32.xor", 0x73, &[I32, I32], Some(I32)),
+    ("i32.shl", 0x74, &[I32, I32], Some(I32)),
+    ("i32.shr_s", 0x75, &[I32, I32], Some(I32)),
+    ("i32.shr_u", 0x76, &[I32, I32], Some(I32)),
+    ("i32.rotl", 0x77, &[I32, I32], Some(I32)),
+    ("i32.rotr", 0x78, &[I32, I32], Some(I32)),
+    ("i64.clz", 0x79, &[I64], Some(I64)),
+    ("i64.ctz", 0x7A, &[I64], Some(I64)),
+    ("i64.popcnt", 0x7B, &[I64], Some(I64)),
+    ("i64.add", 0x7C, &[I64, I64], Some(I64)),
+    ("i64.sub", 0x7D, &[I64, I64], Some(I64)),
+    ("i64.mul", 0x7E, &[I64, I64], Some(I64)),
+    ("i64.div_s", 0x7F, &[I64, I64], Some(I64)),
+    ("i64.div_u", 0x80, &[I64, I64], Some(I64)),
+    ("i64.rem_s", 0x81, &[I64, I64], Some(I64)),
+    ("i64.rem_u", 0x82, &[I64, I64], Some(I64)),
+    ("i64.and", 0x83, &[I64, I64], Some(I64)),
+    ("i64.or", 0x84, &[I64, I64], Some(I64)),
+    ("i64.xor", 0x85, &[I64, I64], Some(I64)),
+    ("i64.shl", 0x86, &[I64, I64], Some(I64)),
+    ("i64.shr_s", 0x87, &[I64, I64], Some(I64)),
+    ("i64.shr_u", 0x88, &[I64, I64], Some(I64)),
+    ("i64.rotl", 0x89, &[I64, I64], Some(I64)),
+    ("i64.rotr", 0x8A, &[I64, I64], Some(I64)),
+    ("f32.abs", 0x8B, &[F32], Some(F32)),
+    ("f32.neg", 0x8C, &[F32], Some(F32)),
+    ("f32.ceil", 0x8D, &[F32], Some(F32)),
+    ("f32.floor", 0x8E, &[F32], Some(F32)),
+    ("f32.trunc", 0x8F, &[F32], Some(F32)),
+    ("f32.nearest", 0x90, &[F32], Some(F32)),
+    ("f32.sqrt", 0x91, &[F32], Some(F32)),
+    ("f32.add", 0x92, &[F32, F32], Some(F32)),
+    ("f32.sub", 0x93, &[F32, F32], Some(F32)),
+    ("f32.mul", 0x94, &[F32, F32], Some(F32)),
+    ("f32.div", 0x95, &[F32, F32], Some(F32)),
+    ("f32.min", 0x96, &[F32, F32], Some(F32)),
+    ("f32.max", 0x97, &[F32, F32], Some(F32)),
+    ("f32.copysign", 0x98, &[F32, F32], Some(F32)),
+    ("f64.abs", 0x99, &[F64], Some(F64)),
+    ("f64.neg", 0x9A, &[F64], Some(F64)),
+    ("f64.ceil", 0x9B, &[F64], Some(F64)),
+    ("f64.floor", 0x9C, &[F64], Some(F64)),
+    ("f64.trunc", 0x9D, &[F64], Some(F64)),
+    ("f64.nearest", 0x9E, &[F64], Some(F64)),
+    ("f64.sqrt", 0x9F, &[F64], Some(F64)),
+    ("f64.add", 0xA0, &[F64, F64], Some(F64)),
+    ("f64.sub", 0xA1, &[F64, F64], Some(F64)),
+    ("f64.mul", 0xA2, &[F64, F64], Some(F64)),
+    ("f64.div", 0xA3, &[F64, F64], Some(F64)),
+    ("f64.min", 0xA4, &[F64, F64], Some(F64)),
+    ("f64.max", 0xA5, &[F64, F64], Some(F64)),
+    ("f64.copysign", 0xA6, &[F64, F64], Some(F64)),
+    ("i32.wrap_i64", 0xA7, &[I64], Some(I32)),
+    ("i32.trunc_f32_s", 0xA8, &[F32], Some(I32)),
+    ("i32.trunc_f32_u", 0xA9, &[F32], Some(I32)),
+    ("i32.trunc_f64_s", 0xAA, &[F64], Some(I32)),
+    ("i32.trunc_f64_u", 0xAB, &[F64], Some(I32)),
+    ("i64.extend_i32_s", 0xAC, &[I32], Some(I64)),
+    ("i64.extend_i32_u", 0xAD, &[I32], Some(I64)),
+    ("i64.trunc_f32_s", 0xAE, &[F32], Some(I64)),
+    ("i64.trunc_f32_u", 0xAF, &[F32], Some(I64)),
+    ("i64.trunc_f64_s", 0xB0, &[F64], Some(I64)),
+    ("i64.trunc_f64_u", 0xB1, &[F64], Some(I64)),
+    ("f32.convert_i32_s", 0xB2, &[I32], Some(F32)),
+    ("f32.convert_i32_u", 0xB3, &[I32], Some(F32)),
+    ("f32.convert_i64_s", 0xB4, &[I64], Some(F32)),
+    ("f32.convert_i64_u", 0xB5, &[I64], Some(F32)),
+    ("f32.demote_f64", 0xB6, &[F64], Some(F32)),
+    ("f64.convert_i32_s", 0xB7, &[I32], Some(F64)),
+    ("f64.convert_i32_u", 0xB8, &[I32], Some(F64)),
+    ("f64.convert_i64_s", 0xB9, &[I64], Some(F64)),
+    ("f64.convert_i64_u", 0xBA, &[I64], Some(F64)),
+    ("f64.promote_f32", 0xBB, &[F32], Some(F64)),
+    ("i32.reinterpret_f32", 0xBC, &[F32], Some(I32)),
+    ("i64.reinterpret_f64", 0xBD, &[F64], Some(I64)),
+    ("f32.reinterpret_i32", 0xBE, &[I32], Some(F32)),
+    ("f64.reinterpret_i64", 0xBF, &[I64], Some(F64)),
+];
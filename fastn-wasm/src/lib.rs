@@ -2,21 +2,26 @@ extern crate core;
 extern crate self as fastn_wasm;
 
 mod ast;
-// mod encoder;
+mod data;
 mod elem;
+mod encoder;
 pub mod export;
 pub mod expression;
 mod func;
 pub mod func_def;
 mod helpers;
 pub mod import;
+mod instruction;
 mod memory;
 mod pl;
 mod table;
 mod ty;
+mod validator;
 
 pub use ast::Ast;
+pub use data::Data;
 pub use elem::Elem;
+pub use encoder::encode_binary;
 pub use export::{Export, ExportDesc};
 pub use expression::{Expression, Index};
 pub use func::{Func, FuncDecl};
@@ -27,6 +32,7 @@ pub use memory::Memory;
 pub use pl::PL;
 pub use table::{table, table_1, table_2, table_3, table_4, Limits, RefType, Table};
 pub use ty::Type;
+pub use validator::{validate, ValidationError};
 
 pub fn named<'a>(kind: &'static str, name: Option<pretty::RcDoc<'a, ()>>) -> pretty::RcDoc<'a, ()> {
     let mut g1 = pretty::RcDoc::text("(").append(kind);
@@ -24,7 +24,7 @@ impl Memory {
 mod test {
     #[track_caller]
     fn e(f: fastn_wasm::Memory, s: &str) {
-        let g = fastn_wasm::encode(&vec![fastn_wasm::Ast::Memory(f)]);
+        let g = fastn_wasm::encode(&[fastn_wasm::Ast::Memory(f)]);
         println!("got: {}", g);
         println!("expected: {}", s);
         assert_eq!(g, s);
@@ -93,10 +93,7 @@ impl Limits {
 
     pub fn to_wat(&self) -> String {
         let min_wat = self.min.to_string();
-        let max_wat = self
-            .max
-            .map(|max| format!(" {}", max))
-            .unwrap_or(String::new());
+        let max_wat = self.max.map(|max| format!(" {}", max)).unwrap_or_default();
         format!("{}{}", min_wat, max_wat)
     }
 }
@@ -123,7 +120,7 @@ impl RefType {
 mod test {
     #[track_caller]
     fn e(f: fastn_wasm::Table, s: &str) {
-        let g = fastn_wasm::encode(&vec![fastn_wasm::Ast::Table(f)]);
+        let g = fastn_wasm::encode(&[fastn_wasm::Ast::Table(f)]);
         println!("got: {}", g);
         println!("expected: {}", s);
         assert_eq!(g, s);
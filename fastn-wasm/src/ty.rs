@@ -1,4 +1,4 @@
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Type {
     I32,
     I64,
@@ -18,7 +18,11 @@ impl Type {
         }
     }
     pub fn to_doc(&self) -> pretty::RcDoc<'static> {
-        pretty::RcDoc::text(match self {
+        pretty::RcDoc::text(self.to_wat())
+    }
+
+    pub fn to_wat(&self) -> &'static str {
+        match self {
             Type::I32 => "i32",
             Type::I64 => "i64",
             Type::F32 => "f32",
@@ -27,6 +31,6 @@ impl Type {
             Type::Void => "void",
             Type::FuncRef => "funcref",
             Type::EmptyBlockType => "empty_block_type",
-        })
+        }
     }
 }
@@ -0,0 +1,618 @@
+/// Validates the structure of the module: the names resolve, the types of the params, locals and
+/// results are value types, the limits are in range, and the body of each function type checks
+/// with the operands of the instructions.
+///
+/// `fastn_wasm::encode_binary()` validates the module before encoding it.
+pub fn validate(module: &[fastn_wasm::Ast]) -> Result<(), ValidationError> {
+    Module::new(module)?.validate()
+}
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum ValidationError {
+    #[error("duplicate {kind} `${name}`")]
+    DuplicateName { kind: &'static str, name: String },
+
+    #[error("duplicate export \"{name}\"")]
+    DuplicateExport { name: String },
+
+    #[error("{context}: unknown function `{index}`")]
+    UnknownFunction { context: String, index: String },
+
+    #[error("{context}: unknown type `{index}`")]
+    UnknownType { context: String, index: String },
+
+    #[error("{context}: unknown local `{index}`")]
+    UnknownLocal { context: String, index: String },
+
+    #[error("{context}: unknown global `{index}`, the module has no globals")]
+    UnknownGlobal { context: String, index: String },
+
+    #[error("{context}: unknown instruction `{name}`")]
+    UnknownInstruction { context: String, name: String },
+
+    #[error("{context}: `{ty}` is not a value type")]
+    InvalidValueType { context: String, ty: String },
+
+    #[error("{context}: `{instruction}` expects {expected}, found `{found}`")]
+    TypeMismatch {
+        context: String,
+        instruction: String,
+        expected: String,
+        found: String,
+    },
+
+    #[error("{context}: `{instruction}` expects {expected}, the stack is empty")]
+    StackUnderflow {
+        context: String,
+        instruction: String,
+        expected: String,
+    },
+
+    #[error("{context}: the body leaves [{found}] on the stack, the result is [{expected}]")]
+    ResultMismatch {
+        context: String,
+        expected: String,
+        found: String,
+    },
+
+    #[error("{context}: no `funcref` table, declare or import one")]
+    MissingTable { context: String },
+
+    #[error("{context}: no memory, declare or import one")]
+    MissingMemory { context: String },
+
+    #[error("{context}: unknown memory `{index}`")]
+    UnknownMemory { context: String, index: u32 },
+
+    #[error("{context}: the min {min} is more than the max {max}")]
+    InvalidLimits { context: String, min: u32, max: u32 },
+
+    #[error("{context}: {pages} pages, a memory can have at most 65536 pages")]
+    MemoryTooLarge { context: String, pages: u32 },
+
+    #[error("{context}: a shared memory must have a max")]
+    SharedMemoryWithoutMax { context: String },
+
+    #[error("found {count} memories, a module can have at most one")]
+    MultipleMemories { count: usize },
+
+    #[error("elem: {count} functions at {start} do not fit in the table of {size}")]
+    ElemOutOfBounds { start: u32, count: usize, size: u32 },
+}
+
+/// What an export refers to, by its index.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Extern {
+    Func(u32),
+    Memory(u32),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Signature {
+    pub(crate) params: Vec<fastn_wasm::Type>,
+    pub(crate) result: Option<fastn_wasm::Type>,
+}
+
+/// The index spaces of the module, an `Ast` refers to the types and the functions by name, the
+/// binary format by index.
+pub(crate) struct Module<'a> {
+    pub(crate) ast: &'a [fastn_wasm::Ast],
+    /// the `FuncDef`s, in order, and then the signatures of the functions that do not match one
+    pub(crate) types: Vec<Signature>,
+    type_names: std::collections::HashMap<&'a str, u32>,
+    /// the type of each function, the imported functions come first
+    pub(crate) funcs: Vec<u32>,
+    /// the names of the functions, by index
+    pub(crate) func_names: Vec<(u32, &'a str)>,
+    func_indices: std::collections::HashMap<&'a str, u32>,
+    /// the tables, and whether they are imported
+    tables: Vec<(&'a fastn_wasm::Table, bool)>,
+    memories: Vec<(&'a fastn_wasm::Memory, String)>,
+}
+
+impl<'a> Module<'a> {
+    pub(crate) fn new(ast: &'a [fastn_wasm::Ast]) -> Result<Module<'a>, ValidationError> {
+        let mut m = Module {
+            ast,
+            types: vec![],
+            type_names: Default::default(),
+            funcs: vec![],
+            func_names: vec![],
+            func_indices: Default::default(),
+            tables: vec![],
+            memories: vec![],
+        };
+
+        for a in ast {
+            if let fastn_wasm::Ast::FuncDef(d) = a {
+                let context = format!("type ${}", d.name);
+                let signature = signature(&d.decl.params, &d.decl.result, context.as_str())?;
+                if m.type_names
+                    .insert(d.name.as_str(), m.types.len() as u32)
+                    .is_some()
+                {
+                    return Err(ValidationError::DuplicateName {
+                        kind: "type",
+                        name: d.name.to_string(),
+                    });
+                }
+                m.types.push(signature);
+            }
+        }
+
+        // the imports come first in the index spaces, wherever they are in the module
+        for a in ast {
+            if let fastn_wasm::Ast::Import(i) = a {
+                let context = format!("import \"{}\" \"{}\"", i.module, i.name);
+                match &i.desc {
+                    fastn_wasm::ImportDesc::Func(decl) => m.add_func(
+                        decl.name.as_deref(),
+                        signature(&decl.params, &decl.result, context.as_str())?,
+                    )?,
+                    fastn_wasm::ImportDesc::Table(t) => m.tables.push((t, true)),
+                    fastn_wasm::ImportDesc::Memory(memory) => m.memories.push((memory, context)),
+                }
+            }
+        }
+
+        for a in ast {
+            match a {
+                fastn_wasm::Ast::Func(f) => {
+                    let context = m.func_context(f, m.funcs.len() as u32);
+                    m.add_func(
+                        f.name.as_deref(),
+                        signature(&f.params, &f.result, context.as_str())?,
+                    )?
+                }
+                fastn_wasm::Ast::Table(t) => m.tables.push((t, false)),
+                fastn_wasm::Ast::Memory(memory) => m.memories.push((memory, "memory".to_string())),
+                _ => {}
+            }
+        }
+
+        Ok(m)
+    }
+
+    fn add_func(
+        &mut self,
+        name: Option<&'a str>,
+        signature: Signature,
+    ) -> Result<(), ValidationError> {
+        let index = self.funcs.len() as u32;
+        if let Some(name) = name {
+            if self.func_indices.insert(name, index).is_some() {
+                return Err(ValidationError::DuplicateName {
+                    kind: "function",
+                    name: name.to_string(),
+                });
+            }
+            self.func_names.push((index, name));
+        }
+        let ty = match self.types.iter().position(|t| t == &signature) {
+            Some(ty) => ty,
+            None => {
+                self.types.push(signature);
+                self.types.len() - 1
+            }
+        };
+        self.funcs.push(ty as u32);
+        Ok(())
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), ValidationError> {
+        for (table, imported) in self.tables.iter() {
+            let context = if *imported { "imported table" } else { "table" };
+            limits(&table.limits, context)?;
+        }
+
+        if self.memories.len() > 1 {
+            return Err(ValidationError::MultipleMemories {
+                count: self.memories.len(),
+            });
+        }
+        for (memory, context) in self.memories.iter() {
+            limits(&memory.limits, context)?;
+            for pages in std::iter::once(memory.limits.min).chain(memory.limits.max) {
+                if pages > 65536 {
+                    return Err(ValidationError::MemoryTooLarge {
+                        context: context.to_string(),
+                        pages,
+                    });
+                }
+            }
+            if memory.shared && memory.limits.max.is_none() {
+                return Err(ValidationError::SharedMemoryWithoutMax {
+                    context: context.to_string(),
+                });
+            }
+        }
+
+        self.exports()?;
+
+        if self.data().next().is_some() && self.memories.is_empty() {
+            return Err(ValidationError::MissingMemory {
+                context: "data".to_string(),
+            });
+        }
+
+        for elem in self.elems() {
+            let (table, imported) = self.table("elem")?;
+            self.elem_indices(elem)?;
+            // the size of an imported table is only known when the module is instantiated
+            if !imported && elem.start as usize + elem.fns.len() > table.limits.min as usize {
+                return Err(ValidationError::ElemOutOfBounds {
+                    start: elem.start,
+                    count: elem.fns.len(),
+                    size: table.limits.min,
+                });
+            }
+        }
+
+        for (index, func) in self.defined_funcs() {
+            self.check_func(func, self.func_context(func, index).as_str())?;
+        }
+
+        Ok(())
+    }
+
+    /// The functions defined in the module, with their index.
+    pub(crate) fn defined_funcs(&self) -> impl Iterator<Item = (u32, &'a fastn_wasm::Func)> {
+        let imported = self.funcs.len() - self.ast_funcs().count();
+        self.ast_funcs()
+            .enumerate()
+            .map(move |(i, f)| ((imported + i) as u32, f))
+    }
+
+    fn ast_funcs(&self) -> impl Iterator<Item = &'a fastn_wasm::Func> {
+        self.ast.iter().filter_map(|a| match a {
+            fastn_wasm::Ast::Func(f) => Some(f),
+            _ => None,
+        })
+    }
+
+    pub(crate) fn elems(&self) -> impl Iterator<Item = &'a fastn_wasm::Elem> {
+        self.ast.iter().filter_map(|a| match a {
+            fastn_wasm::Ast::Elem(e) => Some(e),
+            _ => None,
+        })
+    }
+
+    pub(crate) fn data(&self) -> impl Iterator<Item = &'a fastn_wasm::Data> {
+        self.ast.iter().filter_map(|a| match a {
+            fastn_wasm::Ast::Data(d) => Some(d),
+            _ => None,
+        })
+    }
+
+    /// The exports, the inline exports of the functions and the `Export`s, in order.
+    pub(crate) fn exports(&self) -> Result<Vec<(&'a str, Extern)>, ValidationError> {
+        let mut exports: Vec<(&'a str, Extern)> = vec![];
+        let mut defined = self.defined_funcs();
+        for a in self.ast {
+            let export = match a {
+                fastn_wasm::Ast::Func(f) => {
+                    let (index, _) = defined.next().expect("function index");
+                    match f.export.as_deref() {
+                        Some(name) => (name, Extern::Func(index)),
+                        None => continue,
+                    }
+                }
+                fastn_wasm::Ast::Export(e) => {
+                    let context = format!("export \"{}\"", e.name);
+                    match &e.desc {
+                        fastn_wasm::ExportDesc::Func { index } => (
+                            e.name.as_str(),
+                            Extern::Func(self.func_index(index, &context)?),
+                        ),
+                        fastn_wasm::ExportDesc::Memory { index } => {
+                            if *index as usize >= self.memories.len() {
+                                return Err(ValidationError::UnknownMemory {
+                                    context,
+                                    index: *index,
+                                });
+                            }
+                            (e.name.as_str(), Extern::Memory(*index))
+                        }
+                    }
+                }
+                _ => continue,
+            };
+            if exports.iter().any(|(name, _)| *name == export.0) {
+                return Err(ValidationError::DuplicateExport {
+                    name: export.0.to_string(),
+                });
+            }
+            exports.push(export);
+        }
+        Ok(exports)
+    }
+
+    pub(crate) fn elem_indices(
+        &self,
+        elem: &fastn_wasm::Elem,
+    ) -> Result<Vec<u32>, ValidationError> {
+        elem.fns
+            .iter()
+            .map(|name| self.func_index(&fastn_wasm::Index::Variable(name.to_string()), "elem"))
+            .collect()
+    }
+
+    /// The first table, `call_indirect` and `elem` use it, it must be a `funcref` table.
+    fn table(&self, context: &str) -> Result<(&'a fastn_wasm::Table, bool), ValidationError> {
+        match self.tables.first() {
+            Some((table, imported)) if matches!(table.ref_type, fastn_wasm::RefType::Func) => {
+                Ok((table, *imported))
+            }
+            _ => Err(ValidationError::MissingTable {
+                context: context.to_string(),
+            }),
+        }
+    }
+
+    pub(crate) fn func_index(
+        &self,
+        index: &fastn_wasm::Index,
+        context: &str,
+    ) -> Result<u32, ValidationError> {
+        match index {
+            fastn_wasm::Index::Index(i) if *i >= 0 && (*i as usize) < self.funcs.len() => {
+                Ok(*i as u32)
+            }
+            fastn_wasm::Index::Variable(name) if self.func_indices.contains_key(name.as_str()) => {
+                Ok(self.func_indices[name.as_str()])
+            }
+            _ => Err(ValidationError::UnknownFunction {
+                context: context.to_string(),
+                index: index.to_wat(),
+            }),
+        }
+    }
+
+    pub(crate) fn type_index(&self, name: &str, context: &str) -> Result<u32, ValidationError> {
+        self.type_names
+            .get(name)
+            .copied()
+            .ok_or_else(|| ValidationError::UnknownType {
+                context: context.to_string(),
+                index: format!("${name}"),
+            })
+    }
+
+    /// The index of a local, the params come first, and its type.
+    pub(crate) fn local(
+        &self,
+        func: &'a fastn_wasm::Func,
+        index: &fastn_wasm::Index,
+        context: &str,
+    ) -> Result<(u32, &'a fastn_wasm::Type), ValidationError> {
+        let mut locals = func.params.iter().chain(func.locals.iter());
+        let found = match index {
+            fastn_wasm::Index::Index(i) if *i >= 0 => {
+                locals.nth(*i as usize).map(|pl| (*i as u32, &pl.ty))
+            }
+            fastn_wasm::Index::Index(_) => None,
+            fastn_wasm::Index::Variable(name) => locals
+                .enumerate()
+                .find(|(_, pl)| pl.name.as_ref() == Some(name))
+                .map(|(i, pl)| (i as u32, &pl.ty)),
+        };
+        found.ok_or_else(|| ValidationError::UnknownLocal {
+            context: context.to_string(),
+            index: index.to_wat(),
+        })
+    }
+
+    pub(crate) fn func_context(&self, func: &fastn_wasm::Func, index: u32) -> String {
+        match (&func.name, &func.export) {
+            (Some(name), _) => format!("func ${name}"),
+            (None, Some(export)) => format!("func (export \"{export}\")"),
+            (None, None) => format!("func #{index}"),
+        }
+    }
+
+    fn check_func(&self, func: &'a fastn_wasm::Func, context: &str) -> Result<(), ValidationError> {
+        for local in func.locals.iter() {
+            value_type(&local.ty, context)?;
+        }
+
+        let expected: Vec<_> = func.result.iter().cloned().collect();
+        self.check_block(func, &func.body, &expected, context)
+    }
+
+    /// A block starts with an empty stack, and has to leave the `expected` types on it.
+    fn check_block(
+        &self,
+        func: &'a fastn_wasm::Func,
+        body: &[fastn_wasm::Expression],
+        expected: &[fastn_wasm::Type],
+        context: &str,
+    ) -> Result<(), ValidationError> {
+        let mut stack = vec![];
+        for e in body.iter() {
+            self.check_expression(func, e, &mut stack, context)?;
+        }
+
+        if stack != expected {
+            return Err(ValidationError::ResultMismatch {
+                context: context.to_string(),
+                expected: type_list(expected),
+                found: type_list(&stack),
+            });
+        }
+        Ok(())
+    }
+
+    /// Simulates the operand stack, the operands of a folded expression are pushed before the
+    /// instruction pops them, the way the instruction sequence it encodes to runs.
+    fn check_expression(
+        &self,
+        func: &'a fastn_wasm::Func,
+        e: &fastn_wasm::Expression,
+        stack: &mut Vec<fastn_wasm::Type>,
+        context: &str,
+    ) -> Result<(), ValidationError> {
+        match e {
+            fastn_wasm::Expression::GlobalSet { index, .. } => {
+                return Err(ValidationError::UnknownGlobal {
+                    context: context.to_string(),
+                    index: index.to_wat(),
+                })
+            }
+            fastn_wasm::Expression::LocalSet { index, value } => {
+                let (_, ty) = self.local(func, index, context)?;
+                self.check_expression(func, value, stack, context)?;
+                pop(stack, ty, "local.set", context)?;
+            }
+            fastn_wasm::Expression::LocalGet { index } => {
+                let (_, ty) = self.local(func, index, context)?;
+                stack.push(ty.clone());
+            }
+            fastn_wasm::Expression::I32Const(_) => stack.push(fastn_wasm::Type::I32),
+            fastn_wasm::Expression::I64Const(_) => stack.push(fastn_wasm::Type::I64),
+            fastn_wasm::Expression::F32Const(_) => stack.push(fastn_wasm::Type::F32),
+            fastn_wasm::Expression::F64Const(_) => stack.push(fastn_wasm::Type::F64),
+            fastn_wasm::Expression::Operation { name, values } => {
+                let instruction = fastn_wasm::instruction::instruction(name).ok_or_else(|| {
+                    ValidationError::UnknownInstruction {
+                        context: context.to_string(),
+                        name: name.to_string(),
+                    }
+                })?;
+                for v in values {
+                    self.check_expression(func, v, stack, context)?;
+                }
+                pop_all(stack, instruction.params, name, context)?;
+                stack.extend(instruction.result);
+            }
+            fastn_wasm::Expression::Call { name, params } => {
+                let index =
+                    self.func_index(&fastn_wasm::Index::Variable(name.to_string()), context)?;
+                let signature = &self.types[self.funcs[index as usize] as usize];
+                for p in params {
+                    self.check_expression(func, p, stack, context)?;
+                }
+                pop_all(stack, &signature.params, &format!("call ${name}"), context)?;
+                stack.extend(signature.result.clone());
+            }
+            fastn_wasm::Expression::CallIndirect { type_, params } => {
+                let signature = &self.types[self.type_index(type_, context)? as usize];
+                self.table(context)?;
+                for p in params {
+                    self.check_expression(func, p, stack, context)?;
+                }
+                let instruction = format!("call_indirect (type ${type_})");
+                pop(stack, &fastn_wasm::Type::I32, &instruction, context)?;
+                pop_all(stack, &signature.params, &instruction, context)?;
+                stack.extend(signature.result.clone());
+            }
+            fastn_wasm::Expression::Drop => {
+                if stack.pop().is_none() {
+                    return Err(ValidationError::StackUnderflow {
+                        context: context.to_string(),
+                        instruction: "drop".to_string(),
+                        expected: "a value".to_string(),
+                    });
+                }
+            }
+            fastn_wasm::Expression::If {
+                condition,
+                then,
+                else_,
+            } => {
+                self.check_expression(func, condition, stack, context)?;
+                pop(stack, &fastn_wasm::Type::I32, "if", context)?;
+                self.check_block(func, then, &[], &format!("{context}: `then`"))?;
+                self.check_block(func, else_, &[], &format!("{context}: `else`"))?;
+            }
+            fastn_wasm::Expression::While { condition, body } => {
+                self.check_block(
+                    func,
+                    std::slice::from_ref(condition),
+                    &[fastn_wasm::Type::I32],
+                    &format!("{context}: `while` condition"),
+                )?;
+                self.check_block(func, body, &[], &format!("{context}: `while`"))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn signature(
+    params: &[fastn_wasm::PL],
+    result: &Option<fastn_wasm::Type>,
+    context: &str,
+) -> Result<Signature, ValidationError> {
+    for ty in params.iter().map(|p| &p.ty).chain(result.iter()) {
+        value_type(ty, context)?;
+    }
+    Ok(Signature {
+        params: params.iter().map(|p| p.ty.clone()).collect(),
+        result: result.clone(),
+    })
+}
+
+fn value_type(ty: &fastn_wasm::Type, context: &str) -> Result<(), ValidationError> {
+    match ty {
+        fastn_wasm::Type::Void | fastn_wasm::Type::EmptyBlockType => {
+            Err(ValidationError::InvalidValueType {
+                context: context.to_string(),
+                ty: ty.to_wat().to_string(),
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+fn limits(limits: &fastn_wasm::Limits, context: &str) -> Result<(), ValidationError> {
+    match limits.max {
+        Some(max) if max < limits.min => Err(ValidationError::InvalidLimits {
+            context: context.to_string(),
+            min: limits.min,
+            max,
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn pop_all(
+    stack: &mut Vec<fastn_wasm::Type>,
+    params: &[fastn_wasm::Type],
+    instruction: &str,
+    context: &str,
+) -> Result<(), ValidationError> {
+    for ty in params.iter().rev() {
+        pop(stack, ty, instruction, context)?;
+    }
+    Ok(())
+}
+
+fn pop(
+    stack: &mut Vec<fastn_wasm::Type>,
+    expected: &fastn_wasm::Type,
+    instruction: &str,
+    context: &str,
+) -> Result<(), ValidationError> {
+    match stack.pop() {
+        Some(found) if &found == expected => Ok(()),
+        Some(found) => Err(ValidationError::TypeMismatch {
+            context: context.to_string(),
+            instruction: instruction.to_string(),
+            expected: format!("`{}`", expected.to_wat()),
+            found: found.to_wat().to_string(),
+        }),
+        None => Err(ValidationError::StackUnderflow {
+            context: context.to_string(),
+            instruction: instruction.to_string(),
+            expected: format!("`{}`", expected.to_wat()),
+        }),
+    }
+}
+
+fn type_list(types: &[fastn_wasm::Type]) -> String {
+    types
+        .iter()
+        .map(|t| t.to_wat())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -10,8 +10,10 @@ pub fn main() {
 }
 
 async fn outer_main() {
-    if let Err(e) = async_main().await {
-        eprintln!("{:?}", e);
+    let matches = app(version()).get_matches();
+    if let Err(e) = async_main(&matches).await {
+        // `--json` is global, clap sets it on the top level matches wherever it is passed
+        fastn_issues::reporter::report(&e.report(), matches.get_flag("json"));
         std::process::exit(1);
     }
 }
@@ -24,12 +26,31 @@ pub enum Error {
     FastnCloudError(#[from] commands::cloud::Error),
 }
 
-async fn async_main() -> Result<(), Error> {
-    let matches = app(version()).get_matches();
-    if cloud_commands(&matches).await? {
+impl Error {
+    fn report(&self) -> fastn_issues::reporter::Report {
+        use fastn_issues::reporter::Report;
+
+        match self {
+            Error::FastnCoreError(e) => e.report(),
+            Error::FastnCloudError(commands::cloud::Error::PublishStaticError(
+                commands::cloud::PublishStaticError::Upload(e),
+            )) => match e {
+                fastn_cloud::UploadError::BuildDirNotFound(_) => Report::new(
+                    "no .build directory",
+                    "`fastn publish-static` uploads the .build directory, but it does not exist.",
+                )
+                .with_recommendation("Run `fastn build` first."),
+                _ => Report::new("publish failed", e.to_string()),
+            },
+        }
+    }
+}
+
+async fn async_main(matches: &clap::ArgMatches) -> Result<(), Error> {
+    if cloud_commands(matches).await? {
         return Ok(());
     }
-    fastn_core_commands(&matches).await?;
+    fastn_core_commands(matches).await?;
     Ok(())
 }
 
@@ -41,7 +62,6 @@ async fn cloud_commands(matches: &clap::ArgMatches) -> Result<bool, commands::cl
 }
 
 async fn fastn_core_commands(matches: &clap::ArgMatches) -> fastn_core::Result<()> {
-    use colored::Colorize;
     use fastn_core::utils::ValueOf;
 
     match matches.subcommand() {
@@ -68,16 +88,17 @@ async fn fastn_core_commands(matches: &clap::ArgMatches) -> fastn_core::Result<(
     }
 
     let mut config = fastn_core::Config::read(None, true, None).await?;
-    let package_name = config.package.name.clone();
 
     if let Some(serve) = matches.subcommand_matches("serve") {
-        let port = serve.value_of_("port").map(|p| match p.parse::<u16>() {
-            Ok(v) => v,
-            Err(_) => {
-                eprintln!("Provided port {} is not a valid port.", p.to_string().red());
-                std::process::exit(1);
-            }
-        });
+        let port = match serve.value_of_("port") {
+            Some(p) => Some(
+                p.parse::<u16>()
+                    .map_err(|_| fastn_core::Error::UsageError {
+                        message: format!("Provided port {} is not a valid port.", p),
+                    })?,
+            ),
+            None => None,
+        };
 
         let bind = serve.value_of_("bind").unwrap_or("127.0.0.1").to_string();
         let download_base_url = serve.value_of_("download-base-url");
@@ -86,6 +107,36 @@ async fn fastn_core_commands(matches: &clap::ArgMatches) -> fastn_core::Result<(
         let inline_js = serve.values_of_("js");
         let external_css = serve.values_of_("external-css");
         let inline_css = serve.values_of_("css");
+        let profile = serve.get_flag("profile");
+        let shutdown_grace = serve
+            .value_of_("shutdown-grace")
+            .unwrap_or("10")
+            .parse::<u64>()
+            .map_err(|_| fastn_core::Error::UsageError {
+                message: "--shutdown-grace must be a number of seconds".to_string(),
+            })?;
+        let shutdown_timeout = serve
+            .value_of_("shutdown-timeout")
+            .unwrap_or("30")
+            .parse::<u64>()
+            .map_err(|_| fastn_core::Error::UsageError {
+                message: "--shutdown-timeout must be a number of seconds".to_string(),
+            })?;
+        let tls = match (serve.value_of_("tls-cert"), serve.value_of_("tls-key")) {
+            (Some(cert), Some(key)) => Some(fastn_core::tls::Tls {
+                cert: cert.to_string(),
+                key: key.to_string(),
+                redirect_port: serve
+                    .value_of_("redirect-port")
+                    .map(|p| {
+                        p.parse::<u16>().map_err(|_| fastn_core::Error::UsageError {
+                            message: format!("Provided redirect port {} is not a valid port.", p),
+                        })
+                    })
+                    .transpose()?,
+            }),
+            _ => None,
+        };
 
         return fastn_core::listen(
             bind.as_str(),
@@ -96,7 +147,10 @@ async fn fastn_core_commands(matches: &clap::ArgMatches) -> fastn_core::Result<(
             inline_js,
             external_css,
             inline_css,
-            package_name,
+            profile,
+            tls,
+            shutdown_grace,
+            shutdown_timeout,
         )
         .await;
     }
@@ -149,7 +203,8 @@ async fn fastn_core_commands(matches: &clap::ArgMatches) -> fastn_core::Result<(
             .add_external_js(external_js)
             .add_inline_js(inline_js)
             .add_external_css(external_css)
-            .add_inline_css(inline_css);
+            .add_inline_css(inline_css)
+            .add_minify_js(build.get_flag("minify"));
 
         return fastn_core::build(
             &mut config,
@@ -251,6 +306,7 @@ fn app(version: &'static str) -> clap::Command {
         .arg(clap::arg!(verbose: -v "Sets the level of verbosity"))
         .arg(clap::arg!(--test "Runs the command in test mode").hide(true))
         .arg(clap::arg!(--trace "Activate tracing").hide(true))
+        .arg(clap::arg!(--json "Print errors as JSON, for editors and CI").global(true))
         .subcommand(
             // Initial subcommand format
             // fastn create-package <project-name> [project-path]
@@ -279,6 +335,7 @@ fn app(version: &'static str) -> clap::Command {
                 .arg(clap::arg!(--"css" <URL> "CSS text added in ftd files")
                     .action(clap::ArgAction::Append))
                 .arg(clap::arg!(--edition <EDITION> "The FTD edition"))
+                .arg(clap::arg!(--"minify" "Minify the generated JS and emit source maps."))
         )
         .subcommand(
             clap::Command::new("mark-resolved")
@@ -435,7 +492,18 @@ mod sub_command {
                 .action(clap::ArgAction::Append))
             .arg(clap::arg!(--"css" <URL> "CSS text added in ftd files")
                 .action(clap::ArgAction::Append))
-            .arg(clap::arg!(--"download-base-url" <URL> "If running without files locally, download needed files from here"));
+            .arg(clap::arg!(--"download-base-url" <URL> "If running without files locally, download needed files from here"))
+            .arg(clap::arg!(--profile "Record timings of recent requests, shown at /-/debug/requests/"))
+            .arg(clap::arg!(--"tls-cert" <PATH> "Serve HTTPS (and HTTP/2) using this PEM certificate chain [default: `fastn.tls` in FASTN.ftd]")
+                .requires("tls-key"))
+            .arg(clap::arg!(--"tls-key" <PATH> "Private key (PEM) for --tls-cert")
+                .requires("tls-cert"))
+            .arg(clap::arg!(--"redirect-port" <PORT> "Redirect plain HTTP requests on this port to HTTPS")
+                .requires("tls-cert"))
+            .arg(clap::arg!(--"shutdown-grace" <SECONDS> "On SIGTERM, fail /-/ready/ this long before no longer accepting connections")
+                .default_value("10"))
+            .arg(clap::arg!(--"shutdown-timeout" <SECONDS> "On SIGTERM, wait this long for in-flight requests to finish")
+                .default_value("30"));
         if cfg!(feature = "remote") {
             serve
         } else {
@@ -922,37 +922,121 @@ window.ftd.utils.set_value_helper = function (data, key, remaining, new_value) {
         set_data_value(data, key, new_value);
     }
 };
-window.ftd.dependencies = {};
-window.ftd.dependencies.eval_background_size = function (bg) {
-    if (typeof bg === 'object' && !!bg && "size" in bg) {
-        let sz = bg.size;
-        if (typeof sz === 'object' && !!sz && "x" in sz && "y" in sz) {
-            return `${sz.x} ${sz.y}`;
+function background_size_css(sz) {
+    if (typeof sz === 'object' && !!sz && "x" in sz && "y" in sz) {
+        return `${sz.x} ${sz.y}`;
+    }
+    else {
+        return sz;
+    }
+}
+function background_position_css(pos) {
+    if (typeof pos === 'object' && !!pos && "x" in pos && "y" in pos) {
+        return `${pos.x} ${pos.y}`;
+    }
+    else {
+        return pos.replace("-", " ");
+    }
+}
+function background_layer(layer) {
+    for (const key of ["image", "linear-gradient", "radial-gradient", "conic-gradient"]) {
+        if (typeof layer === 'object' && !!layer && !!layer[key]) {
+            return layer[key];
+        }
+    }
+    return null;
+}
+function gradient_colors_css(colors_vec, data) {
+    let colors = "";
+    for (const c of colors_vec) {
+        if (typeof c === 'object' && !!c && "color" in c) {
+            let color_value = c.color;
+            if (typeof color_value === 'object' && !!color_value && "light" in color_value && "dark" in color_value) {
+                if (colors) {
+                    colors = data["ftd#dark-mode"] ? `${colors}, ${color_value.dark}` : `${colors}, ${color_value.light}`;
+                }
+                else {
+                    colors = data["ftd#dark-mode"] ? `${color_value.dark}` : `${color_value.light}`;
+                }
+                if ("start" in c)
+                    colors = `${colors} ${c.start}`;
+                if ("end" in c)
+                    colors = `${colors} ${c.end}`;
+                if ("stop-position" in c)
+                    colors = `${colors}, ${c["stop-position"]}`;
+            }
+        }
+    }
+    return colors;
+}
+function background_image_css(bg, data) {
+    var _a, _b, _c;
+    if (typeof bg === 'object' && !!bg && "src" in bg) {
+        let img_src = bg.src;
+        if (!data["ftd#dark-mode"] && typeof img_src === 'object' && !!img_src && "light" in img_src) {
+            return `url("${img_src.light}")`;
+        }
+        else if (data["ftd#dark-mode"] && typeof img_src === 'object' && !!img_src && "dark" in img_src) {
+            return `url("${img_src.dark}")`;
         }
         else {
-            return sz;
+            return null;
         }
     }
+    else if (typeof bg === 'object' && !!bg && "colors" in bg && Object.keys(bg.colors).length) {
+        let colors = gradient_colors_css(bg.colors, data);
+        let at = !!bg.position ? ` at ${background_position_css(bg.position)}` : "";
+        if ("shape" in bg) {
+            return `radial-gradient(${(_a = bg.shape) !== null && _a !== void 0 ? _a : "ellipse"}${at}, ${colors})`;
+        }
+        if ("from" in bg) {
+            return `conic-gradient(from ${(_b = bg.from) !== null && _b !== void 0 ? _b : 0}deg${at}, ${colors})`;
+        }
+        // if the bg direction is provided by the user, use it, otherwise default
+        let direction = (_c = bg.direction) !== null && _c !== void 0 ? _c : "to bottom";
+        return `linear-gradient(${direction}, ${colors})`;
+    }
+    else {
+        return null;
+    }
+}
+window.ftd.dependencies = {};
+window.ftd.dependencies.eval_background_size = function (bg) {
+    if (typeof bg === 'object' && !!bg && "layers" in bg && Object.keys(bg.layers).length) {
+        return bg.layers.map((l) => {
+            let img = !!l ? l.image : null;
+            return !!img && !!img.size ? background_size_css(img.size) : "auto";
+        }).join(", ");
+    }
+    else if (typeof bg === 'object' && !!bg && "size" in bg) {
+        return background_size_css(bg.size);
+    }
     else {
         return null;
     }
 };
 window.ftd.dependencies.eval_background_position = function (bg) {
-    if (typeof bg === 'object' && !!bg && "position" in bg) {
-        let pos = bg.position;
-        if (typeof pos === 'object' && !!pos && "x" in pos && "y" in pos) {
-            return `${pos.x} ${pos.y}`;
-        }
-        else {
-            return pos.replace("-", " ");
-        }
+    if (typeof bg === 'object' && !!bg && "layers" in bg && Object.keys(bg.layers).length) {
+        return bg.layers.map((l) => {
+            let img = !!l ? l.image : null;
+            return !!img && !!img.position ? background_position_css(img.position) : "0% 0%";
+        }).join(", ");
+    }
+    else if (typeof bg === 'object' && !!bg && "src" in bg && "position" in bg) {
+        return background_position_css(bg.position);
     }
     else {
         return null;
     }
 };
 window.ftd.dependencies.eval_background_repeat = function (bg) {
-    if (typeof bg === 'object' && !!bg && "repeat" in bg) {
+    if (typeof bg === 'object' && !!bg && "layers" in bg && Object.keys(bg.layers).length) {
+        return bg.layers.map((l) => {
+            let img = !!l ? l.image : null;
+            return !!img && !!img.repeat ? img.repeat : "repeat";
+        }).join(", ");
+    }
+    else if (typeof bg === 'object' && !!bg && "repeat" in bg) {
         return bg.repeat;
     }
     else {
@@ -961,6 +1045,9 @@ window.ftd.dependencies.eval_background_repeat = function (bg) {
 };
 window.ftd.dependencies.eval_background_color = function (bg, data) {
     let img_src = bg;
+    if (typeof img_src === 'object' && !!img_src && "layers" in img_src) {
+        img_src = img_src.color;
+    }
     if (!data["ftd#dark-mode"] && typeof img_src === 'object' && !!img_src && "light" in img_src) {
         return img_src.light;
     }
@@ -975,49 +1062,13 @@ window.ftd.dependencies.eval_background_color = function (bg, data) {
     }
 };
 window.ftd.dependencies.eval_background_image = function (bg, data) {
-    var _a;
-    if (typeof bg === 'object' && !!bg && "src" in bg) {
-        let img_src = bg.src;
-        if (!data["ftd#dark-mode"] && typeof img_src === 'object' && !!img_src && "light" in img_src) {
-            return `url("${img_src.light}")`;
-        }
-        else if (data["ftd#dark-mode"] && typeof img_src === 'object' && !!img_src && "dark" in img_src) {
-            return `url("${img_src.dark}")`;
-        }
-        else {
-            return null;
-        }
-    }
-    else if (typeof bg === 'object' && !!bg && "colors" in bg && Object.keys(bg.colors).length) {
-        let colors = "";
-        // if the bg direction is provided by the user, use it, otherwise default
-        let direction = (_a = bg.direction) !== null && _a !== void 0 ? _a : "to bottom";
-        let colors_vec = bg.colors;
-        for (const c of colors_vec) {
-            if (typeof c === 'object' && !!c && "color" in c) {
-                let color_value = c.color;
-                if (typeof color_value === 'object' && !!color_value && "light" in color_value && "dark" in color_value) {
-                    if (colors) {
-                        colors = data["ftd#dark-mode"] ? `${colors}, ${color_value.dark}` : `${colors}, ${color_value.light}`;
-                    }
-                    else {
-                        colors = data["ftd#dark-mode"] ? `${color_value.dark}` : `${color_value.light}`;
-                    }
-                    if ("start" in c)
-                        colors = `${colors} ${c.start}`;
-                    if ("end" in c)
-                        colors = `${colors} ${c.end}`;
-                    if ("stop-position" in c)
-                        colors = `${colors}, ${c["stop-position"]}`;
-                }
-            }
-        }
-        let res = `linear-gradient(${direction}, ${colors})`;
-        return res;
-    }
-    else {
-        return null;
+    if (typeof bg === 'object' && !!bg && "layers" in bg && Object.keys(bg.layers).length) {
+        return bg.layers
+            .map((l) => background_image_css(background_layer(l), data))
+            .filter((l) => !!l)
+            .join(", ");
     }
+    return background_image_css(bg, data);
 };
 window.ftd.dependencies.eval_box_shadow = function (shadow, data) {
     if (typeof shadow === 'object' && !!shadow) {
@@ -1051,6 +1102,64 @@ window.ftd.dependencies.eval_box_shadow = function (shadow, data) {
         return null;
     }
 };
+window.ftd.dependencies.eval_transform = function (transform) {
+    if (typeof transform === 'object' && !!transform) {
+        let functions = [
+            ["translateX", "translate-x", ""],
+            ["translateY", "translate-y", ""],
+            ["rotate", "rotate", "deg"],
+            ["scale", "scale", ""],
+            ["scaleX", "scale-x", ""],
+            ["scaleY", "scale-y", ""],
+            ["skewX", "skew-x", "deg"],
+            ["skewY", "skew-y", "deg"],
+        ];
+        let res = [];
+        for (let [f, field, unit] of functions) {
+            if ((field in transform) && transform[field] !== null)
+                res.push(`${f}(${transform[field]}${unit})`);
+        }
+        return res.length === 0 ? "none" : res.join(" ");
+    }
+    else {
+        return null;
+    }
+};
+window.ftd.dependencies.eval_transition = function (transition) {
+    if (typeof transition === 'object' && !!transition) {
+        return `${transition.property} ${transition.duration}s ${transition.easing} ${transition.delay}s`;
+    }
+    else {
+        return null;
+    }
+};
+window.ftd.dependencies.eval_animation = function (animation) {
+    if (typeof animation === 'object' && !!animation) {
+        // `@keyframes` of animations first used by a dependency are not in the page yet
+        let id = `ftd-keyframes-${animation.name}`;
+        if (!document.getElementById(id)) {
+            let steps = animation.keyframes.map(function (k) {
+                let css = "";
+                if (("opacity" in k) && k.opacity !== null)
+                    css = `${css} opacity: ${k.opacity};`;
+                if (("transform" in k) && k.transform !== null)
+                    css = `${css} transform: ${window.ftd.dependencies.eval_transform(k.transform)};`;
+                return `${k.at}% {${css} }`;
+            });
+            let style = document.createElement("style");
+            style.id = id;
+            style.innerHTML = `@keyframes ${animation.name} { ${steps.join(" ")} }`;
+            document.head.appendChild(style);
+        }
+        let iterations = (animation.iterations === null || animation.iterations === undefined)
+            ? "infinite" : animation.iterations;
+        let direction = animation.alternate ? " alternate" : "";
+        return `${animation.name} ${animation.duration}s ${animation.easing} ${animation.delay}s ${iterations}${direction}`;
+    }
+    else {
+        return null;
+    }
+};
 window.ftd.utils.add_extra_in_id = function (node_id) {
     let element = document.querySelector(`[data-id=\"${node_id}\"]`);
     if (element) {
@@ -3,6 +3,7 @@ pub enum Element {
     Row(Row),
     Column(Column),
     Container(ContainerElement),
+    Grid(Grid),
     Document(Document),
     Text(Text),
     Integer(Text),
@@ -26,6 +27,7 @@ impl Element {
             Element::Row(r) => Some(&r.common),
             Element::Column(c) => Some(&c.common),
             Element::Container(e) => Some(&e.common),
+            Element::Grid(g) => Some(&g.common),
             Element::Text(t) => Some(&t.common),
             Element::Integer(i) => Some(&i.common),
             Element::Boolean(b) => Some(&b.common),
@@ -63,6 +65,7 @@ impl Element {
             Element::Row(r) => r.common.line_number,
             Element::Column(c) => c.common.line_number,
             Element::Container(e) => e.common.line_number,
+            Element::Grid(g) => g.common.line_number,
             Element::Document(d) => d.line_number,
             Element::Text(t) => t.common.line_number,
             Element::Integer(i) => i.common.line_number,
@@ -136,6 +139,19 @@ pub struct ContainerElement {
     pub display: ftd::executor::Value<Option<ftd::executor::Display>>,
 }
 
+#[derive(serde::Deserialize, Debug, Default, PartialEq, Clone, serde::Serialize)]
+pub struct Grid {
+    pub common: Common,
+    pub children: Vec<ftd::executor::Element>,
+    pub columns: ftd::executor::Value<Vec<ftd::executor::GridTrack>>,
+    pub rows: ftd::executor::Value<Vec<ftd::executor::GridTrack>>,
+    pub areas: ftd::executor::Value<Vec<String>>,
+    pub template: ftd::executor::Value<Option<ftd::executor::ResponsiveGridTemplate>>,
+    pub gap: ftd::executor::Value<Option<ftd::executor::Length>>,
+    pub row_gap: ftd::executor::Value<Option<ftd::executor::Length>>,
+    pub column_gap: ftd::executor::Value<Option<ftd::executor::Length>>,
+}
+
 #[derive(serde::Deserialize, Debug, Default, PartialEq, Clone, serde::Serialize)]
 pub struct HTMLData {
     pub title: ftd::executor::Value<Option<String>>,
@@ -169,7 +185,7 @@ pub struct Text {
 }
 
 impl Text {
-    pub(crate) fn set_auto_id(&mut self) {
+    pub(crate) fn set_auto_id(&mut self, heading_ids: &mut ftd::executor::headings::HeadingIds) {
         if self
             .common
             .region
@@ -180,7 +196,9 @@ impl Text {
             && self.common.id.value.is_none()
         {
             self.common.id = ftd::executor::Value::new(
-                Some(slug::slugify(self.text.value.original.as_str())),
+                Some(heading_ids.next(
+                    ftd::executor::headings::slugify(self.text.value.original.as_str()).as_str(),
+                )),
                 Some(self.common.line_number),
                 vec![],
             )
@@ -199,6 +217,7 @@ pub struct Image {
     pub src: ftd::executor::Value<ImageSrc>,
     pub alt: ftd::executor::Value<Option<String>>,
     pub fit: ftd::executor::Value<Option<ftd::executor::ImageFit>>,
+    pub sizes: ftd::executor::Value<Option<String>>,
     pub common: Common,
 }
 
@@ -206,6 +225,13 @@ pub struct Image {
 pub struct ImageSrc {
     pub light: ftd::executor::Value<String>,
     pub dark: ftd::executor::Value<String>,
+    /// Resized variants, set by `fastn build` for the images of the package
+    pub srcset: ftd::executor::Value<Option<String>>,
+    pub dark_srcset: ftd::executor::Value<Option<String>>,
+    pub width: ftd::executor::Value<Option<i64>>,
+    pub height: ftd::executor::Value<Option<i64>>,
+    /// `data:` url of a blurred version of the image, shown till the image loads
+    pub placeholder: ftd::executor::Value<Option<String>>,
 }
 
 #[allow(dead_code)]
@@ -314,7 +340,43 @@ impl ImageSrc {
             }
         };
 
-        Ok(ImageSrc { light, dark })
+        let optional_string = |key: &str| -> ftd::executor::Result<_> {
+            Ok(match values.get(key) {
+                Some(value) => ftd::executor::Value::new(
+                    value
+                        .clone()
+                        .resolve(&doc.itdoc(), line_number)?
+                        .optional_string(doc.name, line_number)?,
+                    Some(line_number),
+                    vec![value.into_property(ftd::interpreter::PropertySource::header(key))],
+                ),
+                None => ftd::executor::Value::new(None, Some(line_number), vec![]),
+            })
+        };
+
+        let optional_integer = |key: &str| -> ftd::executor::Result<_> {
+            Ok(match values.get(key) {
+                Some(value) => ftd::executor::Value::new(
+                    value
+                        .clone()
+                        .resolve(&doc.itdoc(), line_number)?
+                        .optional_integer(doc.name, line_number)?,
+                    Some(line_number),
+                    vec![value.into_property(ftd::interpreter::PropertySource::header(key))],
+                ),
+                None => ftd::executor::Value::new(None, Some(line_number), vec![]),
+            })
+        };
+
+        Ok(ImageSrc {
+            light,
+            dark,
+            srcset: optional_string("srcset")?,
+            dark_srcset: optional_string("dark-srcset")?,
+            width: optional_integer("width")?,
+            height: optional_integer("height")?,
+            placeholder: optional_string("placeholder")?,
+        })
     }
 
     pub fn image_pattern() -> (String, bool) {
@@ -638,9 +700,17 @@ pub fn iframe_from_properties(
 }
 
 pub fn markup_inline(s: &str) -> Rendered {
+    markup_inline_with_anchors(s, false, &mut Default::default())
+}
+
+pub fn markup_inline_with_anchors(
+    s: &str,
+    heading_anchors: bool,
+    heading_ids: &mut ftd::executor::headings::HeadingIds,
+) -> Rendered {
     Rendered {
         original: s.to_string(),
-        rendered: ftd::executor::markup::markup_inline(s),
+        rendered: ftd::executor::markup::markup_inline(s, heading_anchors, heading_ids),
     }
 }
 
@@ -741,6 +811,7 @@ pub struct Common {
     pub white_space: ftd::executor::Value<Option<ftd::executor::WhiteSpace>>,
     pub text_transform: ftd::executor::Value<Option<ftd::executor::TextTransform>>,
     pub sticky: ftd::executor::Value<Option<bool>>,
+    pub grid_area: ftd::executor::Value<Option<String>>,
     pub border_style: ftd::executor::Value<Option<ftd::executor::BorderStyle>>,
     pub border_style_vertical: ftd::executor::Value<Option<ftd::executor::BorderStyle>>,
     pub border_style_horizontal: ftd::executor::Value<Option<ftd::executor::BorderStyle>>,
@@ -749,6 +820,9 @@ pub struct Common {
     pub border_style_top: ftd::executor::Value<Option<ftd::executor::BorderStyle>>,
     pub border_style_bottom: ftd::executor::Value<Option<ftd::executor::BorderStyle>>,
     pub shadow: ftd::executor::Value<Option<ftd::executor::Shadow>>,
+    pub transform: ftd::executor::Value<Option<Box<ftd::executor::Transform>>>,
+    pub transition: ftd::executor::Value<Option<Box<ftd::executor::Transition>>>,
+    pub animation: ftd::executor::Value<Option<Box<ftd::executor::Animation>>>,
     pub device: Option<ftd::executor::Device>,
 }
 
@@ -802,7 +876,24 @@ pub fn text_from_properties(
             line_number,
         );
     }
-    let text = text.map(|v| ftd::executor::element::markup_inline(v.unwrap_or_default().as_str()));
+    let heading_anchors = ftd::executor::value::optional_bool(
+        "heading-anchors",
+        "ftd#text",
+        properties,
+        arguments,
+        doc,
+        line_number,
+        inherited_variables,
+    )?
+    .value
+    .unwrap_or_default();
+    let text = text.map(|v| {
+        ftd::executor::element::markup_inline_with_anchors(
+            v.unwrap_or_default().as_str(),
+            heading_anchors,
+            doc.heading_ids,
+        )
+    });
     let common = common_from_properties(
         properties,
         events,
@@ -1196,6 +1287,15 @@ pub fn image_from_properties(
         "ftd#image",
     )?;
 
+    let sizes = ftd::executor::value::optional_string(
+        "sizes",
+        "ftd#image",
+        properties,
+        arguments,
+        doc,
+        line_number,
+    )?;
+
     let common = common_from_properties(
         properties,
         events,
@@ -1212,6 +1312,7 @@ pub fn image_from_properties(
         src,
         alt,
         fit,
+        sizes,
         common,
     })
 }
@@ -1332,6 +1433,100 @@ pub fn container_element_from_properties(
     })
 }
 
+#[allow(clippy::too_many_arguments)]
+pub fn grid_from_properties(
+    properties: &[ftd::interpreter::Property],
+    events: &[ftd::interpreter::Event],
+    arguments: &[ftd::interpreter::Argument],
+    condition: &Option<ftd::interpreter::Expression>,
+    doc: &mut ftd::executor::TDoc,
+    local_container: &[usize],
+    line_number: usize,
+    children: Vec<Element>,
+    inherited_variables: &ftd::VecMap<(String, Vec<usize>)>,
+    device: Option<ftd::executor::Device>,
+) -> ftd::executor::Result<Grid> {
+    let common = common_from_properties(
+        properties,
+        events,
+        arguments,
+        condition,
+        doc,
+        local_container,
+        line_number,
+        inherited_variables,
+        "ftd#grid",
+        device,
+    )?;
+    Ok(Grid {
+        common,
+        children,
+        columns: ftd::executor::GridTrack::grid_track_list(
+            properties,
+            arguments,
+            doc,
+            line_number,
+            "columns",
+            inherited_variables,
+            "ftd#grid",
+        )?,
+        rows: ftd::executor::GridTrack::grid_track_list(
+            properties,
+            arguments,
+            doc,
+            line_number,
+            "rows",
+            inherited_variables,
+            "ftd#grid",
+        )?,
+        areas: ftd::executor::value::string_list(
+            "areas",
+            "ftd#grid",
+            properties,
+            arguments,
+            doc,
+            line_number,
+            inherited_variables,
+        )?,
+        template: ftd::executor::ResponsiveGridTemplate::optional_responsive_grid_template(
+            properties,
+            arguments,
+            doc,
+            line_number,
+            "template",
+            inherited_variables,
+            "ftd#grid",
+        )?,
+        gap: ftd::executor::Length::optional_length(
+            properties,
+            arguments,
+            doc,
+            line_number,
+            "gap",
+            inherited_variables,
+            "ftd#grid",
+        )?,
+        row_gap: ftd::executor::Length::optional_length(
+            properties,
+            arguments,
+            doc,
+            line_number,
+            "row-gap",
+            inherited_variables,
+            "ftd#grid",
+        )?,
+        column_gap: ftd::executor::Length::optional_length(
+            properties,
+            arguments,
+            doc,
+            line_number,
+            "column-gap",
+            inherited_variables,
+            "ftd#grid",
+        )?,
+    })
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn rive_from_properties(
     properties: &[ftd::interpreter::Property],
@@ -1613,6 +1808,14 @@ pub fn common_from_properties(
             line_number,
             inherited_variables,
         )?,
+        grid_area: ftd::executor::value::optional_string(
+            "grid-area",
+            component_name,
+            properties,
+            arguments,
+            doc,
+            line_number,
+        )?,
         z_index: ftd::executor::value::optional_i64(
             "z-index",
             component_name,
@@ -2199,6 +2402,33 @@ pub fn common_from_properties(
             inherited_variables,
             component_name,
         )?,
+        transform: ftd::executor::Transform::optional_transform(
+            properties,
+            arguments,
+            doc,
+            line_number,
+            "transform",
+            inherited_variables,
+            component_name,
+        )?,
+        transition: ftd::executor::Transition::optional_transition(
+            properties,
+            arguments,
+            doc,
+            line_number,
+            "transition",
+            inherited_variables,
+            component_name,
+        )?,
+        animation: ftd::executor::Animation::optional_animation(
+            properties,
+            arguments,
+            doc,
+            line_number,
+            "animation",
+            inherited_variables,
+            component_name,
+        )?,
     })
 }
 
@@ -0,0 +1,636 @@
+/// A heading of a document, listed by the `page-toc` processor.
+///
+/// Headings are the markdown headings in the text of `ftd.text`, and the `ftd.text`s with a
+/// heading `region`, eg `h2`, or a heading `role`, eg `$inherited.types.heading-large`.
+#[derive(serde::Serialize, Debug, Clone, PartialEq)]
+pub struct Heading {
+    pub title: String,
+    /// `id` of the heading element
+    pub id: String,
+    /// `#<id>`, link to the heading
+    pub url: String,
+    /// 1 for the top level headings
+    pub level: i64,
+}
+
+impl Heading {
+    fn new(title: &str, id: String, level: i64) -> Heading {
+        Heading {
+            title: title.trim().to_string(),
+            url: format!("#{}", id),
+            id,
+            level,
+        }
+    }
+}
+
+/// Slug of a heading, used as its `id`. Same as the slugs `marked` generates in the browser.
+pub fn slugify(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .filter(|c| {
+            !(c.is_ascii_punctuation() && *c != '-' && *c != '_'
+                || ('\u{2000}'..='\u{206F}').contains(c)
+                || ('\u{2E00}'..='\u{2E7F}').contains(c))
+        })
+        .map(|c| if c.is_whitespace() { '-' } else { c })
+        .collect()
+}
+
+/// Keeps the ids of the headings unique, the second `install` heading gets `install-1`. One is
+/// shared by all the texts of a document.
+#[derive(Debug, Default, PartialEq)]
+pub struct HeadingIds {
+    seen: std::collections::HashMap<String, usize>,
+}
+
+impl HeadingIds {
+    pub fn next(&mut self, slug: &str) -> String {
+        let mut id = slug.to_string();
+        let mut count = 0;
+        if let Some(c) = self.seen.get(slug) {
+            count = *c;
+            loop {
+                count += 1;
+                id = format!("{}-{}", slug, count);
+                if !self.seen.contains_key(&id) {
+                    break;
+                }
+            }
+        }
+        self.seen.insert(slug.to_string(), count);
+        self.seen.insert(id.clone(), 0);
+        id
+    }
+
+    /// Marks an id set by some other means as taken.
+    pub fn reserve(&mut self, id: &str) {
+        self.seen.entry(id.to_string()).or_insert(0);
+    }
+}
+
+/// Level of the heading for the `region` or the `role` of an `ftd.text`, `None` if it is not a
+/// heading.
+pub fn heading_level(region_or_role: &str) -> Option<i64> {
+    match region_or_role.rsplit(['.', '#']).next()? {
+        "h1" | "heading-hero" | "heading-large" => Some(1),
+        "h2" | "heading-medium" => Some(2),
+        "h3" | "heading-small" => Some(3),
+        "h4" | "heading-tiny" => Some(4),
+        "h5" => Some(5),
+        "h6" => Some(6),
+        _ => None,
+    }
+}
+
+/// Markdown headings in `text`, their ids are taken from `ids`.
+pub fn markdown_headings(text: &str, ids: &mut HeadingIds) -> Vec<Heading> {
+    fn plain_text<'a>(node: &'a comrak::nodes::AstNode<'a>, text: &mut String) {
+        match &node.data.borrow().value {
+            comrak::nodes::NodeValue::Text(t) => text.push_str(t),
+            comrak::nodes::NodeValue::Code(c) => text.push_str(c.literal.as_str()),
+            comrak::nodes::NodeValue::SoftBreak | comrak::nodes::NodeValue::LineBreak => {
+                text.push(' ')
+            }
+            _ => {}
+        }
+        for child in node.children() {
+            plain_text(child, text);
+        }
+    }
+
+    let arena = comrak::Arena::new();
+    let root = comrak::parse_document(&arena, text.trim(), &ftd::executor::markup::MD);
+    let mut headings = vec![];
+    for node in root.descendants() {
+        let level = match node.data.borrow().value {
+            comrak::nodes::NodeValue::Heading(ref h) => h.level as i64,
+            _ => continue,
+        };
+        let mut title = String::new();
+        plain_text(node, &mut title);
+        let id = ids.next(slugify(title.as_str()).as_str());
+        headings.push(Heading::new(title.as_str(), id, level));
+    }
+    headings
+}
+
+/// Collects the headings of a document, one `ftd.text` at a time, in the order they appear.
+#[derive(Debug, Default)]
+pub struct Headings {
+    ids: HeadingIds,
+    pub headings: Vec<Heading>,
+}
+
+impl Headings {
+    /// Returns the id of the `ftd.text` if it is a heading itself, `id` being the one set on it.
+    pub fn add_text(
+        &mut self,
+        text: &str,
+        region_or_role: Option<&str>,
+        id: Option<&str>,
+    ) -> Option<String> {
+        if let Some(id) = id {
+            self.ids.reserve(id);
+        }
+        if let Some(level) = region_or_role.and_then(heading_level) {
+            let id = match id {
+                Some(id) => id.to_string(),
+                None => self.ids.next(slugify(text).as_str()),
+            };
+            self.headings.push(Heading::new(text, id.clone(), level));
+            return Some(id);
+        }
+
+        self.headings.extend(markdown_headings(text, &mut self.ids));
+        None
+    }
+}
+
+/// Headings of the interpreted document, in the order they are rendered. The texts of the
+/// `ftd.text`s are resolved the way they are rendered: the `ftd.text`s in the components are
+/// looked up in the component definitions, with the arguments of the invocation, the loops are
+/// expanded and the references to variables are resolved.
+///
+/// The ids are the ones the headings get on the page: the `ftd.text`s of the document get theirs
+/// from `add_ids()`, the ones in components or loops only get an id when they have a heading
+/// `region`, or an `id` of their own.
+pub fn from_document(document: &ftd::interpreter::Document) -> Vec<Heading> {
+    type Scope = ftd::Map<ftd::interpreter::PropertyValue>;
+
+    /// The arguments of the components and the loop aliases in `scope` that `name` refers to,
+    /// with the fields after it.
+    fn local<'a>(
+        name: &str,
+        scope: &'a Scope,
+    ) -> Option<(&'a ftd::interpreter::PropertyValue, Vec<String>)> {
+        scope.iter().find_map(|(key, value)| {
+            let rest = name.strip_prefix(key.as_str())?;
+            if rest.is_empty() {
+                return Some((value, vec![]));
+            }
+            let rest = rest.strip_prefix('.')?;
+            Some((value, rest.split('.').map(ToString::to_string).collect()))
+        })
+    }
+
+    fn resolve(
+        value: &ftd::interpreter::PropertyValue,
+        scope: &Scope,
+        doc: &ftd::interpreter::TDoc,
+    ) -> Option<ftd::interpreter::Value> {
+        match value {
+            ftd::interpreter::PropertyValue::Value { value, .. } => Some(value.clone()),
+            ftd::interpreter::PropertyValue::Reference {
+                name, line_number, ..
+            } => match local(name, scope) {
+                // the values in the scope only refer to variables of the document
+                Some((value, fields)) => fields.iter().try_fold(
+                    resolve(value, &Default::default(), doc)?,
+                    |value, field| {
+                        let fields = match value.inner()? {
+                            ftd::interpreter::Value::Record { fields, .. } => fields,
+                            ftd::interpreter::Value::Object { values } => values,
+                            _ => return None,
+                        };
+                        resolve(fields.get(field)?, &Default::default(), doc)
+                    },
+                ),
+                None => doc.get_value(*line_number, name).ok(),
+            },
+            _ => None,
+        }
+    }
+
+    /// `value` with the references to `scope` replaced by their values, so it can be put in the
+    /// scope of a component definition or a loop.
+    fn bind(
+        value: &ftd::interpreter::PropertyValue,
+        scope: &Scope,
+        doc: &ftd::interpreter::TDoc,
+    ) -> Option<ftd::interpreter::PropertyValue> {
+        match value {
+            ftd::interpreter::PropertyValue::Reference {
+                name, line_number, ..
+            } if local(name, scope).is_some() => Some(ftd::interpreter::PropertyValue::Value {
+                value: resolve(value, scope, doc)?,
+                is_mutable: false,
+                line_number: *line_number,
+            }),
+            value => Some(value.clone()),
+        }
+    }
+
+    fn region_or_role(value: &ftd::interpreter::PropertyValue, scope: &Scope) -> Option<String> {
+        match value {
+            ftd::interpreter::PropertyValue::Reference { name, .. } => match local(name, scope) {
+                Some((value, fields)) if fields.is_empty() => region_or_role(value, scope),
+                Some(_) => None,
+                None => Some(name.to_string()),
+            },
+            ftd::interpreter::PropertyValue::Value { value, .. } => match value.inner()? {
+                ftd::interpreter::Value::OrType { variant, .. } => Some(variant),
+                ftd::interpreter::Value::String { text } => Some(text),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn string(value: Option<ftd::interpreter::Value>) -> Option<String> {
+        match value?.inner()? {
+            ftd::interpreter::Value::String { text } => Some(text),
+            _ => None,
+        }
+    }
+
+    fn walk(
+        component: &ftd::interpreter::Component,
+        scope: &Scope,
+        in_component: bool,
+        doc: &ftd::interpreter::TDoc,
+        headings: &mut Headings,
+    ) {
+        if let Some(ref iteration) = *component.iteration {
+            let data = match resolve(&iteration.on, scope, doc).and_then(|v| v.inner()) {
+                Some(ftd::interpreter::Value::List { data, .. }) => data,
+                _ => return,
+            };
+            let component = ftd::interpreter::Component {
+                iteration: Box::new(None),
+                ..component.clone()
+            };
+            for (index, item) in data.iter().enumerate() {
+                let mut scope = scope.clone();
+                if let Some(item) = bind(item, &scope, doc) {
+                    scope.insert(iteration.alias.to_string(), item);
+                }
+                if let Some(ref counter) = iteration.loop_counter_alias {
+                    scope.insert(
+                        counter.to_string(),
+                        ftd::interpreter::PropertyValue::Value {
+                            value: ftd::interpreter::Value::Integer {
+                                value: index as i64,
+                            },
+                            is_mutable: false,
+                            line_number: iteration.line_number,
+                        },
+                    );
+                }
+                walk(&component, &scope, true, doc, headings);
+            }
+            return;
+        }
+
+        if component.name.eq("ftd#text") {
+            let text = string(text_value(component).and_then(|v| resolve(v, scope, doc)));
+            if let Some(text) = text {
+                let region =
+                    header_value(component, "region").and_then(|v| region_or_role(v, scope));
+                let id = string(header_value(component, "id").and_then(|v| resolve(v, scope, doc)));
+                // only the headings of the document get an id for their `role`
+                let region_or_role = if in_component && id.is_none() {
+                    region
+                } else {
+                    region.or_else(|| {
+                        header_value(component, "role").and_then(|v| region_or_role(v, scope))
+                    })
+                };
+                headings.add_text(text.as_str(), region_or_role.as_deref(), id.as_deref());
+            }
+        }
+
+        match doc.get_component(component.name.as_str(), component.line_number) {
+            Ok(definition) if !definition.definition.name.eq("ftd.kernel") => {
+                let mut inner = scope.clone();
+                for argument in definition.arguments.iter() {
+                    let value = ftd::interpreter::utils::find_properties_by_source_without_default(
+                        argument.to_sources().as_slice(),
+                        component.properties.as_slice(),
+                    )
+                    .into_iter()
+                    .find(|p| p.condition.is_none())
+                    .map(|p| p.value)
+                    .or_else(|| argument.value.clone());
+                    if let Some(value) = value.and_then(|v| bind(&v, scope, doc)) {
+                        inner.insert(format!("{}.{}", definition.name, argument.name), value);
+                    }
+                }
+                if !component.children.is_empty() {
+                    inner.insert(
+                        format!("{}.children", definition.name),
+                        ftd::interpreter::PropertyValue::Value {
+                            value: ftd::interpreter::Value::List {
+                                data: component
+                                    .children
+                                    .iter()
+                                    .map(|child| ftd::interpreter::PropertyValue::Value {
+                                        value: ftd::interpreter::Value::UI {
+                                            name: child.name.to_string(),
+                                            kind: ftd::interpreter::Kind::ui().into_kind_data(),
+                                            component: child.clone(),
+                                        },
+                                        is_mutable: false,
+                                        line_number: child.line_number,
+                                    })
+                                    .collect(),
+                                kind: ftd::interpreter::Kind::ui().into_list().into_kind_data(),
+                            },
+                            is_mutable: false,
+                            line_number: component.line_number,
+                        },
+                    );
+                }
+                walk(&definition.definition, &inner, true, doc, headings);
+                return;
+            }
+            _ => {}
+        }
+
+        for child in component.children.iter() {
+            walk(child, scope, in_component, doc, headings);
+        }
+        // the children of the kernel containers are in the `children` property
+        for property in component.properties.iter() {
+            if property.condition.is_some()
+                || !property.value.kind().inner_list().is_subsection_ui()
+            {
+                continue;
+            }
+            if let Some(ftd::interpreter::Value::List { data, .. }) =
+                resolve(&property.value, scope, doc).and_then(|v| v.inner())
+            {
+                for child in data.iter() {
+                    if let ftd::interpreter::PropertyValue::Value {
+                        value: ftd::interpreter::Value::UI { component, .. },
+                        ..
+                    } = child
+                    {
+                        walk(component, scope, in_component, doc, headings);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut tree = document.tree.clone();
+    let mut headings = Headings {
+        ids: add_ids(tree.as_mut_slice()),
+        headings: vec![],
+    };
+    let doc = ftd::interpreter::TDoc::new(&document.name, &document.aliases, &document.data);
+    for component in tree.iter() {
+        walk(component, &Default::default(), false, &doc, &mut headings);
+    }
+    headings.headings
+}
+
+/// The text of an `ftd.text`: its caption, body or `text` header.
+fn text_value(component: &ftd::interpreter::Component) -> Option<&ftd::interpreter::PropertyValue> {
+    component.properties.iter().find_map(|p| {
+        let is_text = match p.source {
+            ftd::interpreter::PropertySource::Caption | ftd::interpreter::PropertySource::Body => {
+                true
+            }
+            ftd::interpreter::PropertySource::Header { ref name, .. } => name.eq("text"),
+            _ => false,
+        };
+        (is_text && p.condition.is_none()).then_some(&p.value)
+    })
+}
+
+fn header_value<'a>(
+    component: &'a ftd::interpreter::Component,
+    name: &str,
+) -> Option<&'a ftd::interpreter::PropertyValue> {
+    component
+        .properties
+        .iter()
+        .find(|p| {
+            p.condition.is_none()
+                && p.source
+                    .is_equal(&ftd::interpreter::PropertySource::header(name))
+        })
+        .map(|p| &p.value)
+}
+
+/// Sets the `id` of the `ftd.text`s with a heading `region` or `role` in the document, the same
+/// way `from_document()` lists them. Returns the ids of the `ftd.text`s, the markdown headings rendered
+/// later take the ids that are left.
+pub fn add_ids(instructions: &mut [ftd::interpreter::Component]) -> HeadingIds {
+    fn literal(value: &ftd::interpreter::PropertyValue) -> Option<String> {
+        match value {
+            ftd::interpreter::PropertyValue::Value { value, .. } => match value.inner() {
+                Some(ftd::interpreter::Value::String { text }) => Some(text),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn walk(
+        components: &mut [ftd::interpreter::Component],
+        headings: &mut Headings,
+        text_ids: &mut HeadingIds,
+    ) {
+        for component in components.iter_mut() {
+            if component.name.eq("ftd#text") && component.iteration.is_none() {
+                if let Some(text) = text_value(component).and_then(literal) {
+                    let region_or_role = header_value(component, "region")
+                        .or_else(|| header_value(component, "role"))
+                        .and_then(|value| match value {
+                            ftd::interpreter::PropertyValue::Reference { name, .. } => {
+                                Some(name.to_string())
+                            }
+                            ftd::interpreter::PropertyValue::Value { value, .. } => {
+                                match value.inner() {
+                                    Some(ftd::interpreter::Value::OrType { variant, .. }) => {
+                                        Some(variant)
+                                    }
+                                    Some(ftd::interpreter::Value::String { text }) => Some(text),
+                                    _ => None,
+                                }
+                            }
+                            _ => None,
+                        });
+                    let id = header_value(component, "id");
+                    let has_id = id.is_some();
+                    let id = id.and_then(literal);
+                    if let Some(ref id) = id {
+                        text_ids.reserve(id);
+                    }
+                    let heading =
+                        headings.add_text(text.as_str(), region_or_role.as_deref(), id.as_deref());
+                    if let (Some(heading), false) = (heading, has_id) {
+                        text_ids.reserve(heading.as_str());
+                        component.properties.push(ftd::interpreter::Property {
+                            value: ftd::interpreter::PropertyValue::Value {
+                                value: ftd::interpreter::Value::String { text: heading },
+                                is_mutable: false,
+                                line_number: component.line_number,
+                            },
+                            source: ftd::interpreter::PropertySource::header("id"),
+                            condition: None,
+                            line_number: component.line_number,
+                        });
+                    }
+                }
+            }
+            walk(component.children.as_mut_slice(), headings, text_ids);
+            // the children of the kernel containers are in the `children` property
+            for property in component.properties.iter_mut() {
+                if !property.value.kind().inner_list().is_subsection_ui() {
+                    continue;
+                }
+                if let ftd::interpreter::PropertyValue::Value {
+                    value: ftd::interpreter::Value::List { ref mut data, .. },
+                    ..
+                } = property.value
+                {
+                    for child in data.iter_mut() {
+                        if let ftd::interpreter::PropertyValue::Value {
+                            value:
+                                ftd::interpreter::Value::UI {
+                                    ref mut component, ..
+                                },
+                            ..
+                        } = child
+                        {
+                            walk(std::slice::from_mut(component), headings, text_ids);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let mut text_ids = HeadingIds::default();
+    walk(instructions, &mut Headings::default(), &mut text_ids);
+    text_ids
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn slugify() {
+        assert_eq!(super::slugify(" Getting Started! "), "getting-started");
+        assert_eq!(super::slugify("What's `fastn`?"), "whats-fastn");
+        assert_eq!(
+            super::slugify("snake_case & kebab-case"),
+            "snake_case--kebab-case"
+        );
+    }
+
+    #[test]
+    fn heading_ids() {
+        let mut ids = super::HeadingIds::default();
+        assert_eq!(ids.next("install"), "install");
+        assert_eq!(ids.next("install"), "install-1");
+        ids.reserve("usage");
+        assert_eq!(ids.next("usage"), "usage-1");
+        assert_eq!(ids.next("install"), "install-2");
+    }
+
+    #[test]
+    fn from_document() {
+        let doc = ftd::executor::test::interpret_helper(
+            "foo",
+            indoc::indoc!(
+                "
+                -- component h1:
+                caption title:
+
+                -- ftd.text: $h1.title
+                region: h1
+
+                -- end: h1
+
+                -- component card:
+                children items:
+
+                -- ftd.column:
+
+                -- ftd.text: Card
+                role: $inherited.types.heading-small
+
+                -- ftd.column:
+                children: $card.items
+
+                -- end: ftd.column
+
+                -- end: ftd.column
+
+                -- end: card
+
+                -- string list steps:
+
+                -- string: Download
+                -- string: Install
+
+                -- end: steps
+
+                -- string faq: FAQ
+
+                -- ftd.text: Install
+                role: $inherited.types.heading-large
+
+                -- ftd.column:
+
+                -- ftd.text:
+
+                ## Install
+
+                Some text.
+
+                ### With `cargo`
+
+                -- end: ftd.column
+
+                -- ftd.text: Install
+                role: $inherited.types.heading-medium
+
+                -- h1: Getting Started
+
+                -- ftd.text: $step
+                region: h3
+                for: $step in $steps
+
+                -- card:
+
+                -- h1: Usage
+
+                -- end: card
+
+                -- ftd.text: $faq
+                region: h2
+                id: questions
+                "
+            ),
+        )
+        .unwrap();
+
+        let headings = super::from_document(&doc)
+            .into_iter()
+            .map(|h| (h.title, h.id, h.level))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            headings,
+            vec![
+                ("Install".to_string(), "install".to_string(), 1),
+                ("Install".to_string(), "install-1".to_string(), 2),
+                ("With cargo".to_string(), "with-cargo".to_string(), 3),
+                ("Install".to_string(), "install-2".to_string(), 2),
+                (
+                    "Getting Started".to_string(),
+                    "getting-started".to_string(),
+                    1
+                ),
+                ("Download".to_string(), "download".to_string(), 3),
+                ("Install".to_string(), "install-3".to_string(), 3),
+                ("Usage".to_string(), "usage".to_string(), 1),
+                ("FAQ".to_string(), "questions".to_string(), 2),
+            ]
+        );
+    }
+}
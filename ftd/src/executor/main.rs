@@ -8,6 +8,8 @@ pub struct ExecuteDoc<'a> {
     pub element_constructor: &'a mut ftd::Map<ftd::executor::ElementConstructor>,
     pub js: &'a mut std::collections::HashSet<String>,
     pub css: &'a mut std::collections::HashSet<String>,
+    pub keyframes: &'a mut std::collections::HashSet<String>,
+    pub heading_ids: &'a mut ftd::executor::headings::HeadingIds,
     pub rive_data: &'a mut Vec<ftd::executor::RiveData>,
 }
 
@@ -22,6 +24,7 @@ pub struct RT {
     pub element_constructor: ftd::Map<ftd::executor::ElementConstructor>,
     pub js: std::collections::HashSet<String>,
     pub css: std::collections::HashSet<String>,
+    pub keyframes: std::collections::HashSet<String>,
     pub rive_data: Vec<ftd::executor::RiveData>,
 }
 
@@ -37,6 +40,7 @@ impl Default for RT {
             element_constructor: Default::default(),
             js: Default::default(),
             css: Default::default(),
+            keyframes: Default::default(),
             rive_data: vec![],
         }
     }
@@ -46,10 +50,12 @@ impl<'a> ExecuteDoc<'a> {
     #[tracing::instrument(skip_all)]
     pub fn from_interpreter(document: ftd::interpreter::Document) -> ftd::executor::Result<RT> {
         let mut document = document;
+        let mut heading_ids = ftd::executor::headings::add_ids(document.tree.as_mut_slice());
         let mut dummy_instructions = ftd::VecMap::new();
         let mut element_constructor = Default::default();
         let mut js: std::collections::HashSet<String> = document.js;
         let mut css: std::collections::HashSet<String> = document.css;
+        let mut keyframes: std::collections::HashSet<String> = Default::default();
         let mut rive_data: Vec<ftd::executor::RiveData> = vec![];
         let execute_doc = ExecuteDoc {
             name: document.name.as_str(),
@@ -60,6 +66,8 @@ impl<'a> ExecuteDoc<'a> {
             element_constructor: &mut element_constructor,
             js: &mut js,
             css: &mut css,
+            keyframes: &mut keyframes,
+            heading_ids: &mut heading_ids,
             rive_data: &mut rive_data,
         }
         .execute()?;
@@ -104,6 +112,7 @@ impl<'a> ExecuteDoc<'a> {
             element_constructor,
             js,
             css,
+            keyframes,
             rive_data,
         })
     }
@@ -117,6 +126,8 @@ impl<'a> ExecuteDoc<'a> {
             element_constructor: self.element_constructor,
             js: self.js,
             css: self.css,
+            keyframes: self.keyframes,
+            heading_ids: self.heading_ids,
             rive_data: self.rive_data,
         };
 
@@ -374,6 +385,7 @@ impl<'a> ExecuteDoc<'a> {
         .0;
 
         let children_length = iteration.children(&doc.itdoc())?.0.len();
+        let keys = iteration.keys(&doc.itdoc())?;
         let reference_name =
             iteration
                 .on
@@ -397,6 +409,7 @@ impl<'a> ExecuteDoc<'a> {
                 instruction,
                 doc,
                 index,
+                keys.as_ref().map(|keys| keys[index].as_str()),
                 iteration.alias.as_str(),
                 reference_name,
                 inherited_variables,
@@ -608,6 +621,7 @@ impl<'a> ExecuteDoc<'a> {
                     ftd::executor::Element::Row(r) => &mut r.container.children,
                     ftd::executor::Element::Column(r) => &mut r.container.children,
                     ftd::executor::Element::Container(e) => &mut e.children,
+                    ftd::executor::Element::Grid(g) => &mut g.children,
                     ftd::executor::Element::Document(r) => &mut r.children,
                     t => unreachable!("{:?}", t),
                 };
@@ -771,8 +785,8 @@ impl<'a> ExecuteDoc<'a> {
         use itertools::Itertools;
 
         match component_definition.name.as_str() {
-            "ftd#row" | "ftd#column" | "ftd#container" | "ftd#document" | "ftd#desktop"
-            | "ftd#mobile" => {
+            "ftd#row" | "ftd#column" | "ftd#container" | "ftd#grid" | "ftd#document"
+            | "ftd#desktop" | "ftd#mobile" => {
                 doc.insert_local_variables(
                     component_definition.name.as_str(),
                     instruction.properties.as_slice(),
@@ -825,7 +839,7 @@ impl<'a> ExecuteDoc<'a> {
                     inherited_variables,
                     device,
                 )?;
-                text.set_auto_id();
+                text.set_auto_id(doc.heading_ids);
                 ftd::executor::Element::Text(text)
             }
             "ftd#integer" => {
@@ -920,6 +934,20 @@ impl<'a> ExecuteDoc<'a> {
                     device,
                 )?,
             ),
+            "ftd#grid" => {
+                ftd::executor::Element::Grid(ftd::executor::element::grid_from_properties(
+                    instruction.properties.as_slice(),
+                    instruction.events.as_slice(),
+                    component_definition.arguments.as_slice(),
+                    instruction.condition.as_ref(),
+                    doc,
+                    local_container,
+                    instruction.line_number,
+                    vec![],
+                    inherited_variables,
+                    device,
+                )?)
+            }
             "ftd#document" => {
                 if !instruction.events.is_empty() {
                     return ftd::executor::utils::parse_error(
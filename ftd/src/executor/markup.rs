@@ -7,21 +7,112 @@ pub static MD: once_cell::sync::Lazy<comrak::ComrakOptions> = once_cell::sync::L
     m.extension.autolink = true;
     m.extension.tasklist = true;
     m.extension.superscript = true;
+    m.extension.footnotes = true;
+    m.extension.description_lists = true;
     m.parse.smart = true;
     m
 });
 
-pub fn markup(i: &str) -> String {
-    comrak::markdown_to_html(i.replace("![", MAGIC).trim(), &MD)
-        .trim()
-        .replace(MAGIC, "![")
-        .replace('\n', " ")
+/// `heading_anchors` adds a `#` link to each heading, pointing to the heading itself, the ids of
+/// the headings are taken from the `heading_ids` of the document. The math in the markdown is
+/// rendered to MathML.
+pub fn markup(
+    i: &str,
+    heading_anchors: bool,
+    heading_ids: &mut ftd::executor::headings::HeadingIds,
+) -> String {
+    let math = ftd::executor::math::extract(i);
+    let i = math.markdown.replace("![", MAGIC);
+    ftd::executor::math::insert(
+        add_heading_ids(
+            comrak::markdown_to_html(i.trim(), &MD).trim(),
+            ftd::executor::headings::markdown_headings(i.as_str(), heading_ids).as_slice(),
+            heading_anchors,
+        )
+        .as_str(),
+        math.mathml.as_slice(),
+    )
+    .replace(MAGIC, "![")
+    .replace('\n', " ")
+}
+
+/// Html of the markdown `i`, for the texts rendered in the browser, if `marked` can not render it:
+/// it has math, footnotes or definition lists. The headings are left without ids, the browser
+/// gives them the ids, see `fastn_utils.markdown_inline()`.
+pub fn browser_markup(i: &str) -> Option<String> {
+    let math = ftd::executor::math::extract(i);
+    if math.mathml.is_empty() && !(i.contains("[^") || i.contains("\n:")) {
+        return None;
+    }
+    let arena = comrak::Arena::new();
+    let root = comrak::parse_document(&arena, math.markdown.trim(), &MD);
+    let needs_comrak = !math.mathml.is_empty()
+        || root.descendants().any(|node| {
+            matches!(
+                node.data.borrow().value,
+                comrak::nodes::NodeValue::FootnoteDefinition(..)
+                    | comrak::nodes::NodeValue::DescriptionList
+            )
+        });
+    if !needs_comrak {
+        return None;
+    }
+    let mut html = vec![];
+    comrak::format_html(root, &MD, &mut html).ok()?;
+    Some(ftd::executor::math::insert(
+        String::from_utf8(html).ok()?.trim(),
+        math.mathml.as_slice(),
+    ))
+}
+
+/// comrak renders the headings as bare `<h1>` to `<h6>`, in the order they are in the markdown.
+fn add_heading_ids(
+    html: &str,
+    headings: &[ftd::executor::headings::Heading],
+    heading_anchors: bool,
+) -> String {
+    let mut o = String::new();
+    let mut rest = html;
+    for heading in headings {
+        let open = format!("<h{}>", heading.level);
+        let start = match rest.find(open.as_str()) {
+            Some(start) => start,
+            None => break,
+        };
+        o.push_str(&rest[..start]);
+        o.push_str(format!("<h{} id=\"{}\">", heading.level, heading.id).as_str());
+        rest = &rest[start + open.len()..];
+
+        if heading_anchors {
+            if let Some(end) = rest.find(format!("</h{}>", heading.level).as_str()) {
+                o.push_str(&rest[..end]);
+                o.push_str(
+                    format!(
+                        "<a class=\"ft-heading-anchor\" href=\"{}\" aria-hidden=\"true\">#</a>",
+                        heading.url
+                    )
+                    .as_str(),
+                );
+                rest = &rest[end..];
+            }
+        }
+    }
+    o.push_str(rest);
+    o
 }
 
-pub fn markup_inline(i: &str) -> String {
+pub fn markup_inline(
+    i: &str,
+    heading_anchors: bool,
+    heading_ids: &mut ftd::executor::headings::HeadingIds,
+) -> String {
     let (space_before, space_after) = spaces(i);
     let o = {
-        let mut g = ftd::executor::utils::replace_last_occurrence(markup(i).as_str(), "<p>", "");
+        let mut g = ftd::executor::utils::replace_last_occurrence(
+            markup(i, heading_anchors, heading_ids).as_str(),
+            "<p>",
+            "",
+        );
         g = ftd::executor::utils::replace_last_occurrence(g.as_str(), "</p>", "");
         g
     };
@@ -70,3 +161,21 @@ fn spaces(s: &str) -> (usize, usize) {
     }
     (space_before, space_after)
 }
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn browser_markup() {
+        assert_eq!(super::browser_markup("# Hello\n\ncosts $5 or $10"), None);
+        assert_eq!(super::browser_markup("[^x] is not a footnote"), None);
+
+        let html = super::browser_markup("Euler: $e^{i\\pi} + 1 = 0$").unwrap();
+        assert!(html.starts_with("<p>Euler: <math"));
+
+        let html = super::browser_markup("fastn[^1]\n\n[^1]: The framework.").unwrap();
+        assert!(html.contains("<section class=\"footnotes\""));
+
+        let html = super::browser_markup("Term\n: The definition.").unwrap();
+        assert!(html.starts_with("<dl>\n<dt>Term</dt>"));
+    }
+}
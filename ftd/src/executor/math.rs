@@ -0,0 +1,191 @@
+/// Marks the start of a math placeholder, `<START><index><END>`. Private use characters are left
+/// as is by comrak.
+pub const START: char = '\u{E000}';
+pub const END: char = '\u{E001}';
+
+/// Markdown with the math, `$…$` inline and `$$…$$` display, taken out and rendered to MathML.
+///
+/// The math is replaced by placeholders before the markdown is rendered, as comrak does not know
+/// about math and escapes the MathML, and `insert()` puts the MathML back in the rendered html.
+#[derive(Debug, Default, PartialEq)]
+pub struct Math {
+    pub markdown: String,
+    /// MathML of the math in the markdown, in order of the placeholders
+    pub mathml: Vec<String>,
+}
+
+/// Finds the math in the markdown `i`.
+///
+/// Math in code spans and fenced code blocks, `\$`, and `$` followed by a space or closed by a
+/// `$` followed by a digit, eg `$5 or $10`, are left as is. So is the math that can not be
+/// rendered.
+pub fn extract(i: &str) -> Math {
+    let chars = i.chars().collect::<Vec<_>>();
+    let mut math = Math::default();
+    let mut in_fence = false;
+    let mut idx = 0;
+    while idx < chars.len() {
+        if idx == 0 || chars[idx - 1] == '\n' {
+            let end = chars[idx..]
+                .iter()
+                .position(|c| *c == '\n')
+                .map(|p| idx + p + 1)
+                .unwrap_or(chars.len());
+            let line = chars[idx..end].iter().collect::<String>();
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_fence = !in_fence;
+            }
+            if in_fence || trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                math.markdown.push_str(line.as_str());
+                idx = end;
+                continue;
+            }
+        }
+
+        match chars[idx] {
+            '\\' if chars.get(idx + 1) == Some(&'$') => {
+                math.markdown.push_str("\\$");
+                idx += 2;
+            }
+            '`' => {
+                let ticks = chars[idx..].iter().take_while(|c| **c == '`').count();
+                let end = closing_backticks(&chars, idx + ticks, ticks).unwrap_or(idx + ticks);
+                math.markdown.extend(&chars[idx..end]);
+                idx = end;
+            }
+            '$' if chars.get(idx + 1) == Some(&'$') => {
+                match find(&chars, idx + 2, |j| {
+                    chars[j] == '$' && chars.get(j + 1) == Some(&'$')
+                }) {
+                    Some(end) if end > idx + 2 => {
+                        let source = chars[idx + 2..end].iter().collect::<String>();
+                        math.push(
+                            source.trim(),
+                            latex2mathml::DisplayStyle::Block,
+                            &chars[idx..end + 2],
+                        );
+                        idx = end + 2;
+                    }
+                    _ => {
+                        math.markdown.push_str("$$");
+                        idx += 2;
+                    }
+                }
+            }
+            '$' if chars.get(idx + 1).map_or(false, |c| !c.is_whitespace()) => {
+                let end = find(&chars, idx + 1, |j| {
+                    chars[j] == '$'
+                        && !chars[j - 1].is_whitespace()
+                        && chars[j - 1] != '\\'
+                        && !chars.get(j + 1).map_or(false, |c| c.is_ascii_digit())
+                })
+                .filter(|end| !chars[idx + 1..*end].iter().any(|c| *c == '\n' || *c == '`'));
+                match end {
+                    Some(end) => {
+                        let source = chars[idx + 1..end].iter().collect::<String>();
+                        math.push(
+                            source.as_str(),
+                            latex2mathml::DisplayStyle::Inline,
+                            &chars[idx..end + 1],
+                        );
+                        idx = end + 1;
+                    }
+                    None => {
+                        math.markdown.push('$');
+                        idx += 1;
+                    }
+                }
+            }
+            c => {
+                math.markdown.push(c);
+                idx += 1;
+            }
+        }
+    }
+    math
+}
+
+impl Math {
+    fn push(&mut self, source: &str, display: latex2mathml::DisplayStyle, original: &[char]) {
+        match latex2mathml::latex_to_mathml(source, display) {
+            Ok(mathml) => {
+                self.markdown
+                    .push_str(format!("{}{}{}", START, self.mathml.len(), END).as_str());
+                self.mathml.push(mathml.replace('\n', ""));
+            }
+            Err(_) => self.markdown.extend(original),
+        }
+    }
+}
+
+/// Index of the first char from `from` for which `f` is true.
+fn find(chars: &[char], from: usize, f: impl Fn(usize) -> bool) -> Option<usize> {
+    (from..chars.len()).find(|j| f(*j))
+}
+
+/// End of the code span which starts with `ticks` backticks before `from`.
+fn closing_backticks(chars: &[char], from: usize, ticks: usize) -> Option<usize> {
+    let mut j = from;
+    while j < chars.len() {
+        if chars[j] != '`' {
+            j += 1;
+            continue;
+        }
+        let count = chars[j..].iter().take_while(|c| **c == '`').count();
+        if count == ticks {
+            return Some(j + count);
+        }
+        j += count;
+    }
+    None
+}
+
+/// Replaces the placeholders in the rendered `html` with the MathML, a display math alone in a
+/// paragraph replaces the paragraph.
+pub fn insert(html: &str, mathml: &[String]) -> String {
+    let mut html = html.to_string();
+    for (index, m) in mathml.iter().enumerate() {
+        let placeholder = format!("{}{}{}", START, index, END);
+        html = html
+            .replace(format!("<p>{}</p>", placeholder).as_str(), m)
+            .replace(placeholder.as_str(), m);
+    }
+    html
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn extract() {
+        let math = super::extract("Euler: $e^{i\\pi} + 1 = 0$, costs $5 or $10, `$x$`.");
+        assert_eq!(
+            math.markdown,
+            "Euler: \u{E000}0\u{E001}, costs $5 or $10, `$x$`."
+        );
+        assert_eq!(math.mathml.len(), 1);
+        assert!(math.mathml[0].starts_with("<math"));
+        assert!(!math.mathml[0].contains("display=\"block\""));
+
+        let math = super::extract("Sum:\n\n$$\n\\sum_{i=1}^n i\n$$\n\n```\n$$a$$\n```\n");
+        assert_eq!(
+            math.markdown,
+            "Sum:\n\n\u{E000}0\u{E001}\n\n```\n$$a$$\n```\n"
+        );
+        assert!(math.mathml[0].contains("display=\"block\""));
+
+        assert_eq!(super::extract("\\$x$ and $ y$").mathml.len(), 0);
+    }
+
+    #[test]
+    fn insert() {
+        let mathml = vec!["<math>a</math>".to_string(), "<math>b</math>".to_string()];
+        assert_eq!(
+            super::insert(
+                "<p>\u{E000}0\u{E001}</p><p>is \u{E000}1\u{E001}</p>",
+                mathml.as_slice()
+            ),
+            "<math>a</math><p>is <math>b</math></p>"
+        );
+    }
+}
@@ -5,8 +5,10 @@ mod test;
 pub mod code;
 mod dummy;
 mod element;
+pub mod headings;
 mod main;
-mod markup;
+pub(crate) mod markup;
+pub(crate) mod math;
 mod rive;
 mod styles;
 mod tdoc;
@@ -18,18 +20,21 @@ pub type FieldWithValue = (ftd::interpreter::Field, Option<ftd::ast::VariableVal
 
 pub use dummy::{DummyElement, ElementConstructor};
 pub use element::{
-    CheckBox, Code, Column, Common, Container, ContainerElement, Document, Element, Event,
+    CheckBox, Code, Column, Common, Container, ContainerElement, Document, Element, Event, Grid,
     HTMLData, Iframe, Image, ImageSrc, IterativeElement, RawElement, RawImage, Rive, Row, Text,
     TextInput, WebComponent,
 };
 pub use main::{Device, ExecuteDoc, RT};
 pub use rive::RiveData;
 pub use styles::{
-    AlignSelf, Alignment, Anchor, Background, BackgroundImage, BackgroundPosition,
-    BackgroundRepeat, BackgroundSize, BorderStyle, BreakpointWidth, Color, ColorValue, Cursor,
-    Display, FontSize, ImageFit, Length, LineClamp, LinearGradient, LinearGradientColor,
-    LinearGradientDirection, Loading, Overflow, Region, Resize, Resizing, ResponsiveType, Shadow,
-    Spacing, TextAlign, TextInputType, TextStyle, TextTransform, TextWeight, WhiteSpace,
+    AlignSelf, Alignment, Anchor, Animation, Background, BackgroundImage, BackgroundLayer,
+    BackgroundPosition, BackgroundRepeat, BackgroundSize, BorderStyle, BreakpointWidth, Color,
+    ColorValue, ConicGradient, Cursor, Display, Easing, FontSize, GridMinmax, GridTemplate,
+    GridTrack, ImageFit, Keyframe, LayeredBackground, Length, LineClamp, LinearGradient,
+    LinearGradientColor, LinearGradientDirection, Loading, Overflow, RadialGradient,
+    RadialGradientShape, Region, Resize, Resizing, ResponsiveGridTemplate, ResponsiveType, Shadow,
+    Spacing, TextAlign, TextInputType, TextStyle, TextTransform, TextWeight, Transform, Transition,
+    WhiteSpace,
 };
 pub(crate) use tdoc::TDoc;
 pub(crate) use value::Value;
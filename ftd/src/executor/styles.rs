@@ -1352,27 +1352,21 @@ impl LinearGradient {
 }
 
 #[derive(serde::Deserialize, Debug, PartialEq, Clone, serde::Serialize)]
-pub enum Background {
-    Solid(ftd::executor::Color),
-    Image(ftd::executor::BackgroundImage),
-    LinearGradient(ftd::executor::LinearGradient),
+pub enum RadialGradientShape {
+    Circle,
+    Ellipse,
 }
 
-impl Background {
-    fn from_optional_values(
-        or_type_value: Option<(String, ftd::interpreter::PropertyValue)>,
+impl RadialGradientShape {
+    fn from_value(
+        value: ftd::interpreter::PropertyValue,
         doc: &ftd::executor::TDoc,
         line_number: usize,
-    ) -> ftd::executor::Result<Option<Self>> {
-        if let Some(value) = or_type_value {
-            Ok(Some(ftd::executor::Background::from_values(
-                value,
-                doc,
-                line_number,
-            )?))
-        } else {
-            Ok(None)
-        }
+    ) -> ftd::executor::Result<RadialGradientShape> {
+        let binding = value.resolve(&doc.itdoc(), line_number)?;
+        let value = binding.get_or_type(doc.name, line_number)?;
+        let value = (value.1.to_owned(), value.2.to_owned());
+        RadialGradientShape::from_values(value, doc, line_number)
     }
 
     fn from_values(
@@ -1381,483 +1375,1896 @@ impl Background {
         line_number: usize,
     ) -> ftd::executor::Result<Self> {
         match or_type_value.0.as_str() {
-            ftd::interpreter::FTD_BACKGROUND_SOLID => Ok(ftd::executor::Background::Solid(
-                Color::from_value(or_type_value.1, doc, line_number)?,
-            )),
-            ftd::interpreter::FTD_BACKGROUND_IMAGE => Ok(ftd::executor::Background::Image(
-                ftd::executor::BackgroundImage::from_value(or_type_value.1, doc, line_number)?,
-            )),
-            ftd::interpreter::FTD_BACKGROUND_LINEAR_GRADIENT => {
-                Ok(ftd::executor::Background::LinearGradient(
-                    ftd::executor::LinearGradient::from_value(or_type_value.1, doc, line_number)?,
-                ))
-            }
+            ftd::interpreter::FTD_RADIAL_GRADIENT_SHAPE_CIRCLE => Ok(RadialGradientShape::Circle),
+            ftd::interpreter::FTD_RADIAL_GRADIENT_SHAPE_ELLIPSE => Ok(RadialGradientShape::Ellipse),
             t => ftd::executor::utils::parse_error(
-                format!("Unknown variant `{}` for or-type `ftd.background`", t),
+                format!(
+                    "Unknown variant `{}` for or-type `ftd.radial-gradient-shape`",
+                    t
+                ),
                 doc.name,
                 line_number,
             ),
         }
     }
 
-    pub(crate) fn optional_background(
-        properties: &[ftd::interpreter::Property],
-        arguments: &[ftd::interpreter::Argument],
-        doc: &ftd::executor::TDoc,
-        line_number: usize,
-        key: &str,
-        inherited_variables: &ftd::VecMap<(String, Vec<usize>)>,
-        component_name: &str,
-    ) -> ftd::executor::Result<ftd::executor::Value<Option<ftd::executor::Background>>> {
-        let or_type_value = ftd::executor::value::optional_or_type(
-            key,
-            component_name,
-            properties,
-            arguments,
-            doc,
-            line_number,
-            ftd::interpreter::FTD_BACKGROUND,
-            inherited_variables,
-        )?;
-
-        Ok(ftd::executor::Value::new(
-            ftd::executor::Background::from_optional_values(or_type_value.value, doc, line_number)?,
-            or_type_value.line_number,
-            or_type_value.properties,
-        ))
-    }
-
-    pub fn to_solid_css_string(&self) -> String {
+    pub fn to_css_string(&self) -> String {
         match self {
-            ftd::executor::Background::Solid(c) => c.light.value.to_css_string(),
-            ftd::executor::Background::Image(_) => ftd::interpreter::FTD_IGNORE_KEY.to_string(),
-            ftd::executor::Background::LinearGradient(_) => {
-                ftd::interpreter::FTD_IGNORE_KEY.to_string()
-            }
+            RadialGradientShape::Circle => "circle".to_string(),
+            RadialGradientShape::Ellipse => "ellipse".to_string(),
         }
     }
+}
 
-    pub fn to_image_src_css_string(&self, device: &Option<ftd::executor::Device>) -> String {
-        match self {
-            ftd::executor::Background::Solid(_) => ftd::interpreter::FTD_IGNORE_KEY.to_string(),
-            ftd::executor::Background::Image(i) => i.to_image_src_css_string(),
-            ftd::executor::Background::LinearGradient(l) => l.to_css_string(device),
-        }
-    }
+#[derive(serde::Deserialize, Debug, PartialEq, Clone, serde::Serialize)]
+pub struct RadialGradient {
+    pub shape: ftd::executor::Value<RadialGradientShape>,
+    pub position: ftd::executor::Value<Option<ftd::executor::BackgroundPosition>>,
+    pub colors: ftd::executor::Value<Vec<LinearGradientColor>>,
+}
 
-    pub fn to_image_repeat_css_string(&self) -> String {
-        match self {
-            ftd::executor::Background::Solid(_) => ftd::interpreter::FTD_IGNORE_KEY.to_string(),
-            ftd::executor::Background::Image(i) => i.to_repeat_css_string(),
-            ftd::executor::Background::LinearGradient(_) => {
-                ftd::interpreter::FTD_IGNORE_KEY.to_string()
+impl RadialGradient {
+    fn from_value(
+        value: ftd::interpreter::PropertyValue,
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<ftd::executor::RadialGradient> {
+        let value = value.resolve(&doc.itdoc(), line_number)?;
+        let fields = match value.inner() {
+            Some(ftd::interpreter::Value::Record { name, fields })
+                if name.eq(ftd::interpreter::FTD_RADIAL_GRADIENT) =>
+            {
+                fields
             }
-        }
-    }
-
-    pub fn to_image_size_css_string(&self, device: &Option<ftd::executor::Device>) -> String {
-        match self {
-            ftd::executor::Background::Solid(_) => ftd::interpreter::FTD_IGNORE_KEY.to_string(),
-            ftd::executor::Background::Image(i) => i.to_size_css_string(device),
-            ftd::executor::Background::LinearGradient(_) => {
-                ftd::interpreter::FTD_IGNORE_KEY.to_string()
+            t => {
+                return ftd::executor::utils::parse_error(
+                    format!(
+                        "Expected value of type record `{}`, found: {:?}",
+                        ftd::interpreter::FTD_RADIAL_GRADIENT,
+                        t
+                    ),
+                    doc.name,
+                    line_number,
+                )
             }
-        }
+        };
+        ftd::executor::RadialGradient::from_values(fields, doc, line_number)
     }
 
-    pub fn to_image_position_css_string(&self, device: &Option<ftd::executor::Device>) -> String {
-        match self {
-            ftd::executor::Background::Solid(_) => ftd::interpreter::FTD_IGNORE_KEY.to_string(),
-            ftd::executor::Background::Image(i) => i.to_position_css_string(device),
-            ftd::executor::Background::LinearGradient(_) => {
-                ftd::interpreter::FTD_IGNORE_KEY.to_string()
-            }
-        }
-    }
+    fn from_values(
+        values: ftd::Map<ftd::interpreter::PropertyValue>,
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<ftd::executor::RadialGradient> {
+        let get_property_value = |field_name: &str| {
+            values
+                .get(field_name)
+                .ok_or_else(|| ftd::executor::Error::ParseError {
+                    message: format!("`{}` field in ftd.radial-gradient not found", field_name),
+                    doc_id: doc.name.to_string(),
+                    line_number,
+                })
+        };
 
-    pub fn background_image_pattern() -> (String, bool) {
-        (
-            "window.ftd.dependencies.eval_background_image({0}, data)".to_string(),
-            true,
-        )
-    }
+        let shape = ftd::executor::Value::new(
+            ftd::executor::RadialGradientShape::from_value(
+                get_property_value("shape")?.clone(),
+                doc,
+                line_number,
+            )?,
+            Some(line_number),
+            vec![get_property_value("shape")?
+                .into_property(ftd::interpreter::PropertySource::header("shape"))],
+        );
 
-    pub fn background_repeat_pattern() -> (String, bool) {
-        (
-            "window.ftd.dependencies.eval_background_repeat({0})".to_string(),
-            true,
-        )
-    }
+        let position = ftd::executor::Value::new(
+            ftd::executor::BackgroundPosition::from_optional_value(
+                get_property_value("position")?.clone(),
+                doc,
+                line_number,
+            )?,
+            Some(line_number),
+            vec![get_property_value("position")?
+                .into_property(ftd::interpreter::PropertySource::header("position"))],
+        );
 
-    pub fn background_color_pattern() -> (String, bool) {
-        (
-            "window.ftd.dependencies.eval_background_color({0}, data)".to_string(),
-            true,
-        )
-    }
+        let colors = ftd::executor::Value::new(
+            ftd::executor::LinearGradientColor::from_vec_values(
+                get_property_value("colors")?.clone(),
+                doc,
+                line_number,
+            )?,
+            Some(line_number),
+            vec![get_property_value("colors")?
+                .into_property(ftd::interpreter::PropertySource::header("colors"))],
+        );
 
-    pub fn background_size_pattern() -> (String, bool) {
-        (
-            "window.ftd.dependencies.eval_background_size({0})".to_string(),
-            true,
-        )
+        Ok(ftd::executor::RadialGradient {
+            shape,
+            position,
+            colors,
+        })
     }
 
-    pub fn background_position_pattern() -> (String, bool) {
-        (
-            "window.ftd.dependencies.eval_background_position({0})".to_string(),
-            true,
+    pub fn to_css_string(&self, device: &Option<ftd::executor::Device>) -> String {
+        let mut shape = self.shape.value.to_css_string();
+        if let Some(position) = self.position.value.as_ref() {
+            shape.push_str(format!(" at {}", position.to_css_string(device)).as_str());
+        }
+        format!(
+            "radial-gradient({}, {})",
+            shape,
+            self.colors
+                .value
+                .iter()
+                .map(|lc| lc.to_css_string(device))
+                .join(", ")
         )
     }
 }
 
 #[derive(serde::Deserialize, Debug, PartialEq, Clone, serde::Serialize)]
-pub enum BackgroundRepeat {
-    Repeat,
-    RepeatX,
-    RepeatY,
-    NoRepeat,
-    Space,
-    Round,
+pub struct ConicGradient {
+    pub from: ftd::executor::Value<i64>,
+    pub position: ftd::executor::Value<Option<ftd::executor::BackgroundPosition>>,
+    pub colors: ftd::executor::Value<Vec<LinearGradientColor>>,
 }
 
-impl BackgroundRepeat {
-    fn from_optional_value(
+impl ConicGradient {
+    fn from_value(
         value: ftd::interpreter::PropertyValue,
         doc: &ftd::executor::TDoc,
         line_number: usize,
-    ) -> ftd::executor::Result<Option<ftd::executor::BackgroundRepeat>> {
-        let binding = value.resolve(&doc.itdoc(), line_number)?;
-        if let ftd::interpreter::Value::Optional { data, .. } = &binding {
-            if data.is_none() {
-                return Ok(None);
+    ) -> ftd::executor::Result<ftd::executor::ConicGradient> {
+        let value = value.resolve(&doc.itdoc(), line_number)?;
+        let fields = match value.inner() {
+            Some(ftd::interpreter::Value::Record { name, fields })
+                if name.eq(ftd::interpreter::FTD_CONIC_GRADIENT) =>
+            {
+                fields
             }
-        }
-
-        let value = binding.get_or_type(doc.name, line_number)?;
-        let value = (value.1.to_owned(), value.2.to_owned());
-        Ok(Some(ftd::executor::BackgroundRepeat::from_values(
-            value,
-            doc,
-            line_number,
-        )?))
+            t => {
+                return ftd::executor::utils::parse_error(
+                    format!(
+                        "Expected value of type record `{}`, found: {:?}",
+                        ftd::interpreter::FTD_CONIC_GRADIENT,
+                        t
+                    ),
+                    doc.name,
+                    line_number,
+                )
+            }
+        };
+        ftd::executor::ConicGradient::from_values(fields, doc, line_number)
     }
 
     fn from_values(
-        or_type_value: (String, ftd::interpreter::PropertyValue),
+        values: ftd::Map<ftd::interpreter::PropertyValue>,
         doc: &ftd::executor::TDoc,
         line_number: usize,
-    ) -> ftd::executor::Result<ftd::executor::BackgroundRepeat> {
-        match or_type_value.0.as_str() {
-            ftd::interpreter::FTD_BACKGROUND_REPEAT_BOTH_REPEAT => {
-                Ok(ftd::executor::BackgroundRepeat::Repeat)
-            }
-            ftd::interpreter::FTD_BACKGROUND_REPEAT_X_REPEAT => {
-                Ok(ftd::executor::BackgroundRepeat::RepeatX)
-            }
-            ftd::interpreter::FTD_BACKGROUND_REPEAT_Y_REPEAT => {
-                Ok(ftd::executor::BackgroundRepeat::RepeatY)
-            }
-            ftd::interpreter::FTD_BACKGROUND_REPEAT_NO_REPEAT => {
-                Ok(ftd::executor::BackgroundRepeat::NoRepeat)
-            }
-            ftd::interpreter::FTD_BACKGROUND_REPEAT_SPACE => {
-                Ok(ftd::executor::BackgroundRepeat::Space)
-            }
-            ftd::interpreter::FTD_BACKGROUND_REPEAT_ROUND => {
-                Ok(ftd::executor::BackgroundRepeat::Round)
-            }
-            t => ftd::executor::utils::parse_error(
-                format!(
-                    "Unknown variant `{}` for or-type `ftd.background-repeat`",
-                    t
-                ),
-                doc.name,
+    ) -> ftd::executor::Result<ftd::executor::ConicGradient> {
+        let get_property_value = |field_name: &str| {
+            values
+                .get(field_name)
+                .ok_or_else(|| ftd::executor::Error::ParseError {
+                    message: format!("`{}` field in ftd.conic-gradient not found", field_name),
+                    doc_id: doc.name.to_string(),
+                    line_number,
+                })
+        };
+
+        let from = ftd::executor::Value::new(
+            get_property_value("from")?
+                .clone()
+                .resolve(&doc.itdoc(), line_number)?
+                .integer(doc.name, line_number)?,
+            Some(line_number),
+            vec![get_property_value("from")?
+                .into_property(ftd::interpreter::PropertySource::header("from"))],
+        );
+
+        let position = ftd::executor::Value::new(
+            ftd::executor::BackgroundPosition::from_optional_value(
+                get_property_value("position")?.clone(),
+                doc,
                 line_number,
-            ),
-        }
+            )?,
+            Some(line_number),
+            vec![get_property_value("position")?
+                .into_property(ftd::interpreter::PropertySource::header("position"))],
+        );
+
+        let colors = ftd::executor::Value::new(
+            ftd::executor::LinearGradientColor::from_vec_values(
+                get_property_value("colors")?.clone(),
+                doc,
+                line_number,
+            )?,
+            Some(line_number),
+            vec![get_property_value("colors")?
+                .into_property(ftd::interpreter::PropertySource::header("colors"))],
+        );
+
+        Ok(ftd::executor::ConicGradient {
+            from,
+            position,
+            colors,
+        })
     }
 
-    pub fn to_css_string(&self) -> String {
-        match self {
-            ftd::executor::BackgroundRepeat::Repeat => "repeat".to_string(),
-            ftd::executor::BackgroundRepeat::RepeatX => "repeat-x".to_string(),
-            ftd::executor::BackgroundRepeat::RepeatY => "repeat-y".to_string(),
-            ftd::executor::BackgroundRepeat::NoRepeat => "no-repeat".to_string(),
-            ftd::executor::BackgroundRepeat::Space => "space".to_string(),
-            ftd::executor::BackgroundRepeat::Round => "round".to_string(),
+    pub fn to_css_string(&self, device: &Option<ftd::executor::Device>) -> String {
+        let mut from = format!("from {}deg", self.from.value);
+        if let Some(position) = self.position.value.as_ref() {
+            from.push_str(format!(" at {}", position.to_css_string(device)).as_str());
         }
+        format!(
+            "conic-gradient({}, {})",
+            from,
+            self.colors
+                .value
+                .iter()
+                .map(|lc| lc.to_css_string(device))
+                .join(", ")
+        )
     }
 }
 
+/// One layer of `ftd.layered-background`. Exactly one of the fields of the
+/// `ftd.background-layer` record has to be set.
 #[derive(serde::Deserialize, Debug, PartialEq, Clone, serde::Serialize)]
-pub enum BackgroundSize {
-    Auto,
-    Cover,
-    Contain,
-    Length(LengthPair),
+pub enum BackgroundLayer {
+    Image(Box<ftd::executor::BackgroundImage>),
+    LinearGradient(Box<ftd::executor::LinearGradient>),
+    RadialGradient(Box<ftd::executor::RadialGradient>),
+    ConicGradient(Box<ftd::executor::ConicGradient>),
 }
 
-impl BackgroundSize {
-    fn from_optional_value(
+impl BackgroundLayer {
+    fn from_vec_values(
         value: ftd::interpreter::PropertyValue,
         doc: &ftd::executor::TDoc,
         line_number: usize,
-    ) -> ftd::executor::Result<Option<ftd::executor::BackgroundSize>> {
-        let binding = value.resolve(&doc.itdoc(), line_number)?;
-        if let ftd::interpreter::Value::Optional { data, .. } = &binding {
-            if data.is_none() {
-                return Ok(None);
+    ) -> ftd::executor::Result<Vec<BackgroundLayer>> {
+        let mut result = vec![];
+        let value = value.resolve(&doc.itdoc(), line_number)?;
+        match value.inner() {
+            Some(ftd::interpreter::Value::List { data, kind })
+                if kind
+                    .kind
+                    .get_name()
+                    .eq(ftd::interpreter::FTD_BACKGROUND_LAYER) =>
+            {
+                for element in data.iter() {
+                    let ln = element.line_number();
+                    result.push(BackgroundLayer::from_value(element.to_owned(), doc, ln)?)
+                }
             }
-        }
+            t => {
+                return ftd::executor::utils::parse_error(
+                    format!(
+                        "Expected list value of type `{}`, found: {:?}",
+                        ftd::interpreter::FTD_BACKGROUND_LAYER,
+                        t
+                    ),
+                    doc.name,
+                    line_number,
+                )
+            }
+        };
+        Ok(result)
+    }
 
-        let value = binding.get_or_type(doc.name, line_number)?;
-        let value = (value.1.to_owned(), value.2.to_owned());
-        Ok(Some(ftd::executor::BackgroundSize::from_values(
-            value,
-            doc,
-            line_number,
-        )?))
+    fn from_value(
+        value: ftd::interpreter::PropertyValue,
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<BackgroundLayer> {
+        let value = value.resolve(&doc.itdoc(), line_number)?;
+        let fields = match value.inner() {
+            Some(ftd::interpreter::Value::Record { name, fields })
+                if name.eq(ftd::interpreter::FTD_BACKGROUND_LAYER) =>
+            {
+                fields
+            }
+            t => {
+                return ftd::executor::utils::parse_error(
+                    format!(
+                        "Expected value of type record `{}`, found: {:?}",
+                        ftd::interpreter::FTD_BACKGROUND_LAYER,
+                        t
+                    ),
+                    doc.name,
+                    line_number,
+                )
+            }
+        };
+        BackgroundLayer::from_values(fields, doc, line_number)
     }
 
     fn from_values(
-        or_type_value: (String, ftd::interpreter::PropertyValue),
+        values: ftd::Map<ftd::interpreter::PropertyValue>,
         doc: &ftd::executor::TDoc,
         line_number: usize,
-    ) -> ftd::executor::Result<ftd::executor::BackgroundSize> {
-        match or_type_value.0.as_str() {
-            ftd::interpreter::FTD_BACKGROUND_SIZE_AUTO => Ok(ftd::executor::BackgroundSize::Auto),
-            ftd::interpreter::FTD_BACKGROUND_SIZE_COVER => Ok(ftd::executor::BackgroundSize::Cover),
-            ftd::interpreter::FTD_BACKGROUND_SIZE_CONTAIN => {
-                Ok(ftd::executor::BackgroundSize::Contain)
-            }
-            ftd::interpreter::FTD_BACKGROUND_SIZE_LENGTH => {
-                Ok(ftd::executor::BackgroundSize::Length(
-                    LengthPair::from_value(or_type_value.1, doc, line_number)?,
-                ))
+    ) -> ftd::executor::Result<BackgroundLayer> {
+        let mut layers = vec![];
+        for field_name in [
+            "image",
+            "linear-gradient",
+            "radial-gradient",
+            "conic-gradient",
+        ] {
+            let value = match values.get(field_name) {
+                Some(value) => value.clone(),
+                None => continue,
+            };
+            if value
+                .clone()
+                .resolve(&doc.itdoc(), line_number)?
+                .inner()
+                .is_none()
+            {
+                continue;
             }
-            t => ftd::executor::utils::parse_error(
-                format!("Unknown variant `{}` for or-type `ftd.background-size`", t),
+            layers.push(match field_name {
+                "image" => BackgroundLayer::Image(Box::new(
+                    ftd::executor::BackgroundImage::from_value(value, doc, line_number)?,
+                )),
+                "linear-gradient" => BackgroundLayer::LinearGradient(Box::new(
+                    ftd::executor::LinearGradient::from_value(value, doc, line_number)?,
+                )),
+                "radial-gradient" => BackgroundLayer::RadialGradient(Box::new(
+                    ftd::executor::RadialGradient::from_value(value, doc, line_number)?,
+                )),
+                _ => BackgroundLayer::ConicGradient(Box::new(
+                    ftd::executor::ConicGradient::from_value(value, doc, line_number)?,
+                )),
+            });
+        }
+
+        if layers.len() != 1 {
+            return ftd::executor::utils::parse_error(
+                format!(
+                    "Expected exactly one of `image`, `linear-gradient`, `radial-gradient` or \
+                    `conic-gradient` in `{}`, found: {}",
+                    ftd::interpreter::FTD_BACKGROUND_LAYER,
+                    layers.len()
+                ),
                 doc.name,
                 line_number,
-            ),
+            );
         }
+
+        Ok(layers.remove(0))
     }
 
-    pub fn to_css_string(&self, device: &Option<ftd::executor::Device>) -> String {
+    pub fn to_image_css_string(&self, device: &Option<ftd::executor::Device>) -> String {
         match self {
-            ftd::executor::BackgroundSize::Auto => "auto".to_string(),
-            ftd::executor::BackgroundSize::Cover => "cover".to_string(),
-            ftd::executor::BackgroundSize::Contain => "contain".to_string(),
-            ftd::executor::BackgroundSize::Length(l) => l.to_css_string(device),
+            BackgroundLayer::Image(i) => i.to_image_src_css_string(),
+            BackgroundLayer::LinearGradient(l) => l.to_css_string(device),
+            BackgroundLayer::RadialGradient(r) => r.to_css_string(device),
+            BackgroundLayer::ConicGradient(c) => c.to_css_string(device),
+        }
+    }
+
+    pub fn to_repeat_css_string(&self) -> String {
+        match self {
+            BackgroundLayer::Image(i) if i.repeat.value.is_some() => i.to_repeat_css_string(),
+            _ => "repeat".to_string(),
+        }
+    }
+
+    pub fn to_size_css_string(&self, device: &Option<ftd::executor::Device>) -> String {
+        match self {
+            BackgroundLayer::Image(i) if i.size.value.is_some() => i.to_size_css_string(device),
+            _ => "auto".to_string(),
+        }
+    }
+
+    pub fn to_position_css_string(&self, device: &Option<ftd::executor::Device>) -> String {
+        match self {
+            BackgroundLayer::Image(i) if i.position.value.is_some() => {
+                i.to_position_css_string(device)
+            }
+            _ => "0% 0%".to_string(),
         }
     }
 }
 
 #[derive(serde::Deserialize, Debug, PartialEq, Clone, serde::Serialize)]
-pub enum BackgroundPosition {
-    Left,
-    Center,
-    Right,
-    LeftTop,
-    LeftCenter,
-    LeftBottom,
-    CenterTop,
-    CenterCenter,
-    CenterBottom,
-    RightTop,
-    RightCenter,
-    RightBottom,
-    Length(LengthPair),
+pub struct LayeredBackground {
+    pub layers: ftd::executor::Value<Vec<BackgroundLayer>>,
+    pub color: ftd::executor::Value<Option<Color>>,
 }
 
-impl BackgroundPosition {
-    fn from_optional_value(
-        value: ftd::interpreter::PropertyValue,
+impl LayeredBackground {
+    fn from_value(
+        value: ftd::interpreter::PropertyValue,
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<ftd::executor::LayeredBackground> {
+        let value = value.resolve(&doc.itdoc(), line_number)?;
+        let fields = match value.inner() {
+            Some(ftd::interpreter::Value::Record { name, fields })
+                if name.eq(ftd::interpreter::FTD_LAYERED_BACKGROUND) =>
+            {
+                fields
+            }
+            t => {
+                return ftd::executor::utils::parse_error(
+                    format!(
+                        "Expected value of type record `{}`, found: {:?}",
+                        ftd::interpreter::FTD_LAYERED_BACKGROUND,
+                        t
+                    ),
+                    doc.name,
+                    line_number,
+                )
+            }
+        };
+        ftd::executor::LayeredBackground::from_values(fields, doc, line_number)
+    }
+
+    fn from_values(
+        values: ftd::Map<ftd::interpreter::PropertyValue>,
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<ftd::executor::LayeredBackground> {
+        let get_property_value = |field_name: &str| {
+            values
+                .get(field_name)
+                .ok_or_else(|| ftd::executor::Error::ParseError {
+                    message: format!("`{}` field in ftd.layered-background not found", field_name),
+                    doc_id: doc.name.to_string(),
+                    line_number,
+                })
+        };
+
+        let layers = ftd::executor::Value::new(
+            ftd::executor::BackgroundLayer::from_vec_values(
+                get_property_value("layers")?.clone(),
+                doc,
+                line_number,
+            )?,
+            Some(line_number),
+            vec![get_property_value("layers")?
+                .into_property(ftd::interpreter::PropertySource::header("layers"))],
+        );
+
+        let color = match values.get("color") {
+            Some(value) => {
+                let color = match value.clone().resolve(&doc.itdoc(), line_number)?.inner() {
+                    Some(_) => Some(Color::from_value(value.clone(), doc, line_number)?),
+                    None => None,
+                };
+                ftd::executor::Value::new(
+                    color,
+                    Some(line_number),
+                    vec![value.into_property(ftd::interpreter::PropertySource::header("color"))],
+                )
+            }
+            None => ftd::executor::Value::new(None, Some(line_number), vec![]),
+        };
+
+        Ok(ftd::executor::LayeredBackground { layers, color })
+    }
+
+    fn join_layers<F>(&self, f: F) -> String
+    where
+        F: Fn(&BackgroundLayer) -> String,
+    {
+        if self.layers.value.is_empty() {
+            return ftd::interpreter::FTD_IGNORE_KEY.to_string();
+        }
+        self.layers.value.iter().map(f).join(", ")
+    }
+
+    pub fn to_solid_css_string(&self) -> String {
+        match self.color.value.as_ref() {
+            Some(c) => c.light.value.to_css_string(),
+            None => ftd::interpreter::FTD_IGNORE_KEY.to_string(),
+        }
+    }
+
+    pub fn to_image_css_string(&self, device: &Option<ftd::executor::Device>) -> String {
+        self.join_layers(|l| l.to_image_css_string(device))
+    }
+
+    pub fn to_repeat_css_string(&self) -> String {
+        self.join_layers(|l| l.to_repeat_css_string())
+    }
+
+    pub fn to_size_css_string(&self, device: &Option<ftd::executor::Device>) -> String {
+        self.join_layers(|l| l.to_size_css_string(device))
+    }
+
+    pub fn to_position_css_string(&self, device: &Option<ftd::executor::Device>) -> String {
+        self.join_layers(|l| l.to_position_css_string(device))
+    }
+}
+
+#[derive(serde::Deserialize, Debug, PartialEq, Clone, serde::Serialize)]
+pub enum Background {
+    Solid(ftd::executor::Color),
+    Image(ftd::executor::BackgroundImage),
+    LinearGradient(ftd::executor::LinearGradient),
+    RadialGradient(ftd::executor::RadialGradient),
+    ConicGradient(ftd::executor::ConicGradient),
+    Layers(ftd::executor::LayeredBackground),
+}
+
+impl Background {
+    fn from_optional_values(
+        or_type_value: Option<(String, ftd::interpreter::PropertyValue)>,
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<Option<Self>> {
+        if let Some(value) = or_type_value {
+            Ok(Some(ftd::executor::Background::from_values(
+                value,
+                doc,
+                line_number,
+            )?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn from_values(
+        or_type_value: (String, ftd::interpreter::PropertyValue),
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<Self> {
+        match or_type_value.0.as_str() {
+            ftd::interpreter::FTD_BACKGROUND_SOLID => Ok(ftd::executor::Background::Solid(
+                Color::from_value(or_type_value.1, doc, line_number)?,
+            )),
+            ftd::interpreter::FTD_BACKGROUND_IMAGE => Ok(ftd::executor::Background::Image(
+                ftd::executor::BackgroundImage::from_value(or_type_value.1, doc, line_number)?,
+            )),
+            ftd::interpreter::FTD_BACKGROUND_LINEAR_GRADIENT => {
+                Ok(ftd::executor::Background::LinearGradient(
+                    ftd::executor::LinearGradient::from_value(or_type_value.1, doc, line_number)?,
+                ))
+            }
+            ftd::interpreter::FTD_BACKGROUND_RADIAL_GRADIENT => {
+                Ok(ftd::executor::Background::RadialGradient(
+                    ftd::executor::RadialGradient::from_value(or_type_value.1, doc, line_number)?,
+                ))
+            }
+            ftd::interpreter::FTD_BACKGROUND_CONIC_GRADIENT => {
+                Ok(ftd::executor::Background::ConicGradient(
+                    ftd::executor::ConicGradient::from_value(or_type_value.1, doc, line_number)?,
+                ))
+            }
+            ftd::interpreter::FTD_BACKGROUND_LAYERS => Ok(ftd::executor::Background::Layers(
+                ftd::executor::LayeredBackground::from_value(or_type_value.1, doc, line_number)?,
+            )),
+            t => ftd::executor::utils::parse_error(
+                format!("Unknown variant `{}` for or-type `ftd.background`", t),
+                doc.name,
+                line_number,
+            ),
+        }
+    }
+
+    pub(crate) fn optional_background(
+        properties: &[ftd::interpreter::Property],
+        arguments: &[ftd::interpreter::Argument],
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+        key: &str,
+        inherited_variables: &ftd::VecMap<(String, Vec<usize>)>,
+        component_name: &str,
+    ) -> ftd::executor::Result<ftd::executor::Value<Option<ftd::executor::Background>>> {
+        let or_type_value = ftd::executor::value::optional_or_type(
+            key,
+            component_name,
+            properties,
+            arguments,
+            doc,
+            line_number,
+            ftd::interpreter::FTD_BACKGROUND,
+            inherited_variables,
+        )?;
+
+        Ok(ftd::executor::Value::new(
+            ftd::executor::Background::from_optional_values(or_type_value.value, doc, line_number)?,
+            or_type_value.line_number,
+            or_type_value.properties,
+        ))
+    }
+
+    pub fn to_solid_css_string(&self) -> String {
+        match self {
+            ftd::executor::Background::Solid(c) => c.light.value.to_css_string(),
+            ftd::executor::Background::Layers(l) => l.to_solid_css_string(),
+            _ => ftd::interpreter::FTD_IGNORE_KEY.to_string(),
+        }
+    }
+
+    pub fn to_image_src_css_string(&self, device: &Option<ftd::executor::Device>) -> String {
+        match self {
+            ftd::executor::Background::Solid(_) => ftd::interpreter::FTD_IGNORE_KEY.to_string(),
+            ftd::executor::Background::Image(i) => i.to_image_src_css_string(),
+            ftd::executor::Background::LinearGradient(l) => l.to_css_string(device),
+            ftd::executor::Background::RadialGradient(r) => r.to_css_string(device),
+            ftd::executor::Background::ConicGradient(c) => c.to_css_string(device),
+            ftd::executor::Background::Layers(l) => l.to_image_css_string(device),
+        }
+    }
+
+    pub fn to_image_repeat_css_string(&self) -> String {
+        match self {
+            ftd::executor::Background::Image(i) => i.to_repeat_css_string(),
+            ftd::executor::Background::Layers(l) => l.to_repeat_css_string(),
+            _ => ftd::interpreter::FTD_IGNORE_KEY.to_string(),
+        }
+    }
+
+    pub fn to_image_size_css_string(&self, device: &Option<ftd::executor::Device>) -> String {
+        match self {
+            ftd::executor::Background::Image(i) => i.to_size_css_string(device),
+            ftd::executor::Background::Layers(l) => l.to_size_css_string(device),
+            _ => ftd::interpreter::FTD_IGNORE_KEY.to_string(),
+        }
+    }
+
+    pub fn to_image_position_css_string(&self, device: &Option<ftd::executor::Device>) -> String {
+        match self {
+            ftd::executor::Background::Image(i) => i.to_position_css_string(device),
+            ftd::executor::Background::Layers(l) => l.to_position_css_string(device),
+            _ => ftd::interpreter::FTD_IGNORE_KEY.to_string(),
+        }
+    }
+
+    pub fn background_image_pattern() -> (String, bool) {
+        (
+            "window.ftd.dependencies.eval_background_image({0}, data)".to_string(),
+            true,
+        )
+    }
+
+    pub fn background_repeat_pattern() -> (String, bool) {
+        (
+            "window.ftd.dependencies.eval_background_repeat({0})".to_string(),
+            true,
+        )
+    }
+
+    pub fn background_color_pattern() -> (String, bool) {
+        (
+            "window.ftd.dependencies.eval_background_color({0}, data)".to_string(),
+            true,
+        )
+    }
+
+    pub fn background_size_pattern() -> (String, bool) {
+        (
+            "window.ftd.dependencies.eval_background_size({0})".to_string(),
+            true,
+        )
+    }
+
+    pub fn background_position_pattern() -> (String, bool) {
+        (
+            "window.ftd.dependencies.eval_background_position({0})".to_string(),
+            true,
+        )
+    }
+}
+
+#[derive(serde::Deserialize, Debug, PartialEq, Clone, serde::Serialize)]
+pub enum BackgroundRepeat {
+    Repeat,
+    RepeatX,
+    RepeatY,
+    NoRepeat,
+    Space,
+    Round,
+}
+
+impl BackgroundRepeat {
+    fn from_optional_value(
+        value: ftd::interpreter::PropertyValue,
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<Option<ftd::executor::BackgroundRepeat>> {
+        let binding = value.resolve(&doc.itdoc(), line_number)?;
+        if let ftd::interpreter::Value::Optional { data, .. } = &binding {
+            if data.is_none() {
+                return Ok(None);
+            }
+        }
+
+        let value = binding.get_or_type(doc.name, line_number)?;
+        let value = (value.1.to_owned(), value.2.to_owned());
+        Ok(Some(ftd::executor::BackgroundRepeat::from_values(
+            value,
+            doc,
+            line_number,
+        )?))
+    }
+
+    fn from_values(
+        or_type_value: (String, ftd::interpreter::PropertyValue),
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<ftd::executor::BackgroundRepeat> {
+        match or_type_value.0.as_str() {
+            ftd::interpreter::FTD_BACKGROUND_REPEAT_BOTH_REPEAT => {
+                Ok(ftd::executor::BackgroundRepeat::Repeat)
+            }
+            ftd::interpreter::FTD_BACKGROUND_REPEAT_X_REPEAT => {
+                Ok(ftd::executor::BackgroundRepeat::RepeatX)
+            }
+            ftd::interpreter::FTD_BACKGROUND_REPEAT_Y_REPEAT => {
+                Ok(ftd::executor::BackgroundRepeat::RepeatY)
+            }
+            ftd::interpreter::FTD_BACKGROUND_REPEAT_NO_REPEAT => {
+                Ok(ftd::executor::BackgroundRepeat::NoRepeat)
+            }
+            ftd::interpreter::FTD_BACKGROUND_REPEAT_SPACE => {
+                Ok(ftd::executor::BackgroundRepeat::Space)
+            }
+            ftd::interpreter::FTD_BACKGROUND_REPEAT_ROUND => {
+                Ok(ftd::executor::BackgroundRepeat::Round)
+            }
+            t => ftd::executor::utils::parse_error(
+                format!(
+                    "Unknown variant `{}` for or-type `ftd.background-repeat`",
+                    t
+                ),
+                doc.name,
+                line_number,
+            ),
+        }
+    }
+
+    pub fn to_css_string(&self) -> String {
+        match self {
+            ftd::executor::BackgroundRepeat::Repeat => "repeat".to_string(),
+            ftd::executor::BackgroundRepeat::RepeatX => "repeat-x".to_string(),
+            ftd::executor::BackgroundRepeat::RepeatY => "repeat-y".to_string(),
+            ftd::executor::BackgroundRepeat::NoRepeat => "no-repeat".to_string(),
+            ftd::executor::BackgroundRepeat::Space => "space".to_string(),
+            ftd::executor::BackgroundRepeat::Round => "round".to_string(),
+        }
+    }
+}
+
+#[derive(serde::Deserialize, Debug, PartialEq, Clone, serde::Serialize)]
+pub enum BackgroundSize {
+    Auto,
+    Cover,
+    Contain,
+    Length(LengthPair),
+}
+
+impl BackgroundSize {
+    fn from_optional_value(
+        value: ftd::interpreter::PropertyValue,
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<Option<ftd::executor::BackgroundSize>> {
+        let binding = value.resolve(&doc.itdoc(), line_number)?;
+        if let ftd::interpreter::Value::Optional { data, .. } = &binding {
+            if data.is_none() {
+                return Ok(None);
+            }
+        }
+
+        let value = binding.get_or_type(doc.name, line_number)?;
+        let value = (value.1.to_owned(), value.2.to_owned());
+        Ok(Some(ftd::executor::BackgroundSize::from_values(
+            value,
+            doc,
+            line_number,
+        )?))
+    }
+
+    fn from_values(
+        or_type_value: (String, ftd::interpreter::PropertyValue),
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<ftd::executor::BackgroundSize> {
+        match or_type_value.0.as_str() {
+            ftd::interpreter::FTD_BACKGROUND_SIZE_AUTO => Ok(ftd::executor::BackgroundSize::Auto),
+            ftd::interpreter::FTD_BACKGROUND_SIZE_COVER => Ok(ftd::executor::BackgroundSize::Cover),
+            ftd::interpreter::FTD_BACKGROUND_SIZE_CONTAIN => {
+                Ok(ftd::executor::BackgroundSize::Contain)
+            }
+            ftd::interpreter::FTD_BACKGROUND_SIZE_LENGTH => {
+                Ok(ftd::executor::BackgroundSize::Length(
+                    LengthPair::from_value(or_type_value.1, doc, line_number)?,
+                ))
+            }
+            t => ftd::executor::utils::parse_error(
+                format!("Unknown variant `{}` for or-type `ftd.background-size`", t),
+                doc.name,
+                line_number,
+            ),
+        }
+    }
+
+    pub fn to_css_string(&self, device: &Option<ftd::executor::Device>) -> String {
+        match self {
+            ftd::executor::BackgroundSize::Auto => "auto".to_string(),
+            ftd::executor::BackgroundSize::Cover => "cover".to_string(),
+            ftd::executor::BackgroundSize::Contain => "contain".to_string(),
+            ftd::executor::BackgroundSize::Length(l) => l.to_css_string(device),
+        }
+    }
+}
+
+#[derive(serde::Deserialize, Debug, PartialEq, Clone, serde::Serialize)]
+pub enum BackgroundPosition {
+    Left,
+    Center,
+    Right,
+    LeftTop,
+    LeftCenter,
+    LeftBottom,
+    CenterTop,
+    CenterCenter,
+    CenterBottom,
+    RightTop,
+    RightCenter,
+    RightBottom,
+    Length(LengthPair),
+}
+
+impl BackgroundPosition {
+    fn from_optional_value(
+        value: ftd::interpreter::PropertyValue,
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<Option<ftd::executor::BackgroundPosition>> {
+        let binding = value.resolve(&doc.itdoc(), line_number)?;
+        if let ftd::interpreter::Value::Optional { data, .. } = &binding {
+            if data.is_none() {
+                return Ok(None);
+            }
+        }
+
+        let value = binding.get_or_type(doc.name, line_number)?;
+        let value = (value.1.to_owned(), value.2.to_owned());
+        Ok(Some(ftd::executor::BackgroundPosition::from_values(
+            value,
+            doc,
+            line_number,
+        )?))
+    }
+
+    fn from_values(
+        or_type_value: (String, ftd::interpreter::PropertyValue),
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<ftd::executor::BackgroundPosition> {
+        match or_type_value.0.as_str() {
+            ftd::interpreter::FTD_BACKGROUND_POSITION_LEFT => {
+                Ok(ftd::executor::BackgroundPosition::Left)
+            }
+            ftd::interpreter::FTD_BACKGROUND_POSITION_CENTER => {
+                Ok(ftd::executor::BackgroundPosition::Center)
+            }
+            ftd::interpreter::FTD_BACKGROUND_POSITION_RIGHT => {
+                Ok(ftd::executor::BackgroundPosition::Right)
+            }
+            ftd::interpreter::FTD_BACKGROUND_POSITION_LEFT_TOP => {
+                Ok(ftd::executor::BackgroundPosition::LeftTop)
+            }
+            ftd::interpreter::FTD_BACKGROUND_POSITION_LEFT_CENTER => {
+                Ok(ftd::executor::BackgroundPosition::LeftCenter)
+            }
+            ftd::interpreter::FTD_BACKGROUND_POSITION_LEFT_BOTTOM => {
+                Ok(ftd::executor::BackgroundPosition::LeftBottom)
+            }
+            ftd::interpreter::FTD_BACKGROUND_POSITION_CENTER_TOP => {
+                Ok(ftd::executor::BackgroundPosition::CenterTop)
+            }
+            ftd::interpreter::FTD_BACKGROUND_POSITION_CENTER_CENTER => {
+                Ok(ftd::executor::BackgroundPosition::CenterCenter)
+            }
+            ftd::interpreter::FTD_BACKGROUND_POSITION_CENTER_BOTTOM => {
+                Ok(ftd::executor::BackgroundPosition::CenterBottom)
+            }
+            ftd::interpreter::FTD_BACKGROUND_POSITION_RIGHT_TOP => {
+                Ok(ftd::executor::BackgroundPosition::RightTop)
+            }
+            ftd::interpreter::FTD_BACKGROUND_POSITION_RIGHT_CENTER => {
+                Ok(ftd::executor::BackgroundPosition::RightCenter)
+            }
+            ftd::interpreter::FTD_BACKGROUND_POSITION_RIGHT_BOTTOM => {
+                Ok(ftd::executor::BackgroundPosition::RightBottom)
+            }
+            ftd::interpreter::FTD_BACKGROUND_POSITION_LENGTH => {
+                Ok(ftd::executor::BackgroundPosition::Length(
+                    LengthPair::from_value(or_type_value.1, doc, line_number)?,
+                ))
+            }
+            t => ftd::executor::utils::parse_error(
+                format!(
+                    "Unknown variant `{}` for or-type `ftd.background-position`",
+                    t
+                ),
+                doc.name,
+                line_number,
+            ),
+        }
+    }
+
+    pub fn to_css_string(&self, device: &Option<ftd::executor::Device>) -> String {
+        match self {
+            ftd::executor::BackgroundPosition::Left => "left".to_string(),
+            ftd::executor::BackgroundPosition::Center => "center".to_string(),
+            ftd::executor::BackgroundPosition::Right => "right".to_string(),
+            ftd::executor::BackgroundPosition::LeftTop => "left top".to_string(),
+            ftd::executor::BackgroundPosition::LeftCenter => "left center".to_string(),
+            ftd::executor::BackgroundPosition::LeftBottom => "left bottom".to_string(),
+            ftd::executor::BackgroundPosition::CenterTop => "center top".to_string(),
+            ftd::executor::BackgroundPosition::CenterCenter => "center center".to_string(),
+            ftd::executor::BackgroundPosition::CenterBottom => "center bottom".to_string(),
+            ftd::executor::BackgroundPosition::RightTop => "right top".to_string(),
+            ftd::executor::BackgroundPosition::RightCenter => "right center".to_string(),
+            ftd::executor::BackgroundPosition::RightBottom => "right bottom".to_string(),
+            ftd::executor::BackgroundPosition::Length(l) => l.to_css_string(device),
+        }
+    }
+}
+
+#[derive(serde::Deserialize, Debug, Default, PartialEq, Clone, serde::Serialize)]
+pub struct Shadow {
+    pub x_offset: ftd::executor::Value<Length>,
+    pub y_offset: ftd::executor::Value<Length>,
+    pub blur: ftd::executor::Value<Length>,
+    pub spread: ftd::executor::Value<Length>,
+    pub inset: ftd::executor::Value<bool>,
+    pub color: ftd::executor::Value<Color>,
+}
+
+impl Shadow {
+    fn from_values(
+        values: ftd::Map<ftd::interpreter::PropertyValue>,
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<ftd::executor::Shadow> {
+        let get_property_value = |field_name: &str| {
+            values
+                .get(field_name)
+                .ok_or_else(|| ftd::executor::Error::ParseError {
+                    message: format!("`{}` field in ftd.shadow not found", field_name),
+                    doc_id: doc.name.to_string(),
+                    line_number,
+                })
+        };
+
+        let x_offset = ftd::executor::Value::new(
+            Length::from_value(get_property_value("x-offset")?.clone(), doc, line_number)?,
+            Some(line_number),
+            vec![get_property_value("x-offset")?
+                .into_property(ftd::interpreter::PropertySource::header("x-offset"))],
+        );
+
+        let y_offset = ftd::executor::Value::new(
+            Length::from_value(get_property_value("y-offset")?.clone(), doc, line_number)?,
+            Some(line_number),
+            vec![get_property_value("y-offset")?
+                .into_property(ftd::interpreter::PropertySource::header("y-offset"))],
+        );
+
+        let blur = ftd::executor::Value::new(
+            Length::from_value(get_property_value("blur")?.clone(), doc, line_number)?,
+            Some(line_number),
+            vec![get_property_value("blur")?
+                .into_property(ftd::interpreter::PropertySource::header("blur"))],
+        );
+
+        let spread = ftd::executor::Value::new(
+            Length::from_value(get_property_value("spread")?.clone(), doc, line_number)?,
+            Some(line_number),
+            vec![get_property_value("spread")?
+                .into_property(ftd::interpreter::PropertySource::header("spread"))],
+        );
+
+        let color = ftd::executor::Value::new(
+            Color::from_value(get_property_value("color")?.clone(), doc, line_number)?,
+            Some(line_number),
+            vec![get_property_value("color")?
+                .into_property(ftd::interpreter::PropertySource::header("color"))],
+        );
+
+        let inset = ftd::executor::Value::new(
+            get_property_value("inset")?
+                .clone()
+                .resolve(&doc.itdoc(), line_number)?
+                .bool(doc.name, line_number)?,
+            Some(line_number),
+            vec![get_property_value("inset")?
+                .into_property(ftd::interpreter::PropertySource::header("inset"))],
+        );
+
+        Ok(ftd::executor::Shadow {
+            x_offset,
+            y_offset,
+            blur,
+            spread,
+            color,
+            inset,
+        })
+    }
+
+    fn from_optional_values(
+        or_type_value: Option<ftd::Map<ftd::interpreter::PropertyValue>>,
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<Option<ftd::executor::Shadow>> {
+        if let Some(value) = or_type_value {
+            Ok(Some(ftd::executor::Shadow::from_values(
+                value,
+                doc,
+                line_number,
+            )?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub(crate) fn optional_shadow(
+        properties: &[ftd::interpreter::Property],
+        arguments: &[ftd::interpreter::Argument],
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+        key: &str,
+        inherited_variables: &ftd::VecMap<(String, Vec<usize>)>,
+        component_name: &str,
+    ) -> ftd::executor::Result<ftd::executor::Value<Option<ftd::executor::Shadow>>> {
+        let record_values = ftd::executor::value::optional_record_inherited(
+            key,
+            component_name,
+            properties,
+            arguments,
+            doc,
+            line_number,
+            ftd::interpreter::FTD_SHADOW,
+            inherited_variables,
+        )?;
+
+        Ok(ftd::executor::Value::new(
+            ftd::executor::Shadow::from_optional_values(record_values.value, doc, line_number)?,
+            record_values.line_number,
+            record_values.properties,
+        ))
+    }
+
+    pub fn to_css_string(&self, device: &Option<ftd::executor::Device>) -> String {
+        let x_offset = self.x_offset.value.to_css_string(device);
+        let y_offset = self.y_offset.value.to_css_string(device);
+        let blur = self.blur.value.to_css_string(device);
+        let spread = self.spread.value.to_css_string(device);
+        let inset = match self.inset.value {
+            true => "inset".to_string(),
+            false => "".to_string(),
+        };
+        let color = self.color.value.to_css_string();
+
+        format!(
+            "{} {} {} {} {} {}",
+            inset, color, x_offset, y_offset, blur, spread
+        )
+    }
+
+    pub fn box_shadow_pattern() -> (String, bool) {
+        (
+            "window.ftd.dependencies.eval_box_shadow({0}, data)".to_string(),
+            true,
+        )
+    }
+}
+
+#[derive(serde::Deserialize, Debug, Default, PartialEq, Clone, serde::Serialize)]
+pub struct Transform {
+    pub translate_x: Option<Length>,
+    pub translate_y: Option<Length>,
+    pub rotate: Option<i64>,
+    pub scale: Option<f64>,
+    pub scale_x: Option<f64>,
+    pub scale_y: Option<f64>,
+    pub skew_x: Option<i64>,
+    pub skew_y: Option<i64>,
+}
+
+impl Transform {
+    fn from_optional_value(
+        value: ftd::interpreter::PropertyValue,
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<Option<Transform>> {
+        let value = value.resolve(&doc.itdoc(), line_number)?;
+        match value.inner() {
+            Some(ftd::interpreter::Value::Record { name, fields })
+                if name.eq(ftd::interpreter::FTD_TRANSFORM) =>
+            {
+                Ok(Some(Transform::from_values(fields, doc, line_number)?))
+            }
+            None => Ok(None),
+            t => ftd::executor::utils::parse_error(
+                format!(
+                    "Expected value of type record `{}`, found: {:?}",
+                    ftd::interpreter::FTD_TRANSFORM,
+                    t
+                ),
+                doc.name,
+                line_number,
+            ),
+        }
+    }
+
+    fn from_optional_values(
+        or_type_value: Option<ftd::Map<ftd::interpreter::PropertyValue>>,
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<Option<Transform>> {
+        if let Some(value) = or_type_value {
+            Ok(Some(Transform::from_values(value, doc, line_number)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn from_values(
+        values: ftd::Map<ftd::interpreter::PropertyValue>,
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<Transform> {
+        let length = |field_name: &str| {
+            Length::from_optional_value(values.get(field_name).cloned(), doc, line_number)
+        };
+        let integer = |field_name: &str| -> ftd::executor::Result<Option<i64>> {
+            if let Some(value) = values.get(field_name) {
+                Ok(value
+                    .clone()
+                    .resolve(&doc.itdoc(), line_number)?
+                    .optional_integer(doc.name, line_number)?)
+            } else {
+                Ok(None)
+            }
+        };
+        let decimal = |field_name: &str| -> ftd::executor::Result<Option<f64>> {
+            if let Some(value) = values.get(field_name) {
+                Ok(value
+                    .clone()
+                    .resolve(&doc.itdoc(), line_number)?
+                    .optional_decimal(doc.name, line_number)?)
+            } else {
+                Ok(None)
+            }
+        };
+
+        Ok(Transform {
+            translate_x: length("translate-x")?,
+            translate_y: length("translate-y")?,
+            rotate: integer("rotate")?,
+            scale: decimal("scale")?,
+            scale_x: decimal("scale-x")?,
+            scale_y: decimal("scale-y")?,
+            skew_x: integer("skew-x")?,
+            skew_y: integer("skew-y")?,
+        })
+    }
+
+    pub(crate) fn optional_transform(
+        properties: &[ftd::interpreter::Property],
+        arguments: &[ftd::interpreter::Argument],
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+        key: &str,
+        inherited_variables: &ftd::VecMap<(String, Vec<usize>)>,
+        component_name: &str,
+    ) -> ftd::executor::Result<ftd::executor::Value<Option<Box<Transform>>>> {
+        let record_values = ftd::executor::value::optional_record_inherited(
+            key,
+            component_name,
+            properties,
+            arguments,
+            doc,
+            line_number,
+            ftd::interpreter::FTD_TRANSFORM,
+            inherited_variables,
+        )?;
+
+        Ok(ftd::executor::Value::new(
+            Transform::from_optional_values(record_values.value, doc, line_number)?.map(Box::new),
+            record_values.line_number,
+            record_values.properties,
+        ))
+    }
+
+    pub fn to_css_string(&self, device: &Option<ftd::executor::Device>) -> String {
+        let mut functions = vec![];
+        if let Some(ref x) = self.translate_x {
+            functions.push(format!("translateX({})", x.to_css_string(device)));
+        }
+        if let Some(ref y) = self.translate_y {
+            functions.push(format!("translateY({})", y.to_css_string(device)));
+        }
+        if let Some(rotate) = self.rotate {
+            functions.push(format!("rotate({}deg)", rotate));
+        }
+        if let Some(scale) = self.scale {
+            functions.push(format!("scale({})", scale));
+        }
+        if let Some(scale_x) = self.scale_x {
+            functions.push(format!("scaleX({})", scale_x));
+        }
+        if let Some(scale_y) = self.scale_y {
+            functions.push(format!("scaleY({})", scale_y));
+        }
+        if let Some(skew_x) = self.skew_x {
+            functions.push(format!("skewX({}deg)", skew_x));
+        }
+        if let Some(skew_y) = self.skew_y {
+            functions.push(format!("skewY({}deg)", skew_y));
+        }
+        if functions.is_empty() {
+            "none".to_string()
+        } else {
+            functions.join(" ")
+        }
+    }
+
+    pub fn transform_pattern() -> (String, bool) {
+        (
+            "window.ftd.dependencies.eval_transform({0})".to_string(),
+            true,
+        )
+    }
+}
+
+#[derive(serde::Deserialize, Debug, Default, PartialEq, Clone, serde::Serialize)]
+pub enum Easing {
+    Linear,
+    #[default]
+    Ease,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    fn from_value(
+        value: ftd::interpreter::PropertyValue,
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<Easing> {
+        let binding = value.resolve(&doc.itdoc(), line_number)?;
+        let value = binding.get_or_type(doc.name, line_number)?;
+        match value.1.as_str() {
+            ftd::interpreter::FTD_EASING_LINEAR => Ok(Easing::Linear),
+            ftd::interpreter::FTD_EASING_EASE => Ok(Easing::Ease),
+            ftd::interpreter::FTD_EASING_EASE_IN => Ok(Easing::EaseIn),
+            ftd::interpreter::FTD_EASING_EASE_OUT => Ok(Easing::EaseOut),
+            ftd::interpreter::FTD_EASING_EASE_IN_OUT => Ok(Easing::EaseInOut),
+            t => ftd::executor::utils::parse_error(
+                format!("Unknown variant `{}` for or-type `ftd.easing`", t),
+                doc.name,
+                line_number,
+            ),
+        }
+    }
+
+    pub fn to_css_string(&self) -> String {
+        match self {
+            Easing::Linear => "linear".to_string(),
+            Easing::Ease => "ease".to_string(),
+            Easing::EaseIn => "ease-in".to_string(),
+            Easing::EaseOut => "ease-out".to_string(),
+            Easing::EaseInOut => "ease-in-out".to_string(),
+        }
+    }
+}
+
+#[derive(serde::Deserialize, Debug, Default, PartialEq, Clone, serde::Serialize)]
+pub struct Transition {
+    pub property: String,
+    pub duration: f64,
+    pub easing: Easing,
+    pub delay: f64,
+}
+
+impl Transition {
+    fn from_optional_values(
+        or_type_value: Option<ftd::Map<ftd::interpreter::PropertyValue>>,
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<Option<Transition>> {
+        if let Some(value) = or_type_value {
+            Ok(Some(Transition::from_values(value, doc, line_number)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn from_values(
+        values: ftd::Map<ftd::interpreter::PropertyValue>,
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<Transition> {
+        let get_property_value = |field_name: &str| {
+            values
+                .get(field_name)
+                .ok_or_else(|| ftd::executor::Error::ParseError {
+                    message: format!("`{}` field in ftd.transition not found", field_name),
+                    doc_id: doc.name.to_string(),
+                    line_number,
+                })
+        };
+        let resolve = |field_name: &str| -> ftd::executor::Result<ftd::interpreter::Value> {
+            Ok(get_property_value(field_name)?
+                .clone()
+                .resolve(&doc.itdoc(), line_number)?)
+        };
+
+        Ok(Transition {
+            property: resolve("property")?.string(doc.name, line_number)?,
+            duration: resolve("duration")?.decimal(doc.name, line_number)?,
+            easing: Easing::from_value(get_property_value("easing")?.clone(), doc, line_number)?,
+            delay: resolve("delay")?.decimal(doc.name, line_number)?,
+        })
+    }
+
+    pub(crate) fn optional_transition(
+        properties: &[ftd::interpreter::Property],
+        arguments: &[ftd::interpreter::Argument],
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+        key: &str,
+        inherited_variables: &ftd::VecMap<(String, Vec<usize>)>,
+        component_name: &str,
+    ) -> ftd::executor::Result<ftd::executor::Value<Option<Box<Transition>>>> {
+        let record_values = ftd::executor::value::optional_record_inherited(
+            key,
+            component_name,
+            properties,
+            arguments,
+            doc,
+            line_number,
+            ftd::interpreter::FTD_TRANSITION,
+            inherited_variables,
+        )?;
+
+        Ok(ftd::executor::Value::new(
+            Transition::from_optional_values(record_values.value, doc, line_number)?.map(Box::new),
+            record_values.line_number,
+            record_values.properties,
+        ))
+    }
+
+    pub fn to_css_string(&self) -> String {
+        format!(
+            "{} {}s {} {}s",
+            self.property,
+            self.duration,
+            self.easing.to_css_string(),
+            self.delay
+        )
+    }
+
+    pub fn transition_pattern() -> (String, bool) {
+        (
+            "window.ftd.dependencies.eval_transition({0})".to_string(),
+            true,
+        )
+    }
+}
+
+#[derive(serde::Deserialize, Debug, Default, PartialEq, Clone, serde::Serialize)]
+pub struct Keyframe {
+    pub at: i64,
+    pub opacity: Option<f64>,
+    pub transform: Option<Transform>,
+}
+
+impl Keyframe {
+    fn from_vec_values(
+        value: ftd::interpreter::PropertyValue,
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<Vec<Keyframe>> {
+        let mut result = vec![];
+        let value = value.resolve(&doc.itdoc(), line_number)?;
+        match value.inner() {
+            Some(ftd::interpreter::Value::List { data, kind })
+                if kind.kind.get_name().eq(ftd::interpreter::FTD_KEYFRAME) =>
+            {
+                for element in data.iter() {
+                    let ln = element.line_number();
+                    let fields = element
+                        .clone()
+                        .resolve(&doc.itdoc(), ln)?
+                        .record_fields(doc.name, ln)?;
+                    result.push(Keyframe::from_values(fields, doc, ln)?)
+                }
+            }
+            t => {
+                return ftd::executor::utils::parse_error(
+                    format!(
+                        "Expected list value of type `{}`, found: {:?}",
+                        ftd::interpreter::FTD_KEYFRAME,
+                        t
+                    ),
+                    doc.name,
+                    line_number,
+                )
+            }
+        };
+        Ok(result)
+    }
+
+    fn from_values(
+        values: ftd::Map<ftd::interpreter::PropertyValue>,
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<Keyframe> {
+        let at = match values.get("at") {
+            Some(value) => value
+                .clone()
+                .resolve(&doc.itdoc(), line_number)?
+                .integer(doc.name, line_number)?,
+            None => {
+                return ftd::executor::utils::parse_error(
+                    "`at` field in ftd.keyframe not found",
+                    doc.name,
+                    line_number,
+                )
+            }
+        };
+        let opacity = match values.get("opacity") {
+            Some(value) => value
+                .clone()
+                .resolve(&doc.itdoc(), line_number)?
+                .optional_decimal(doc.name, line_number)?,
+            None => None,
+        };
+        let transform = match values.get("transform") {
+            Some(value) => Transform::from_optional_value(value.clone(), doc, line_number)?,
+            None => None,
+        };
+        Ok(Keyframe {
+            at,
+            opacity,
+            transform,
+        })
+    }
+
+    pub fn to_css_string(&self, device: &Option<ftd::executor::Device>) -> String {
+        let mut css = String::new();
+        if let Some(opacity) = self.opacity {
+            css.push_str(format!(" opacity: {};", opacity).as_str());
+        }
+        if let Some(ref transform) = self.transform {
+            css.push_str(format!(" transform: {};", transform.to_css_string(device)).as_str());
+        }
+        format!("{}% {{{} }}", self.at, css)
+    }
+}
+
+#[derive(serde::Deserialize, Debug, Default, PartialEq, Clone, serde::Serialize)]
+pub struct Animation {
+    pub name: String,
+    pub keyframes: Vec<Keyframe>,
+    pub duration: f64,
+    pub easing: Easing,
+    pub delay: f64,
+    pub iterations: Option<i64>,
+    pub alternate: bool,
+}
+
+impl Animation {
+    fn from_optional_values(
+        or_type_value: Option<ftd::Map<ftd::interpreter::PropertyValue>>,
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<Option<Animation>> {
+        if let Some(value) = or_type_value {
+            Ok(Some(Animation::from_values(value, doc, line_number)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn from_values(
+        values: ftd::Map<ftd::interpreter::PropertyValue>,
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<Animation> {
+        let get_property_value = |field_name: &str| {
+            values
+                .get(field_name)
+                .ok_or_else(|| ftd::executor::Error::ParseError {
+                    message: format!("`{}` field in ftd.animation not found", field_name),
+                    doc_id: doc.name.to_string(),
+                    line_number,
+                })
+        };
+        let resolve = |field_name: &str| -> ftd::executor::Result<ftd::interpreter::Value> {
+            Ok(get_property_value(field_name)?
+                .clone()
+                .resolve(&doc.itdoc(), line_number)?)
+        };
+
+        Ok(Animation {
+            name: resolve("name")?.string(doc.name, line_number)?,
+            keyframes: Keyframe::from_vec_values(
+                get_property_value("keyframes")?.clone(),
+                doc,
+                line_number,
+            )?,
+            duration: resolve("duration")?.decimal(doc.name, line_number)?,
+            easing: Easing::from_value(get_property_value("easing")?.clone(), doc, line_number)?,
+            delay: resolve("delay")?.decimal(doc.name, line_number)?,
+            iterations: resolve("iterations")?.optional_integer(doc.name, line_number)?,
+            alternate: resolve("alternate")?.bool(doc.name, line_number)?,
+        })
+    }
+
+    pub(crate) fn optional_animation(
+        properties: &[ftd::interpreter::Property],
+        arguments: &[ftd::interpreter::Argument],
+        doc: &mut ftd::executor::TDoc,
+        line_number: usize,
+        key: &str,
+        inherited_variables: &ftd::VecMap<(String, Vec<usize>)>,
+        component_name: &str,
+    ) -> ftd::executor::Result<ftd::executor::Value<Option<Box<Animation>>>> {
+        let record_values = ftd::executor::value::optional_record_inherited(
+            key,
+            component_name,
+            properties,
+            arguments,
+            doc,
+            line_number,
+            ftd::interpreter::FTD_ANIMATION,
+            inherited_variables,
+        )?;
+
+        let animation =
+            Animation::from_optional_values(record_values.value, doc, line_number)?.map(Box::new);
+        // the `@keyframes` rule is page level css, see `ftd::html::utils::get_keyframes_html`
+        if let Some(ref animation) = animation {
+            doc.keyframes
+                .insert(animation.to_keyframes_css_string(&None));
+        }
+
+        Ok(ftd::executor::Value::new(
+            animation,
+            record_values.line_number,
+            record_values.properties,
+        ))
+    }
+
+    pub fn to_css_string(&self) -> String {
+        let iterations = match self.iterations {
+            Some(count) => count.to_string(),
+            None => "infinite".to_string(),
+        };
+        format!(
+            "{} {}s {} {}s {}{}",
+            self.name,
+            self.duration,
+            self.easing.to_css_string(),
+            self.delay,
+            iterations,
+            if self.alternate { " alternate" } else { "" }
+        )
+    }
+
+    pub fn to_keyframes_css_string(&self, device: &Option<ftd::executor::Device>) -> String {
+        format!(
+            "@keyframes {} {{ {} }}",
+            self.name,
+            self.keyframes
+                .iter()
+                .map(|k| k.to_css_string(device))
+                .join(" ")
+        )
+    }
+
+    pub fn animation_pattern() -> (String, bool) {
+        (
+            "window.ftd.dependencies.eval_animation({0})".to_string(),
+            true,
+        )
+    }
+}
+
+#[derive(serde::Deserialize, Debug, Default, PartialEq, Clone, serde::Serialize)]
+pub struct GridMinmax {
+    pub min: Length,
+    pub max: Option<Length>,
+    pub max_fr: i64,
+}
+
+impl GridMinmax {
+    fn from_value(
+        value: ftd::interpreter::PropertyValue,
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<GridMinmax> {
+        let value = value.resolve(&doc.itdoc(), line_number)?;
+        let fields = match value.inner() {
+            Some(ftd::interpreter::Value::Record { name, fields })
+                if name.eq(ftd::interpreter::FTD_GRID_MINMAX) =>
+            {
+                fields
+            }
+            t => {
+                return ftd::executor::utils::parse_error(
+                    format!(
+                        "Expected value of type record `{}`, found: {:?}",
+                        ftd::interpreter::FTD_GRID_MINMAX,
+                        t
+                    ),
+                    doc.name,
+                    line_number,
+                )
+            }
+        };
+
+        let min = {
+            let value = fields.get("min").ok_or(ftd::executor::Error::ParseError {
+                message: "`min` field in ftd.grid-minmax not found".to_string(),
+                doc_id: doc.name.to_string(),
+                line_number,
+            })?;
+            Length::from_value(value.to_owned(), doc, line_number)?
+        };
+
+        let max = Length::from_optional_value(fields.get("max").cloned(), doc, line_number)?;
+
+        let max_fr = match fields.get("max-fr") {
+            Some(value) => value
+                .clone()
+                .resolve(&doc.itdoc(), line_number)?
+                .integer(doc.name, line_number)?,
+            None => 1,
+        };
+
+        Ok(GridMinmax { min, max, max_fr })
+    }
+
+    pub fn to_css_string(&self, device: &Option<ftd::executor::Device>) -> String {
+        let max = match self.max {
+            Some(ref max) => max.to_css_string(device),
+            None => format!("{}fr", self.max_fr),
+        };
+        format!("minmax({}, {})", self.min.to_css_string(device), max)
+    }
+}
+
+#[derive(serde::Deserialize, Debug, PartialEq, Clone, serde::Serialize)]
+pub enum GridTrack {
+    Fixed(Length),
+    Fr(i64),
+    Auto,
+    Minmax(GridMinmax),
+    AutoFill(GridMinmax),
+    AutoFit(GridMinmax),
+}
+
+impl GridTrack {
+    fn from_values(
+        or_type_value: (String, ftd::interpreter::PropertyValue),
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<GridTrack> {
+        match or_type_value.0.as_str() {
+            ftd::interpreter::FTD_GRID_TRACK_FIXED => Ok(GridTrack::Fixed(Length::from_value(
+                or_type_value.1,
+                doc,
+                line_number,
+            )?)),
+            ftd::interpreter::FTD_GRID_TRACK_FR => Ok(GridTrack::Fr(
+                or_type_value
+                    .1
+                    .resolve(&doc.itdoc(), line_number)?
+                    .integer(doc.name, line_number)?,
+            )),
+            ftd::interpreter::FTD_GRID_TRACK_AUTO => Ok(GridTrack::Auto),
+            ftd::interpreter::FTD_GRID_TRACK_MINMAX => Ok(GridTrack::Minmax(
+                GridMinmax::from_value(or_type_value.1, doc, line_number)?,
+            )),
+            ftd::interpreter::FTD_GRID_TRACK_AUTO_FILL => Ok(GridTrack::AutoFill(
+                GridMinmax::from_value(or_type_value.1, doc, line_number)?,
+            )),
+            ftd::interpreter::FTD_GRID_TRACK_AUTO_FIT => Ok(GridTrack::AutoFit(
+                GridMinmax::from_value(or_type_value.1, doc, line_number)?,
+            )),
+            t => ftd::executor::utils::parse_error(
+                format!("Unknown variant `{}` for or-type `ftd.grid-track`", t),
+                doc.name,
+                line_number,
+            ),
+        }
+    }
+
+    fn from_vec_values(
+        value: ftd::interpreter::PropertyValue,
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<Vec<GridTrack>> {
+        let mut result = vec![];
+        let value = value.resolve(&doc.itdoc(), line_number)?;
+        match value.inner() {
+            Some(ftd::interpreter::Value::List { data, kind })
+                if kind.kind.get_name().eq(ftd::interpreter::FTD_GRID_TRACK) =>
+            {
+                for element in data.iter() {
+                    let ln = element.line_number();
+                    let binding = element.clone().resolve(&doc.itdoc(), ln)?;
+                    let value = binding.get_or_type(doc.name, ln)?;
+                    result.push(GridTrack::from_values(
+                        (value.1.to_owned(), value.2.to_owned()),
+                        doc,
+                        ln,
+                    )?)
+                }
+            }
+            None => {}
+            t => {
+                return ftd::executor::utils::parse_error(
+                    format!(
+                        "Expected list value of type `{}`, found: {:?}",
+                        ftd::interpreter::FTD_GRID_TRACK,
+                        t
+                    ),
+                    doc.name,
+                    line_number,
+                )
+            }
+        };
+        Ok(result)
+    }
+
+    pub(crate) fn grid_track_list(
+        properties: &[ftd::interpreter::Property],
+        arguments: &[ftd::interpreter::Argument],
         doc: &ftd::executor::TDoc,
         line_number: usize,
-    ) -> ftd::executor::Result<Option<ftd::executor::BackgroundPosition>> {
-        let binding = value.resolve(&doc.itdoc(), line_number)?;
-        if let ftd::interpreter::Value::Optional { data, .. } = &binding {
-            if data.is_none() {
-                return Ok(None);
-            }
-        }
-
-        let value = binding.get_or_type(doc.name, line_number)?;
-        let value = (value.1.to_owned(), value.2.to_owned());
-        Ok(Some(ftd::executor::BackgroundPosition::from_values(
-            value,
+        key: &str,
+        inherited_variables: &ftd::VecMap<(String, Vec<usize>)>,
+        component_name: &str,
+    ) -> ftd::executor::Result<ftd::executor::Value<Vec<GridTrack>>> {
+        let or_type_value = ftd::executor::value::optional_or_type_list(
+            key,
+            component_name,
+            properties,
+            arguments,
             doc,
             line_number,
-        )?))
+            ftd::interpreter::FTD_GRID_TRACK,
+            inherited_variables,
+        )?;
+
+        let mut tracks = vec![];
+        for value in or_type_value.value {
+            tracks.push(GridTrack::from_values(value, doc, line_number)?);
+        }
+
+        Ok(ftd::executor::Value::new(
+            tracks,
+            or_type_value.line_number,
+            or_type_value.properties,
+        ))
     }
 
-    fn from_values(
-        or_type_value: (String, ftd::interpreter::PropertyValue),
-        doc: &ftd::executor::TDoc,
-        line_number: usize,
-    ) -> ftd::executor::Result<ftd::executor::BackgroundPosition> {
-        match or_type_value.0.as_str() {
-            ftd::interpreter::FTD_BACKGROUND_POSITION_LEFT => {
-                Ok(ftd::executor::BackgroundPosition::Left)
-            }
-            ftd::interpreter::FTD_BACKGROUND_POSITION_CENTER => {
-                Ok(ftd::executor::BackgroundPosition::Center)
-            }
-            ftd::interpreter::FTD_BACKGROUND_POSITION_RIGHT => {
-                Ok(ftd::executor::BackgroundPosition::Right)
-            }
-            ftd::interpreter::FTD_BACKGROUND_POSITION_LEFT_TOP => {
-                Ok(ftd::executor::BackgroundPosition::LeftTop)
-            }
-            ftd::interpreter::FTD_BACKGROUND_POSITION_LEFT_CENTER => {
-                Ok(ftd::executor::BackgroundPosition::LeftCenter)
-            }
-            ftd::interpreter::FTD_BACKGROUND_POSITION_LEFT_BOTTOM => {
-                Ok(ftd::executor::BackgroundPosition::LeftBottom)
-            }
-            ftd::interpreter::FTD_BACKGROUND_POSITION_CENTER_TOP => {
-                Ok(ftd::executor::BackgroundPosition::CenterTop)
-            }
-            ftd::interpreter::FTD_BACKGROUND_POSITION_CENTER_CENTER => {
-                Ok(ftd::executor::BackgroundPosition::CenterCenter)
-            }
-            ftd::interpreter::FTD_BACKGROUND_POSITION_CENTER_BOTTOM => {
-                Ok(ftd::executor::BackgroundPosition::CenterBottom)
-            }
-            ftd::interpreter::FTD_BACKGROUND_POSITION_RIGHT_TOP => {
-                Ok(ftd::executor::BackgroundPosition::RightTop)
-            }
-            ftd::interpreter::FTD_BACKGROUND_POSITION_RIGHT_CENTER => {
-                Ok(ftd::executor::BackgroundPosition::RightCenter)
-            }
-            ftd::interpreter::FTD_BACKGROUND_POSITION_RIGHT_BOTTOM => {
-                Ok(ftd::executor::BackgroundPosition::RightBottom)
+    pub fn to_css_string(&self, device: &Option<ftd::executor::Device>) -> String {
+        match self {
+            GridTrack::Fixed(length) => length.to_css_string(device),
+            GridTrack::Fr(fr) => format!("{}fr", fr),
+            GridTrack::Auto => "auto".to_string(),
+            GridTrack::Minmax(minmax) => minmax.to_css_string(device),
+            GridTrack::AutoFill(minmax) => {
+                format!("repeat(auto-fill, {})", minmax.to_css_string(device))
             }
-            ftd::interpreter::FTD_BACKGROUND_POSITION_LENGTH => {
-                Ok(ftd::executor::BackgroundPosition::Length(
-                    LengthPair::from_value(or_type_value.1, doc, line_number)?,
-                ))
+            GridTrack::AutoFit(minmax) => {
+                format!("repeat(auto-fit, {})", minmax.to_css_string(device))
             }
-            t => ftd::executor::utils::parse_error(
-                format!(
-                    "Unknown variant `{}` for or-type `ftd.background-position`",
-                    t
-                ),
-                doc.name,
-                line_number,
-            ),
         }
     }
 
-    pub fn to_css_string(&self, device: &Option<ftd::executor::Device>) -> String {
-        match self {
-            ftd::executor::BackgroundPosition::Left => "left".to_string(),
-            ftd::executor::BackgroundPosition::Center => "center".to_string(),
-            ftd::executor::BackgroundPosition::Right => "right".to_string(),
-            ftd::executor::BackgroundPosition::LeftTop => "left top".to_string(),
-            ftd::executor::BackgroundPosition::LeftCenter => "left center".to_string(),
-            ftd::executor::BackgroundPosition::LeftBottom => "left bottom".to_string(),
-            ftd::executor::BackgroundPosition::CenterTop => "center top".to_string(),
-            ftd::executor::BackgroundPosition::CenterCenter => "center center".to_string(),
-            ftd::executor::BackgroundPosition::CenterBottom => "center bottom".to_string(),
-            ftd::executor::BackgroundPosition::RightTop => "right top".to_string(),
-            ftd::executor::BackgroundPosition::RightCenter => "right center".to_string(),
-            ftd::executor::BackgroundPosition::RightBottom => "right bottom".to_string(),
-            ftd::executor::BackgroundPosition::Length(l) => l.to_css_string(device),
+    pub fn list_to_css_string(
+        tracks: &[GridTrack],
+        device: &Option<ftd::executor::Device>,
+    ) -> Option<String> {
+        if tracks.is_empty() {
+            return None;
+        }
+        Some(tracks.iter().map(|t| t.to_css_string(device)).join(" "))
+    }
+
+    pub fn areas_to_css_string(areas: &[String]) -> Option<String> {
+        if areas.is_empty() {
+            return None;
         }
+        // single quotes, the value ends up in the `style` attribute
+        Some(areas.iter().map(|a| format!("'{}'", a.trim())).join(" "))
     }
 }
 
 #[derive(serde::Deserialize, Debug, Default, PartialEq, Clone, serde::Serialize)]
-pub struct Shadow {
-    pub x_offset: ftd::executor::Value<Length>,
-    pub y_offset: ftd::executor::Value<Length>,
-    pub blur: ftd::executor::Value<Length>,
-    pub spread: ftd::executor::Value<Length>,
-    pub inset: ftd::executor::Value<bool>,
-    pub color: ftd::executor::Value<Color>,
+pub struct GridTemplate {
+    pub columns: Vec<GridTrack>,
+    pub rows: Vec<GridTrack>,
+    pub areas: Vec<String>,
 }
 
-impl Shadow {
-    fn from_values(
-        values: ftd::Map<ftd::interpreter::PropertyValue>,
+impl GridTemplate {
+    fn from_value(
+        value: ftd::interpreter::PropertyValue,
         doc: &ftd::executor::TDoc,
         line_number: usize,
-    ) -> ftd::executor::Result<ftd::executor::Shadow> {
-        let get_property_value = |field_name: &str| {
-            values
-                .get(field_name)
-                .ok_or_else(|| ftd::executor::Error::ParseError {
-                    message: format!("`{}` field in ftd.shadow not found", field_name),
-                    doc_id: doc.name.to_string(),
+    ) -> ftd::executor::Result<GridTemplate> {
+        let value = value.resolve(&doc.itdoc(), line_number)?;
+        let fields = match value.inner() {
+            Some(ftd::interpreter::Value::Record { name, fields })
+                if name.eq(ftd::interpreter::FTD_GRID_TEMPLATE) =>
+            {
+                fields
+            }
+            t => {
+                return ftd::executor::utils::parse_error(
+                    format!(
+                        "Expected value of type record `{}`, found: {:?}",
+                        ftd::interpreter::FTD_GRID_TEMPLATE,
+                        t
+                    ),
+                    doc.name,
                     line_number,
-                })
+                )
+            }
         };
 
-        let x_offset = ftd::executor::Value::new(
-            Length::from_value(get_property_value("x-offset")?.clone(), doc, line_number)?,
-            Some(line_number),
-            vec![get_property_value("x-offset")?
-                .into_property(ftd::interpreter::PropertySource::header("x-offset"))],
-        );
-
-        let y_offset = ftd::executor::Value::new(
-            Length::from_value(get_property_value("y-offset")?.clone(), doc, line_number)?,
-            Some(line_number),
-            vec![get_property_value("y-offset")?
-                .into_property(ftd::interpreter::PropertySource::header("y-offset"))],
-        );
-
-        let blur = ftd::executor::Value::new(
-            Length::from_value(get_property_value("blur")?.clone(), doc, line_number)?,
-            Some(line_number),
-            vec![get_property_value("blur")?
-                .into_property(ftd::interpreter::PropertySource::header("blur"))],
-        );
-
-        let spread = ftd::executor::Value::new(
-            Length::from_value(get_property_value("spread")?.clone(), doc, line_number)?,
-            Some(line_number),
-            vec![get_property_value("spread")?
-                .into_property(ftd::interpreter::PropertySource::header("spread"))],
-        );
-
-        let color = ftd::executor::Value::new(
-            Color::from_value(get_property_value("color")?.clone(), doc, line_number)?,
-            Some(line_number),
-            vec![get_property_value("color")?
-                .into_property(ftd::interpreter::PropertySource::header("color"))],
-        );
+        let tracks = |field_name: &str| match fields.get(field_name) {
+            Some(value) => GridTrack::from_vec_values(value.to_owned(), doc, line_number),
+            None => Ok(vec![]),
+        };
 
-        let inset = ftd::executor::Value::new(
-            get_property_value("inset")?
-                .clone()
-                .resolve(&doc.itdoc(), line_number)?
-                .bool(doc.name, line_number)?,
-            Some(line_number),
-            vec![get_property_value("inset")?
-                .into_property(ftd::interpreter::PropertySource::header("inset"))],
-        );
+        let mut areas = vec![];
+        if let Some(value) = fields.get("areas") {
+            if let Some(ftd::interpreter::Value::List { data, .. }) =
+                value.clone().resolve(&doc.itdoc(), line_number)?.inner()
+            {
+                for d in data {
+                    areas.push(
+                        d.resolve(&doc.itdoc(), line_number)?
+                            .string(doc.name, line_number)?,
+                    );
+                }
+            }
+        }
 
-        Ok(ftd::executor::Shadow {
-            x_offset,
-            y_offset,
-            blur,
-            spread,
-            color,
-            inset,
+        Ok(GridTemplate {
+            columns: tracks("columns")?,
+            rows: tracks("rows")?,
+            areas,
         })
     }
+}
+
+#[derive(serde::Deserialize, Debug, Default, PartialEq, Clone, serde::Serialize)]
+pub struct ResponsiveGridTemplate {
+    pub desktop: GridTemplate,
+    pub mobile: GridTemplate,
+}
 
+impl ResponsiveGridTemplate {
     fn from_optional_values(
         or_type_value: Option<ftd::Map<ftd::interpreter::PropertyValue>>,
         doc: &ftd::executor::TDoc,
         line_number: usize,
-    ) -> ftd::executor::Result<Option<ftd::executor::Shadow>> {
+    ) -> ftd::executor::Result<Option<ResponsiveGridTemplate>> {
         if let Some(value) = or_type_value {
-            Ok(Some(ftd::executor::Shadow::from_values(
+            Ok(Some(ResponsiveGridTemplate::from_values(
                 value,
                 doc,
                 line_number,
@@ -1867,7 +3274,35 @@ impl Shadow {
         }
     }
 
-    pub(crate) fn optional_shadow(
+    fn from_values(
+        values: ftd::Map<ftd::interpreter::PropertyValue>,
+        doc: &ftd::executor::TDoc,
+        line_number: usize,
+    ) -> ftd::executor::Result<ResponsiveGridTemplate> {
+        let desktop = {
+            let value = values
+                .get("desktop")
+                .ok_or(ftd::executor::Error::ParseError {
+                    message: "`desktop` field in ftd.responsive-grid-template not found"
+                        .to_string(),
+                    doc_id: doc.name.to_string(),
+                    line_number,
+                })?;
+            GridTemplate::from_value(value.to_owned(), doc, line_number)?
+        };
+
+        let mobile = {
+            if let Some(value) = values.get("mobile") {
+                GridTemplate::from_value(value.to_owned(), doc, line_number)?
+            } else {
+                desktop.clone()
+            }
+        };
+
+        Ok(ResponsiveGridTemplate { desktop, mobile })
+    }
+
+    pub(crate) fn optional_responsive_grid_template(
         properties: &[ftd::interpreter::Property],
         arguments: &[ftd::interpreter::Argument],
         doc: &ftd::executor::TDoc,
@@ -1875,7 +3310,7 @@ impl Shadow {
         key: &str,
         inherited_variables: &ftd::VecMap<(String, Vec<usize>)>,
         component_name: &str,
-    ) -> ftd::executor::Result<ftd::executor::Value<Option<ftd::executor::Shadow>>> {
+    ) -> ftd::executor::Result<ftd::executor::Value<Option<ResponsiveGridTemplate>>> {
         let record_values = ftd::executor::value::optional_record_inherited(
             key,
             component_name,
@@ -1883,39 +3318,22 @@ impl Shadow {
             arguments,
             doc,
             line_number,
-            ftd::interpreter::FTD_SHADOW,
+            ftd::interpreter::FTD_RESPONSIVE_GRID_TEMPLATE,
             inherited_variables,
         )?;
 
         Ok(ftd::executor::Value::new(
-            ftd::executor::Shadow::from_optional_values(record_values.value, doc, line_number)?,
+            ResponsiveGridTemplate::from_optional_values(record_values.value, doc, line_number)?,
             record_values.line_number,
             record_values.properties,
         ))
     }
 
-    pub fn to_css_string(&self, device: &Option<ftd::executor::Device>) -> String {
-        let x_offset = self.x_offset.value.to_css_string(device);
-        let y_offset = self.y_offset.value.to_css_string(device);
-        let blur = self.blur.value.to_css_string(device);
-        let spread = self.spread.value.to_css_string(device);
-        let inset = match self.inset.value {
-            true => "inset".to_string(),
-            false => "".to_string(),
-        };
-        let color = self.color.value.to_css_string();
-
-        format!(
-            "{} {} {} {} {} {}",
-            inset, color, x_offset, y_offset, blur, spread
-        )
-    }
-
-    pub fn box_shadow_pattern() -> (String, bool) {
-        (
-            "window.ftd.dependencies.eval_box_shadow({0}, data)".to_string(),
-            true,
-        )
+    pub fn get(&self, device: &Option<ftd::executor::Device>) -> &GridTemplate {
+        match device {
+            Some(ftd::executor::Device::Mobile) => &self.mobile,
+            _ => &self.desktop,
+        }
     }
 }
 
@@ -7,6 +7,8 @@ pub struct TDoc<'a> {
     pub element_constructor: &'a mut ftd::Map<ftd::executor::ElementConstructor>,
     pub js: &'a mut std::collections::HashSet<String>,
     pub css: &'a mut std::collections::HashSet<String>,
+    pub keyframes: &'a mut std::collections::HashSet<String>,
+    pub heading_ids: &'a mut ftd::executor::headings::HeadingIds,
     pub rive_data: &'a mut Vec<ftd::executor::RiveData>,
 }
 
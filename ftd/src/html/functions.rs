@@ -185,6 +185,15 @@ impl ExpressionGenerator {
             .join("");
         }
 
+        if matches!(node.operator(), fastn_grammar::evalexpr::Operator::Index) {
+            let result = node
+                .children()
+                .iter()
+                .map(|children| self.to_string_(children, false, arguments, extra_args))
+                .collect_vec();
+            return format!("{}[{}]", result[0], result[1..].join(""));
+        }
+
         if let Some(operator) = self.has_operator(node.operator()) {
             // Todo: if node.children().len() != 2 {throw error}
             let first = node.children().first().unwrap(); //todo remove unwrap()
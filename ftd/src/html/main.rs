@@ -96,7 +96,13 @@ impl HtmlUI {
             immutable_variable,
             html_data: node_data.html_data.to_html_data(),
             js: ftd::html::utils::get_js_html(node_data.js.into_iter().collect_vec().as_slice()),
-            css: ftd::html::utils::get_css_html(node_data.css.into_iter().collect_vec().as_slice()),
+            css: format!(
+                "{}{}",
+                ftd::html::utils::get_css_html(node_data.css.into_iter().collect_vec().as_slice()),
+                ftd::html::utils::get_keyframes_html(
+                    node_data.keyframes.into_iter().collect_vec().as_slice()
+                )
+            ),
             rive_data: ftd::html::utils::get_rive_data_html(
                 node_data.rive_data.as_slice(),
                 id,
@@ -403,6 +403,12 @@ impl ftd::interpreter::Value {
                             Some(value)
                         }
                     }
+                    Some(value)
+                        if name.eq(ftd::interpreter::FTD_GRID_TRACK)
+                            && variant.eq(ftd::interpreter::FTD_GRID_TRACK_FR) =>
+                    {
+                        Some(format!("`{{0}}fr`.format(JSON.stringify({}))", value))
+                    }
                     Some(value)
                         if name.eq(ftd::interpreter::FTD_RESIZING)
                             && variant.ne(ftd::interpreter::FTD_RESIZING_FIXED) =>
@@ -899,6 +905,15 @@ pub fn get_css_html(external_css: &[String]) -> String {
     result
 }
 
+pub fn get_keyframes_html(keyframes: &[String]) -> String {
+    use itertools::Itertools;
+
+    if keyframes.is_empty() {
+        return "".to_string();
+    }
+    format!("<style>{}</style>", keyframes.iter().sorted().join("\n"))
+}
+
 pub fn get_meta_data(html_data: &ftd::html::HTMLData) -> String {
     let mut result = vec![];
     if let Some(ref title) = html_data.og_title {
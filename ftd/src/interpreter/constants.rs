@@ -48,6 +48,9 @@ pub const FTD_BACKGROUND: &str = "ftd#background";
 pub const FTD_BACKGROUND_SOLID: &str = "ftd#background.solid";
 pub const FTD_BACKGROUND_IMAGE: &str = "ftd#background.image";
 pub const FTD_BACKGROUND_LINEAR_GRADIENT: &str = "ftd#background.linear-gradient";
+pub const FTD_BACKGROUND_RADIAL_GRADIENT: &str = "ftd#background.radial-gradient";
+pub const FTD_BACKGROUND_CONIC_GRADIENT: &str = "ftd#background.conic-gradient";
+pub const FTD_BACKGROUND_LAYERS: &str = "ftd#background.layers";
 
 pub const FTD_LENGTH_PAIR: &str = "ftd#length-pair";
 pub const FTD_LENGTH_PAIR_X: &str = "ftd#length-pair.x";
@@ -67,6 +70,16 @@ pub const FTD_LINEAR_GRADIENT_COLOR_START: &str = "ftd#linear-gradient-color.sta
 pub const FTD_LINEAR_GRADIENT_COLOR_END: &str = "ftd#linear-gradient-color.end";
 pub const FTD_LINEAR_GRADIENT_COLOR_STOP_POSITION: &str = "ftd#linear-gradient-color.stop-position";
 
+pub const FTD_RADIAL_GRADIENT: &str = "ftd#radial-gradient";
+pub const FTD_RADIAL_GRADIENT_SHAPE: &str = "ftd#radial-gradient-shape";
+pub const FTD_RADIAL_GRADIENT_SHAPE_CIRCLE: &str = "ftd#radial-gradient-shape.circle";
+pub const FTD_RADIAL_GRADIENT_SHAPE_ELLIPSE: &str = "ftd#radial-gradient-shape.ellipse";
+
+pub const FTD_CONIC_GRADIENT: &str = "ftd#conic-gradient";
+
+pub const FTD_BACKGROUND_LAYER: &str = "ftd#background-layer";
+pub const FTD_LAYERED_BACKGROUND: &str = "ftd#layered-background";
+
 pub const FTD_LINEAR_GRADIENT_DIRECTIONS: &str = "ftd#linear-gradient-directions";
 pub const FTD_LINEAR_GRADIENT_DIRECTIONS_ANGLE: &str = "ftd#linear-gradient-directions.angle";
 pub const FTD_LINEAR_GRADIENT_DIRECTIONS_TURN: &str = "ftd#linear-gradient-directions.turn";
@@ -157,6 +170,31 @@ pub const FTD_TEXT_ALIGN_JUSTIFY: &str = "ftd#text-align.justify";
 pub const FTD_SHADOW: &str = "ftd#shadow";
 pub const FTD_SHADOW_COLOR: &str = "ftd#shadow.color";
 
+pub const FTD_TRANSFORM: &str = "ftd#transform";
+pub const FTD_TRANSITION: &str = "ftd#transition";
+pub const FTD_KEYFRAME: &str = "ftd#keyframe";
+pub const FTD_ANIMATION: &str = "ftd#animation";
+
+pub const FTD_EASING: &str = "ftd#easing";
+pub const FTD_EASING_LINEAR: &str = "ftd#easing.linear";
+pub const FTD_EASING_EASE: &str = "ftd#easing.ease";
+pub const FTD_EASING_EASE_IN: &str = "ftd#easing.ease-in";
+pub const FTD_EASING_EASE_OUT: &str = "ftd#easing.ease-out";
+pub const FTD_EASING_EASE_IN_OUT: &str = "ftd#easing.ease-in-out";
+
+pub const FTD_GRID_TRACK: &str = "ftd#grid-track";
+pub const FTD_GRID_TRACK_FIXED: &str = "ftd#grid-track.fixed";
+pub const FTD_GRID_TRACK_FR: &str = "ftd#grid-track.fr";
+pub const FTD_GRID_TRACK_AUTO: &str = "ftd#grid-track.auto";
+pub const FTD_GRID_TRACK_MINMAX: &str = "ftd#grid-track.minmax";
+pub const FTD_GRID_TRACK_AUTO_FILL: &str = "ftd#grid-track.auto-fill";
+pub const FTD_GRID_TRACK_AUTO_FIT: &str = "ftd#grid-track.auto-fit";
+
+pub const FTD_GRID_MINMAX: &str = "ftd#grid-minmax";
+pub const FTD_GRID_TEMPLATE: &str = "ftd#grid-template";
+pub const FTD_RESPONSIVE_GRID_TEMPLATE: &str = "ftd#responsive-grid-template";
+pub const FTD_RESPONSIVE_GRID_TEMPLATE_DESKTOP: &str = "ftd#responsive-grid-template.desktop";
+
 // FTD overflow(todo docs link)
 pub const FTD_OVERFLOW: &str = "ftd#overflow";
 pub const FTD_OVERFLOW_SCROLL: &str = "ftd#overflow.scroll";
@@ -20,6 +20,9 @@
 ///
 /// - `instructions`: a `Vec` of `ftd::interpreter::Component`s that represents the instructions
 /// that the interpreter has processed.
+///
+/// - `all_imported_things`: a bool, if set all the things of the imported documents are
+/// interpreted, not only the ones the document uses, see `interpret_with_all_imported_things()`.
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct InterpreterState {
     pub id: String,
@@ -30,6 +33,7 @@ pub struct InterpreterState {
     pub pending_imports: PendingImports,
     pub parsed_libs: ftd::Map<ParsedDocument>,
     pub instructions: Vec<ftd::interpreter::Component>,
+    pub all_imported_things: bool,
 }
 
 #[derive(Debug, Clone, Default, PartialEq)]
@@ -742,8 +746,26 @@ impl InterpreterState {
         foreign_function: Vec<String>,
         _ignore_line_numbers: usize,
     ) -> ftd::interpreter::Result<Interpreter> {
+        use itertools::Itertools;
+
         document.add_foreign_function(foreign_function);
         document.add_foreign_variable(foreign_variable);
+        if self.all_imported_things {
+            // processed after the thing the document is imported for, which is pushed on top
+            let things = document
+                .ast
+                .iter()
+                .filter(|v| !v.is_component())
+                .map(|v| ftd::interpreter::ToProcessItem {
+                    number_of_scan: 0,
+                    ast: v.to_owned(),
+                    exports: vec![],
+                })
+                .collect_vec();
+            if !things.is_empty() {
+                self.to_process.stack.push((module.to_string(), things));
+            }
+        }
         self.parsed_libs.insert(module.to_string(), document);
         self.continue_processing()
     }
@@ -834,18 +856,40 @@ pub fn interpret<'a>(id: &'a str, source: &'a str) -> ftd::interpreter::Result<I
 pub fn interpret_with_line_number(
     id: &str,
     document: ParsedDocument,
+    line_number: usize,
+) -> ftd::interpreter::Result<Interpreter> {
+    interpret_with_state(InterpreterState::new(id.to_string()), document, line_number)
+}
+
+/// Same as `interpret_with_line_number()`, but all the things of the imported documents are
+/// interpreted, so the JS of an imported document does not depend on what the document uses, see
+/// `fastn_js::split_modules()`.
+#[tracing::instrument(skip_all)]
+pub fn interpret_with_all_imported_things(
+    id: &str,
+    document: ParsedDocument,
+    line_number: usize,
+) -> ftd::interpreter::Result<Interpreter> {
+    let mut s = InterpreterState::new(id.to_string());
+    s.all_imported_things = true;
+    interpret_with_state(s, document, line_number)
+}
+
+fn interpret_with_state(
+    mut s: InterpreterState,
+    document: ParsedDocument,
     _line_number: usize,
 ) -> ftd::interpreter::Result<Interpreter> {
     use itertools::Itertools;
 
-    tracing::info!(msg = "ftd: interpreting", doc = id);
+    let id = s.id.to_string();
+    tracing::info!(msg = "ftd: interpreting", doc = id.as_str());
 
-    let mut s = InterpreterState::new(id.to_string());
     s.parsed_libs.insert(id.to_string(), document);
     s.to_process.stack.push((
         id.to_string(),
         s.parsed_libs
-            .get(id)
+            .get(id.as_str())
             .unwrap()
             .ast
             .iter()
@@ -288,6 +288,27 @@ impl<'a> TDoc<'a> {
                     }
                     Ok(value)
                 }
+                ftd::interpreter::Value::Map { data, kind } => {
+                    let value = data
+                        .get(p1.as_str())
+                        .ok_or(ftd::interpreter::Error::ParseError {
+                            message: format!("Can't find key `{}` in map of kind `{:?}`", p1, kind),
+                            doc_id: doc.name.to_string(),
+                            line_number,
+                        })?
+                        .clone()
+                        .resolve_with_inherited(doc, line_number, inherited_variables)?;
+                    if let Some(p2) = p2 {
+                        return resolve_(
+                            p2.as_str(),
+                            &value,
+                            line_number,
+                            doc,
+                            inherited_variables,
+                        );
+                    }
+                    Ok(value)
+                }
                 t => ftd::interpreter::utils::e2(
                     format!("Expected record found `{:?}`", t).as_str(),
                     doc.name,
@@ -391,6 +412,15 @@ impl<'a> TDoc<'a> {
                             return Ok(Some(variable));
                         }
                     }
+                    ftd::interpreter::Value::Map { data, .. } => {
+                        if let Some(field_value) = data.get(p1.as_str()) {
+                            if let Some(variable) =
+                                find_variable_reference(field_value, p2, doc, line_number)?
+                            {
+                                return Ok(Some(variable));
+                            }
+                        }
+                    }
                     t => {
                         return ftd::interpreter::utils::e2(
                             format!(
@@ -443,6 +473,25 @@ impl<'a> TDoc<'a> {
                             )?;
                             change_value(field, set, p2, doc, line_number)?;
                         }
+                        ftd::interpreter::Value::Map { data, kind } => match p2 {
+                            // `$map.key` on a missing key adds it
+                            None if !data.contains_key(p1.as_str()) => {
+                                data.insert(p1, set);
+                            }
+                            p2 => {
+                                let field = data.get_mut(p1.as_str()).ok_or(
+                                    ftd::interpreter::Error::ParseError {
+                                        message: format!(
+                                            "Can't find key `{}` in map of kind `{:?}`",
+                                            p1, kind
+                                        ),
+                                        doc_id: doc.name.to_string(),
+                                        line_number,
+                                    },
+                                )?;
+                                change_value(field, set, p2, doc, line_number)?;
+                            }
+                        },
                         t => {
                             return ftd::interpreter::utils::e2(
                                 format!("Expected record, found `{:?}`", t).as_str(),
@@ -644,7 +693,7 @@ impl<'a> TDoc<'a> {
                         Ok(ftd::interpreter::StateWithThing::new_thing(field_kind))
                     }
                 }
-                ftd::interpreter::Kind::List { kind } => {
+                ftd::interpreter::Kind::List { kind } | ftd::interpreter::Kind::Map { kind } => {
                     if let Some(remaining) = remaining {
                         get_kind_(*kind, &remaining, doc, line_number)
                     } else {
@@ -862,6 +911,7 @@ impl<'a> TDoc<'a> {
                     let fields = match value.resolve(doc, line_number)?.inner() {
                         Some(ftd::interpreter::Value::Record { fields, .. }) => fields,
                         Some(ftd::interpreter::Value::Object { values }) => values,
+                        Some(ftd::interpreter::Value::Map { data, .. }) => data,
                         Some(ftd::interpreter::Value::List { data, .. }) => data
                             .into_iter()
                             .enumerate()
@@ -1283,6 +1333,7 @@ impl<'a> TDoc<'a> {
                     let fields = match value.resolve(doc, line_number)?.inner() {
                         Some(ftd::interpreter::Value::Record { fields, .. }) => fields,
                         Some(ftd::interpreter::Value::Object { values }) => values,
+                        Some(ftd::interpreter::Value::Map { data, .. }) => data,
                         Some(ftd::interpreter::Value::List { data, .. }) => data
                             .into_iter()
                             .enumerate()
@@ -1996,6 +2047,28 @@ impl<'a> TDoc<'a> {
                     }
                 },
             },
+            ftd::interpreter::Kind::DateTime => match json {
+                serde_json::Value::String(s) => {
+                    ftd::interpreter::Value::new_datetime(s, self.name, line_number)?
+                }
+                serde_json::Value::Number(n) if n.is_i64() => {
+                    ftd::interpreter::Value::new_datetime(
+                        n.to_string().as_str(),
+                        self.name,
+                        line_number,
+                    )?
+                }
+                serde_json::Value::Object(o) => {
+                    return self.handle_object(kind, o, default_value, record_name, line_number)
+                }
+                _ => {
+                    return ftd::interpreter::utils::e2(
+                        format!("Can't parse to datetime, found: {}", json),
+                        self.name,
+                        line_number,
+                    )
+                }
+            },
             ftd::interpreter::Kind::Record { name, .. } => {
                 let rec_fields = self.get_record(name, line_number)?.fields;
                 let mut fields: ftd::Map<ftd::interpreter::PropertyValue> = Default::default();
@@ -2087,6 +2160,37 @@ impl<'a> TDoc<'a> {
                     kind: kind.to_owned().into_kind_data(),
                 }
             }
+            ftd::interpreter::Kind::Map { kind } => {
+                let mut data: ftd::Map<ftd::interpreter::PropertyValue> = Default::default();
+                if let serde_json::Value::Object(o) = json {
+                    for (key, item) in o {
+                        ftd::interpreter::utils::validate_map_key(
+                            &data,
+                            key.as_str(),
+                            self.name,
+                            line_number,
+                        )?;
+                        data.insert(
+                            key.to_string(),
+                            ftd::interpreter::PropertyValue::Value {
+                                value: self.as_json_(kind, item, None, None, line_number)?,
+                                is_mutable: false,
+                                line_number,
+                            },
+                        );
+                    }
+                } else {
+                    return ftd::interpreter::utils::e2(
+                        format!("expected object of map type, found: {}", json),
+                        self.name,
+                        line_number,
+                    );
+                }
+                ftd::interpreter::Value::Map {
+                    data,
+                    kind: kind.as_ref().clone().into_kind_data(),
+                }
+            }
             ftd::interpreter::Kind::Optional { kind, .. } => {
                 let kind = kind.as_ref();
                 match json {
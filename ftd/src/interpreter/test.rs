@@ -258,3 +258,460 @@ fn evalexpr_test() {
         }
             */
 }
+
+#[test]
+fn map_kind_test() {
+    let doc = interpret_helper(
+        "foo",
+        indoc::indoc!(
+            "
+            -- map<string, integer> counts:
+            apples: 3
+            pears: 5
+
+            -- ftd.integer: $counts.pears
+            "
+        ),
+    )
+    .unwrap_or_else(|e| panic!("{:?}", e));
+
+    let variable = match doc.data.get("foo#counts") {
+        Some(ftd::interpreter::Thing::Variable(v)) => v,
+        t => panic!("Expected variable, found: {:?}", t),
+    };
+    assert_eq!(
+        variable.kind.kind,
+        ftd::interpreter::Kind::integer().into_map()
+    );
+    let data = match &variable.value {
+        ftd::interpreter::PropertyValue::Value {
+            value: ftd::interpreter::Value::Map { data, .. },
+            ..
+        } => data,
+        t => panic!("Expected map value, found: {:?}", t),
+    };
+    assert_eq!(
+        data.keys().collect::<Vec<_>>(),
+        vec!["apples", "pears"],
+        "keys are iterated in order"
+    );
+    assert_eq!(
+        data.get("pears").and_then(|v| v.value_optional()).cloned(),
+        Some(ftd::interpreter::Value::Integer { value: 5 })
+    );
+
+    assert!(interpret_helper(
+        "foo",
+        "-- map<integer, string> names:\n\n-- ftd.text: $names.0\n"
+    )
+    .is_err());
+
+    assert_eq!(
+        interpret_helper(
+            "foo",
+            indoc::indoc!(
+                "
+                -- map<string, integer> counts:
+                green-pears: 3
+                green_pears: 5
+
+                -- ftd.integer: $counts.green-pears
+                "
+            ),
+        )
+        .map(|_| ())
+        .map_err(|e| e.to_string()),
+        Err(
+            "foo:3 -> Key `green_pears` of the map is the same name as the key `green-pears`"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn datetime_kind_test() {
+    let doc = interpret_helper(
+        "foo",
+        indoc::indoc!(
+            "
+            -- datetime created-at: 2023-10-18 09:30:00+05:30
+
+            -- string created-on: $ftd.format-datetime(value = $created-at, pattern = %e %B %Y)
+
+            -- ftd.text: $created-on
+            "
+        ),
+    )
+    .unwrap_or_else(|e| panic!("{:?}", e));
+
+    let value = |name: &str| match doc.data.get(name) {
+        Some(ftd::interpreter::Thing::Variable(v)) => v.value.clone(),
+        t => panic!("Expected variable, found: {:?}", t),
+    };
+    assert_eq!(
+        value("foo#created-at").value_optional().cloned(),
+        Some(ftd::interpreter::Value::DateTime {
+            value: "2023-10-18T09:30:00+05:30".to_string()
+        })
+    );
+    assert_eq!(
+        value("foo#created-on").kind(),
+        ftd::interpreter::Kind::string()
+    );
+
+    assert!(interpret_helper(
+        "foo",
+        indoc::indoc!(
+            "
+            -- datetime created-at: yesterday
+
+            -- ftd.text: $ftd.format-datetime(value = $created-at, pattern = %Y)
+            "
+        )
+    )
+    .is_err());
+}
+
+#[test]
+fn transform_transition_animation_test() {
+    interpret_helper(
+        "foo",
+        indoc::indoc!(
+            "
+            -- ftd.keyframe list fade-in-frames:
+
+            -- ftd.keyframe:
+            at: 0
+            opacity: 0.0
+
+            -- ftd.keyframe:
+            at: 100
+            opacity: 1.0
+
+            -- end: fade-in-frames
+
+            -- ftd.animation fade-in:
+            name: fade-in
+            keyframes: $fade-in-frames
+            duration: 0.5
+
+            -- ftd.transform lift:
+            translate-y.px: -4
+            scale: 1.05
+
+            -- ftd.transition quick:
+            property: transform
+            easing: ease-out
+
+            -- ftd.text: Hello
+            transform: $lift
+            transition: $quick
+            animation: $fade-in
+            "
+        ),
+    )
+    .unwrap_or_else(|e| panic!("{:?}", e));
+
+    let error = interpret_helper(
+        "foo",
+        indoc::indoc!(
+            "
+            -- ftd.transition slow:
+            easing: sometimes
+
+            -- ftd.text: Hello
+            transition: $slow
+            "
+        ),
+    )
+    .map(|_| ())
+    .unwrap_err()
+    .to_string();
+    assert!(
+        error.starts_with("foo:2 -> foo: Can't find variant `sometimes` in or-type `ftd#easing`"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn layered_background_test() {
+    interpret_helper(
+        "foo",
+        indoc::indoc!(
+            "
+            -- ftd.linear-gradient-color list stops:
+
+            -- ftd.linear-gradient-color: red
+            stop-position.percent: 40
+
+            -- ftd.linear-gradient-color: yellow
+
+            -- end: stops
+
+            -- ftd.radial-gradient glow:
+            shape: circle
+            position: center
+            colors: $stops
+
+            -- ftd.conic-gradient wheel:
+            from: 90
+            colors: $stops
+
+            -- ftd.background-layer list layers:
+
+            -- ftd.background-layer:
+            radial-gradient: $glow
+
+            -- ftd.background-layer:
+            conic-gradient: $wheel
+
+            -- end: layers
+
+            -- ftd.layered-background bg:
+            layers: $layers
+
+            -- ftd.column:
+            background.layers: $bg
+
+            -- ftd.text: Glow
+            background.radial-gradient: $glow
+
+            -- end: ftd.column
+            "
+        ),
+    )
+    .unwrap_or_else(|e| panic!("{:?}", e));
+
+    assert!(interpret_helper(
+        "foo",
+        indoc::indoc!(
+            "
+            -- ftd.linear-gradient-color list stops:
+
+            -- ftd.linear-gradient-color: red
+
+            -- end: stops
+
+            -- ftd.radial-gradient glow:
+            shape: square
+            colors: $stops
+
+            -- ftd.text: Glow
+            background.radial-gradient: $glow
+            "
+        ),
+    )
+    .is_err());
+}
+
+#[test]
+fn responsive_image_test() {
+    let doc = interpret_helper(
+        "foo",
+        indoc::indoc!(
+            "
+            -- ftd.image-src hero:
+            light: -/fastn.com/images/hero.png
+            srcset: -/fastn.com/images/hero-480w.webp 480w, -/fastn.com/images/hero-1200w.webp 1200w
+            width: 1200
+            height: 600
+
+            -- ftd.image:
+            src: $hero
+            sizes: (max-width: 600px) 480px, 1200px
+            "
+        ),
+    )
+    .unwrap_or_else(|e| panic!("{:?}", e));
+
+    let fields = match doc.data.get("foo#hero") {
+        Some(ftd::interpreter::Thing::Variable(ftd::interpreter::Variable {
+            value:
+                ftd::interpreter::PropertyValue::Value {
+                    value: ftd::interpreter::Value::Record { fields, .. },
+                    ..
+                },
+            ..
+        })) => fields,
+        t => panic!("Expected record variable, found: {:?}", t),
+    };
+    let field = |name: &str| fields.get(name).and_then(|v| v.value_optional()).unwrap();
+    assert_eq!(
+        field("srcset")
+            .optional_string("foo", 0)
+            .unwrap()
+            .as_deref(),
+        Some("-/fastn.com/images/hero-480w.webp 480w, -/fastn.com/images/hero-1200w.webp 1200w")
+    );
+    assert_eq!(
+        field("dark-srcset").optional_string("foo", 0).unwrap(),
+        None
+    );
+    assert_eq!(
+        field("width").optional_integer("foo", 0).unwrap(),
+        Some(1200)
+    );
+    assert_eq!(
+        field("height").optional_integer("foo", 0).unwrap(),
+        Some(600)
+    );
+    assert_eq!(
+        field("placeholder").optional_string("foo", 0).unwrap(),
+        None
+    );
+
+    let sizes = doc.tree[0]
+        .properties
+        .iter()
+        .find_map(|p| match &p.source {
+            ftd::interpreter::PropertySource::Header { name, .. } if name == "sizes" => {
+                p.value.value_optional()
+            }
+            _ => None,
+        })
+        .and_then(|v| v.optional_string("foo", 0).unwrap());
+    assert_eq!(sizes.as_deref(), Some("(max-width: 600px) 480px, 1200px"));
+
+    assert_eq!(
+        interpret_helper(
+            "foo",
+            indoc::indoc!(
+                "
+                -- ftd.image-src hero:
+                light: -/fastn.com/images/hero.png
+                width: wide
+
+                -- ftd.image:
+                src: $hero
+                "
+            ),
+        )
+        .map(|_| ())
+        .map_err(|e| e.to_string()),
+        Err("ParseIntError: invalid digit found in string".to_string())
+    );
+}
+
+#[test]
+fn grid_test() {
+    interpret_helper(
+        "foo",
+        indoc::indoc!(
+            "
+            -- ftd.grid:
+            columns: auto, auto
+            areas: header header, side main
+            gap.px: 8
+
+            -- ftd.text: Header
+            grid-area: header
+
+            -- ftd.text: Main
+            grid-area: main
+
+            -- end: ftd.grid
+            "
+        ),
+    )
+    .unwrap_or_else(|e| panic!("{:?}", e));
+
+    assert!(interpret_helper(
+        "foo",
+        indoc::indoc!(
+            "
+            -- ftd.grid:
+            columns: sometimes
+            "
+        ),
+    )
+    .is_err());
+}
+
+#[test]
+fn function_type_check_test() {
+    let error = |body: &str| {
+        let source = format!(
+            "-- record person:\nstring name:\ninteger age:\n\n\
+             -- integer total-age(people):\nperson list people:\n\n{}\n\n\
+             -- person list crowd:\n\n-- end: crowd\n\n\
+             -- ftd.integer: $total-age(people = $crowd)\n",
+            body
+        );
+        match interpret_helper("foo", source.as_str()) {
+            Err(ftd::interpreter::Error::ParseError {
+                message,
+                line_number,
+                ..
+            }) => Some((message, line_number)),
+            Ok(_) => None,
+            Err(e) => panic!("{:?}", e),
+        }
+    };
+
+    assert_eq!(error("reduce(people, 0, (sum, p) -> sum + p.age)"), None);
+    assert_eq!(error("len(filter(people, p -> p.age >= 18))"), None);
+
+    let (message, line_number) = error("people[0].name").unwrap();
+    assert_eq!(
+        message,
+        "Function `foo#total-age` returns `string`, expected `integer`"
+    );
+    assert_eq!(line_number, 8);
+    let (message, second_line) = error("n = len(people);\nn + count").unwrap();
+    assert_eq!(
+        message,
+        "Unknown variable `count` in function `foo#total-age`"
+    );
+    assert_eq!(second_line, line_number + 1);
+    assert_eq!(
+        error("people.0.email").unwrap().0,
+        "Record `foo#person` has no field `email`"
+    );
+    assert_eq!(
+        error("n = 0; n = people[0].name; n").unwrap().0,
+        "Can not assign `string` to `n` of kind `integer`"
+    );
+    assert_eq!(
+        error("str::to_uppercase(len(people))").unwrap().0,
+        "Function `str::to_uppercase` expects a string, found `integer`"
+    );
+}
+
+#[test]
+fn all_imported_things() {
+    let interpret = |all_imported_things: bool| {
+        let source = "-- import: lib\n\n-- ftd.text: $lib.used\n";
+        let document = ftd::interpreter::ParsedDocument::parse("foo", source).unwrap();
+        let mut s = if all_imported_things {
+            ftd::interpreter::interpret_with_all_imported_things("foo", document, 0)
+        } else {
+            ftd::interpreter::interpret_with_line_number("foo", document, 0)
+        }
+        .unwrap();
+        loop {
+            match s {
+                ftd::interpreter::Interpreter::Done { document } => return document,
+                ftd::interpreter::Interpreter::StuckOnImport {
+                    module, state: st, ..
+                } => {
+                    let source = "-- string used: Hello\n\n-- string unused: $used\n";
+                    let document =
+                        ftd::interpreter::ParsedDocument::parse(module.as_str(), source).unwrap();
+                    s = st
+                        .continue_after_import(module.as_str(), document, vec![], vec![], 0)
+                        .unwrap();
+                }
+                _ => unreachable!(),
+            }
+        }
+    };
+
+    let document = interpret(false);
+    assert!(document.data.contains_key("lib#used"));
+    assert!(!document.data.contains_key("lib#unused"));
+
+    let document = interpret(true);
+    assert!(document.data.contains_key("lib#used"));
+    assert!(document.data.contains_key("lib#unused"));
+}
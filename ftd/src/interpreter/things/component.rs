@@ -1250,9 +1250,11 @@ impl Loop {
     ) -> ftd::interpreter::Result<ftd::interpreter::Kind> {
         let kind = self.on.kind();
         match kind {
-            ftd::interpreter::Kind::List { kind } => Ok(kind.as_ref().to_owned()),
+            ftd::interpreter::Kind::List { kind } | ftd::interpreter::Kind::Map { kind } => {
+                Ok(kind.as_ref().to_owned())
+            }
             t => ftd::interpreter::utils::e2(
-                format!("Expected list kind, found: {:?}", t),
+                format!("Expected list or map kind, found: {:?}", t),
                 doc_id,
                 self.line_number,
             ),
@@ -1323,16 +1325,28 @@ impl Loop {
         ftd::interpreter::KindData,
     )> {
         let value = self.on.clone().resolve(doc, self.line_number)?;
-        if let ftd::interpreter::Value::List { data, kind } = value {
-            Ok((data, kind))
-        } else {
-            ftd::interpreter::utils::e2(
-                format!("Expected list type data, found: {:?}", self.on),
+        match value {
+            ftd::interpreter::Value::List { data, kind } => Ok((data, kind)),
+            ftd::interpreter::Value::Map { data, kind } => Ok((data.into_values().collect(), kind)),
+            _ => ftd::interpreter::utils::e2(
+                format!("Expected list or map type data, found: {:?}", self.on),
                 doc.name,
                 self.line_number,
-            )
+            ),
         }
     }
+
+    /// Keys of the map being looped over, in iteration order. `None` for lists.
+    pub fn keys(
+        &self,
+        doc: &ftd::interpreter::TDoc,
+    ) -> ftd::interpreter::Result<Option<Vec<String>>> {
+        let value = self.on.clone().resolve(doc, self.line_number)?;
+        Ok(match value {
+            ftd::interpreter::Value::Map { data, .. } => Some(data.into_keys().collect()),
+            _ => None,
+        })
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
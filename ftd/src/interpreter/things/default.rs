@@ -114,6 +114,76 @@ pub fn default_functions() -> ftd::Map<fastn_grammar::evalexpr::Function> {
                 }
             }),
         ),
+        (
+            "ftd.format_datetime".to_string(),
+            Function::new(|argument| {
+                let args = datetime_arguments(argument, 3)?;
+                let dt = fastn_grammar::datetime::parse(&args[0].as_string()?)?;
+                let locale = args[2].as_string()?;
+                Ok(Value::String(fastn_grammar::datetime::format(
+                    &dt,
+                    &args[1].as_string()?,
+                    Some(locale.as_str()),
+                )?))
+            }),
+        ),
+        (
+            "ftd.relative_datetime".to_string(),
+            Function::new(|argument| {
+                let args = datetime_arguments(argument, 2)?;
+                let dt = fastn_grammar::datetime::parse(&args[0].as_string()?)?;
+                let locale = args[1].as_string()?;
+                Ok(Value::String(fastn_grammar::datetime::relative(
+                    &dt,
+                    &fastn_grammar::datetime::now(),
+                    Some(locale.as_str()),
+                )))
+            }),
+        ),
+        (
+            "ftd.add_to_datetime".to_string(),
+            Function::new(|argument| {
+                let args = datetime_arguments(argument, 3)?;
+                let dt = fastn_grammar::datetime::parse(&args[0].as_string()?)?;
+                let dt = fastn_grammar::datetime::add(
+                    &dt,
+                    args[1].as_int()?,
+                    &args[2].as_string()?,
+                )?;
+                Ok(Value::String(fastn_grammar::datetime::to_string(&dt)))
+            }),
+        ),
+        (
+            "ftd.datetime_diff".to_string(),
+            Function::new(|argument| {
+                let args = datetime_arguments(argument, 3)?;
+                let a = fastn_grammar::datetime::parse(&args[0].as_string()?)?;
+                let b = fastn_grammar::datetime::parse(&args[1].as_string()?)?;
+                Ok(Value::Int(fastn_grammar::datetime::diff(
+                    &a,
+                    &b,
+                    &args[2].as_string()?,
+                )?))
+            }),
+        ),
+        (
+            "ftd.start_of_day".to_string(),
+            Function::new(|argument| {
+                let dt = fastn_grammar::datetime::parse(&argument.as_string()?)?;
+                Ok(Value::String(fastn_grammar::datetime::to_string(
+                    &fastn_grammar::datetime::start_of_day(&dt),
+                )))
+            }),
+        ),
+        (
+            "ftd.datetime_in_offset".to_string(),
+            Function::new(|argument| {
+                let args = datetime_arguments(argument, 2)?;
+                let dt = fastn_grammar::datetime::parse(&args[0].as_string()?)?;
+                let dt = fastn_grammar::datetime::with_offset(&dt, &args[1].as_string()?)?;
+                Ok(Value::String(fastn_grammar::datetime::to_string(&dt)))
+            }),
+        ),
         (
             "enable_dark_mode".to_string(),
             Function::new(|_| Ok(Value::Empty)),
@@ -130,6 +200,22 @@ pub fn default_functions() -> ftd::Map<fastn_grammar::evalexpr::Function> {
     .collect()
 }
 
+fn datetime_arguments(
+    argument: &fastn_grammar::evalexpr::Value,
+    expected: usize,
+) -> fastn_grammar::evalexpr::EvalexprResult<fastn_grammar::evalexpr::TupleType> {
+    let tuple = argument.as_tuple()?;
+    if tuple.len().ne(&expected) {
+        return Err(
+            fastn_grammar::evalexpr::error::EvalexprError::WrongFunctionArgumentAmount {
+                expected,
+                actual: tuple.len(),
+            },
+        );
+    }
+    Ok(tuple)
+}
+
 pub fn default_context() -> ftd::interpreter::Result<fastn_grammar::evalexpr::HashMapContext> {
     let mut context = fastn_grammar::evalexpr::HashMapContext::new();
     for (key, function) in default_functions() {
@@ -148,7 +234,9 @@ themselves.
 pub fn default_bag() -> indexmap::IndexMap<String, ftd::interpreter::Thing> {
     let record = |n: &str, r: &str| (n.to_string(), ftd::interpreter::Kind::record(r));
     let _color = |n: &str| record(n, "ftd#color");
-    std::iter::IntoIterator::into_iter([
+    // a `Vec` and not an array, the iterator adapters would get the array by value and the debug
+    // builds would need a bigger stack than the 2MiB of the test and tokio threads
+    vec![
         (
             "ftd#row".to_string(),
             ftd::interpreter::Thing::Component(row_function()),
@@ -161,6 +249,10 @@ pub fn default_bag() -> indexmap::IndexMap<String, ftd::interpreter::Thing> {
             "ftd#container".to_string(),
             ftd::interpreter::Thing::Component(container_function()),
         ),
+        (
+            "ftd#grid".to_string(),
+            ftd::interpreter::Thing::Component(grid_function()),
+        ),
         (
             "ftd#desktop".to_string(),
             ftd::interpreter::Thing::Component(desktop_function()),
@@ -725,6 +817,60 @@ pub fn default_bag() -> indexmap::IndexMap<String, ftd::interpreter::Thing> {
                 external_implementation: true
             })
         ),
+        datetime_function(
+            "ftd#format-datetime",
+            ftd::interpreter::Kind::string(),
+            vec![
+                ("value", ftd::interpreter::Kind::datetime(), None),
+                ("pattern", ftd::interpreter::Kind::string(), Some("%Y-%m-%d %H:%M")),
+                ("locale", ftd::interpreter::Kind::string(), Some("en")),
+            ],
+            "ftd.format_datetime(value, pattern, locale)",
+        ),
+        datetime_function(
+            "ftd#relative-datetime",
+            ftd::interpreter::Kind::string(),
+            vec![
+                ("value", ftd::interpreter::Kind::datetime(), None),
+                ("locale", ftd::interpreter::Kind::string(), Some("en")),
+            ],
+            "ftd.relative_datetime(value, locale)",
+        ),
+        datetime_function(
+            "ftd#add-to-datetime",
+            ftd::interpreter::Kind::datetime(),
+            vec![
+                ("value", ftd::interpreter::Kind::datetime(), None),
+                ("amount", ftd::interpreter::Kind::integer(), None),
+                ("unit", ftd::interpreter::Kind::string(), Some("day")),
+            ],
+            "ftd.add_to_datetime(value, amount, unit)",
+        ),
+        datetime_function(
+            "ftd#datetime-diff",
+            ftd::interpreter::Kind::integer(),
+            vec![
+                ("value", ftd::interpreter::Kind::datetime(), None),
+                ("other", ftd::interpreter::Kind::datetime(), None),
+                ("unit", ftd::interpreter::Kind::string(), Some("day")),
+            ],
+            "ftd.datetime_diff(value, other, unit)",
+        ),
+        datetime_function(
+            "ftd#start-of-day",
+            ftd::interpreter::Kind::datetime(),
+            vec![("value", ftd::interpreter::Kind::datetime(), None)],
+            "ftd.start_of_day(value)",
+        ),
+        datetime_function(
+            "ftd#datetime-in-offset",
+            ftd::interpreter::Kind::datetime(),
+            vec![
+                ("value", ftd::interpreter::Kind::datetime(), None),
+                ("offset", ftd::interpreter::Kind::string(), None),
+            ],
+            "ftd.datetime_in_offset(value, offset)",
+        ),
         (
             "ftd#copy-to-clipboard".to_string(),
             ftd::interpreter::Thing::Function(ftd::interpreter::Function {
@@ -972,6 +1118,46 @@ pub fn default_bag() -> indexmap::IndexMap<String, ftd::interpreter::Thing> {
                         access_modifier: Default::default(),
                         line_number: 0,
                     },
+                    ftd::interpreter::Field {
+                        name: "srcset".to_string(),
+                        kind: ftd::interpreter::Kind::string().into_optional().into_kind_data(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "dark-srcset".to_string(),
+                        kind: ftd::interpreter::Kind::string().into_optional().into_kind_data(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "width".to_string(),
+                        kind: ftd::interpreter::Kind::integer().into_optional().into_kind_data(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "height".to_string(),
+                        kind: ftd::interpreter::Kind::integer().into_optional().into_kind_data(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "placeholder".to_string(),
+                        kind: ftd::interpreter::Kind::string().into_optional().into_kind_data(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
                 ])
                 .collect(),
                 line_number: 0,
@@ -1063,6 +1249,480 @@ pub fn default_bag() -> indexmap::IndexMap<String, ftd::interpreter::Thing> {
                 line_number: 0,
             }),
         ),
+        (
+            ftd::interpreter::FTD_TRANSFORM.to_string(),
+            ftd::interpreter::Thing::Record(ftd::interpreter::Record {
+                name: ftd::interpreter::FTD_TRANSFORM.to_string(),
+                fields: std::iter::IntoIterator::into_iter([
+                    ftd::interpreter::Field {
+                        name: "translate-x".to_string(),
+                        kind: ftd::interpreter::Kind::or_type(ftd::interpreter::FTD_LENGTH)
+                            .into_optional()
+                            .into_kind_data(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "translate-y".to_string(),
+                        kind: ftd::interpreter::Kind::or_type(ftd::interpreter::FTD_LENGTH)
+                            .into_optional()
+                            .into_kind_data(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "rotate".to_string(),
+                        kind: ftd::interpreter::Kind::integer().into_optional().into_kind_data(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "scale".to_string(),
+                        kind: ftd::interpreter::Kind::decimal().into_optional().into_kind_data(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "scale-x".to_string(),
+                        kind: ftd::interpreter::Kind::decimal().into_optional().into_kind_data(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "scale-y".to_string(),
+                        kind: ftd::interpreter::Kind::decimal().into_optional().into_kind_data(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "skew-x".to_string(),
+                        kind: ftd::interpreter::Kind::integer().into_optional().into_kind_data(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "skew-y".to_string(),
+                        kind: ftd::interpreter::Kind::integer().into_optional().into_kind_data(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                ])
+                .collect(),
+                line_number: 0,
+            }),
+        ),
+        (
+            ftd::interpreter::FTD_EASING.to_string(),
+            ftd::interpreter::Thing::OrType(ftd::interpreter::OrType {
+                name: ftd::interpreter::FTD_EASING.to_string(),
+                variants: vec![
+                    ftd::interpreter::OrTypeVariant::new_constant(ftd::interpreter::Field::new(
+                        ftd::interpreter::FTD_EASING_LINEAR,
+                        ftd::interpreter::Kind::string().into_kind_data(),
+                        false,
+                        Some(
+                            ftd::interpreter::Value::new_string("linear")
+                                .into_property_value(false, 0),
+                        ),
+                        0,
+                    )),
+                    ftd::interpreter::OrTypeVariant::new_constant(ftd::interpreter::Field::new(
+                        ftd::interpreter::FTD_EASING_EASE,
+                        ftd::interpreter::Kind::string().into_kind_data(),
+                        false,
+                        Some(
+                            ftd::interpreter::Value::new_string("ease")
+                                .into_property_value(false, 0),
+                        ),
+                        0,
+                    )),
+                    ftd::interpreter::OrTypeVariant::new_constant(ftd::interpreter::Field::new(
+                        ftd::interpreter::FTD_EASING_EASE_IN,
+                        ftd::interpreter::Kind::string().into_kind_data(),
+                        false,
+                        Some(
+                            ftd::interpreter::Value::new_string("ease-in")
+                                .into_property_value(false, 0),
+                        ),
+                        0,
+                    )),
+                    ftd::interpreter::OrTypeVariant::new_constant(ftd::interpreter::Field::new(
+                        ftd::interpreter::FTD_EASING_EASE_OUT,
+                        ftd::interpreter::Kind::string().into_kind_data(),
+                        false,
+                        Some(
+                            ftd::interpreter::Value::new_string("ease-out")
+                                .into_property_value(false, 0),
+                        ),
+                        0,
+                    )),
+                    ftd::interpreter::OrTypeVariant::new_constant(ftd::interpreter::Field::new(
+                        ftd::interpreter::FTD_EASING_EASE_IN_OUT,
+                        ftd::interpreter::Kind::string().into_kind_data(),
+                        false,
+                        Some(
+                            ftd::interpreter::Value::new_string("ease-in-out")
+                                .into_property_value(false, 0),
+                        ),
+                        0,
+                    )),
+                ],
+                line_number: 0,
+            }),
+        ),
+        (
+            ftd::interpreter::FTD_TRANSITION.to_string(),
+            ftd::interpreter::Thing::Record(ftd::interpreter::Record {
+                name: ftd::interpreter::FTD_TRANSITION.to_string(),
+                fields: std::iter::IntoIterator::into_iter([
+                    ftd::interpreter::Field {
+                        name: "property".to_string(),
+                        kind: ftd::interpreter::Kind::string().into_kind_data(),
+                        mutable: false,
+                        value: Some(ftd::interpreter::Value::new_string("all").into_property_value(false, 0)),
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "duration".to_string(),
+                        kind: ftd::interpreter::Kind::decimal().into_kind_data(),
+                        mutable: false,
+                        value: Some(ftd::interpreter::Value::Decimal { value: 0.2 }.into_property_value(false, 0)),
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "easing".to_string(),
+                        kind: ftd::interpreter::Kind::or_type(ftd::interpreter::FTD_EASING).into_kind_data(),
+                        mutable: false,
+                        value: Some(ftd::interpreter::Value::new_or_type(
+                            ftd::interpreter::FTD_EASING,
+                            ftd::interpreter::FTD_EASING_EASE,
+                            ftd::interpreter::FTD_EASING_EASE,
+                            ftd::interpreter::Value::new_string("ease").into_property_value(false, 0),
+                        )
+                        .into_property_value(false, 0)),
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "delay".to_string(),
+                        kind: ftd::interpreter::Kind::decimal().into_kind_data(),
+                        mutable: false,
+                        value: Some(ftd::interpreter::Value::Decimal { value: 0.0 }.into_property_value(false, 0)),
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                ])
+                .collect(),
+                line_number: 0,
+            }),
+        ),
+        (
+            ftd::interpreter::FTD_KEYFRAME.to_string(),
+            ftd::interpreter::Thing::Record(ftd::interpreter::Record {
+                name: ftd::interpreter::FTD_KEYFRAME.to_string(),
+                fields: std::iter::IntoIterator::into_iter([
+                    ftd::interpreter::Field {
+                        name: "at".to_string(),
+                        kind: ftd::interpreter::Kind::integer().into_kind_data(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "opacity".to_string(),
+                        kind: ftd::interpreter::Kind::decimal().into_optional().into_kind_data(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "transform".to_string(),
+                        kind: ftd::interpreter::Kind::record(ftd::interpreter::FTD_TRANSFORM)
+                            .into_optional()
+                            .into_kind_data(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                ])
+                .collect(),
+                line_number: 0,
+            }),
+        ),
+        (
+            ftd::interpreter::FTD_ANIMATION.to_string(),
+            ftd::interpreter::Thing::Record(ftd::interpreter::Record {
+                name: ftd::interpreter::FTD_ANIMATION.to_string(),
+                fields: std::iter::IntoIterator::into_iter([
+                    ftd::interpreter::Field {
+                        name: "name".to_string(),
+                        kind: ftd::interpreter::Kind::string().into_kind_data(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "keyframes".to_string(),
+                        kind: ftd::interpreter::Kind::record(ftd::interpreter::FTD_KEYFRAME)
+                            .into_list()
+                            .into_kind_data(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "duration".to_string(),
+                        kind: ftd::interpreter::Kind::decimal().into_kind_data(),
+                        mutable: false,
+                        value: Some(ftd::interpreter::Value::Decimal { value: 1.0 }.into_property_value(false, 0)),
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "easing".to_string(),
+                        kind: ftd::interpreter::Kind::or_type(ftd::interpreter::FTD_EASING).into_kind_data(),
+                        mutable: false,
+                        value: Some(ftd::interpreter::Value::new_or_type(
+                            ftd::interpreter::FTD_EASING,
+                            ftd::interpreter::FTD_EASING_EASE,
+                            ftd::interpreter::FTD_EASING_EASE,
+                            ftd::interpreter::Value::new_string("ease").into_property_value(false, 0),
+                        )
+                        .into_property_value(false, 0)),
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "delay".to_string(),
+                        kind: ftd::interpreter::Kind::decimal().into_kind_data(),
+                        mutable: false,
+                        value: Some(ftd::interpreter::Value::Decimal { value: 0.0 }.into_property_value(false, 0)),
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "iterations".to_string(),
+                        kind: ftd::interpreter::Kind::integer().into_optional().into_kind_data(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "alternate".to_string(),
+                        kind: ftd::interpreter::Kind::boolean().into_kind_data(),
+                        mutable: false,
+                        value: Some(ftd::interpreter::Value::Boolean { value: false }.into_property_value(false, 0)),
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                ])
+                .collect(),
+                line_number: 0,
+            }),
+        ),
+        (
+            ftd::interpreter::FTD_GRID_MINMAX.to_string(),
+            ftd::interpreter::Thing::Record(ftd::interpreter::Record {
+                name: ftd::interpreter::FTD_GRID_MINMAX.to_string(),
+                fields: std::iter::IntoIterator::into_iter([
+                    ftd::interpreter::Field {
+                        name: "min".to_string(),
+                        kind: ftd::interpreter::Kind::or_type(ftd::interpreter::FTD_LENGTH)
+                            .into_kind_data()
+                            .caption(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "max".to_string(),
+                        kind: ftd::interpreter::Kind::or_type(ftd::interpreter::FTD_LENGTH)
+                            .into_optional()
+                            .into_kind_data(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "max-fr".to_string(),
+                        kind: ftd::interpreter::Kind::integer().into_kind_data(),
+                        mutable: false,
+                        value: Some(ftd::interpreter::Value::Integer { value: 1 }.into_property_value(false, 0)),
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                ])
+                .collect(),
+                line_number: 0,
+            }),
+        ),
+        (
+            ftd::interpreter::FTD_GRID_TRACK.to_string(),
+            ftd::interpreter::Thing::OrType(ftd::interpreter::OrType {
+                name: ftd::interpreter::FTD_GRID_TRACK.to_string(),
+                variants: vec![
+                    ftd::interpreter::OrTypeVariant::Regular(ftd::interpreter::Field::new(
+                        ftd::interpreter::FTD_GRID_TRACK_FIXED,
+                        ftd::interpreter::Kind::or_type(ftd::interpreter::FTD_LENGTH)
+                            .into_kind_data()
+                            .caption(),
+                        false,
+                        None,
+                        0,
+                    )),
+                    ftd::interpreter::OrTypeVariant::Regular(ftd::interpreter::Field::new(
+                        ftd::interpreter::FTD_GRID_TRACK_FR,
+                        ftd::interpreter::Kind::integer()
+                            .into_kind_data()
+                            .caption(),
+                        false,
+                        None,
+                        0,
+                    )),
+                    ftd::interpreter::OrTypeVariant::new_constant(ftd::interpreter::Field::new(
+                        ftd::interpreter::FTD_GRID_TRACK_AUTO,
+                        ftd::interpreter::Kind::string().into_kind_data(),
+                        false,
+                        Some(
+                            ftd::interpreter::Value::new_string("auto")
+                                .into_property_value(false, 0),
+                        ),
+                        0,
+                    )),
+                    ftd::interpreter::OrTypeVariant::Regular(ftd::interpreter::Field::new(
+                        ftd::interpreter::FTD_GRID_TRACK_MINMAX,
+                        ftd::interpreter::Kind::record(ftd::interpreter::FTD_GRID_MINMAX)
+                            .into_kind_data()
+                            .caption(),
+                        false,
+                        None,
+                        0,
+                    )),
+                    ftd::interpreter::OrTypeVariant::Regular(ftd::interpreter::Field::new(
+                        ftd::interpreter::FTD_GRID_TRACK_AUTO_FILL,
+                        ftd::interpreter::Kind::record(ftd::interpreter::FTD_GRID_MINMAX)
+                            .into_kind_data()
+                            .caption(),
+                        false,
+                        None,
+                        0,
+                    )),
+                    ftd::interpreter::OrTypeVariant::Regular(ftd::interpreter::Field::new(
+                        ftd::interpreter::FTD_GRID_TRACK_AUTO_FIT,
+                        ftd::interpreter::Kind::record(ftd::interpreter::FTD_GRID_MINMAX)
+                            .into_kind_data()
+                            .caption(),
+                        false,
+                        None,
+                        0,
+                    )),
+                ],
+                line_number: 0,
+            }),
+        ),
+        (
+            ftd::interpreter::FTD_GRID_TEMPLATE.to_string(),
+            ftd::interpreter::Thing::Record(ftd::interpreter::Record {
+                name: ftd::interpreter::FTD_GRID_TEMPLATE.to_string(),
+                fields: std::iter::IntoIterator::into_iter([
+                    ftd::interpreter::Field {
+                        name: "columns".to_string(),
+                        kind: ftd::interpreter::Kind::or_type(ftd::interpreter::FTD_GRID_TRACK)
+                            .into_list()
+                            .into_kind_data(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "rows".to_string(),
+                        kind: ftd::interpreter::Kind::or_type(ftd::interpreter::FTD_GRID_TRACK)
+                            .into_list()
+                            .into_kind_data(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "areas".to_string(),
+                        kind: ftd::interpreter::Kind::string()
+                            .into_list()
+                            .into_kind_data(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                ])
+                .collect(),
+                line_number: 0,
+            }),
+        ),
+        (
+            ftd::interpreter::FTD_RESPONSIVE_GRID_TEMPLATE.to_string(),
+            ftd::interpreter::Thing::Record(ftd::interpreter::Record {
+                name: ftd::interpreter::FTD_RESPONSIVE_GRID_TEMPLATE.to_string(),
+                fields: std::iter::IntoIterator::into_iter([
+                    ftd::interpreter::Field {
+                        name: "desktop".to_string(),
+                        kind: ftd::interpreter::Kind::record(ftd::interpreter::FTD_GRID_TEMPLATE)
+                            .into_kind_data(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "mobile".to_string(),
+                        kind: ftd::interpreter::Kind::record(ftd::interpreter::FTD_GRID_TEMPLATE)
+                            .into_kind_data(),
+                        mutable: false,
+                        access_modifier: Default::default(),
+                        value: Some(ftd::interpreter::PropertyValue::Reference {
+                            name: ftd::interpreter::FTD_RESPONSIVE_GRID_TEMPLATE_DESKTOP.to_string(),
+                            kind: ftd::interpreter::Kind::record(ftd::interpreter::FTD_GRID_TEMPLATE)
+                                .into_kind_data(),
+                            source: ftd::interpreter::PropertyValueSource::Local(
+                                ftd::interpreter::FTD_RESPONSIVE_GRID_TEMPLATE.to_string(),
+                            ),
+                            is_mutable: false,
+                            line_number: 0,
+                        }),
+                        line_number: 0,
+                    },
+                ])
+                .collect(),
+                line_number: 0,
+            }),
+        ),
         (
             ftd::interpreter::FTD_SHADOW.to_string(),
             ftd::interpreter::Thing::Record(ftd::interpreter::Record {
@@ -1417,21 +2077,94 @@ pub fn default_bag() -> indexmap::IndexMap<String, ftd::interpreter::Thing> {
                         0,
                     )),
                     ftd::interpreter::OrTypeVariant::new_constant(ftd::interpreter::Field::new(
-                        ftd::interpreter::FTD_LINEAR_GRADIENT_DIRECTIONS_TOP_RIGHT,
+                        ftd::interpreter::FTD_LINEAR_GRADIENT_DIRECTIONS_TOP_RIGHT,
+                        ftd::interpreter::Kind::string().into_kind_data(),
+                        false,
+                        Some(
+                            ftd::interpreter::Value::new_string("to top right")
+                                .into_property_value(false, 0),
+                        ),
+                        0,
+                    )),
+                    ftd::interpreter::OrTypeVariant::new_constant(ftd::interpreter::Field::new(
+                        ftd::interpreter::FTD_LINEAR_GRADIENT_DIRECTIONS_BOTTOM_RIGHT,
+                        ftd::interpreter::Kind::string().into_kind_data(),
+                        false,
+                        Some(
+                            ftd::interpreter::Value::new_string("to bottom right")
+                                .into_property_value(false, 0),
+                        ),
+                        0,
+                    )),
+                ],
+                line_number: 0,
+            }),
+        ),
+        (
+            ftd::interpreter::FTD_LINEAR_GRADIENT.to_string(),
+            ftd::interpreter::Thing::Record(ftd::interpreter::Record {
+                name: ftd::interpreter::FTD_LINEAR_GRADIENT.to_string(),
+                fields: std::iter::IntoIterator::into_iter([
+                    ftd::interpreter::Field {
+                        name: "direction".to_string(),
+                        kind: ftd::interpreter::Kind::or_type(ftd::interpreter::FTD_LINEAR_GRADIENT_DIRECTIONS)
+                            .into_kind_data().into_optional(),
+                        mutable: false,
+                        value: Some(ftd::interpreter::PropertyValue::Value {
+                            value: ftd::interpreter::Value::OrType {
+                                name: ftd::interpreter::FTD_LINEAR_GRADIENT_DIRECTIONS.to_string(),
+                                variant: ftd::interpreter::FTD_LINEAR_GRADIENT_DIRECTIONS_BOTTOM
+                                    .to_string(),
+                                full_variant: ftd::interpreter::FTD_LINEAR_GRADIENT_DIRECTIONS_BOTTOM.to_string(),
+                                value: Box::new
+                                    (ftd::interpreter::PropertyValue::Value {
+                                        value: ftd::interpreter::Value::String {
+                                            text: "bottom".to_string(),
+                                        },
+                                        is_mutable: false,
+                                        line_number: 0
+                                    }),
+                            },
+                            is_mutable: false,
+                            line_number: 0,
+                        }),
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "colors".to_string(),
+                        kind: ftd::interpreter::Kind::record(ftd::interpreter::FTD_LINEAR_GRADIENT_COLOR)
+                            .into_list().into_kind_data(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                ]).collect(),
+                line_number: 0,
+            }),
+        ),
+        (
+            ftd::interpreter::FTD_RADIAL_GRADIENT_SHAPE.to_string(),
+            ftd::interpreter::Thing::OrType(ftd::interpreter::OrType {
+                name: ftd::interpreter::FTD_RADIAL_GRADIENT_SHAPE.to_string(),
+                variants: vec![
+                    ftd::interpreter::OrTypeVariant::new_constant(ftd::interpreter::Field::new(
+                        ftd::interpreter::FTD_RADIAL_GRADIENT_SHAPE_CIRCLE,
                         ftd::interpreter::Kind::string().into_kind_data(),
                         false,
                         Some(
-                            ftd::interpreter::Value::new_string("to top right")
+                            ftd::interpreter::Value::new_string("circle")
                                 .into_property_value(false, 0),
                         ),
                         0,
                     )),
                     ftd::interpreter::OrTypeVariant::new_constant(ftd::interpreter::Field::new(
-                        ftd::interpreter::FTD_LINEAR_GRADIENT_DIRECTIONS_BOTTOM_RIGHT,
+                        ftd::interpreter::FTD_RADIAL_GRADIENT_SHAPE_ELLIPSE,
                         ftd::interpreter::Kind::string().into_kind_data(),
                         false,
                         Some(
-                            ftd::interpreter::Value::new_string("to bottom right")
+                            ftd::interpreter::Value::new_string("ellipse")
                                 .into_property_value(false, 0),
                         ),
                         0,
@@ -1441,25 +2174,25 @@ pub fn default_bag() -> indexmap::IndexMap<String, ftd::interpreter::Thing> {
             }),
         ),
         (
-            ftd::interpreter::FTD_LINEAR_GRADIENT.to_string(),
+            ftd::interpreter::FTD_RADIAL_GRADIENT.to_string(),
             ftd::interpreter::Thing::Record(ftd::interpreter::Record {
-                name: ftd::interpreter::FTD_LINEAR_GRADIENT.to_string(),
+                name: ftd::interpreter::FTD_RADIAL_GRADIENT.to_string(),
                 fields: std::iter::IntoIterator::into_iter([
                     ftd::interpreter::Field {
-                        name: "direction".to_string(),
-                        kind: ftd::interpreter::Kind::or_type(ftd::interpreter::FTD_LINEAR_GRADIENT_DIRECTIONS)
-                            .into_kind_data().into_optional(),
+                        name: "shape".to_string(),
+                        kind: ftd::interpreter::Kind::or_type(ftd::interpreter::FTD_RADIAL_GRADIENT_SHAPE)
+                            .into_kind_data(),
                         mutable: false,
                         value: Some(ftd::interpreter::PropertyValue::Value {
                             value: ftd::interpreter::Value::OrType {
-                                name: ftd::interpreter::FTD_LINEAR_GRADIENT_DIRECTIONS.to_string(),
-                                variant: ftd::interpreter::FTD_LINEAR_GRADIENT_DIRECTIONS_BOTTOM
+                                name: ftd::interpreter::FTD_RADIAL_GRADIENT_SHAPE.to_string(),
+                                variant: ftd::interpreter::FTD_RADIAL_GRADIENT_SHAPE_ELLIPSE
                                     .to_string(),
-                                full_variant: ftd::interpreter::FTD_LINEAR_GRADIENT_DIRECTIONS_BOTTOM.to_string(),
+                                full_variant: ftd::interpreter::FTD_RADIAL_GRADIENT_SHAPE_ELLIPSE.to_string(),
                                 value: Box::new
                                     (ftd::interpreter::PropertyValue::Value {
                                         value: ftd::interpreter::Value::String {
-                                            text: "bottom".to_string(),
+                                            text: "ellipse".to_string(),
                                         },
                                         is_mutable: false,
                                         line_number: 0
@@ -1471,6 +2204,50 @@ pub fn default_bag() -> indexmap::IndexMap<String, ftd::interpreter::Thing> {
                         access_modifier: Default::default(),
                         line_number: 0,
                     },
+                    ftd::interpreter::Field {
+                        name: "position".to_string(),
+                        kind: ftd::interpreter::Kind::or_type(ftd::interpreter::FTD_BACKGROUND_POSITION)
+                            .into_kind_data().into_optional(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "colors".to_string(),
+                        kind: ftd::interpreter::Kind::record(ftd::interpreter::FTD_LINEAR_GRADIENT_COLOR)
+                            .into_list().into_kind_data(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                ]).collect(),
+                line_number: 0,
+            }),
+        ),
+        (
+            ftd::interpreter::FTD_CONIC_GRADIENT.to_string(),
+            ftd::interpreter::Thing::Record(ftd::interpreter::Record {
+                name: ftd::interpreter::FTD_CONIC_GRADIENT.to_string(),
+                fields: std::iter::IntoIterator::into_iter([
+                    ftd::interpreter::Field {
+                        name: "from".to_string(),
+                        kind: ftd::interpreter::Kind::integer().into_kind_data(),
+                        mutable: false,
+                        value: Some(ftd::interpreter::Value::Integer { value: 0 }.into_property_value(false, 0)),
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "position".to_string(),
+                        kind: ftd::interpreter::Kind::or_type(ftd::interpreter::FTD_BACKGROUND_POSITION)
+                            .into_kind_data().into_optional(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
                     ftd::interpreter::Field {
                         name: "colors".to_string(),
                         kind: ftd::interpreter::Kind::record(ftd::interpreter::FTD_LINEAR_GRADIENT_COLOR)
@@ -1484,6 +2261,78 @@ pub fn default_bag() -> indexmap::IndexMap<String, ftd::interpreter::Thing> {
                 line_number: 0,
             }),
         ),
+        (
+            ftd::interpreter::FTD_BACKGROUND_LAYER.to_string(),
+            ftd::interpreter::Thing::Record(ftd::interpreter::Record {
+                name: ftd::interpreter::FTD_BACKGROUND_LAYER.to_string(),
+                fields: std::iter::IntoIterator::into_iter([
+                    ftd::interpreter::Field {
+                        name: "image".to_string(),
+                        kind: ftd::interpreter::Kind::record(ftd::interpreter::FTD_BG_IMAGE)
+                            .into_kind_data().into_optional(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "linear-gradient".to_string(),
+                        kind: ftd::interpreter::Kind::record(ftd::interpreter::FTD_LINEAR_GRADIENT)
+                            .into_kind_data().into_optional(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "radial-gradient".to_string(),
+                        kind: ftd::interpreter::Kind::record(ftd::interpreter::FTD_RADIAL_GRADIENT)
+                            .into_kind_data().into_optional(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "conic-gradient".to_string(),
+                        kind: ftd::interpreter::Kind::record(ftd::interpreter::FTD_CONIC_GRADIENT)
+                            .into_kind_data().into_optional(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                ]).collect(),
+                line_number: 0,
+            }),
+        ),
+        (
+            ftd::interpreter::FTD_LAYERED_BACKGROUND.to_string(),
+            ftd::interpreter::Thing::Record(ftd::interpreter::Record {
+                name: ftd::interpreter::FTD_LAYERED_BACKGROUND.to_string(),
+                fields: std::iter::IntoIterator::into_iter([
+                    ftd::interpreter::Field {
+                        name: "layers".to_string(),
+                        kind: ftd::interpreter::Kind::record(ftd::interpreter::FTD_BACKGROUND_LAYER)
+                            .into_list().into_kind_data(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                    ftd::interpreter::Field {
+                        name: "color".to_string(),
+                        kind: ftd::interpreter::Kind::record(ftd::interpreter::FTD_COLOR)
+                            .into_kind_data().into_optional(),
+                        mutable: false,
+                        value: None,
+                        access_modifier: Default::default(),
+                        line_number: 0,
+                    },
+                ]).collect(),
+                line_number: 0,
+            }),
+        ),
         (
             ftd::interpreter::FTD_BACKGROUND.to_string(),
             ftd::interpreter::Thing::OrType(ftd::interpreter::OrType {
@@ -1514,6 +2363,30 @@ pub fn default_bag() -> indexmap::IndexMap<String, ftd::interpreter::Thing> {
                         None,
                         0,
                     )),
+                    ftd::interpreter::OrTypeVariant::Regular(ftd::interpreter::Field::new(
+                        ftd::interpreter::FTD_BACKGROUND_RADIAL_GRADIENT,
+                        ftd::interpreter::Kind::record(ftd::interpreter::FTD_RADIAL_GRADIENT)
+                            .into_kind_data(),
+                        false,
+                        None,
+                        0,
+                    )),
+                    ftd::interpreter::OrTypeVariant::Regular(ftd::interpreter::Field::new(
+                        ftd::interpreter::FTD_BACKGROUND_CONIC_GRADIENT,
+                        ftd::interpreter::Kind::record(ftd::interpreter::FTD_CONIC_GRADIENT)
+                            .into_kind_data(),
+                        false,
+                        None,
+                        0,
+                    )),
+                    ftd::interpreter::OrTypeVariant::Regular(ftd::interpreter::Field::new(
+                        ftd::interpreter::FTD_BACKGROUND_LAYERS,
+                        ftd::interpreter::Kind::record(ftd::interpreter::FTD_LAYERED_BACKGROUND)
+                            .into_kind_data(),
+                        false,
+                        None,
+                        0,
+                    )),
                 ],
                 line_number: 0,
             }),
@@ -9543,7 +10416,8 @@ pub fn default_bag() -> indexmap::IndexMap<String, ftd::interpreter::Thing> {
                 is_static: false
             })
         )
-    ])
+    ]
+    .into_iter()
     .collect()
 }
 
@@ -9571,6 +10445,12 @@ pub fn image_function() -> ftd::interpreter::ComponentDefinition {
                         .into_kind_data()
                         .into_optional(),
                 ),
+                ftd::interpreter::Argument::default(
+                    "sizes",
+                    ftd::interpreter::Kind::string()
+                        .into_kind_data()
+                        .into_optional(),
+                ),
             ],
         ]
         .concat()
@@ -9856,12 +10736,20 @@ pub fn markup_function() -> ftd::interpreter::ComponentDefinition {
         arguments: [
             text_arguments(),
             common_arguments(),
-            vec![ftd::interpreter::Argument::default(
-                "text",
-                ftd::interpreter::Kind::string()
-                    .into_kind_data()
-                    .caption_or_body(),
-            )],
+            vec![
+                ftd::interpreter::Argument::default(
+                    "text",
+                    ftd::interpreter::Kind::string()
+                        .into_kind_data()
+                        .caption_or_body(),
+                ),
+                ftd::interpreter::Argument::default(
+                    "heading-anchors",
+                    ftd::interpreter::Kind::boolean()
+                        .into_optional()
+                        .into_kind_data(),
+                ),
+            ],
         ]
         .concat()
         .into_iter()
@@ -9977,6 +10865,66 @@ pub fn container_function() -> ftd::interpreter::ComponentDefinition {
     }
 }
 
+pub fn grid_function() -> ftd::interpreter::ComponentDefinition {
+    ftd::interpreter::ComponentDefinition {
+        name: "ftd#grid".to_string(),
+        arguments: [
+            container_root_arguments(),
+            common_arguments(),
+            vec![
+                ftd::interpreter::Argument::default(
+                    "columns",
+                    ftd::interpreter::Kind::or_type(ftd::interpreter::FTD_GRID_TRACK)
+                        .into_list()
+                        .into_kind_data(),
+                ),
+                ftd::interpreter::Argument::default(
+                    "rows",
+                    ftd::interpreter::Kind::or_type(ftd::interpreter::FTD_GRID_TRACK)
+                        .into_list()
+                        .into_kind_data(),
+                ),
+                ftd::interpreter::Argument::default(
+                    "areas",
+                    ftd::interpreter::Kind::string()
+                        .into_list()
+                        .into_kind_data(),
+                ),
+                ftd::interpreter::Argument::default(
+                    "template",
+                    ftd::interpreter::Kind::record(ftd::interpreter::FTD_RESPONSIVE_GRID_TEMPLATE)
+                        .into_optional()
+                        .into_kind_data(),
+                ),
+                ftd::interpreter::Argument::default(
+                    "gap",
+                    ftd::interpreter::Kind::or_type(ftd::interpreter::FTD_LENGTH)
+                        .into_optional()
+                        .into_kind_data(),
+                ),
+                ftd::interpreter::Argument::default(
+                    "row-gap",
+                    ftd::interpreter::Kind::or_type(ftd::interpreter::FTD_LENGTH)
+                        .into_optional()
+                        .into_kind_data(),
+                ),
+                ftd::interpreter::Argument::default(
+                    "column-gap",
+                    ftd::interpreter::Kind::or_type(ftd::interpreter::FTD_LENGTH)
+                        .into_optional()
+                        .into_kind_data(),
+                ),
+            ],
+        ]
+        .concat()
+        .into_iter()
+        .collect(),
+        definition: ftd::interpreter::Component::from_name("ftd.kernel"),
+        css: None,
+        line_number: 0,
+    }
+}
+
 pub fn desktop_function() -> ftd::interpreter::ComponentDefinition {
     ftd::interpreter::ComponentDefinition {
         name: "ftd#desktop".to_string(),
@@ -10247,6 +11195,12 @@ pub fn document_function() -> ftd::interpreter::ComponentDefinition {
                     .into_optional()
                     .into_kind_data(),
             ),
+            ftd::interpreter::Argument::default(
+                "static",
+                ftd::interpreter::Kind::boolean()
+                    .into_optional()
+                    .into_kind_data(),
+            ),
         ]]
         .concat()
         .into_iter()
@@ -10317,6 +11271,30 @@ fn common_arguments() -> Vec<ftd::interpreter::Argument> {
                 .into_optional()
                 .into_kind_data(),
         ),
+        ftd::interpreter::Argument::default(
+            "transform",
+            ftd::interpreter::Kind::record(ftd::interpreter::FTD_TRANSFORM)
+                .into_optional()
+                .into_kind_data(),
+        ),
+        ftd::interpreter::Argument::default(
+            "transition",
+            ftd::interpreter::Kind::record(ftd::interpreter::FTD_TRANSITION)
+                .into_optional()
+                .into_kind_data(),
+        ),
+        ftd::interpreter::Argument::default(
+            "animation",
+            ftd::interpreter::Kind::record(ftd::interpreter::FTD_ANIMATION)
+                .into_optional()
+                .into_kind_data(),
+        ),
+        ftd::interpreter::Argument::default(
+            "grid-area",
+            ftd::interpreter::Kind::string()
+                .into_optional()
+                .into_kind_data(),
+        ),
         ftd::interpreter::Argument::default(
             "sticky",
             ftd::interpreter::Kind::boolean()
@@ -10792,3 +11770,45 @@ fn text_arguments() -> Vec<ftd::interpreter::Argument> {
         line_number: 0,
     }
 }*/
+
+/// Built-in datetime functions are all implemented by `default_functions` in rust and by
+/// `ftd.js` in the browser, `arguments` are `(name, kind, default)`.
+fn datetime_function(
+    name: &str,
+    return_kind: ftd::interpreter::Kind,
+    arguments: Vec<(&str, ftd::interpreter::Kind, Option<&str>)>,
+    expression: &str,
+) -> (String, ftd::interpreter::Thing) {
+    let arguments = arguments
+        .into_iter()
+        .map(|(name, kind, default)| ftd::interpreter::Argument {
+            name: name.to_string(),
+            kind: kind.into_kind_data(),
+            mutable: false,
+            value: default.map(|text| ftd::interpreter::PropertyValue::Value {
+                value: ftd::interpreter::Value::String {
+                    text: text.to_string(),
+                },
+                is_mutable: false,
+                line_number: 0,
+            }),
+            access_modifier: Default::default(),
+            line_number: 0,
+        })
+        .collect();
+    (
+        name.to_string(),
+        ftd::interpreter::Thing::Function(ftd::interpreter::Function {
+            name: name.to_string(),
+            return_kind: return_kind.into_kind_data(),
+            arguments,
+            expression: vec![ftd::interpreter::things::function::Expression {
+                expression: expression.to_string(),
+                line_number: 0,
+            }],
+            js: None,
+            line_number: 0,
+            external_implementation: true,
+        }),
+    )
+}
@@ -175,7 +175,11 @@ fn get_variable_identifier_read(node: &mut fastn_grammar::evalexpr::ExprNode) ->
                     value: fastn_grammar::evalexpr::Value::Empty,
                 };
             } else if !write_variable.contains(&operator) {
-                values.push(operator);
+                // `p.age` of a lambda `p -> p.age` reads the parameter `p`
+                let head = operator.split('.').next().unwrap_or_default();
+                if !write_variable.iter().any(|w| w.eq(head)) {
+                    values.push(operator);
+                }
             }
         }
         for child in node.mut_children().iter_mut() {
@@ -217,14 +221,25 @@ impl Expression {
                 operator
             {
                 if format!("${}", ftd::interpreter::FTD_LOOP_COUNTER).eq(identifier) {
-                    if let Some(ftd::interpreter::PropertyValue::Value {
-                        value: ftd::interpreter::Value::Integer { value },
-                        ..
-                    }) = references.get(identifier)
-                    {
-                        operator = fastn_grammar::evalexpr::Operator::VariableIdentifierRead {
-                            identifier: value.to_string(),
+                    match references.get(identifier) {
+                        Some(ftd::interpreter::PropertyValue::Value {
+                            value: ftd::interpreter::Value::Integer { value },
+                            ..
+                        }) => {
+                            operator = fastn_grammar::evalexpr::Operator::VariableIdentifierRead {
+                                identifier: value.to_string(),
+                            }
+                        }
+                        // key of the entry when looping over a map
+                        Some(ftd::interpreter::PropertyValue::Value {
+                            value: ftd::interpreter::Value::String { text },
+                            ..
+                        }) => {
+                            operator = fastn_grammar::evalexpr::Operator::VariableIdentifierRead {
+                                identifier: format!("{:?}", text),
+                            }
                         }
+                        _ => {}
                     }
                 } else if let Some(ftd::interpreter::PropertyValue::Reference { name, .. }) =
                     references.get(identifier)
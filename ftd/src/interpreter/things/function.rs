@@ -94,14 +94,17 @@ impl Function {
             line_number: function.definition.line_number,
         }];
 
-        Ok(ftd::interpreter::StateWithThing::new_thing(Function::new(
+        let function = Function::new(
             name.as_str(),
             kind,
             arguments,
             expression,
             js,
             function.line_number,
-        )))
+        );
+        ftd::interpreter::things::type_check::TypeChecker::check(&function, doc)?;
+
+        Ok(ftd::interpreter::StateWithThing::new_thing(function))
     }
 
     pub(crate) fn resolve(
@@ -185,7 +188,7 @@ impl Function {
                     let value = ftd::interpreter::Value::from_evalexpr_value(
                         evalexpr_context.get_value(key.as_str()).unwrap().clone(),
                         &context.kind,
-                        doc.name,
+                        doc,
                         line_number,
                     )?;
                     // TODO: insert new value in doc.bag
@@ -207,7 +210,7 @@ impl Function {
             return Ok(Some(ftd::interpreter::Value::from_evalexpr_value(
                 eval,
                 &self.return_kind.kind,
-                doc.name,
+                doc,
                 line_number,
             )?));
         }
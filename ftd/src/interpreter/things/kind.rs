@@ -5,6 +5,7 @@ pub enum Kind {
     Integer,
     Decimal,
     Boolean,
+    DateTime,
     Record {
         name: String,
     }, // the full name of the record (full document name.record name)
@@ -16,6 +17,10 @@ pub enum Kind {
     List {
         kind: Box<Kind>,
     },
+    /// `map<string, <kind>>`, keys are always strings
+    Map {
+        kind: Box<Kind>,
+    },
     Optional {
         kind: Box<Kind>,
     },
@@ -38,8 +43,10 @@ impl Kind {
             Kind::Integer { .. } => "integer".to_string(),
             Kind::Boolean { .. } => "boolean".to_string(),
             Kind::Decimal { .. } => "decimal".to_string(),
+            Kind::DateTime => "datetime".to_string(),
             Kind::Constant { .. } => "constant".to_string(),
             Kind::List { .. } => "list".to_string(),
+            Kind::Map { .. } => "map".to_string(),
             Kind::Object { .. } => "object".to_string(),
             Kind::OrType { name, .. } => name.clone(),
             Kind::Optional { .. } => "optional".to_string(),
@@ -61,6 +68,7 @@ impl Kind {
             (Self::Optional { kind, .. }, _) => kind.is_same_as(other),
             (_, Self::Optional { kind: other, .. }) => self.is_same_as(other),
             (Self::List { kind: k1 }, Self::List { kind: k2 }) => k1.is_same_as(k2),
+            (Self::Map { kind: k1 }, Self::Map { kind: k2 }) => k1.is_same_as(k2),
             _ => self.eq(other),
         }
     }
@@ -81,6 +89,10 @@ impl Kind {
         Kind::Boolean
     }
 
+    pub fn datetime() -> Kind {
+        Kind::DateTime
+    }
+
     pub fn module() -> Kind {
         Kind::Module
     }
@@ -153,6 +165,12 @@ impl Kind {
         }
     }
 
+    pub fn into_map(self) -> Kind {
+        Kind::Map {
+            kind: Box::new(self),
+        }
+    }
+
     pub fn into_optional(self) -> Kind {
         Kind::Optional {
             kind: Box::new(self),
@@ -198,6 +216,10 @@ impl Kind {
         matches!(self, Kind::List { .. })
     }
 
+    pub fn is_map(&self) -> bool {
+        matches!(self, Kind::Map { .. })
+    }
+
     pub fn is_subsection_ui(&self) -> bool {
         matches!(
             self,
@@ -289,6 +311,10 @@ impl Kind {
         matches!(self, Kind::Decimal { .. })
     }
 
+    pub fn is_datetime(&self) -> bool {
+        matches!(self, Kind::DateTime)
+    }
+
     pub fn is_void(&self) -> bool {
         matches!(self, Kind::Void { .. })
     }
@@ -376,9 +402,17 @@ impl KindData {
         line_number: usize,
     ) -> ftd::interpreter::Result<()> {
         let ast_kind = var_kind.kind;
+        if let Some(value_kind) = map_value_kind(ast_kind.as_str(), doc.name, line_number)? {
+            return KindData::scan_ast_kind(
+                ftd::ast::VariableKind::get_kind(value_kind.as_str(), doc.name, line_number)?,
+                known_kinds,
+                doc,
+                line_number,
+            );
+        }
         match ast_kind.as_ref() {
-            "string" | "object" | "integer" | "decimal" | "boolean" | "void" | "ftd.ui"
-            | "children" => Ok(()),
+            "string" | "object" | "integer" | "decimal" | "boolean" | "datetime" | "void"
+            | "ftd.ui" | "children" => Ok(()),
             k if known_kinds.contains_key(k) => Ok(()),
             k => doc.scan_thing(k, line_number),
         }
@@ -415,12 +449,30 @@ impl KindData {
 
             return Ok(ftd::interpreter::StateWithThing::new_thing(kind_data));
         }
+        if let Some(value_kind) = map_value_kind(ast_kind.as_str(), doc.name, line_number)? {
+            let value_kind = try_ok_state!(KindData::from_ast_kind(
+                ftd::ast::VariableKind::get_kind(value_kind.as_str(), doc.name, line_number)?,
+                known_kinds,
+                doc,
+                line_number,
+            )?);
+            let mut kind_data = KindData {
+                kind: value_kind.kind.into_map(),
+                caption,
+                body,
+            };
+            if let Some(ref modifier) = var_kind.modifier {
+                kind_data = kind_data.into_by_ast_modifier(modifier);
+            }
+            return Ok(ftd::interpreter::StateWithThing::new_thing(kind_data));
+        }
         let kind = match ast_kind.as_ref() {
             "string" => Kind::string(),
             "object" => Kind::object(),
             "integer" => Kind::integer(),
             "decimal" => Kind::decimal(),
             "boolean" => Kind::boolean(),
+            "datetime" => Kind::datetime(),
             "void" => Kind::void(),
             "ftd.ui" => Kind::ui(),
             "module" => Kind::module(),
@@ -496,6 +548,10 @@ impl KindData {
         self.kind.is_list()
     }
 
+    pub fn is_map(&self) -> bool {
+        self.kind.is_map()
+    }
+
     pub fn is_or_type(&self) -> bool {
         self.kind.is_or_type()
     }
@@ -544,6 +600,10 @@ impl KindData {
         self.kind.is_decimal()
     }
 
+    pub fn is_datetime(&self) -> bool {
+        self.kind.is_datetime()
+    }
+
     pub fn is_void(&self) -> bool {
         self.kind.is_void()
     }
@@ -573,6 +633,41 @@ impl KindData {
     }
 }
 
+/// For `map<string, <kind>>` returns `<kind>`, `None` if `kind` is not a map.
+pub(crate) fn map_value_kind(
+    kind: &str,
+    doc_name: &str,
+    line_number: usize,
+) -> ftd::interpreter::Result<Option<String>> {
+    let inner = match kind
+        .trim()
+        .strip_prefix("map<")
+        .and_then(|v| v.strip_suffix('>'))
+    {
+        Some(inner) => inner,
+        None => return Ok(None),
+    };
+
+    match inner.split_once(',') {
+        Some((key, value)) if key.trim().eq("string") && !value.trim().is_empty() => {
+            Ok(Some(value.trim().to_string()))
+        }
+        Some((key, _)) if !key.trim().eq("string") => ftd::interpreter::utils::e2(
+            format!(
+                "Only `string` keys are supported in map, found: `{}`",
+                key.trim()
+            ),
+            doc_name,
+            line_number,
+        ),
+        _ => ftd::interpreter::utils::e2(
+            format!("Expected `map<string, <kind>>`, found: `{}`", kind),
+            doc_name,
+            line_number,
+        ),
+    }
+}
+
 pub fn check_for_caption_and_body(s: &mut String) -> (bool, bool) {
     use itertools::Itertools;
 
@@ -0,0 +1,600 @@
+/// Static type checking of the body of an ftd function, the kinds of the expressions are inferred
+/// over the `ExprNode` tree of the body, from the kinds of the arguments of the function.
+///
+/// The kind of an expression is `None` if it can not be known before running it, like the value
+/// returned by a JS function, these are not checked.
+pub(crate) struct TypeChecker<'a> {
+    function: &'a ftd::interpreter::Function,
+    /// the body being checked, for the line number of the errors
+    expression: &'a ftd::interpreter::things::function::Expression,
+    /// the arguments of the function, the variables assigned in the body and the parameters of
+    /// the lambdas, the last one with a name shadows the others
+    variables: Vec<(String, Option<ftd::interpreter::Kind>)>,
+}
+
+impl TypeChecker<'_> {
+    /// Checks the bodies of `function` and that they return its `return_kind`.
+    pub(crate) fn check(
+        function: &ftd::interpreter::Function,
+        doc: &ftd::interpreter::TDoc,
+    ) -> ftd::interpreter::Result<()> {
+        for expression in function.expression.iter() {
+            let node = fastn_grammar::evalexpr::build_operator_tree(expression.expression.as_str())
+                .map_err(|e| ftd::interpreter::Error::ParseError {
+                    message: format!("{} in function `{}`", e, function.name),
+                    doc_id: doc.name.to_string(),
+                    line_number: body_line_number(expression, 0),
+                })?;
+            let mut checker = TypeChecker {
+                function,
+                expression,
+                variables: function
+                    .arguments
+                    .iter()
+                    .map(|argument| (argument.name.to_string(), Some(argument.kind.kind.clone())))
+                    .collect(),
+            };
+            let kind = checker.infer(&node, doc)?;
+            let return_kind = &function.return_kind.kind;
+            match kind {
+                // the value of a body ending with an assignment is not used
+                Some(kind)
+                    if !return_kind.is_void()
+                        && !kind.is_void()
+                        && !is_assignable(return_kind, &kind) =>
+                {
+                    return checker.error(
+                        format!(
+                            "Function `{}` returns `{}`, expected `{}`",
+                            function.name,
+                            kind_name(&kind),
+                            kind_name(return_kind)
+                        ),
+                        None,
+                        doc,
+                    );
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn infer(
+        &mut self,
+        node: &fastn_grammar::evalexpr::ExprNode,
+        doc: &ftd::interpreter::TDoc,
+    ) -> ftd::interpreter::Result<Option<ftd::interpreter::Kind>> {
+        use fastn_grammar::evalexpr::Operator;
+
+        let children = node.children();
+        Ok(match node.operator() {
+            Operator::RootNode | Operator::Chain => {
+                let mut kind = Some(ftd::interpreter::Kind::void());
+                for child in children {
+                    kind = self.infer(child, doc)?;
+                }
+                kind
+            }
+            Operator::Const { value } => value_kind(value),
+            Operator::VariableIdentifierRead { identifier } => {
+                self.variable_kind(identifier, doc)?
+            }
+            Operator::VariableIdentifierWrite { .. } | Operator::Lambda => None,
+            Operator::Assign => {
+                let kind = self.infer_child(children, 1, doc)?;
+                self.assign(children, kind, doc)?;
+                Some(ftd::interpreter::Kind::void())
+            }
+            Operator::AddAssign
+            | Operator::SubAssign
+            | Operator::MulAssign
+            | Operator::DivAssign
+            | Operator::ModAssign
+            | Operator::ExpAssign
+            | Operator::AndAssign
+            | Operator::OrAssign => {
+                let variable = match children.first().map(|c| c.operator()) {
+                    Some(Operator::VariableIdentifierWrite { identifier }) => {
+                        self.variable_kind(identifier, doc)?
+                    }
+                    _ => None,
+                };
+                let value = self.infer_child(children, 1, doc)?;
+                let kind = self.operation(node.operator(), &[variable, value], doc)?;
+                self.assign(children, kind, doc)?;
+                Some(ftd::interpreter::Kind::void())
+            }
+            Operator::Index => {
+                let kind = self.infer_child(children, 0, doc)?;
+                self.infer_child(children, 1, doc)?;
+                let key = match children.get(1).map(|c| c.operator()) {
+                    Some(Operator::Const {
+                        value: fastn_grammar::evalexpr::Value::String(key),
+                    }) => Some(key.as_str()),
+                    _ => None,
+                };
+                match kind {
+                    Some(kind) => self.item_kind(&kind, key, doc)?,
+                    None => None,
+                }
+            }
+            Operator::Tuple => {
+                for child in children {
+                    self.infer(child, doc)?;
+                }
+                None
+            }
+            Operator::FunctionIdentifier { identifier } => {
+                let arguments = match children.first().map(unwrap_root) {
+                    Some(node) if node.operator() == &Operator::Tuple => {
+                        node.children().iter().map(unwrap_root).collect()
+                    }
+                    Some(node)
+                        if node.children().is_empty() && node.operator() == &Operator::RootNode =>
+                    {
+                        vec![]
+                    }
+                    Some(node) => vec![node],
+                    None => vec![],
+                };
+                self.call(identifier, arguments.as_slice(), doc)?
+            }
+            operator => {
+                let mut kinds = vec![];
+                for child in children {
+                    kinds.push(self.infer(child, doc)?);
+                }
+                self.operation(operator, kinds.as_slice(), doc)?
+            }
+        })
+    }
+
+    fn infer_child(
+        &mut self,
+        children: &[fastn_grammar::evalexpr::ExprNode],
+        index: usize,
+        doc: &ftd::interpreter::TDoc,
+    ) -> ftd::interpreter::Result<Option<ftd::interpreter::Kind>> {
+        match children.get(index) {
+            Some(child) => self.infer(child, doc),
+            None => Ok(None),
+        }
+    }
+
+    /// The kind of the result of the unary or binary `operator` on values of `kinds`.
+    fn operation(
+        &self,
+        operator: &fastn_grammar::evalexpr::Operator,
+        kinds: &[Option<ftd::interpreter::Kind>],
+        doc: &ftd::interpreter::TDoc,
+    ) -> ftd::interpreter::Result<Option<ftd::interpreter::Kind>> {
+        use fastn_grammar::evalexpr::Operator;
+
+        let known = kinds
+            .iter()
+            .map(|kind| kind.as_ref().map(|kind| kind.ref_inner().clone()))
+            .collect::<Option<Vec<_>>>();
+        let numbers = |kinds: &[ftd::interpreter::Kind]| {
+            if kinds.iter().all(|kind| kind.is_integer()) {
+                Some(ftd::interpreter::Kind::integer())
+            } else if kinds
+                .iter()
+                .all(|kind| kind.is_integer() || kind.is_decimal())
+            {
+                Some(ftd::interpreter::Kind::decimal())
+            } else {
+                None
+            }
+        };
+        let (kind, valid) = match (operator, known) {
+            (Operator::Eq | Operator::Neq, _) => (Some(ftd::interpreter::Kind::boolean()), true),
+            (Operator::Gt | Operator::Lt | Operator::Geq | Operator::Leq, Some(kinds)) => (
+                Some(ftd::interpreter::Kind::boolean()),
+                numbers(kinds.as_slice()).is_some()
+                    || kinds
+                        .iter()
+                        .all(|kind| kind.is_string() || kind.is_datetime()),
+            ),
+            (Operator::Gt | Operator::Lt | Operator::Geq | Operator::Leq, None) => {
+                (Some(ftd::interpreter::Kind::boolean()), true)
+            }
+            (
+                Operator::And
+                | Operator::Or
+                | Operator::Not
+                | Operator::AndAssign
+                | Operator::OrAssign,
+                kinds,
+            ) => (
+                Some(ftd::interpreter::Kind::boolean()),
+                kinds
+                    .unwrap_or_default()
+                    .iter()
+                    .all(|kind| kind.is_boolean()),
+            ),
+            // `"a" + 1` is `"a1"`, as in JS
+            (Operator::Add | Operator::AddAssign, Some(kinds))
+                if kinds.iter().any(|kind| kind.is_string()) =>
+            {
+                (Some(ftd::interpreter::Kind::string()), true)
+            }
+            (_, Some(kinds)) => {
+                let kind = numbers(kinds.as_slice());
+                let valid = kind.is_some();
+                (kind, valid)
+            }
+            (_, None) => (None, true),
+        };
+        if valid {
+            return Ok(kind);
+        }
+        self.error(
+            format!(
+                "Operator `{}` can not be applied to {}",
+                operator.to_string().trim(),
+                kinds
+                    .iter()
+                    .flatten()
+                    .map(|kind| format!("`{}`", kind_name(kind)))
+                    .collect::<Vec<_>>()
+                    .join(" and ")
+            ),
+            None,
+            doc,
+        )
+    }
+
+    /// Checks the assignment of a value of `kind` to the variable of `children[0]`, a variable
+    /// assigned for the first time is a new variable of the body.
+    fn assign(
+        &mut self,
+        children: &[fastn_grammar::evalexpr::ExprNode],
+        kind: Option<ftd::interpreter::Kind>,
+        doc: &ftd::interpreter::TDoc,
+    ) -> ftd::interpreter::Result<()> {
+        let identifier = match children.first().map(|c| c.operator()) {
+            Some(fastn_grammar::evalexpr::Operator::VariableIdentifierWrite { identifier }) => {
+                identifier
+            }
+            _ => return Ok(()),
+        };
+        match self.variable(identifier) {
+            Some(Some(expected)) => match kind {
+                Some(kind) if !is_assignable(expected, &kind) => self.error(
+                    format!(
+                        "Can not assign `{}` to `{}` of kind `{}`",
+                        kind_name(&kind),
+                        identifier,
+                        kind_name(expected)
+                    ),
+                    Some(identifier),
+                    doc,
+                ),
+                _ => Ok(()),
+            },
+            Some(None) => Ok(()),
+            None => {
+                self.variables.push((identifier.to_string(), kind));
+                Ok(())
+            }
+        }
+    }
+
+    fn variable(&self, name: &str) -> Option<&Option<ftd::interpreter::Kind>> {
+        self.variables
+            .iter()
+            .rev()
+            .find(|(variable, _)| variable.eq(name))
+            .map(|(_, kind)| kind)
+    }
+
+    /// The kind of `identifier`, a variable or a path in it, `person.address.city` or
+    /// `people.0.name`. The dotted names of no variable, like `ftd.dark-mode`, are JS globals.
+    fn variable_kind(
+        &self,
+        identifier: &str,
+        doc: &ftd::interpreter::TDoc,
+    ) -> ftd::interpreter::Result<Option<ftd::interpreter::Kind>> {
+        let identifier = identifier.trim_start_matches(ftd::interpreter::utils::REFERENCE);
+        if identifier.eq(ftd::ast::NULL) {
+            return Ok(None);
+        }
+        if let Some(kind) = self.variable(identifier) {
+            return Ok(kind.clone());
+        }
+        let mut segments = identifier.split('.');
+        let head = segments.next().unwrap_or_default();
+        let mut kind = match self.variable(head) {
+            Some(Some(kind)) => kind.clone(),
+            Some(None) => return Ok(None),
+            None if identifier.contains('.') => return Ok(None),
+            None => {
+                return self.error(
+                    format!(
+                        "Unknown variable `{}` in function `{}`",
+                        identifier, self.function.name
+                    ),
+                    Some(identifier),
+                    doc,
+                )
+            }
+        };
+        for segment in segments {
+            kind = match self.item_kind(&kind, Some(segment), doc)? {
+                Some(kind) => kind,
+                None => return Ok(None),
+            };
+        }
+        Ok(Some(kind))
+    }
+
+    /// The kind of `kind[key]`, the item of a list or the field `key` of a record.
+    fn item_kind(
+        &self,
+        kind: &ftd::interpreter::Kind,
+        key: Option<&str>,
+        doc: &ftd::interpreter::TDoc,
+    ) -> ftd::interpreter::Result<Option<ftd::interpreter::Kind>> {
+        Ok(match (kind.ref_inner(), key) {
+            (ftd::interpreter::Kind::List { kind }, _)
+            | (ftd::interpreter::Kind::Map { kind }, _) => Some(kind.as_ref().clone()),
+            (ftd::interpreter::Kind::Record { name }, Some(key)) => {
+                let record = match doc.get_record(name, self.expression.line_number) {
+                    Ok(record) => record,
+                    Err(_) => return Ok(None),
+                };
+                match record.fields.iter().find(|field| field.name.eq(key)) {
+                    Some(field) => Some(field.kind.kind.clone()),
+                    None => {
+                        return self.error(
+                            format!("Record `{}` has no field `{}`", name, key),
+                            Some(key),
+                            doc,
+                        )
+                    }
+                }
+            }
+            (ftd::interpreter::Kind::String, _) => Some(ftd::interpreter::Kind::string()),
+            _ => None,
+        })
+    }
+
+    /// The kind returned by the builtin function `identifier`, the calls of the other functions,
+    /// like the JS functions of the document, are not checked.
+    fn call(
+        &mut self,
+        identifier: &str,
+        arguments: &[&fastn_grammar::evalexpr::ExprNode],
+        doc: &ftd::interpreter::TDoc,
+    ) -> ftd::interpreter::Result<Option<ftd::interpreter::Kind>> {
+        if let ("map" | "filter" | "reduce" | "sort", Some(lambda)) = (
+            identifier,
+            arguments
+                .iter()
+                .find(|node| node.operator() == &fastn_grammar::evalexpr::Operator::Lambda),
+        ) {
+            return self.lambda_call(identifier, arguments, lambda, doc);
+        }
+
+        let mut kinds = vec![];
+        for argument in arguments {
+            kinds.push(self.infer(argument, doc)?);
+        }
+        let first = kinds
+            .first()
+            .cloned()
+            .flatten()
+            .map(|kind| kind.ref_inner().clone());
+        let (kind, expected): (_, Option<&str>) = match identifier {
+            "len" => (
+                Some(ftd::interpreter::Kind::integer()),
+                first
+                    .as_ref()
+                    .filter(|kind| !(kind.is_string() || kind.is_list() || kind.is_map()))
+                    .map(|_| "a string or a list"),
+            ),
+            "sort" => (first.clone(), None),
+            "if" => (kinds.get(1).cloned().flatten(), None),
+            "min" | "max" => (
+                kinds
+                    .iter()
+                    .flatten()
+                    .map(|kind| kind.ref_inner())
+                    .find(|kind| kind.is_decimal())
+                    .or(first.as_ref())
+                    .cloned(),
+                None,
+            ),
+            "contains" | "math::is_nan" | "math::is_finite" | "math::is_infinite"
+            | "math::is_normal" | "str::regex_matches" => {
+                (Some(ftd::interpreter::Kind::boolean()), None)
+            }
+            "join" | "concat" | "typeof" | "str::from" => {
+                (Some(ftd::interpreter::Kind::string()), None)
+            }
+            "str::to_lowercase" | "str::to_uppercase" | "str::trim" | "str::regex_replace" => (
+                Some(ftd::interpreter::Kind::string()),
+                first
+                    .as_ref()
+                    .filter(|kind| !kind.is_string())
+                    .map(|_| "a string"),
+            ),
+            "floor" | "round" | "ceil" | "random" => {
+                (Some(ftd::interpreter::Kind::decimal()), None)
+            }
+            identifier if identifier.starts_with("math::") => (
+                Some(ftd::interpreter::Kind::decimal()),
+                first
+                    .as_ref()
+                    .filter(|kind| !(kind.is_integer() || kind.is_decimal()))
+                    .map(|_| "a number"),
+            ),
+            "bitand" | "bitor" | "bitxor" | "bitnot" | "shl" | "shr" => (
+                Some(ftd::interpreter::Kind::integer()),
+                first
+                    .as_ref()
+                    .filter(|kind| !kind.is_integer())
+                    .map(|_| "an integer"),
+            ),
+            _ => (None, None),
+        };
+        match (expected, first) {
+            (Some(expected), Some(found)) => self.error(
+                format!(
+                    "Function `{}` expects {}, found `{}`",
+                    identifier,
+                    expected,
+                    kind_name(&found)
+                ),
+                Some(identifier),
+                doc,
+            ),
+            _ => Ok(kind),
+        }
+    }
+
+    /// `map(list, (item, index) -> …)`, `filter(list, (item, index) -> …)`,
+    /// `reduce(list, init, (acc, item) -> …)` and `sort(list, item -> …)`.
+    fn lambda_call(
+        &mut self,
+        identifier: &str,
+        arguments: &[&fastn_grammar::evalexpr::ExprNode],
+        lambda: &fastn_grammar::evalexpr::ExprNode,
+        doc: &ftd::interpreter::TDoc,
+    ) -> ftd::interpreter::Result<Option<ftd::interpreter::Kind>> {
+        let list = match arguments.first() {
+            Some(list) => self.infer(list, doc)?,
+            None => None,
+        };
+        let item = match list.as_ref().map(|kind| kind.ref_inner()) {
+            Some(ftd::interpreter::Kind::List { kind }) => Some(kind.as_ref().clone()),
+            Some(kind) => {
+                return self.error(
+                    format!(
+                        "Function `{}` expects a list, found `{}`",
+                        identifier,
+                        kind_name(kind)
+                    ),
+                    Some(identifier),
+                    doc,
+                )
+            }
+            None => None,
+        };
+        let init = match (identifier, arguments.get(1)) {
+            ("reduce", Some(init)) => self.infer(init, doc)?,
+            _ => None,
+        };
+        let parameters = match identifier {
+            "reduce" => vec![init.clone(), item],
+            _ => vec![item, Some(ftd::interpreter::Kind::integer())],
+        };
+
+        let (names, body) = match lambda.children() {
+            [names, body] => (names, body),
+            _ => return Ok(None),
+        };
+        let scope = self.variables.len();
+        let names = match unwrap_root(names) {
+            node if node.operator() == &fastn_grammar::evalexpr::Operator::Tuple => {
+                node.children().iter().map(unwrap_root).collect()
+            }
+            node => vec![node],
+        };
+        for (name, kind) in names.into_iter().zip(parameters) {
+            if let fastn_grammar::evalexpr::Operator::VariableIdentifierWrite { identifier }
+            | fastn_grammar::evalexpr::Operator::VariableIdentifierRead { identifier } =
+                name.operator()
+            {
+                self.variables.push((identifier.to_string(), kind));
+            }
+        }
+        let body = self.infer(body, doc)?;
+        self.variables.truncate(scope);
+
+        Ok(match identifier {
+            "map" => body.map(ftd::interpreter::Kind::into_list),
+            "filter" | "sort" => list,
+            _ => init.or(body),
+        })
+    }
+
+    /// The error `message`, at the line of the body with `needle` if given.
+    fn error<T>(
+        &self,
+        message: String,
+        needle: Option<&str>,
+        doc: &ftd::interpreter::TDoc,
+    ) -> ftd::interpreter::Result<T> {
+        let offset = needle
+            .and_then(|needle| {
+                self.expression
+                    .expression
+                    .lines()
+                    .position(|line| line.contains(needle))
+            })
+            .unwrap_or_default();
+        ftd::interpreter::utils::e2(message, doc.name, body_line_number(self.expression, offset))
+    }
+}
+
+/// The line number of the `offset`th line of the body `expression`. The parser gives a body the
+/// line number of the line before the next section, which is the blank line separating them.
+fn body_line_number(
+    expression: &ftd::interpreter::things::function::Expression,
+    offset: usize,
+) -> usize {
+    let lines = expression.expression.lines().count().max(1);
+    (expression.line_number + offset).saturating_sub(lines)
+}
+
+/// Whether a value of `found` can be used where `expected` is, the numbers are all numbers in JS.
+fn is_assignable(expected: &ftd::interpreter::Kind, found: &ftd::interpreter::Kind) -> bool {
+    use ftd::interpreter::Kind;
+
+    match (expected.ref_inner(), found.ref_inner()) {
+        (Kind::Decimal, Kind::Integer) | (Kind::DateTime, Kind::String) => true,
+        (Kind::List { kind: expected }, Kind::List { kind: found }) => {
+            is_assignable(expected, found)
+        }
+        (
+            Kind::String
+            | Kind::Integer
+            | Kind::Decimal
+            | Kind::Boolean
+            | Kind::DateTime
+            | Kind::List { .. }
+            | Kind::Record { .. }
+            | Kind::Void,
+            found,
+        ) => expected.is_same_as(found),
+        _ => true,
+    }
+}
+
+fn value_kind(value: &fastn_grammar::evalexpr::Value) -> Option<ftd::interpreter::Kind> {
+    match value {
+        fastn_grammar::evalexpr::Value::String(_) => Some(ftd::interpreter::Kind::string()),
+        fastn_grammar::evalexpr::Value::Int(_) => Some(ftd::interpreter::Kind::integer()),
+        fastn_grammar::evalexpr::Value::Float(_) => Some(ftd::interpreter::Kind::decimal()),
+        fastn_grammar::evalexpr::Value::Boolean(_) => Some(ftd::interpreter::Kind::boolean()),
+        _ => None,
+    }
+}
+
+/// `list of string`, `person`, …
+fn kind_name(kind: &ftd::interpreter::Kind) -> String {
+    match kind {
+        ftd::interpreter::Kind::List { kind } => format!("list of {}", kind_name(kind)),
+        ftd::interpreter::Kind::Optional { kind } => format!("optional {}", kind_name(kind)),
+        kind => kind.get_name(),
+    }
+}
+
+fn unwrap_root(node: &fastn_grammar::evalexpr::ExprNode) -> &fastn_grammar::evalexpr::ExprNode {
+    match (node.operator(), node.children()) {
+        (fastn_grammar::evalexpr::Operator::RootNode, [child]) => unwrap_root(child),
+        _ => node,
+    }
+}
@@ -809,6 +809,17 @@ impl PropertyValue {
                         line_number: value.line_number(),
                     })
                 }
+                ftd::interpreter::Kind::DateTime => {
+                    ftd::interpreter::StateWithThing::new_thing(PropertyValue::Value {
+                        value: Value::new_datetime(
+                            value.string(doc.name)?.as_str(),
+                            doc.name,
+                            value.line_number(),
+                        )?,
+                        is_mutable,
+                        line_number: value.line_number(),
+                    })
+                }
                 ftd::interpreter::Kind::List { kind } => {
                     let line_number = value.line_number();
                     let value_list = value.into_list(doc.name, kind)?;
@@ -856,6 +867,57 @@ impl PropertyValue {
                         line_number,
                     })
                 }
+                ftd::interpreter::Kind::Map { kind } => {
+                    let line_number = value.line_number();
+                    let headers = match value {
+                        ftd::ast::VariableValue::Optional { value, .. } if value.is_none() => {
+                            vec![]
+                        }
+                        ftd::ast::VariableValue::Record { headers, .. } => headers.0,
+                        t => {
+                            return ftd::interpreter::utils::e2(
+                                format!("Expected map of `{:?}`, found: `{:?}`", kind, t),
+                                doc.name,
+                                line_number,
+                            )
+                        }
+                    };
+                    let mut values: ftd::Map<PropertyValue> = Default::default();
+                    for header in headers {
+                        if values.contains_key(header.key.as_str()) {
+                            return ftd::interpreter::utils::e2(
+                                format!("Duplicate key `{}` in map", header.key),
+                                doc.name,
+                                header.line_number,
+                            );
+                        }
+                        ftd::interpreter::utils::validate_map_key(
+                            &values,
+                            header.key.as_str(),
+                            doc.name,
+                            header.line_number,
+                        )?;
+                        let value = try_ok_state!(PropertyValue::from_ast_value(
+                            header.value,
+                            doc,
+                            is_mutable,
+                            Some(&ftd::interpreter::KindData {
+                                kind: kind.as_ref().clone(),
+                                caption: false,
+                                body: false,
+                            }),
+                        )?);
+                        values.insert(header.key, value);
+                    }
+                    ftd::interpreter::StateWithThing::new_thing(PropertyValue::Value {
+                        value: ftd::interpreter::Value::Map {
+                            data: values,
+                            kind: kind.as_ref().clone().into_kind_data(),
+                        },
+                        is_mutable,
+                        line_number,
+                    })
+                }
                 ftd::interpreter::Kind::Record { name }
                     if value.is_record() || value.is_string() =>
                 {
@@ -1452,6 +1514,10 @@ pub enum Value {
     Boolean {
         value: bool,
     },
+    /// RFC 3339 timestamp, see `fastn_grammar::datetime`
+    DateTime {
+        value: String,
+    },
     Object {
         values: ftd::Map<PropertyValue>,
     },
@@ -1469,6 +1535,10 @@ pub enum Value {
         data: Vec<PropertyValue>,
         kind: ftd::interpreter::KindData,
     },
+    Map {
+        data: ftd::Map<PropertyValue>,
+        kind: ftd::interpreter::KindData,
+    },
     Optional {
         data: Box<Option<Value>>,
         kind: ftd::interpreter::KindData,
@@ -1503,12 +1573,11 @@ impl Value {
     }
 
     pub fn is_empty(&self) -> bool {
-        if let Self::List { data, .. } = self {
-            if data.is_empty() {
-                return true;
-            }
+        match self {
+            Self::List { data, .. } => data.is_empty(),
+            Self::Map { data, .. } => data.is_empty(),
+            _ => false,
         }
-        false
     }
 
     pub fn is_record(&self, rec_name: &str) -> bool {
@@ -1620,7 +1689,8 @@ impl Value {
                 is_static
             }
             ftd::interpreter::Value::Record { fields, .. }
-            | ftd::interpreter::Value::Object { values: fields, .. } => {
+            | ftd::interpreter::Value::Object { values: fields, .. }
+            | ftd::interpreter::Value::Map { data: fields, .. } => {
                 let mut is_static = true;
                 for d in fields.values() {
                     if !d.is_static(doc) {
@@ -1640,9 +1710,11 @@ impl Value {
             Value::Integer { .. } => ftd::interpreter::Kind::integer(),
             Value::Decimal { .. } => ftd::interpreter::Kind::decimal(),
             Value::Boolean { .. } => ftd::interpreter::Kind::boolean(),
+            Value::DateTime { .. } => ftd::interpreter::Kind::datetime(),
             Value::Object { .. } => ftd::interpreter::Kind::object(),
             Value::Record { name, .. } => ftd::interpreter::Kind::record(name),
             Value::List { kind, .. } => kind.kind.clone().into_list(),
+            Value::Map { kind, .. } => kind.kind.clone().into_map(),
             Value::Optional { kind, .. } => ftd::interpreter::Kind::Optional {
                 kind: Box::new(kind.kind.clone()),
             },
@@ -1667,6 +1739,7 @@ impl Value {
             Value::Integer { value } => fastn_grammar::evalexpr::Value::Int(*value),
             Value::Decimal { value } => fastn_grammar::evalexpr::Value::Float(*value),
             Value::Boolean { value } => fastn_grammar::evalexpr::Value::Boolean(*value),
+            Value::DateTime { value } => fastn_grammar::evalexpr::Value::String(value.to_string()),
             Value::List { data, .. } => {
                 let mut values = vec![];
                 for value in data {
@@ -1685,6 +1758,17 @@ impl Value {
                     fastn_grammar::evalexpr::Value::Empty
                 }
             }
+            Value::Record { fields, .. } => {
+                let mut values = fastn_grammar::evalexpr::RecordType::new();
+                for (name, value) in fields {
+                    let v = value
+                        .clone()
+                        .resolve(doc, line_number)?
+                        .to_evalexpr_value(doc, value.line_number())?;
+                    values.insert(name.to_string(), v);
+                }
+                fastn_grammar::evalexpr::Value::Record(values)
+            }
             t => unimplemented!("{:?}", t),
         })
     }
@@ -1692,9 +1776,10 @@ impl Value {
     pub(crate) fn from_evalexpr_value(
         value: fastn_grammar::evalexpr::Value,
         expected_kind: &ftd::interpreter::Kind,
-        doc_name: &str,
+        doc: &ftd::interpreter::TDoc,
         line_number: usize,
     ) -> ftd::interpreter::Result<Value> {
+        let doc_name = doc.name;
         Ok(match value {
             fastn_grammar::evalexpr::Value::String(text) if expected_kind.is_string() => {
                 Value::String { text }
@@ -1708,12 +1793,18 @@ impl Value {
             fastn_grammar::evalexpr::Value::Boolean(value) if expected_kind.is_boolean() => {
                 Value::Boolean { value }
             }
+            fastn_grammar::evalexpr::Value::String(text) if expected_kind.is_datetime() => {
+                Value::new_datetime(text.as_str(), doc_name, line_number)?
+            }
+            fastn_grammar::evalexpr::Value::Int(value) if expected_kind.is_datetime() => {
+                Value::new_datetime(value.to_string().as_str(), doc_name, line_number)?
+            }
             fastn_grammar::evalexpr::Value::Tuple(data) if expected_kind.is_list() => {
                 let mut values = vec![];
                 let val_kind = expected_kind.list_type(doc_name, line_number)?;
                 for val in data {
                     values.push(ftd::interpreter::PropertyValue::Value {
-                        value: Value::from_evalexpr_value(val, &val_kind, doc_name, line_number)?,
+                        value: Value::from_evalexpr_value(val, &val_kind, doc, line_number)?,
                         is_mutable: false,
                         line_number,
                     });
@@ -1723,6 +1814,40 @@ impl Value {
                     kind: ftd::interpreter::KindData::new(val_kind),
                 }
             }
+            fastn_grammar::evalexpr::Value::Record(mut data)
+                if expected_kind.ref_inner().is_record() =>
+            {
+                let name = expected_kind.ref_inner().get_record_name().unwrap();
+                let record = doc.get_record(name, line_number)?;
+                let mut fields: ftd::Map<ftd::interpreter::PropertyValue> = Default::default();
+                for field in record.fields {
+                    let value = match data.remove(field.name.as_str()) {
+                        Some(value) => {
+                            Value::from_evalexpr_value(value, &field.kind.kind, doc, line_number)?
+                        }
+                        None if field.kind.is_optional() => Value::new_none(field.kind.clone()),
+                        None => {
+                            return ftd::interpreter::utils::e2(
+                                format!("Field `{}` of `{}` not found", field.name, name),
+                                doc_name,
+                                line_number,
+                            )
+                        }
+                    };
+                    fields.insert(
+                        field.name,
+                        ftd::interpreter::PropertyValue::Value {
+                            value,
+                            is_mutable: false,
+                            line_number,
+                        },
+                    );
+                }
+                Value::Record {
+                    name: record.name,
+                    fields,
+                }
+            }
             fastn_grammar::evalexpr::Value::Empty if expected_kind.is_optional() => {
                 Value::Optional {
                     data: Box::new(None),
@@ -1752,6 +1877,24 @@ impl Value {
         }
     }
 
+    /// Parses ISO-8601 / epoch `text` into a `datetime` value
+    pub(crate) fn new_datetime(
+        text: &str,
+        doc_id: &str,
+        line_number: usize,
+    ) -> ftd::interpreter::Result<ftd::interpreter::Value> {
+        let datetime = fastn_grammar::datetime::parse(text).map_err(|e| {
+            ftd::interpreter::Error::ParseError {
+                message: e.to_string(),
+                doc_id: doc_id.to_string(),
+                line_number,
+            }
+        })?;
+        Ok(ftd::interpreter::Value::DateTime {
+            value: fastn_grammar::datetime::to_string(&datetime),
+        })
+    }
+
     pub(crate) fn new_or_type(
         name: &str,
         variant: &str,
@@ -1783,6 +1926,9 @@ impl Value {
             ftd::interpreter::Value::Boolean { value } => {
                 Ok(fastn_grammar::evalexpr::Value::Boolean(value))
             }
+            ftd::interpreter::Value::DateTime { value } => {
+                Ok(fastn_grammar::evalexpr::Value::String(value))
+            }
             ftd::interpreter::Value::Optional { data, .. } => {
                 if let Some(data) = data.as_ref() {
                     data.clone().into_evalexpr_value(doc)
@@ -1794,12 +1940,16 @@ impl Value {
                 let line_number = value.line_number();
                 value.resolve(doc, line_number)?.into_evalexpr_value(doc)
             }
-            ftd::interpreter::Value::Record { .. } => {
-                if let Ok(Some(value)) = ftd::interpreter::utils::get_value(doc, &self) {
-                    Ok(fastn_grammar::evalexpr::Value::String(value.to_string()))
-                } else {
-                    unimplemented!("{:?}", self)
+            ftd::interpreter::Value::Record { fields, .. } => {
+                let mut values = fastn_grammar::evalexpr::RecordType::new();
+                for (name, value) in fields {
+                    let line_number = value.line_number();
+                    values.insert(
+                        name,
+                        value.resolve(doc, line_number)?.into_evalexpr_value(doc)?,
+                    );
                 }
+                Ok(fastn_grammar::evalexpr::Value::Record(values))
             }
             ftd::interpreter::Value::List { data, .. } => {
                 let mut values = vec![];
@@ -1966,6 +2116,28 @@ impl Value {
         }
     }
 
+    pub fn optional_decimal(
+        &self,
+        doc_id: &str,
+        line_number: usize,
+    ) -> ftd::interpreter::Result<Option<f64>> {
+        match self {
+            ftd::interpreter::Value::Optional { data, kind } if kind.is_decimal() => {
+                if let Some(data) = data.as_ref() {
+                    data.optional_decimal(doc_id, line_number)
+                } else {
+                    Ok(None)
+                }
+            }
+            ftd::interpreter::Value::Decimal { value } => Ok(Some(*value)),
+            t => ftd::interpreter::utils::e2(
+                format!("Expected Optional Decimal, found: `{:?}`", t),
+                doc_id,
+                line_number,
+            ),
+        }
+    }
+
     pub fn into_property_value(self, is_mutable: bool, line_number: usize) -> PropertyValue {
         PropertyValue::Value {
             value: self,
@@ -1980,6 +2152,7 @@ impl Value {
             Value::Integer { value } => Some(serde_json::json!(value)),
             Value::Decimal { value } => Some(serde_json::json!(value)),
             Value::Boolean { value } => Some(serde_json::Value::Bool(value.to_owned())),
+            Value::DateTime { value } => Some(serde_json::Value::String(value.to_string())),
             Value::Optional { data, .. } => {
                 if let Some(data) = data.as_ref() {
                     data.to_serde_value()
@@ -1998,7 +2171,7 @@ impl Value {
                 }
                 serde_json::to_value(&new_values).ok()
             }
-            Value::Record { fields, .. } => {
+            Value::Record { fields, .. } | Value::Map { data: fields, .. } => {
                 let mut new_values: ftd::Map<serde_json::Value> = Default::default();
                 for (k, v) in fields {
                     if let ftd::interpreter::PropertyValue::Value { value, .. } = v {
@@ -2030,6 +2203,7 @@ impl Value {
             Value::Integer { value } => Some(value.to_string()),
             Value::Decimal { value } => Some(value.to_string()),
             Value::Boolean { value } => Some(value.to_string()),
+            Value::DateTime { value } => Some(value.to_string()),
             Value::Optional { data, .. } => {
                 if let Some(data) = data.as_ref() {
                     data.to_string()
@@ -2037,9 +2211,10 @@ impl Value {
                     Some("".to_string())
                 }
             }
-            Value::Object { .. } | Value::Record { .. } | Value::List { .. } => {
-                serde_json::to_string(&self.to_serde_value()).ok()
-            }
+            Value::Object { .. }
+            | Value::Record { .. }
+            | Value::List { .. }
+            | Value::Map { .. } => serde_json::to_string(&self.to_serde_value()).ok(),
             _ => None,
         }
     }
@@ -364,14 +364,23 @@ pub fn get_argument_for_reference_and_remaining(
                 ftd::interpreter::PropertyValueSource::Loop(loop_name.to_string()),
             )));
         }
+        // Looping over a map gives the key of the entry instead of its index
+        let counter_kind = if loop_argument
+            .value
+            .as_ref()
+            .map(|v| v.kind().is_map())
+            .unwrap_or_default()
+        {
+            ftd::interpreter::Kind::string()
+        } else {
+            ftd::interpreter::Kind::integer()
+        };
         if name.starts_with(format!("{}#{}", doc.name, ftd::interpreter::FTD_LOOP_COUNTER).as_str())
         {
             return Ok(Some((
                 ftd::interpreter::Field::default(
                     ftd::interpreter::FTD_LOOP_COUNTER,
-                    ftd::interpreter::Kind::integer()
-                        .into_optional()
-                        .into_kind_data(),
+                    counter_kind.into_optional().into_kind_data(),
                 ),
                 None,
                 ftd::interpreter::PropertyValueSource::Loop(loop_name.to_string()),
@@ -383,9 +392,7 @@ pub fn get_argument_for_reference_and_remaining(
                 return Ok(Some((
                     ftd::interpreter::Field::default(
                         loop_counter_alias,
-                        ftd::interpreter::Kind::integer()
-                            .into_optional()
-                            .into_kind_data(),
+                        counter_kind.into_optional().into_kind_data(),
                     ),
                     None,
                     ftd::interpreter::PropertyValueSource::Loop(loop_name.to_string()),
@@ -607,6 +614,34 @@ pub(crate) fn get_value(
     })
 }
 
+/// References reach the entries of a map by name, `$map.some-key`, and `some-key`, `some_key` and
+/// `some.key` are the same name, so no two keys of a map can be the same name.
+pub(crate) fn validate_map_key<T>(
+    data: &ftd::Map<T>,
+    key: &str,
+    doc_id: &str,
+    line_number: usize,
+) -> ftd::interpreter::Result<()> {
+    let name = |key: &str| {
+        if key.is_empty() {
+            String::new()
+        } else {
+            fastn_js::utils::name_to_js_(key)
+        }
+    };
+    match data.keys().find(|k| name(k) == name(key)) {
+        Some(existing) => e2(
+            format!(
+                "Key `{}` of the map is the same name as the key `{}`",
+                key, existing
+            ),
+            doc_id,
+            line_number,
+        ),
+        None => Ok(()),
+    }
+}
+
 pub(crate) fn js_reference_name(s: &str) -> String {
     let mut s = s.replace("\\\\", "/").replace('\\', "/");
     if s.contains("LOOP.COUNTER") {
@@ -11,6 +11,7 @@ pub enum Element {
     Column(Column),
     Row(Row),
     ContainerElement(ContainerElement),
+    Grid(Grid),
     Image(Image),
     Video(Video),
     Device(Device),
@@ -35,6 +36,7 @@ impl Element {
             "ftd#column" => Element::Column(Column::from(component)),
             "ftd#row" => Element::Row(Row::from(component)),
             "ftd#container" => Element::ContainerElement(ContainerElement::from(component)),
+            "ftd#grid" => Element::Grid(Grid::from(component)),
             "ftd#image" => Element::Image(Image::from(component)),
             "ftd#video" => Element::Video(Video::from(component)),
             "ftd#checkbox" => Element::CheckBox(CheckBox::from(component)),
@@ -105,6 +107,14 @@ impl Element {
                 should_return,
                 has_rive_components,
             ),
+            Element::Grid(grid) => grid.to_component_statements(
+                parent,
+                index,
+                doc,
+                &mut rdata,
+                should_return,
+                has_rive_components,
+            ),
             Element::Image(image) => {
                 image.to_component_statements(parent, index, doc, &mut rdata, should_return)
             }
@@ -552,14 +562,22 @@ impl Code {
         let kernel = create_element(fastn_js::ElementKind::Code, parent, index, rdata);
         component_statements.push(fastn_js::ComponentStatement::CreateKernel(kernel.clone()));
 
-        component_statements.push(fastn_js::ComponentStatement::SetProperty(
-            self.code.to_set_property(
-                fastn_js::PropertyKind::Code,
-                doc,
-                kernel.name.as_str(),
-                rdata,
-            ),
-        ));
+        let mut code = self.code.to_set_property(
+            fastn_js::PropertyKind::Code,
+            doc,
+            kernel.name.as_str(),
+            rdata,
+        );
+        // the code is not markdown, its html is not needed
+        if let fastn_js::SetPropertyValue::Value(fastn_js::Value::RenderedMarkdown {
+            ref mut text,
+            ..
+        }) = code.value
+        {
+            code.value =
+                fastn_js::SetPropertyValue::Value(fastn_js::Value::String(std::mem::take(text)));
+        }
+        component_statements.push(fastn_js::ComponentStatement::SetProperty(code));
 
         component_statements.push(fastn_js::ComponentStatement::SetProperty(
             self.lang.to_set_property(
@@ -614,6 +632,7 @@ pub struct Image {
     pub src: ftd::js::Value,
     pub fit: Option<ftd::js::Value>,
     pub alt: Option<ftd::js::Value>,
+    pub sizes: Option<ftd::js::Value>,
     pub common: Common,
 }
 
@@ -642,6 +661,11 @@ impl Image {
                 component.properties.as_slice(),
                 component_definition.arguments.as_slice(),
             ),
+            sizes: ftd::js::value::get_optional_js_value(
+                "sizes",
+                component.properties.as_slice(),
+                component_definition.arguments.as_slice(),
+            ),
             common: Common::from(
                 component.properties.as_slice(),
                 component_definition.arguments.as_slice(),
@@ -689,6 +713,16 @@ impl Image {
                 ),
             ));
         }
+        if let Some(ref sizes) = self.sizes {
+            component_statements.push(fastn_js::ComponentStatement::SetProperty(
+                sizes.to_set_property(
+                    fastn_js::PropertyKind::ImageSizes,
+                    doc,
+                    kernel.name.as_str(),
+                    rdata,
+                ),
+            ));
+        }
         component_statements.extend(self.common.to_set_properties(
             kernel.name.as_str(),
             doc,
@@ -866,6 +900,7 @@ impl Video {
 #[derive(Debug)]
 pub struct Text {
     pub text: ftd::js::Value,
+    pub heading_anchors: Option<ftd::js::Value>,
     pub common: Common,
     pub text_common: TextCommon,
 }
@@ -1051,6 +1086,19 @@ pub struct ContainerElement {
     pub common: Common,
 }
 
+#[derive(Debug)]
+pub struct Grid {
+    pub container: Container,
+    pub columns: Option<ftd::js::Value>,
+    pub rows: Option<ftd::js::Value>,
+    pub areas: Option<ftd::js::Value>,
+    pub template: Option<ftd::js::Value>,
+    pub gap: Option<ftd::js::Value>,
+    pub row_gap: Option<ftd::js::Value>,
+    pub column_gap: Option<ftd::js::Value>,
+    pub common: Common,
+}
+
 #[derive(Debug)]
 pub struct Row {
     pub container: Container,
@@ -1121,6 +1169,11 @@ impl Text {
                 component_definition.arguments.as_slice(),
             )
             .unwrap(),
+            heading_anchors: ftd::js::value::get_optional_js_value(
+                "heading-anchors",
+                component.properties.as_slice(),
+                component_definition.arguments.as_slice(),
+            ),
             common: Common::from(
                 component.properties.as_slice(),
                 component_definition.arguments.as_slice(),
@@ -1144,6 +1197,17 @@ impl Text {
         let mut component_statements = vec![];
         let kernel = create_element(fastn_js::ElementKind::Text, parent, index, rdata);
         component_statements.push(fastn_js::ComponentStatement::CreateKernel(kernel.clone()));
+        // set before the text, which is rendered with the anchors
+        if let Some(ref heading_anchors) = self.heading_anchors {
+            component_statements.push(fastn_js::ComponentStatement::SetProperty(
+                heading_anchors.to_set_property(
+                    fastn_js::PropertyKind::HeadingAnchors,
+                    doc,
+                    kernel.name.as_str(),
+                    rdata,
+                ),
+            ));
+        }
         component_statements.push(fastn_js::ComponentStatement::SetProperty(
             fastn_js::SetProperty {
                 kind: fastn_js::PropertyKind::StringValue,
@@ -1812,6 +1876,82 @@ impl ContainerElement {
     }
 }
 
+impl Grid {
+    pub fn from(component: &ftd::interpreter::Component) -> Grid {
+        let component_definition = ftd::interpreter::default::default_bag()
+            .get("ftd#grid")
+            .unwrap()
+            .clone()
+            .component()
+            .unwrap();
+        let properties = component.properties.as_slice();
+        let arguments = component_definition.arguments.as_slice();
+
+        Grid {
+            container: Container::from(properties, arguments),
+            columns: ftd::js::value::get_optional_js_value("columns", properties, arguments),
+            rows: ftd::js::value::get_optional_js_value("rows", properties, arguments),
+            areas: ftd::js::value::get_optional_js_value("areas", properties, arguments),
+            template: ftd::js::value::get_optional_js_value("template", properties, arguments),
+            gap: ftd::js::value::get_optional_js_value("gap", properties, arguments),
+            row_gap: ftd::js::value::get_optional_js_value("row-gap", properties, arguments),
+            column_gap: ftd::js::value::get_optional_js_value("column-gap", properties, arguments),
+            common: Common::from(properties, arguments, component.events.as_slice()),
+        }
+    }
+
+    pub fn to_component_statements(
+        &self,
+        parent: &str,
+        index: usize,
+        doc: &ftd::interpreter::TDoc,
+        rdata: &mut ftd::js::ResolverData,
+        should_return: bool,
+        has_rive_components: &mut bool,
+    ) -> Vec<fastn_js::ComponentStatement> {
+        let mut component_statements = vec![];
+        let kernel = create_element(fastn_js::ElementKind::Grid, parent, index, rdata);
+        component_statements.push(fastn_js::ComponentStatement::CreateKernel(kernel.clone()));
+
+        component_statements.extend(self.common.to_set_properties(
+            kernel.name.as_str(),
+            doc,
+            rdata,
+        ));
+
+        let grid_properties = [
+            (&self.columns, fastn_js::PropertyKind::GridColumns),
+            (&self.rows, fastn_js::PropertyKind::GridRows),
+            (&self.areas, fastn_js::PropertyKind::GridAreas),
+            (&self.template, fastn_js::PropertyKind::GridTemplate),
+            (&self.gap, fastn_js::PropertyKind::Gap),
+            (&self.row_gap, fastn_js::PropertyKind::RowGap),
+            (&self.column_gap, fastn_js::PropertyKind::ColumnGap),
+        ];
+        for (value, kind) in grid_properties {
+            if let Some(value) = value {
+                component_statements.push(fastn_js::ComponentStatement::SetProperty(
+                    value.to_set_property(kind, doc, kernel.name.as_str(), rdata),
+                ));
+            }
+        }
+
+        component_statements.extend(self.container.to_component_statements(
+            doc,
+            rdata,
+            has_rive_components,
+            false,
+        ));
+
+        if should_return {
+            component_statements.push(fastn_js::ComponentStatement::Return {
+                component_name: kernel.name,
+            });
+        }
+        component_statements
+    }
+}
+
 #[derive(Debug)]
 pub struct Device {
     pub container: Container,
@@ -2205,6 +2345,10 @@ pub struct Common {
     pub classes: Option<ftd::js::Value>,
     pub anchor: Option<ftd::js::Value>,
     pub shadow: Option<ftd::js::Value>,
+    pub transform: Option<ftd::js::Value>,
+    pub transition: Option<ftd::js::Value>,
+    pub animation: Option<ftd::js::Value>,
+    pub grid_area: Option<ftd::js::Value>,
     pub css: Option<ftd::js::Value>,
     pub js: Option<ftd::js::Value>,
     pub events: Vec<ftd::interpreter::Event>,
@@ -2422,6 +2566,10 @@ impl Common {
             min_width: ftd::js::value::get_optional_js_value("min-width", properties, arguments),
             whitespace: ftd::js::value::get_optional_js_value("white-space", properties, arguments),
             shadow: ftd::js::value::get_optional_js_value("shadow", properties, arguments),
+            transform: ftd::js::value::get_optional_js_value("transform", properties, arguments),
+            transition: ftd::js::value::get_optional_js_value("transition", properties, arguments),
+            animation: ftd::js::value::get_optional_js_value("animation", properties, arguments),
+            grid_area: ftd::js::value::get_optional_js_value("grid-area", properties, arguments),
             events: events.to_vec(),
         }
     }
@@ -2989,6 +3137,46 @@ impl Common {
                 shadow.to_set_property(fastn_js::PropertyKind::Shadow, doc, element_name, rdata),
             ));
         }
+        if let Some(ref transform) = self.transform {
+            component_statements.push(fastn_js::ComponentStatement::SetProperty(
+                transform.to_set_property(
+                    fastn_js::PropertyKind::Transform,
+                    doc,
+                    element_name,
+                    rdata,
+                ),
+            ));
+        }
+        if let Some(ref transition) = self.transition {
+            component_statements.push(fastn_js::ComponentStatement::SetProperty(
+                transition.to_set_property(
+                    fastn_js::PropertyKind::Transition,
+                    doc,
+                    element_name,
+                    rdata,
+                ),
+            ));
+        }
+        if let Some(ref animation) = self.animation {
+            component_statements.push(fastn_js::ComponentStatement::SetProperty(
+                animation.to_set_property(
+                    fastn_js::PropertyKind::Animation,
+                    doc,
+                    element_name,
+                    rdata,
+                ),
+            ));
+        }
+        if let Some(ref grid_area) = self.grid_area {
+            component_statements.push(fastn_js::ComponentStatement::SetProperty(
+                grid_area.to_set_property(
+                    fastn_js::PropertyKind::GridArea,
+                    doc,
+                    element_name,
+                    rdata,
+                ),
+            ));
+        }
         if let Some(ref link) = self.link {
             component_statements.push(fastn_js::ComponentStatement::SetProperty(
                 link.to_set_property(fastn_js::PropertyKind::Link, doc, element_name, rdata),
@@ -3101,6 +3289,7 @@ pub fn is_kernel(s: &str) -> bool {
         "ftd#integer",
         "ftd#decimal",
         "ftd#container",
+        "ftd#grid",
         "ftd#boolean",
         "ftd#desktop",
         "ftd#mobile",
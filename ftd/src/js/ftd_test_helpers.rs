@@ -161,7 +161,7 @@ fn p(s: &str, t: &str, fix: bool, manual: bool, script: bool, file_location: &st
                             <link rel="stylesheet" href="../../prism/prism-line-numbers.css">
                             <script>{}</script>
                         "#,
-                                ftd::js::all_js_without_test("foo")
+                                ftd::js::all_js_without_test()
                             )
                         } else {
                             "<script src=\"fastn-js.js\"></script>".to_string()
@@ -243,6 +243,152 @@ fn fastn_js_test_all() {
     }
 }
 
+#[test]
+fn is_static() {
+    let js_ast_data = |s: &str| {
+        ftd::js::document_into_js_ast(
+            interpret_helper("foo", s).unwrap_or_else(|e| panic!("{:?}", e)),
+        )
+    };
+    let is_static = |s: &str| {
+        let js_ast_data = js_ast_data(s);
+        js_ast_data.is_static.unwrap_or_else(|| {
+            ftd::js::is_static(fastn_js::to_js(js_ast_data.asts.as_slice(), true, "foo").as_str())
+        })
+    };
+
+    assert!(is_static(indoc::indoc!(
+        "
+        -- ftd.text: Hello
+        "
+    )));
+    assert!(!is_static(indoc::indoc!(
+        "
+        -- boolean $flag: false
+
+        -- ftd.text: Hello
+        $on-click$: $ftd.toggle($a = $flag)
+        "
+    )));
+    assert!(!is_static(indoc::indoc!(
+        "
+        -- ftd.text: Mobile
+        if: { ftd.device == \"mobile\" }
+        "
+    )));
+    assert!(!is_static(indoc::indoc!(
+        "
+        -- ftd.responsive-length width:
+        desktop.px: 500
+        mobile.px: 300
+
+        -- ftd.text: Hello
+        width.fixed.responsive: $width
+        "
+    )));
+    assert!(!is_static(indoc::indoc!(
+        "
+        -- ftd.grid-template desktop-layout:
+        columns: auto, auto
+
+        -- ftd.grid-template mobile-layout:
+        columns: auto
+
+        -- ftd.responsive-grid-template layout:
+        desktop: $desktop-layout
+        mobile: $mobile-layout
+
+        -- ftd.grid:
+        template: $layout
+
+        -- ftd.text: Hello
+
+        -- end: ftd.grid
+        "
+    )));
+    assert!(is_static(indoc::indoc!(
+        "
+        -- ftd.grid-template desktop-layout:
+        columns: auto, auto
+
+        -- ftd.responsive-grid-template layout:
+        desktop: $desktop-layout
+
+        -- ftd.grid:
+        template: $layout
+
+        -- ftd.text: Hello
+
+        -- end: ftd.grid
+        "
+    )));
+    assert_eq!(
+        js_ast_data(indoc::indoc!(
+            "
+            -- ftd.document:
+            static: false
+
+            -- ftd.text: Hello
+
+            -- end: ftd.document
+            "
+        ))
+        .is_static,
+        Some(false)
+    );
+}
+
+#[test]
+fn relative_datetime() {
+    // the server renders `ftd.relative-datetime` and `ftd.format-datetime` with `fastn_grammar`,
+    // the browser with `ftd_datetime`, they must agree or hydration changes the text
+    let now = "2023-10-18T09:30:00Z";
+    let values = [
+        "2023-10-18T09:30:00Z",
+        "2023-10-18T09:29:01Z",
+        "2023-10-18T09:29:00Z",
+        "2023-10-18T09:25:00Z",
+        "2023-10-18T10:30:00Z",
+        "2023-10-18T10:29:59+01:00",
+        "2023-10-17T09:30:00Z",
+        "2023-10-15T09:30:00Z",
+        "2023-11-18T09:30:00Z",
+        "2023-03-18T09:30:00Z",
+        "2024-10-18T09:30:00Z",
+        "2021-10-18T09:30:00Z",
+    ];
+
+    let locales = ["en", "fr", "de-DE", "es", "pt_BR", "it", "xx"];
+    let patterns = ["%A %e %B %Y", "%a %d %b %y, %I:%M %p"];
+
+    let js = format!(
+        "(function () {{\n  let now = ftd_datetime.parse({:?});\n  let values = [{}].map((v) => ftd_datetime.parse(v));\n  return [{}].flatMap((l) => values.flatMap((v) => [ftd_datetime.relative(v, now, l), {}])).join(\"\\n\");\n}})()",
+        now,
+        values.iter().map(|v| format!("{:?}", v)).collect::<Vec<_>>().join(", "),
+        locales.iter().map(|l| format!("{:?}", l)).collect::<Vec<_>>().join(", "),
+        patterns
+            .iter()
+            .map(|p| format!("ftd_datetime.format(v, {:?}, l)", p))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    let client = fastn_js::ssr_str(js.as_str());
+
+    let now = fastn_grammar::datetime::parse(now).unwrap();
+    let mut server = vec![];
+    for l in locales {
+        for v in values {
+            let v = fastn_grammar::datetime::parse(v).unwrap();
+            server.push(fastn_grammar::datetime::relative(&v, &now, Some(l)));
+            for p in patterns {
+                server.push(fastn_grammar::datetime::format(&v, p, Some(l)).unwrap());
+            }
+        }
+    }
+    let server = server.join("\n");
+    assert_eq!(client, server);
+}
+
 fn find_file_groups(
     manual: bool,
     script: bool,
@@ -14,10 +14,22 @@ pub use value::Value;
 
 pub const CODE_DEFAULT_THEME: &str = "fastn-theme.dark";
 
-pub fn all_js_without_test(package_name: &str) -> String {
-    let all_js = fastn_js::all_js_without_test();
-    let default_bag_js = fastn_js::to_js(default_bag_into_js_ast().as_slice(), false, package_name);
-    format!("{all_js}\n{default_bag_js}")
+static DEFAULT_BAG_JS: once_cell::sync::Lazy<String> = once_cell::sync::Lazy::new(|| {
+    fastn_js::to_js(default_bag_into_js_ast().as_slice(), false, "ftd")
+});
+
+/// JS of the `default_bag`, same for every document, so it is generated only once.
+pub fn default_bag_js() -> &'static str {
+    &DEFAULT_BAG_JS
+}
+
+static ALL_JS_WITHOUT_TEST: once_cell::sync::Lazy<String> = once_cell::sync::Lazy::new(|| {
+    format!("{}\n{}", fastn_js::all_js_without_test(), default_bag_js())
+});
+
+/// The runtime and the `default_bag`, the JS shared by all the documents.
+pub fn all_js_without_test() -> &'static str {
+    &ALL_JS_WITHOUT_TEST
 }
 
 /// This returns asts of things present in `ftd` module or `default_bag`
@@ -86,13 +98,113 @@ pub struct JSAstData {
     /// This contains external scripts provided by user and also `ftd`
     /// internally supports (like rive).
     pub scripts: Vec<String>,
+    /// Names of the things needed even if nothing refers to them, see `fastn_js::tree_shake()`:
+    /// `main`, and the variables of the document if it has its own js, which may read them.
+    pub roots: Vec<String>,
+    /// Where the things are defined in the ftd files, by their name, for the source maps of the
+    /// minified js, see `fastn_js::Minifier`.
+    pub locations: std::collections::HashMap<String, fastn_js::SourceLocation>,
+    /// Whether the page is shown without JS, if it is known from the document: set by the
+    /// `static` header of `ftd.document`, and `false` if the document has its own js or a grid
+    /// template for mobile. Otherwise it is detected from the generated js, see `is_static()`.
+    pub is_static: Option<bool>,
+}
+
+/// What the generated js of a page uses if the page needs the JS once it is rendered on the
+/// server: the event handlers, which are the only way the variables change, the device and dark
+/// mode variables, `ftd.desktop` and `ftd.mobile` and the responsive lengths depend on the device
+/// too, and the elements rendered in the browser only.
+const NEEDS_JS: [&str; 9] = [
+    "addEventHandler",
+    "ftd.device",
+    "ftd.dark_mode",
+    "ftd.system_dark_mode",
+    "ftd.follow_system_dark_mode",
+    "fastn_dom.Length.Responsive",
+    "fastn_dom.ElementKind.Rive",
+    "fastn_dom.ElementKind.Code",
+    "fastn_dom.ElementKind.WebComponent",
+];
+
+/// Whether the page with the generated `js`, without the js of the default bag, is fully static
+/// and its server rendered html and css is shown as is, with no JS, see
+/// `fastn_js::ssr_static_with_js_string()`.
+pub fn is_static(js: &str) -> bool {
+    !NEEDS_JS.iter().any(|needle| js.contains(needle))
+}
+
+/// Whether a `ftd.responsive-grid-template` of the document has a `mobile` template other than
+/// its `desktop` one, the grids using it change with the device. The generated js does not tell,
+/// `mobile` is set to the `desktop` template when it is not given.
+fn has_mobile_grid_template(document: &ftd::interpreter::Document) -> bool {
+    fn value(v: &ftd::interpreter::Value) -> bool {
+        match v {
+            ftd::interpreter::Value::Record { name, fields }
+                if name.eq(ftd::interpreter::FTD_RESPONSIVE_GRID_TEMPLATE) =>
+            {
+                match (fields.get("desktop"), fields.get("mobile")) {
+                    (
+                        Some(ftd::interpreter::PropertyValue::Reference { name: desktop, .. }),
+                        Some(ftd::interpreter::PropertyValue::Reference { name: mobile, .. }),
+                    ) => desktop.ne(mobile),
+                    (desktop, mobile) => mobile.is_some() && desktop.ne(&mobile),
+                }
+            }
+            ftd::interpreter::Value::Record { fields: values, .. }
+            | ftd::interpreter::Value::Object { values } => values.values().any(property_value),
+            ftd::interpreter::Value::List { data, .. } => data.iter().any(property_value),
+            ftd::interpreter::Value::Optional { data, .. } => {
+                data.as_ref().as_ref().is_some_and(value)
+            }
+            _ => false,
+        }
+    }
+
+    fn property_value(property_value: &ftd::interpreter::PropertyValue) -> bool {
+        match property_value {
+            ftd::interpreter::PropertyValue::Value { value: v, .. } => value(v),
+            _ => false,
+        }
+    }
+
+    document.data.values().any(|thing| match thing {
+        ftd::interpreter::Thing::Variable(v) => property_value(&v.value),
+        _ => false,
+    })
+}
+
+/// The `static` header of `ftd.document`, if the document is one.
+fn document_static(document: &ftd::interpreter::Document) -> Option<bool> {
+    let component = document.tree.first()?;
+    if component.name.ne("ftd#document") {
+        return None;
+    }
+    let argument = ftd::interpreter::default::document_function()
+        .arguments
+        .into_iter()
+        .find(|v| v.name.eq("static"))?;
+    let property = ftd::interpreter::utils::find_properties_by_source_without_default(
+        argument.to_sources().as_slice(),
+        component.properties.as_slice(),
+    )
+    .into_iter()
+    .find(|p| p.condition.is_none())?;
+    property
+        .value
+        .value_optional()?
+        .inner()?
+        .bool(document.name.as_str(), property.line_number)
+        .ok()
 }
 
-pub fn document_into_js_ast(document: ftd::interpreter::Document) -> JSAstData {
+pub fn document_into_js_ast(mut document: ftd::interpreter::Document) -> JSAstData {
     use itertools::Itertools;
+    ftd::executor::headings::add_ids(document.tree.as_mut_slice());
     let doc = ftd::interpreter::TDoc::new(&document.name, &document.aliases, &document.data);
     // Check if document tree has rive. This is used to add rive script.
     let mut has_rive_components = false;
+    let is_static = document_static(&document)
+        .or((!document.js.is_empty() || has_mobile_grid_template(&document)).then_some(false));
     let mut document_asts = vec![ftd::js::from_tree(
         document.tree.as_slice(),
         &doc,
@@ -104,11 +216,30 @@ pub fn document_into_js_ast(document: ftd::interpreter::Document) -> JSAstData {
         .collect_vec();
 
     let mut export_asts = vec![];
+    let mut locations = std::collections::HashMap::new();
+    if let Some(first) = document.tree.first() {
+        locations.insert(
+            fastn_js::MAIN_FUNCTION.to_string(),
+            fastn_js::SourceLocation {
+                source: format!("{}.ftd", document.name),
+                line: first.line_number,
+            },
+        );
+    }
 
     for (key, thing) in document.data.iter() {
         if default_thing_name.contains(key) {
             continue;
         }
+        if let Some((module, _)) = key.split_once('#') {
+            locations.insert(
+                key.to_string(),
+                fastn_js::SourceLocation {
+                    source: format!("{}.ftd", module),
+                    line: thing.line_number(),
+                },
+            );
+        }
         if let ftd::interpreter::Thing::Component(c) = thing {
             document_asts.push(c.to_ast(&doc, &mut has_rive_components));
         } else if let ftd::interpreter::Thing::Variable(v) = thing {
@@ -133,6 +264,10 @@ pub fn document_into_js_ast(document: ftd::interpreter::Document) -> JSAstData {
     }
 
     document_asts.extend(export_asts);
+    let mut roots = vec![fastn_js::MAIN_FUNCTION.to_string()];
+    if !document.js.is_empty() {
+        roots.extend(document_asts.iter().filter_map(|a| a.get_variable_name()));
+    }
     let mut scripts = ftd::js::utils::get_external_scripts(has_rive_components);
     scripts.push(ftd::js::utils::get_js_html(
         document.js.into_iter().collect_vec().as_slice(),
@@ -144,6 +279,9 @@ pub fn document_into_js_ast(document: ftd::interpreter::Document) -> JSAstData {
     JSAstData {
         asts: document_asts,
         scripts,
+        roots,
+        locations,
+        is_static,
     }
 }
 
@@ -335,11 +335,24 @@ impl ftd::interpreter::Value {
                 }
             }
             ftd::interpreter::Value::String { text } => {
-                fastn_js::SetPropertyValue::Value(fastn_js::Value::String(text.to_string()))
+                // the strings of the texts are rendered in the browser, whatever `marked` can not
+                // render is rendered here
+                fastn_js::SetPropertyValue::Value(
+                    match ftd::executor::markup::browser_markup(text.as_str()) {
+                        Some(html) => fastn_js::Value::RenderedMarkdown {
+                            text: text.to_string(),
+                            html,
+                        },
+                        None => fastn_js::Value::String(text.to_string()),
+                    },
+                )
             }
             ftd::interpreter::Value::Integer { value } => {
                 fastn_js::SetPropertyValue::Value(fastn_js::Value::Integer(*value))
             }
+            ftd::interpreter::Value::DateTime { value } => {
+                fastn_js::SetPropertyValue::Value(fastn_js::Value::String(value.to_string()))
+            }
             ftd::interpreter::Value::Decimal { value } => {
                 fastn_js::SetPropertyValue::Value(fastn_js::Value::Decimal(*value))
             }
@@ -390,6 +403,19 @@ impl ftd::interpreter::Value {
                     other_references: vec![],
                 })
             }
+            ftd::interpreter::Value::Map { data, .. } => {
+                fastn_js::SetPropertyValue::Value(fastn_js::Value::Map {
+                    value: data
+                        .iter()
+                        .map(|(k, v)| {
+                            (
+                                k.to_string(),
+                                v.to_fastn_js_value(doc, rdata, should_return),
+                            )
+                        })
+                        .collect_vec(),
+                })
+            }
             ftd::interpreter::Value::UI { component, .. } => {
                 fastn_js::SetPropertyValue::Value(fastn_js::Value::UI {
                     value: component.to_component_statements(
@@ -454,6 +480,13 @@ fn ftd_to_js_variant(name: &str, variant: &str) -> (String, bool) {
                 js_variant.1,
             )
         }
+        "ftd#radial-gradient-shape" => {
+            let js_variant = radial_gradient_shape_variants(variant);
+            (
+                format!("fastn_dom.RadialGradientShape.{}", js_variant),
+                false,
+            )
+        }
         "ftd#background-position" => {
             let js_variant = background_position_variants(variant);
             (
@@ -485,6 +518,17 @@ fn ftd_to_js_variant(name: &str, variant: &str) -> (String, bool) {
             let js_variant = text_align_variants(variant);
             (format!("fastn_dom.TextAlign.{}", js_variant), false)
         }
+        "ftd#easing" => {
+            let js_variant = easing_variants(variant);
+            (format!("fastn_dom.Easing.{}", js_variant), false)
+        }
+        "ftd#grid-track" => {
+            let js_variant = grid_track_variants(variant);
+            (
+                format!("fastn_dom.GridTrack.{}", js_variant.0),
+                js_variant.1,
+            )
+        }
         "ftd#cursor" => {
             let js_variant = cursor_variants(variant);
             (format!("fastn_dom.Cursor.{}", js_variant), false)
@@ -593,6 +637,9 @@ fn background_variants(name: &str) -> &'static str {
         "solid" => "Solid",
         "image" => "Image",
         "linear-gradient" => "LinearGradient",
+        "radial-gradient" => "RadialGradient",
+        "conic-gradient" => "ConicGradient",
+        "layers" => "Layers",
         t => todo!("invalid background variant {}", t),
     }
 }
@@ -654,6 +701,14 @@ fn linear_gradient_direction_variants(name: &str) -> (&'static str, bool) {
     }
 }
 
+fn radial_gradient_shape_variants(name: &str) -> &'static str {
+    match name {
+        "circle" => "Circle",
+        "ellipse" => "Ellipse",
+        t => unreachable!("invalid radial-gradient shape variant {}", t),
+    }
+}
+
 fn font_size_variants(name: &str) -> &'static str {
     match name {
         "px" => "Px",
@@ -714,6 +769,29 @@ fn text_align_variants(name: &str) -> &'static str {
     }
 }
 
+fn easing_variants(name: &str) -> &'static str {
+    match name {
+        "linear" => "Linear",
+        "ease" => "Ease",
+        "ease-in" => "EaseIn",
+        "ease-out" => "EaseOut",
+        "ease-in-out" => "EaseInOut",
+        t => unreachable!("invalid easing variant {}, checked by the interpreter", t),
+    }
+}
+
+fn grid_track_variants(name: &str) -> (&'static str, bool) {
+    match name {
+        "fixed" => ("Fixed", true),
+        "fr" => ("Fr", true),
+        "auto" => ("Auto", false),
+        "minmax" => ("Minmax", true),
+        "auto-fill" => ("AutoFill", true),
+        "auto-fit" => ("AutoFit", true),
+        t => unreachable!("invalid grid-track variant {}", t),
+    }
+}
+
 fn cursor_variants(name: &str) -> &'static str {
     match name {
         "none" => "None",
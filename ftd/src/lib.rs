@@ -30,7 +30,7 @@ pub mod taffy;
 pub mod terminal;
 pub mod test_helper;
 mod utils;
-#[cfg(feature = "native-rendering")]
+#[cfg(feature = "wasm")]
 mod wasm;
 
 pub const PROCESSOR_MARKER: &str = "$processor$";
@@ -60,6 +60,11 @@ pub fn ftd_js_html() -> &'static str {
     include_str!("../ftd-js.html")
 }
 
+/// The page shown without JS, see `ftd::js::is_static()`.
+pub fn ftd_static_html() -> &'static str {
+    include_str!("../ftd-static.html")
+}
+
 pub fn ftd_js_css() -> &'static str {
     include_str!("../ftd-js.css")
 }
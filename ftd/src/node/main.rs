@@ -233,6 +233,7 @@ impl ftd::executor::Element {
             ftd::executor::Element::Row(r) => r.to_node(doc_id, anchor_ids),
             ftd::executor::Element::Column(c) => c.to_node(doc_id, anchor_ids),
             ftd::executor::Element::Container(e) => e.to_node(doc_id, anchor_ids),
+            ftd::executor::Element::Grid(g) => g.to_node(doc_id, anchor_ids),
             ftd::executor::Element::Text(t) => t.to_node(doc_id, anchor_ids),
             ftd::executor::Element::Integer(t) => t.to_node(doc_id, anchor_ids),
             ftd::executor::Element::Decimal(t) => t.to_node(doc_id, anchor_ids),
@@ -484,6 +485,104 @@ impl ftd::executor::ContainerElement {
     }
 }
 
+impl ftd::executor::Grid {
+    pub fn to_node(&self, doc_id: &str, anchor_ids: &mut Vec<String>) -> Node {
+        use ftd::node::utils::CheckMap;
+
+        let device = &self.common.device;
+        let mut n = Node::from_children(&self.common, &self.children, doc_id, "grid", anchor_ids);
+        if !self.common.is_not_visible {
+            n.style
+                .insert(s("display"), ftd::node::Value::from_string("grid"));
+        }
+
+        // `template` carries per-device tracks and areas and wins over the flat lists. It is
+        // rendered once for the device, the html data drops the variants of `ftd.grid-track` so
+        // the browser can not evaluate it again
+        let (columns, rows, areas) = if self.template.value.is_some() {
+            type Render =
+                fn(&ftd::executor::GridTemplate, &Option<ftd::executor::Device>) -> Option<String>;
+            let template = |f: Render| {
+                ftd::executor::Value::new(
+                    self.template
+                        .value
+                        .as_ref()
+                        .and_then(|t| f(t.get(device), device)),
+                    self.template.line_number,
+                    vec![],
+                )
+            };
+            (
+                template(|t, d| ftd::executor::GridTrack::list_to_css_string(&t.columns, d)),
+                template(|t, d| ftd::executor::GridTrack::list_to_css_string(&t.rows, d)),
+                template(|t, _| ftd::executor::GridTrack::areas_to_css_string(&t.areas)),
+            )
+        } else {
+            (
+                self.columns
+                    .to_owned()
+                    .map(|v| ftd::executor::GridTrack::list_to_css_string(&v, device)),
+                self.rows
+                    .to_owned()
+                    .map(|v| ftd::executor::GridTrack::list_to_css_string(&v, device)),
+                self.areas
+                    .to_owned()
+                    .map(|v| ftd::executor::GridTrack::areas_to_css_string(&v)),
+            )
+        };
+
+        n.style.check_and_insert(
+            "grid-template-columns",
+            ftd::node::Value::from_executor_value(columns.value.clone(), columns, None, doc_id),
+        );
+
+        n.style.check_and_insert(
+            "grid-template-rows",
+            ftd::node::Value::from_executor_value(rows.value.clone(), rows, None, doc_id),
+        );
+
+        n.style.check_and_insert(
+            "grid-template-areas",
+            ftd::node::Value::from_executor_value(areas.value.clone(), areas, None, doc_id),
+        );
+
+        n.style.check_and_insert(
+            "gap",
+            ftd::node::Value::from_executor_value(
+                self.gap.value.as_ref().map(|v| v.to_css_string(device)),
+                self.gap.to_owned(),
+                None,
+                doc_id,
+            ),
+        );
+
+        n.style.check_and_insert(
+            "row-gap",
+            ftd::node::Value::from_executor_value(
+                self.row_gap.value.as_ref().map(|v| v.to_css_string(device)),
+                self.row_gap.to_owned(),
+                None,
+                doc_id,
+            ),
+        );
+
+        n.style.check_and_insert(
+            "column-gap",
+            ftd::node::Value::from_executor_value(
+                self.column_gap
+                    .value
+                    .as_ref()
+                    .map(|v| v.to_css_string(device)),
+                self.column_gap.to_owned(),
+                None,
+                doc_id,
+            ),
+        );
+
+        n
+    }
+}
+
 impl ftd::executor::Text {
     pub fn to_node(&self, doc_id: &str, anchor_ids: &mut Vec<String>) -> Node {
         use ftd::node::utils::CheckMap;
@@ -494,7 +593,9 @@ impl ftd::executor::Text {
         if self.common.region.value.is_some() {
             n.attrs.insert_if_not_contains(
                 "id",
-                ftd::node::Value::from_string(slug::slugify(&self.text.value.original)),
+                ftd::node::Value::from_string(ftd::executor::headings::slugify(
+                    &self.text.value.original,
+                )),
             );
         }
 
@@ -1054,6 +1155,56 @@ impl ftd::executor::Image {
                     doc_id,
                 ),
             );
+
+            // The variants generated by `fastn build`. `src` switches to the dark image at runtime,
+            // so `srcset` is only added when both the modes use the same image.
+            let src = &image.src.value;
+            if src.srcset.value.is_some() && src.srcset.value == src.dark_srcset.value {
+                n.attrs.insert(
+                    s("srcset"),
+                    ftd::node::Value::from_executor_value(
+                        src.srcset.value.to_owned(),
+                        src.srcset.to_owned(),
+                        None,
+                        doc_id,
+                    ),
+                );
+                n.attrs.insert(
+                    s("sizes"),
+                    ftd::node::Value::from_executor_value(
+                        image.sizes.to_owned().value,
+                        image.sizes.to_owned(),
+                        None,
+                        doc_id,
+                    ),
+                );
+            }
+            n.attrs.insert(
+                s("width"),
+                ftd::node::Value::from_executor_value(
+                    src.width.value.map(|v| v.to_string()),
+                    src.width.to_owned(),
+                    None,
+                    doc_id,
+                ),
+            );
+            n.attrs.insert(
+                s("height"),
+                ftd::node::Value::from_executor_value(
+                    src.height.value.map(|v| v.to_string()),
+                    src.height.to_owned(),
+                    None,
+                    doc_id,
+                ),
+            );
+            if let Some(ref placeholder) = src.placeholder.value {
+                n.style.insert(
+                    s("background-image"),
+                    ftd::node::Value::from_string(format!("url(\"{}\")", placeholder)),
+                );
+                n.style
+                    .insert(s("background-size"), ftd::node::Value::from_string("cover"));
+            }
             n
         }
     }
@@ -1228,6 +1379,49 @@ impl ftd::executor::Common {
             ),
         );
 
+        d.check_and_insert(
+            "transform",
+            ftd::node::Value::from_executor_value(
+                self.transform
+                    .value
+                    .as_ref()
+                    .map(|v| v.to_css_string(&self.device)),
+                self.transform.to_owned(),
+                Some(ftd::executor::Transform::transform_pattern()),
+                doc_id,
+            ),
+        );
+
+        d.check_and_insert(
+            "transition",
+            ftd::node::Value::from_executor_value(
+                self.transition.value.as_ref().map(|v| v.to_css_string()),
+                self.transition.to_owned(),
+                Some(ftd::executor::Transition::transition_pattern()),
+                doc_id,
+            ),
+        );
+
+        d.check_and_insert(
+            "animation",
+            ftd::node::Value::from_executor_value(
+                self.animation.value.as_ref().map(|v| v.to_css_string()),
+                self.animation.to_owned(),
+                Some(ftd::executor::Animation::animation_pattern()),
+                doc_id,
+            ),
+        );
+
+        d.check_and_insert(
+            "grid-area",
+            ftd::node::Value::from_executor_value(
+                self.grid_area.value.to_owned(),
+                self.grid_area.to_owned(),
+                None,
+                doc_id,
+            ),
+        );
+
         d.check_and_insert(
             "top",
             ftd::node::Value::from_executor_value(
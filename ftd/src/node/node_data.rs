@@ -11,6 +11,7 @@ pub struct NodeData {
     pub raw_nodes: ftd::Map<ftd::node::RawNode>,
     pub js: std::collections::HashSet<String>,
     pub css: std::collections::HashSet<String>,
+    pub keyframes: std::collections::HashSet<String>,
     pub rive_data: Vec<ftd::executor::RiveData>,
 }
 
@@ -34,6 +35,7 @@ impl NodeData {
             raw_nodes: raw_node,
             js: rt.js,
             css: rt.css,
+            keyframes: rt.keyframes,
             rive_data: rt.rive_data,
         }
     }
@@ -0,0 +1,642 @@
+impl<'a> ftd::wasm::Module<'a> {
+    /// Creates `component` under the node in the local `parent`. Returns the local of the node
+    /// created, `None` if the component has a condition or a loop.
+    pub(crate) fn component(
+        &mut self,
+        scope: &mut ftd::wasm::Scope,
+        parent: &str,
+        component: &ftd::interpreter::Component,
+        body: &mut Vec<fastn_wasm::Expression>,
+    ) -> ftd::interpreter::Result<Option<String>> {
+        if let Some(iteration) = component.iteration.as_ref() {
+            self.iteration(scope, parent, component, iteration, body)?;
+            return Ok(None);
+        }
+
+        if let Some(condition) = component.condition.as_ref() {
+            let condition = self.condition(scope, condition, body)?;
+            let mut then = vec![];
+            self.instance(scope, parent, component, &mut then)?;
+            body.push(fastn_wasm::expression::if_(condition, then, vec![]));
+            return Ok(None);
+        }
+
+        self.instance(scope, parent, component, body).map(Some)
+    }
+
+    /// `for: item in $list`, the component is created for each item of the list, the condition
+    /// of the component is checked for each of them.
+    fn iteration(
+        &mut self,
+        scope: &mut ftd::wasm::Scope,
+        parent: &str,
+        component: &ftd::interpreter::Component,
+        iteration: &ftd::interpreter::Loop,
+        body: &mut Vec<fastn_wasm::Expression>,
+    ) -> ftd::interpreter::Result<()> {
+        let list = self.local(scope, "list", fastn_wasm::Type::ExternRef);
+        let index = self.local(scope, "index", fastn_wasm::Type::I32);
+        let item = self.local(scope, "item", fastn_wasm::Type::ExternRef);
+        let counter = self.local(scope, "counter", fastn_wasm::Type::ExternRef);
+
+        let on = self.value(scope, &iteration.on, body)?;
+        body.push(fastn_wasm::expression::local_set(list.as_str(), on));
+        body.push(fastn_wasm::expression::local_set(
+            index.as_str(),
+            fastn_wasm::expression::i32(0),
+        ));
+
+        let counter_name = match iteration.loop_counter_alias {
+            Some(ref alias) => {
+                ftd::interpreter::utils::resolve_name(alias, self.doc.name, self.doc.aliases)
+            }
+            None => format!("{}#{}", self.doc.name, ftd::interpreter::FTD_LOOP_COUNTER),
+        };
+        scope.push(
+            iteration.alias.as_str(),
+            item.as_str(),
+            iteration.on.kind().inner_list(),
+        );
+        scope.push(
+            counter_name.as_str(),
+            counter.as_str(),
+            ftd::interpreter::Kind::integer(),
+        );
+
+        let mut each = vec![
+            fastn_wasm::expression::local_set(
+                item.as_str(),
+                fastn_wasm::expression::call2(
+                    "get_func_arg_ref",
+                    fastn_wasm::expression::local(list.as_str()),
+                    fastn_wasm::expression::local(index.as_str()),
+                ),
+            ),
+            fastn_wasm::expression::local_set(
+                counter.as_str(),
+                fastn_wasm::expression::call1(
+                    "create_i32",
+                    fastn_wasm::expression::local(index.as_str()),
+                ),
+            ),
+        ];
+        let created = match component.condition.as_ref() {
+            Some(condition) => self
+                .condition(scope, condition, &mut each)
+                .and_then(|condition| {
+                    let mut then = vec![];
+                    self.instance(scope, parent, component, &mut then)?;
+                    each.push(fastn_wasm::expression::if_(condition, then, vec![]));
+                    Ok(())
+                }),
+            None => self
+                .instance(scope, parent, component, &mut each)
+                .map(|_| ()),
+        };
+        scope.pop();
+        scope.pop();
+        created?;
+
+        each.push(fastn_wasm::expression::local_set(
+            index.as_str(),
+            fastn_wasm::expression::operation_2(
+                "i32.add",
+                fastn_wasm::expression::local(index.as_str()),
+                fastn_wasm::expression::i32(1),
+            ),
+        ));
+        body.push(fastn_wasm::expression::while_(
+            fastn_wasm::expression::operation_2(
+                "i32.lt_s",
+                fastn_wasm::expression::local(index.as_str()),
+                fastn_wasm::expression::call1(
+                    "list_len",
+                    fastn_wasm::expression::local(list.as_str()),
+                ),
+            ),
+            each,
+        ));
+
+        Ok(())
+    }
+
+    /// Creates the node of `component`, ignoring its condition and loop, and attaches its events.
+    fn instance(
+        &mut self,
+        scope: &mut ftd::wasm::Scope,
+        parent: &str,
+        component: &ftd::interpreter::Component,
+        body: &mut Vec<fastn_wasm::Expression>,
+    ) -> ftd::interpreter::Result<String> {
+        let node = match component.name.as_str() {
+            "ftd#text" => self.text(scope, parent, component, TEXT, body)?,
+            "ftd#integer" => self.text(scope, parent, component, INTEGER, body)?,
+            "ftd#decimal" => self.text(scope, parent, component, DECIMAL, body)?,
+            "ftd#boolean" => self.text(scope, parent, component, BOOLEAN, body)?,
+            "ftd#column" => self.container(
+                scope,
+                parent,
+                component,
+                fastn_runtime::ElementKind::Column,
+                body,
+            )?,
+            "ftd#row" => self.container(
+                scope,
+                parent,
+                component,
+                fastn_runtime::ElementKind::Row,
+                body,
+            )?,
+            "ftd#container" => self.container(
+                scope,
+                parent,
+                component,
+                fastn_runtime::ElementKind::Container,
+                body,
+            )?,
+            _ => self.invocation(scope, parent, component, body)?,
+        };
+
+        for event in component.events.iter() {
+            self.event(scope, node.as_str(), event, body)?;
+        }
+
+        Ok(node)
+    }
+
+    /// `ftd.text`, `ftd.integer`, `ftd.decimal` and `ftd.boolean` show the value of an argument.
+    fn text(
+        &mut self,
+        scope: &mut ftd::wasm::Scope,
+        parent: &str,
+        component: &ftd::interpreter::Component,
+        kernel: TextKernel,
+        body: &mut Vec<fastn_wasm::Expression>,
+    ) -> ftd::interpreter::Result<String> {
+        let (element_kind, argument, kind) = kernel;
+        let node = self.create_kernel(scope, parent, element_kind, body);
+
+        let definition = self
+            .doc
+            .get_component(component.name.as_str(), component.line_number)?;
+        let argument = definition
+            .arguments
+            .iter()
+            .find(|v| v.name.eq(argument))
+            .expect("kernel argument not found");
+        let value = self.argument(scope, component, argument, body)?;
+
+        body.push(fastn_wasm::expression::call3(
+            "set_text",
+            fastn_wasm::expression::local(node.as_str()),
+            fastn_wasm::expression::i32(kind.into()),
+            value,
+        ));
+        self.properties(node.as_str(), component, &[argument.name.as_str()], body)?;
+
+        Ok(node)
+    }
+
+    /// `ftd.column`, `ftd.row` and `ftd.container` and their children.
+    fn container(
+        &mut self,
+        scope: &mut ftd::wasm::Scope,
+        parent: &str,
+        component: &ftd::interpreter::Component,
+        element_kind: fastn_runtime::ElementKind,
+        body: &mut Vec<fastn_wasm::Expression>,
+    ) -> ftd::interpreter::Result<String> {
+        let node = self.create_kernel(scope, parent, element_kind, body);
+        self.properties(node.as_str(), component, &["children"], body)?;
+        for child in component.get_children(&self.doc)?.iter() {
+            self.component(scope, node.as_str(), child, body)?;
+        }
+
+        Ok(node)
+    }
+
+    /// Sets the properties of the kernel `component`, but the arguments in `skip`, on `node`.
+    /// The runtime only has the fixed sizes, spacing, margin and padding for now, the other
+    /// properties, and the ones that are not constants, are an error rather than left out.
+    fn properties(
+        &mut self,
+        node: &str,
+        component: &ftd::interpreter::Component,
+        skip: &[&str],
+        body: &mut Vec<fastn_wasm::Expression>,
+    ) -> ftd::interpreter::Result<()> {
+        let definition = self
+            .doc
+            .get_component(component.name.as_str(), component.line_number)?;
+        for argument in definition.arguments.iter() {
+            if skip.contains(&argument.name.as_str()) {
+                continue;
+            }
+            let properties = ftd::interpreter::utils::find_properties_by_source_without_default(
+                argument.to_sources().as_slice(),
+                component.properties.as_slice(),
+            );
+            let property = match properties.as_slice() {
+                [] => continue,
+                [property] if property.condition.is_none() => property,
+                _ => {
+                    return ftd::interpreter::utils::e2(
+                        format!(
+                            "the conditional `{}` of `{}` can not be compiled to wasm yet",
+                            argument.name, component.name
+                        ),
+                        self.doc.name,
+                        component.line_number,
+                    )
+                }
+            };
+
+            let (ui_property, value) = match ui_property(argument.name.as_str(), &property.value) {
+                Some(v) => v,
+                None => {
+                    return ftd::interpreter::utils::e2(
+                        format!(
+                            "`{}` of `{}` can not be compiled to wasm yet, only the constant \
+                                fixed sizes, spacing, margin and padding can",
+                            argument.name, component.name
+                        ),
+                        self.doc.name,
+                        property.line_number,
+                    )
+                }
+            };
+            let (function, value) = match value {
+                Length::Px(px) => (
+                    "set_property_i32",
+                    fastn_wasm::expression::i32(self.integer(px, property.line_number)?),
+                ),
+                Length::Percent(percent) => (
+                    "set_property_f32",
+                    fastn_wasm::Expression::F32Const(percent as f32),
+                ),
+            };
+            body.push(fastn_wasm::expression::call3(
+                function,
+                fastn_wasm::expression::local(node),
+                fastn_wasm::expression::i32(ui_property.into()),
+                value,
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn create_kernel(
+        &mut self,
+        scope: &mut ftd::wasm::Scope,
+        parent: &str,
+        element_kind: fastn_runtime::ElementKind,
+        body: &mut Vec<fastn_wasm::Expression>,
+    ) -> String {
+        let node = self.local(scope, "node", fastn_wasm::Type::ExternRef);
+        body.push(fastn_wasm::expression::local_set(
+            node.as_str(),
+            fastn_wasm::expression::call2(
+                "create_kernel",
+                fastn_wasm::expression::local(parent),
+                fastn_wasm::expression::i32(element_kind.into()),
+            ),
+        ));
+        node
+    }
+
+    /// A component defined in a document, its definition is compiled to a function that takes
+    /// the parent and the arguments, and returns the node created.
+    fn invocation(
+        &mut self,
+        scope: &mut ftd::wasm::Scope,
+        parent: &str,
+        component: &ftd::interpreter::Component,
+        body: &mut Vec<fastn_wasm::Expression>,
+    ) -> ftd::interpreter::Result<String> {
+        let definition = self
+            .doc
+            .get_component(component.name.as_str(), component.line_number)?;
+        if definition.definition.name.eq("ftd.kernel") {
+            return ftd::interpreter::utils::e2(
+                format!("`{}` can not be compiled to wasm yet", component.name),
+                self.doc.name,
+                component.line_number,
+            );
+        }
+        self.component_definition(&definition)?;
+
+        let mut params = vec![fastn_wasm::expression::local(parent)];
+        for argument in definition.arguments.iter() {
+            params.push(self.argument(scope, component, argument, body)?);
+        }
+
+        let node = self.local(scope, "node", fastn_wasm::Type::ExternRef);
+        body.push(fastn_wasm::expression::local_set(
+            node.as_str(),
+            fastn_wasm::Expression::Call {
+                name: definition.name,
+                params,
+            },
+        ));
+
+        Ok(node)
+    }
+
+    fn component_definition(
+        &mut self,
+        definition: &ftd::interpreter::ComponentDefinition,
+    ) -> ftd::interpreter::Result<()> {
+        if !self.start_compiling(definition.name.as_str()) {
+            return Ok(());
+        }
+
+        let mut scope = ftd::wasm::Scope::default();
+        let mut params = vec![fastn_wasm::Type::ExternRef.to_pl(PARENT)];
+        for argument in definition.arguments.iter() {
+            params.push(fastn_wasm::Type::ExternRef.to_pl(argument.name.as_str()));
+            scope.push(
+                format!("{}.{}", definition.name, argument.name).as_str(),
+                argument.name.as_str(),
+                argument.kind.kind.clone(),
+            );
+        }
+
+        let mut body = vec![];
+        let node = match self.component(&mut scope, PARENT, &definition.definition, &mut body)? {
+            Some(node) => node,
+            None => {
+                return ftd::interpreter::utils::e2(
+                    format!(
+                        "the root of `{}` can not have a condition or a loop",
+                        definition.name
+                    ),
+                    self.doc.name,
+                    definition.line_number,
+                )
+            }
+        };
+        body.push(fastn_wasm::expression::local(node.as_str()));
+
+        self.funcs.push(fastn_wasm::Func {
+            name: Some(definition.name.to_string()),
+            export: None,
+            params,
+            locals: scope.locals,
+            result: Some(fastn_wasm::Type::ExternRef),
+            body,
+        });
+
+        Ok(())
+    }
+
+    /// The pointer to the value of `argument` of `component`. The properties with a condition
+    /// override the ones before them when the condition holds.
+    fn argument(
+        &mut self,
+        scope: &mut ftd::wasm::Scope,
+        component: &ftd::interpreter::Component,
+        argument: &ftd::interpreter::Argument,
+        body: &mut Vec<fastn_wasm::Expression>,
+    ) -> ftd::interpreter::Result<fastn_wasm::Expression> {
+        let properties = ftd::interpreter::utils::find_properties_by_source_without_default(
+            argument.to_sources().as_slice(),
+            component.properties.as_slice(),
+        );
+
+        match properties.as_slice() {
+            [property] if property.condition.is_none() => {
+                return self.value(scope, &property.value, body)
+            }
+            [] => {
+                return match argument.value {
+                    Some(ref value) => self.value(scope, value, body),
+                    None => ftd::interpreter::utils::e2(
+                        format!("`{}` of `{}` has no value", argument.name, component.name),
+                        self.doc.name,
+                        component.line_number,
+                    ),
+                }
+            }
+            _ => {}
+        }
+
+        let local = self.local(scope, "value", fastn_wasm::Type::ExternRef);
+        match (properties.first(), argument.value.as_ref()) {
+            (
+                Some(ftd::interpreter::Property {
+                    condition: None, ..
+                }),
+                _,
+            ) => {}
+            (_, Some(value)) => {
+                let value = self.value(scope, value, body)?;
+                body.push(fastn_wasm::expression::local_set(local.as_str(), value));
+            }
+            (_, None) => {
+                return ftd::interpreter::utils::e2(
+                    format!(
+                        "`{}` of `{}` has no value when none of its conditions hold",
+                        argument.name, component.name
+                    ),
+                    self.doc.name,
+                    component.line_number,
+                )
+            }
+        }
+
+        for property in properties.iter() {
+            match property.condition {
+                Some(ref condition) => {
+                    let condition = self.condition(scope, condition, body)?;
+                    let mut then = vec![];
+                    let value = self.value(scope, &property.value, &mut then)?;
+                    then.push(fastn_wasm::expression::local_set(local.as_str(), value));
+                    body.push(fastn_wasm::expression::if_(condition, then, vec![]));
+                }
+                None => {
+                    let value = self.value(scope, &property.value, body)?;
+                    body.push(fastn_wasm::expression::local_set(local.as_str(), value));
+                }
+            }
+        }
+
+        Ok(fastn_wasm::expression::local(local.as_str()))
+    }
+
+    /// The arguments of the event handler are passed in a list, in the order of the arguments
+    /// of the function.
+    fn event(
+        &mut self,
+        scope: &mut ftd::wasm::Scope,
+        node: &str,
+        event: &ftd::interpreter::Event,
+        body: &mut Vec<fastn_wasm::Expression>,
+    ) -> ftd::interpreter::Result<()> {
+        let event_kind = match event.name {
+            ftd::interpreter::EventName::Click => fastn_runtime::DomEventKind::OnClick,
+            ftd::interpreter::EventName::MouseEnter => fastn_runtime::DomEventKind::OnMouseEnter,
+            ftd::interpreter::EventName::MouseLeave => fastn_runtime::DomEventKind::OnMouseLeave,
+            ref t => {
+                return ftd::interpreter::utils::e2(
+                    format!("the {:?} event can not be compiled to wasm yet", t),
+                    self.doc.name,
+                    event.line_number,
+                )
+            }
+        };
+
+        let function = self
+            .doc
+            .get_function(event.action.name.as_str(), event.line_number)?;
+        let table_index = self.event_handler(&function)?;
+
+        let list = self.local(scope, "args", fastn_wasm::Type::ExternRef);
+        body.push(fastn_wasm::expression::local_set(
+            list.as_str(),
+            fastn_wasm::expression::call("create_list"),
+        ));
+        for argument in function.arguments.iter() {
+            let value = match event
+                .action
+                .values
+                .get(argument.name.as_str())
+                .or(argument.value.as_ref())
+            {
+                Some(value) => self.value(scope, value, body)?,
+                None => {
+                    return ftd::interpreter::utils::e2(
+                        format!("`{}` of `{}` has no value", argument.name, function.name),
+                        self.doc.name,
+                        event.line_number,
+                    )
+                }
+            };
+            body.push(self.list_push(
+                list.as_str(),
+                &argument.kind.kind,
+                value,
+                event.line_number,
+            )?);
+        }
+
+        body.push(fastn_wasm::expression::call4(
+            "attach_event_handler",
+            fastn_wasm::expression::local(node),
+            fastn_wasm::expression::i32(event_kind.into()),
+            fastn_wasm::expression::i32(table_index),
+            fastn_wasm::expression::local(list.as_str()),
+        ));
+
+        Ok(())
+    }
+}
+
+enum Length {
+    Px(i64),
+    Percent(f64),
+}
+
+/// The property of the runtime for the constant `value` of the kernel argument `name`.
+fn ui_property(
+    name: &str,
+    value: &ftd::interpreter::PropertyValue,
+) -> Option<(fastn_runtime::UIProperty, Length)> {
+    use fastn_runtime::UIProperty;
+
+    let (variant, inner) = or_type(value)?;
+    Some(match (name, variant, length(inner)) {
+        ("width", ftd::interpreter::FTD_RESIZING_FIXED, Some(Length::Px(px))) => {
+            (UIProperty::WidthFixedPx, Length::Px(px))
+        }
+        ("width", ftd::interpreter::FTD_RESIZING_FIXED, Some(percent @ Length::Percent(_))) => {
+            (UIProperty::WidthFixedPercentage, percent)
+        }
+        ("height", ftd::interpreter::FTD_RESIZING_FIXED, Some(Length::Px(px))) => {
+            (UIProperty::HeightFixedPx, Length::Px(px))
+        }
+        ("height", ftd::interpreter::FTD_RESIZING_FIXED, Some(percent @ Length::Percent(_))) => {
+            (UIProperty::HeightFixedPercentage, percent)
+        }
+        ("width", ftd::interpreter::FTD_RESIZING_FILL_CONTAINER, _) => {
+            (UIProperty::WidthFixedPercentage, Length::Percent(100.0))
+        }
+        ("height", ftd::interpreter::FTD_RESIZING_FILL_CONTAINER, _) => {
+            (UIProperty::HeightFixedPercentage, Length::Percent(100.0))
+        }
+        ("spacing", ftd::interpreter::FTD_SPACING_FIXED, Some(Length::Px(px))) => {
+            (UIProperty::SpacingFixedPx, Length::Px(px))
+        }
+        ("margin", ftd::interpreter::FTD_LENGTH_PX, _) => {
+            (UIProperty::MarginFixedPx, length(value)?)
+        }
+        ("padding", ftd::interpreter::FTD_LENGTH_PX, _) => {
+            (UIProperty::PaddingFixedPx, length(value)?)
+        }
+        _ => return None,
+    })
+}
+
+/// The variant of the constant or-type `value` and the value of the variant.
+fn or_type(
+    value: &ftd::interpreter::PropertyValue,
+) -> Option<(&str, &ftd::interpreter::PropertyValue)> {
+    match value {
+        ftd::interpreter::PropertyValue::Value {
+            value: ftd::interpreter::Value::OrType { variant, value, .. },
+            ..
+        } => Some((variant.as_str(), value.as_ref())),
+        _ => None,
+    }
+}
+
+/// The constant `ftd.length` in `px` or in `percent` of `value`.
+fn length(value: &ftd::interpreter::PropertyValue) -> Option<Length> {
+    match or_type(value)? {
+        (
+            ftd::interpreter::FTD_LENGTH_PX,
+            ftd::interpreter::PropertyValue::Value {
+                value: ftd::interpreter::Value::Integer { value },
+                ..
+            },
+        ) => Some(Length::Px(*value)),
+        (
+            ftd::interpreter::FTD_LENGTH_PERCENT,
+            ftd::interpreter::PropertyValue::Value {
+                value: ftd::interpreter::Value::Decimal { value },
+                ..
+            },
+        ) => Some(Length::Percent(*value)),
+        _ => None,
+    }
+}
+
+/// The param of the component functions, the node the component is created under.
+const PARENT: &str = "#parent";
+
+type TextKernel = (
+    fastn_runtime::ElementKind,
+    &'static str,
+    fastn_runtime::PointerKind,
+);
+
+const TEXT: TextKernel = (
+    fastn_runtime::ElementKind::Text,
+    "text",
+    fastn_runtime::PointerKind::String,
+);
+const INTEGER: TextKernel = (
+    fastn_runtime::ElementKind::Integer,
+    "value",
+    fastn_runtime::PointerKind::Integer,
+);
+const DECIMAL: TextKernel = (
+    fastn_runtime::ElementKind::Decimal,
+    "value",
+    fastn_runtime::PointerKind::Decimal,
+);
+const BOOLEAN: TextKernel = (
+    fastn_runtime::ElementKind::Boolean,
+    "value",
+    fastn_runtime::PointerKind::Boolean,
+);
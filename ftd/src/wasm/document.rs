@@ -1,28 +1,190 @@
 impl ftd::interpreter::Document {
-    pub fn generate_wasm(&self) -> Vec<fastn_wasm::Ast> {
-        let mut globals = std::collections::HashMap::new();
-
-        let mut wasm = vec![];
-        // handle all global variables
-        let mut main = fastn_wasm::Func {
-            name: Some("main".to_string()),
-            export: Some("main".to_string()),
-            params: vec![],
-            locals: vec![],
-            body: vec![],
-        };
-
-        let mut global_index = 0usize;
-        for thing in self.data.values() {
-            if let ftd::interpreter::Thing::Variable(v) = thing {
-                globals.insert(v.name.clone(), global_index);
-                main.body.push(v.global_expression(global_index));
-                global_index += 1;
+    /// Compiles the document to a wasm module for `fastn_runtime::Dom`.
+    ///
+    /// The `main` of the module sets the globals the UI refers to and then creates the UI of
+    /// the document under the root node. Only a part of ftd is compiled, see `ftd::wasm`, the
+    /// rest is an error.
+    pub fn generate_wasm(&self) -> ftd::interpreter::Result<Vec<fastn_wasm::Ast>> {
+        let mut module = ftd::wasm::Module::new(self.tdoc());
+        let mut scope = ftd::wasm::Scope::default();
+        let mut body = vec![];
+        for component in self.tree.iter() {
+            module.component(&mut scope, ROOT, component, &mut body)?;
+        }
+        Ok(module.finish(scope, body))
+    }
+}
+
+/// The param of `main`, the root node of the document.
+const ROOT: &str = "#root";
+
+/// The state of a module while the document is compiled to it.
+pub(crate) struct Module<'a> {
+    pub(crate) doc: ftd::interpreter::TDoc<'a>,
+    /// The globals set so far, the position is the index passed to `get_global`.
+    pub(crate) globals: Vec<(String, ftd::interpreter::Kind)>,
+    /// The part of `main` that sets the globals, it runs before the UI is created.
+    pub(crate) globals_body: Vec<fastn_wasm::Expression>,
+    pub(crate) globals_locals: Vec<fastn_wasm::PL>,
+    /// The string constants, read by `create_string_constant`.
+    pub(crate) data: Vec<fastn_wasm::Data>,
+    pub(crate) data_end: u32,
+    pub(crate) funcs: Vec<fastn_wasm::Func>,
+    /// The components and functions compiled to `funcs` so far.
+    pub(crate) compiled: std::collections::HashSet<String>,
+    /// The event handlers, the position is the index passed to `attach_event_handler`.
+    pub(crate) table: Vec<String>,
+    pub(crate) next_local: usize,
+}
+
+impl<'a> Module<'a> {
+    pub(crate) fn new(doc: ftd::interpreter::TDoc<'a>) -> Module<'a> {
+        Module {
+            doc,
+            globals: vec![],
+            globals_body: vec![],
+            globals_locals: vec![],
+            data: vec![],
+            data_end: 0,
+            funcs: vec![],
+            compiled: Default::default(),
+            table: vec![],
+            next_local: 0,
+        }
+    }
+
+    /// Assembles the module, `ui` is the body of `main` that creates the UI, it runs in `scope`.
+    pub(crate) fn finish(
+        self,
+        scope: ftd::wasm::Scope,
+        ui: Vec<fastn_wasm::Expression>,
+    ) -> Vec<fastn_wasm::Ast> {
+        let mut m = fastn_runtime::Dom::imports();
+
+        m.push(fastn_wasm::func_def::func1ret(
+            "return_externref",
+            fastn_wasm::Type::ExternRef.into(),
+            fastn_wasm::Type::ExternRef,
+        ));
+        m.push(fastn_wasm::func_def::func1(
+            "no_return",
+            fastn_wasm::Type::ExternRef.into(),
+        ));
+
+        m.push(fastn_wasm::table(
+            self.table.len() as u32,
+            fastn_wasm::RefType::Func,
+        ));
+        if !self.table.is_empty() {
+            m.push(fastn_wasm::Ast::Elem(fastn_wasm::Elem {
+                start: 0,
+                fns: self.table,
+            }));
+        }
+
+        m.push(fastn_wasm::Ast::Memory(fastn_wasm::Memory {
+            limits: fastn_wasm::Limits {
+                min: std::cmp::max(1, (self.data_end + 0xFFFF) / 0x10000),
+                max: None,
+            },
+            shared: false,
+        }));
+        m.push(fastn_wasm::Ast::Export(fastn_wasm::Export {
+            name: "memory".to_string(),
+            desc: fastn_wasm::ExportDesc::Memory { index: 0 },
+        }));
+        m.extend(self.data.into_iter().map(fastn_wasm::Ast::Data));
+
+        m.push(by_index("call_by_index", "return_externref", true));
+        m.push(by_index("void_by_index", "no_return", false));
+
+        // the frame of `main` is never ended, the globals and the values of the UI live in it
+        let mut body = vec![fastn_wasm::expression::call("create_frame")];
+        body.extend(self.globals_body);
+        body.extend(ui);
+
+        let mut locals = self.globals_locals;
+        locals.extend(scope.locals);
+
+        m.push(
+            fastn_wasm::Func {
+                name: None,
+                export: Some("main".to_string()),
+                params: vec![fastn_wasm::Type::ExternRef.to_pl(ROOT)],
+                locals,
+                result: None,
+                body,
             }
+            .to_ast(),
+        );
+        m.extend(self.funcs.into_iter().map(fastn_wasm::Func::to_ast));
+
+        m
+    }
+
+    /// Declares a new local in `scope`, its name starts with `prefix`.
+    pub(crate) fn local(
+        &mut self,
+        scope: &mut ftd::wasm::Scope,
+        prefix: &str,
+        ty: fastn_wasm::Type,
+    ) -> String {
+        self.next_local += 1;
+        let name = format!("{}#{}", prefix, self.next_local);
+        scope.locals.push(ty.to_pl(name.as_str()));
+        name
+    }
+
+    /// A pointer to a new string, `text` is stored in the memory of the module.
+    pub(crate) fn string_constant(&mut self, text: &str) -> fastn_wasm::Expression {
+        let offset = self.data_end;
+        let data = text.as_bytes().to_vec();
+        self.data_end += data.len() as u32;
+        if !data.is_empty() {
+            self.data.push(fastn_wasm::Data { offset, data });
         }
 
-        wasm.push(WasmAst::Func(main));
+        fastn_wasm::expression::call2(
+            "create_string_constant",
+            fastn_wasm::expression::i32(offset as i32),
+            fastn_wasm::expression::i32(text.len() as i32),
+        )
+    }
+
+    /// Returns false if `name` was compiled already, a component or a function is compiled once.
+    pub(crate) fn start_compiling(&mut self, name: &str) -> bool {
+        self.compiled.insert(name.to_string())
+    }
+
+    /// The index of the event handler `name` in the table, it is added if it is not there yet.
+    pub(crate) fn table_index(&mut self, name: &str) -> i32 {
+        match self.table.iter().position(|v| v.eq(name)) {
+            Some(index) => index as i32,
+            None => {
+                self.table.push(name.to_string());
+                self.table.len() as i32 - 1
+            }
+        }
+    }
+}
 
-        wasm
+/// `call_by_index` and `void_by_index`, the runtime calls the closures and the event handlers
+/// through them.
+fn by_index(export: &str, type_: &str, returns: bool) -> fastn_wasm::Ast {
+    fastn_wasm::Func {
+        name: None,
+        export: Some(export.to_string()),
+        params: vec![
+            fastn_wasm::Type::I32.to_pl("fn_idx"),
+            fastn_wasm::Type::ExternRef.to_pl("arr"),
+        ],
+        locals: vec![],
+        result: returns.then_some(fastn_wasm::Type::ExternRef),
+        body: vec![fastn_wasm::expression::call_indirect2(
+            type_,
+            fastn_wasm::expression::local("arr"),
+            fastn_wasm::expression::local("fn_idx"),
+        )],
     }
+    .to_ast()
 }
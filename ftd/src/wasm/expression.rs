@@ -0,0 +1,387 @@
+use fastn_grammar::evalexpr::Operator;
+
+impl<'a> ftd::wasm::Module<'a> {
+    /// The `i32` of an `if: { .. }`, the identifiers of the condition are its references.
+    pub(crate) fn condition(
+        &mut self,
+        scope: &mut ftd::wasm::Scope,
+        condition: &ftd::interpreter::Expression,
+        body: &mut Vec<fastn_wasm::Expression>,
+    ) -> ftd::interpreter::Result<fastn_wasm::Expression> {
+        let (value, ty) = self.expression(
+            scope,
+            &condition.expression,
+            Some(&condition.references),
+            condition.line_number,
+            body,
+        )?;
+        match ty {
+            ftd::wasm::Ty::Boolean => Ok(value),
+            t => ftd::interpreter::utils::e2(
+                format!("the condition is a {:?}, not a boolean", t),
+                self.doc.name,
+                condition.line_number,
+            ),
+        }
+    }
+
+    /// An expression of a condition or of the body of a function. The identifiers are the
+    /// `references` of a condition, or the locals of `scope` in a function.
+    pub(crate) fn expression(
+        &mut self,
+        scope: &mut ftd::wasm::Scope,
+        node: &fastn_grammar::evalexpr::ExprNode,
+        references: Option<&ftd::Map<ftd::interpreter::PropertyValue>>,
+        line_number: usize,
+        body: &mut Vec<fastn_wasm::Expression>,
+    ) -> ftd::interpreter::Result<(fastn_wasm::Expression, ftd::wasm::Ty)> {
+        let children = node.children();
+        match node.operator() {
+            Operator::RootNode if children.len() == 1 => {
+                self.expression(scope, &children[0], references, line_number, body)
+            }
+            Operator::Chain if !children.is_empty() => {
+                let (last, statements) = children.split_last().unwrap();
+                for statement in statements {
+                    self.statement(scope, statement, line_number, body)?;
+                }
+                self.expression(scope, last, references, line_number, body)
+            }
+            Operator::Const { value } => match value {
+                fastn_grammar::evalexpr::Value::String(text) => Ok((
+                    self.string_constant(text),
+                    ftd::wasm::Ty::Pointer(ftd::interpreter::Kind::string()),
+                )),
+                fastn_grammar::evalexpr::Value::Int(value) => Ok((
+                    fastn_wasm::expression::i32(self.integer(*value, line_number)?),
+                    ftd::wasm::Ty::Integer,
+                )),
+                fastn_grammar::evalexpr::Value::Float(value) => Ok((
+                    fastn_wasm::Expression::F32Const(*value as f32),
+                    ftd::wasm::Ty::Decimal,
+                )),
+                fastn_grammar::evalexpr::Value::Boolean(value) => Ok((
+                    fastn_wasm::expression::i32(*value as i32),
+                    ftd::wasm::Ty::Boolean,
+                )),
+                t => ftd::interpreter::utils::e2(
+                    format!("{:?} can not be compiled to wasm yet", t),
+                    self.doc.name,
+                    line_number,
+                ),
+            },
+            Operator::VariableIdentifierRead { identifier } => {
+                let (pointer, kind) =
+                    self.identifier(scope, identifier, references, line_number, body)?;
+                Ok(ftd::wasm::value::load(pointer, &kind))
+            }
+            Operator::Not | Operator::Neg if children.len() == 1 => {
+                let (value, ty) =
+                    self.expression(scope, &children[0], references, line_number, body)?;
+                let op = node.operator();
+                match (op, &ty) {
+                    (Operator::Not, ftd::wasm::Ty::Boolean) => {
+                        Ok((unary("i32.eqz", value), ftd::wasm::Ty::Boolean))
+                    }
+                    (Operator::Neg, ftd::wasm::Ty::Integer) => Ok((
+                        fastn_wasm::expression::operation_2(
+                            "i32.sub",
+                            fastn_wasm::expression::i32(0),
+                            value,
+                        ),
+                        ftd::wasm::Ty::Integer,
+                    )),
+                    (Operator::Neg, ftd::wasm::Ty::Decimal) => {
+                        Ok((unary("f32.neg", value), ftd::wasm::Ty::Decimal))
+                    }
+                    _ => self.unsupported(op, &ty, &ty, line_number),
+                }
+            }
+            op if children.len() == 2 && binary(op).is_some() => {
+                let left = self.expression(scope, &children[0], references, line_number, body)?;
+                let right = self.expression(scope, &children[1], references, line_number, body)?;
+                self.binary(op, left, right, line_number)
+            }
+            t => ftd::interpreter::utils::e2(
+                format!("{:?} can not be compiled to wasm yet", t),
+                self.doc.name,
+                line_number,
+            ),
+        }
+    }
+
+    /// An expression of the body of a function that is run for what it does: an assignment to
+    /// an argument, `ftd.append` or a chain of them.
+    pub(crate) fn statement(
+        &mut self,
+        scope: &mut ftd::wasm::Scope,
+        node: &fastn_grammar::evalexpr::ExprNode,
+        line_number: usize,
+        body: &mut Vec<fastn_wasm::Expression>,
+    ) -> ftd::interpreter::Result<()> {
+        let children = node.children();
+        match node.operator() {
+            Operator::RootNode | Operator::Chain => {
+                for child in children {
+                    self.statement(scope, child, line_number, body)?;
+                }
+                Ok(())
+            }
+            op @ (Operator::Assign
+            | Operator::AddAssign
+            | Operator::SubAssign
+            | Operator::MulAssign
+            | Operator::DivAssign
+            | Operator::ModAssign)
+                if children.len() == 2 =>
+            {
+                let identifier = match children[0].operator() {
+                    Operator::VariableIdentifierWrite { identifier } => identifier,
+                    t => {
+                        return ftd::interpreter::utils::e2(
+                            format!("can not assign to {:?}", t),
+                            self.doc.name,
+                            line_number,
+                        )
+                    }
+                };
+                let (pointer, kind) =
+                    self.identifier(scope, identifier, None, line_number, body)?;
+                let mut value = self.expression(scope, &children[1], None, line_number, body)?;
+                if let Some(op) = assign(op) {
+                    let current = ftd::wasm::value::load(pointer.clone(), &kind);
+                    value = self.binary(&op, current, value, line_number)?;
+                }
+                body.push(self.store(pointer, &kind, value, line_number)?);
+                Ok(())
+            }
+            Operator::FunctionIdentifier { identifier } if identifier.eq("ftd.append") => {
+                let arguments = arguments(node);
+                let (list, value) = match arguments.as_slice() {
+                    [list, value] => (list, value),
+                    _ => {
+                        return ftd::interpreter::utils::e2(
+                            "ftd.append takes a list and a value",
+                            self.doc.name,
+                            line_number,
+                        )
+                    }
+                };
+                let (list, kind) = self.expression(scope, list, None, line_number, body)?;
+                let (value, ty) = self.expression(scope, value, None, line_number, body)?;
+                let item_kind = match kind {
+                    ftd::wasm::Ty::Pointer(ftd::interpreter::Kind::List { kind }) => *kind,
+                    t => {
+                        return ftd::interpreter::utils::e2(
+                            format!("ftd.append to a {:?}, not a list", t),
+                            self.doc.name,
+                            line_number,
+                        )
+                    }
+                };
+                body.push(fastn_wasm::expression::call3(
+                    "list_push",
+                    list,
+                    fastn_wasm::expression::i32(self.pointer_kind(&item_kind, line_number)?.into()),
+                    ftd::wasm::value::to_pointer(value, &ty),
+                ));
+                Ok(())
+            }
+            _ => {
+                let (value, _) = self.expression(scope, node, None, line_number, body)?;
+                body.push(value);
+                body.push(fastn_wasm::Expression::Drop);
+                Ok(())
+            }
+        }
+    }
+
+    /// The pointer an identifier refers to and its kind.
+    fn identifier(
+        &mut self,
+        scope: &mut ftd::wasm::Scope,
+        identifier: &str,
+        references: Option<&ftd::Map<ftd::interpreter::PropertyValue>>,
+        line_number: usize,
+        body: &mut Vec<fastn_wasm::Expression>,
+    ) -> ftd::interpreter::Result<(fastn_wasm::Expression, ftd::interpreter::Kind)> {
+        let references = match references {
+            Some(references) => references,
+            None => return self.reference(scope, identifier, line_number),
+        };
+        match references.get(identifier) {
+            Some(ftd::interpreter::PropertyValue::Reference {
+                name, line_number, ..
+            }) => self.reference(scope, name, *line_number),
+            Some(value) => Ok((self.value(scope, value, body)?, value.kind())),
+            None => ftd::interpreter::utils::e2(
+                format!("unknown variable `{}`", identifier),
+                self.doc.name,
+                line_number,
+            ),
+        }
+    }
+
+    /// Sets the value `pointer` points to.
+    fn store(
+        &self,
+        pointer: fastn_wasm::Expression,
+        kind: &ftd::interpreter::Kind,
+        (value, ty): (fastn_wasm::Expression, ftd::wasm::Ty),
+        line_number: usize,
+    ) -> ftd::interpreter::Result<fastn_wasm::Expression> {
+        let set = match (kind.ref_inner(), &ty) {
+            (ftd::interpreter::Kind::Integer, ftd::wasm::Ty::Integer) => "set_i32",
+            (ftd::interpreter::Kind::Boolean, ftd::wasm::Ty::Boolean) => "set_boolean",
+            (ftd::interpreter::Kind::Decimal, ftd::wasm::Ty::Decimal) => "set_f32",
+            (ftd::interpreter::Kind::Decimal, ftd::wasm::Ty::Integer) => {
+                return Ok(fastn_wasm::expression::call2(
+                    "set_f32",
+                    pointer,
+                    unary("f32.convert_i32_s", value),
+                ))
+            }
+            (
+                ftd::interpreter::Kind::String,
+                ftd::wasm::Ty::Pointer(ftd::interpreter::Kind::String),
+            ) => "set_string",
+            (k, t) => {
+                return ftd::interpreter::utils::e2(
+                    format!("can not assign a {:?} to a {:?}", t, k),
+                    self.doc.name,
+                    line_number,
+                )
+            }
+        };
+        Ok(fastn_wasm::expression::call2(set, pointer, value))
+    }
+
+    fn binary(
+        &self,
+        op: &Operator,
+        (left, left_ty): (fastn_wasm::Expression, ftd::wasm::Ty),
+        (right, right_ty): (fastn_wasm::Expression, ftd::wasm::Ty),
+        line_number: usize,
+    ) -> ftd::interpreter::Result<(fastn_wasm::Expression, ftd::wasm::Ty)> {
+        use ftd::wasm::Ty;
+
+        let (integer, decimal, boolean) = match binary(op) {
+            Some(v) => v,
+            None => return self.unsupported(op, &left_ty, &right_ty, line_number),
+        };
+        let result = |name: &str, ty: Ty, left, right| {
+            Ok((fastn_wasm::expression::operation_2(name, left, right), ty))
+        };
+        let comparison = matches!(
+            op,
+            Operator::Eq
+                | Operator::Neq
+                | Operator::Gt
+                | Operator::Lt
+                | Operator::Geq
+                | Operator::Leq
+        );
+
+        match (&left_ty, &right_ty) {
+            (
+                Ty::Pointer(ftd::interpreter::Kind::String),
+                Ty::Pointer(ftd::interpreter::Kind::String),
+            ) if matches!(op, Operator::Add) => Ok((
+                fastn_wasm::expression::call2("concat_string", left, right),
+                Ty::Pointer(ftd::interpreter::Kind::string()),
+            )),
+            (Ty::Integer, Ty::Integer) if !integer.is_empty() => result(
+                integer,
+                if comparison { Ty::Boolean } else { Ty::Integer },
+                left,
+                right,
+            ),
+            (Ty::Boolean, Ty::Boolean) if !boolean.is_empty() => {
+                result(boolean, Ty::Boolean, left, right)
+            }
+            (Ty::Integer | Ty::Decimal, Ty::Integer | Ty::Decimal) if !decimal.is_empty() => {
+                let to_decimal = |value, ty: &Ty| match ty {
+                    Ty::Integer => unary("f32.convert_i32_s", value),
+                    _ => value,
+                };
+                result(
+                    decimal,
+                    if comparison { Ty::Boolean } else { Ty::Decimal },
+                    to_decimal(left, &left_ty),
+                    to_decimal(right, &right_ty),
+                )
+            }
+            _ => self.unsupported(op, &left_ty, &right_ty, line_number),
+        }
+    }
+
+    fn unsupported<T>(
+        &self,
+        op: &Operator,
+        left: &ftd::wasm::Ty,
+        right: &ftd::wasm::Ty,
+        line_number: usize,
+    ) -> ftd::interpreter::Result<T> {
+        ftd::interpreter::utils::e2(
+            format!(
+                "{:?} of {:?} and {:?} can not be compiled to wasm yet",
+                op, left, right
+            ),
+            self.doc.name,
+            line_number,
+        )
+    }
+}
+
+/// The wasm instructions of a binary operator for integers, decimals and booleans, empty if the
+/// operator does not apply to them.
+fn binary(op: &Operator) -> Option<(&'static str, &'static str, &'static str)> {
+    Some(match op {
+        Operator::Add => ("i32.add", "f32.add", ""),
+        Operator::Sub => ("i32.sub", "f32.sub", ""),
+        Operator::Mul => ("i32.mul", "f32.mul", ""),
+        Operator::Div => ("i32.div_s", "f32.div", ""),
+        Operator::Mod => ("i32.rem_s", "", ""),
+        Operator::Eq => ("i32.eq", "f32.eq", "i32.eq"),
+        Operator::Neq => ("i32.ne", "f32.ne", "i32.ne"),
+        Operator::Gt => ("i32.gt_s", "f32.gt", ""),
+        Operator::Lt => ("i32.lt_s", "f32.lt", ""),
+        Operator::Geq => ("i32.ge_s", "f32.ge", ""),
+        Operator::Leq => ("i32.le_s", "f32.le", ""),
+        Operator::And => ("", "", "i32.and"),
+        Operator::Or => ("", "", "i32.or"),
+        _ => return None,
+    })
+}
+
+/// The operator of `a op= b`, `None` for `a = b`.
+fn assign(op: &Operator) -> Option<Operator> {
+    Some(match op {
+        Operator::AddAssign => Operator::Add,
+        Operator::SubAssign => Operator::Sub,
+        Operator::MulAssign => Operator::Mul,
+        Operator::DivAssign => Operator::Div,
+        Operator::ModAssign => Operator::Mod,
+        _ => return None,
+    })
+}
+
+/// The arguments of a function call, `f(a, b)` is a function identifier with a tuple child.
+fn arguments(node: &fastn_grammar::evalexpr::ExprNode) -> Vec<&fastn_grammar::evalexpr::ExprNode> {
+    let mut node = node;
+    while let [child] = node.children() {
+        match child.operator() {
+            Operator::RootNode => node = child,
+            Operator::Tuple => return child.children().iter().collect(),
+            _ => return vec![child],
+        }
+    }
+    node.children().iter().collect()
+}
+
+fn unary(name: &str, value: fastn_wasm::Expression) -> fastn_wasm::Expression {
+    fastn_wasm::Expression::Operation {
+        name: name.to_string(),
+        values: vec![value],
+    }
+}
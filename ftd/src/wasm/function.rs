@@ -0,0 +1,140 @@
+impl<'a> ftd::wasm::Module<'a> {
+    /// Compiles `function` to a function that takes a pointer for each argument, and returns a
+    /// pointer to the result unless the function is `void`. Returns the name of the function.
+    pub(crate) fn function(
+        &mut self,
+        function: &ftd::interpreter::Function,
+    ) -> ftd::interpreter::Result<String> {
+        if !self.start_compiling(function.name.as_str()) {
+            return Ok(function.name.to_string());
+        }
+        if function.external_implementation || function.js.is_some() {
+            return ftd::interpreter::utils::e2(
+                format!(
+                    "`{}` is implemented in js, it can not be compiled to wasm",
+                    function.name
+                ),
+                self.doc.name,
+                function.line_number,
+            );
+        }
+
+        let mut scope = ftd::wasm::Scope::default();
+        let mut params = vec![];
+        for argument in function.arguments.iter() {
+            params.push(fastn_wasm::Type::ExternRef.to_pl(argument.name.as_str()));
+            scope.push(
+                argument.name.as_str(),
+                argument.name.as_str(),
+                argument.kind.kind.clone(),
+            );
+        }
+
+        let returns = !function.return_kind.is_void();
+        let mut body = vec![];
+        for (i, expression) in function.expression.iter().enumerate() {
+            let node =
+                fastn_grammar::evalexpr::build_operator_tree(expression.expression.as_str())?;
+            if returns && i + 1 == function.expression.len() {
+                let (value, ty) =
+                    self.expression(&mut scope, &node, None, expression.line_number, &mut body)?;
+                body.push(ftd::wasm::value::to_pointer(value, &ty));
+            } else {
+                self.statement(&mut scope, &node, expression.line_number, &mut body)?;
+            }
+        }
+
+        self.funcs.push(fastn_wasm::Func {
+            name: Some(function.name.to_string()),
+            export: None,
+            params,
+            locals: scope.locals,
+            result: returns.then_some(fastn_wasm::Type::ExternRef),
+            body,
+        });
+
+        Ok(function.name.to_string())
+    }
+
+    /// A call to the function with a pointer for each argument, in the order of the arguments.
+    pub(crate) fn function_call(
+        &mut self,
+        scope: &mut ftd::wasm::Scope,
+        call: &ftd::interpreter::FunctionCall,
+        body: &mut Vec<fastn_wasm::Expression>,
+    ) -> ftd::interpreter::Result<fastn_wasm::Expression> {
+        let function = self
+            .doc
+            .get_function(call.name.as_str(), call.line_number)?;
+        if function.return_kind.is_void() {
+            return ftd::interpreter::utils::e2(
+                format!("`{}` does not return a value", function.name),
+                self.doc.name,
+                call.line_number,
+            );
+        }
+        let name = self.function(&function)?;
+
+        let mut params = vec![];
+        for argument in function.arguments.iter() {
+            match call
+                .values
+                .get(argument.name.as_str())
+                .or(argument.value.as_ref())
+            {
+                Some(value) => params.push(self.value(scope, value, body)?),
+                None => {
+                    return ftd::interpreter::utils::e2(
+                        format!("`{}` of `{}` has no value", argument.name, function.name),
+                        self.doc.name,
+                        call.line_number,
+                    )
+                }
+            }
+        }
+
+        Ok(fastn_wasm::Expression::Call { name, params })
+    }
+
+    /// The index of the event handler of `function` in the table. The handler gets the arguments
+    /// of `function` in a list, see `attach_event_handler`.
+    pub(crate) fn event_handler(
+        &mut self,
+        function: &ftd::interpreter::Function,
+    ) -> ftd::interpreter::Result<i32> {
+        let name = format!("{}#event", function.name);
+        if self.start_compiling(name.as_str()) {
+            let function_name = self.function(function)?;
+            let params = (0..function.arguments.len())
+                .map(|i| {
+                    fastn_wasm::expression::call2(
+                        "get_func_arg_ref",
+                        fastn_wasm::expression::local(FUNC_DATA),
+                        fastn_wasm::expression::i32(i as i32),
+                    )
+                })
+                .collect();
+            let mut body = vec![fastn_wasm::Expression::Call {
+                name: function_name,
+                params,
+            }];
+            if !function.return_kind.is_void() {
+                body.push(fastn_wasm::Expression::Drop);
+            }
+
+            self.funcs.push(fastn_wasm::Func {
+                name: Some(name.to_string()),
+                export: None,
+                params: vec![fastn_wasm::Type::ExternRef.to_pl(FUNC_DATA)],
+                locals: vec![],
+                result: None,
+                body,
+            });
+        }
+
+        Ok(self.table_index(name.as_str()))
+    }
+}
+
+/// The param of the event handlers, the list of the arguments.
+const FUNC_DATA: &str = "#func-data";
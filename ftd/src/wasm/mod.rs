@@ -1,3 +1,25 @@
+//! Compiles an interpreted document to a wasm module for `fastn_runtime`.
+//!
+//! The module imports the functions of `fastn_runtime::Dom` and exports `main`, which creates
+//! the UI of the document under the root node it is called with. Every value lives in the memory
+//! of the runtime, the module only holds pointers to them (`externref`s).
+//!
+//! Only a part of ftd is compiled for now: the variables, records and lists, the component
+//! definitions and invocations, the conditions and loops of the components, the click and mouse
+//! enter and leave events, `ftd.text`, `ftd.integer`, `ftd.decimal`, `ftd.boolean`, `ftd.row`,
+//! `ftd.column` and `ftd.container`, and their constant sizes, spacing, margin and padding. The
+//! conditional properties and values, the other styles and the other kernel components are an
+//! error. The tests list the `ftd/t/js` documents that compile, and what each of the others needs.
+
+mod component;
 mod document;
+mod expression;
+mod function;
 mod value;
 mod variable;
+
+#[cfg(test)]
+mod test;
+
+pub(crate) use document::Module;
+pub(crate) use value::{Scope, Ty};
@@ -0,0 +1,348 @@
+/// The `ftd/t/js` documents that compile to wasm, the html `fastn_runtime::server` renders for
+/// them is compared with the html of `fastn_js::ssr`. Each of the other documents is in
+/// `EXCLUDED`, with the reason.
+const FIXTURES: &[&str] = &[
+    "05-dynamic-dom-list",
+    "06-dynamic-dom-list-2",
+    "07-dynamic-dom-record-list",
+    "16-container",
+    "17-clone",
+    "28-mutable-component-arguments",
+    "loop",
+];
+
+/// The `ftd/t/js` documents that are not compared yet, and why. The reason is the first thing
+/// the compiler or the runtime does not have, the document may need more. The styles are only
+/// compiled when they are constants for now.
+const EXCLUDED: &[(&str, &str)] = &[
+    ("01-basic", "the `id` of a text"),
+    ("01-basic-module", "the `role` of a text"),
+    ("02-property", "the `background` of a text"),
+    ("03-common-properties", "the `background` of a column"),
+    ("04-variable", "a conditional `padding`"),
+    ("08-inherited", "the `background` of a text"),
+    ("09-text-properties", "the `border-style` of a column"),
+    ("10-color-test", "a conditional `background`"),
+    ("11-device", "`ftd.desktop`"),
+    ("12-children", "the `color` of a column"),
+    ("13-non-style-properties", "the `align-content` of a column"),
+    ("14-code", "the `background` of a column"),
+    ("15-function-call-in-property", "the `padding` in `em`"),
+    ("17-events", "a conditional `color`"),
+    ("18-rive", "`ftd.rive`"),
+    ("19-image", "`ftd.image`"),
+    (
+        "20-background-properties",
+        "the `align-content` of a column",
+    ),
+    (
+        "21-markdown",
+        "the runtime shows the text as is, `fastn_js` renders its markdown",
+    ),
+    ("22-document", "`ftd.document`"),
+    ("23-record-list", "the `color` of an integer"),
+    ("24-device", "`ftd.desktop`"),
+    (
+        "24-re-export",
+        "the imports of the other `t/js` documents are not interpreted here",
+    ),
+    (
+        "25-re-re-export",
+        "the imports of the other `t/js` documents are not interpreted here",
+    ),
+    ("26-re-export", "a component with the name of an import"),
+    ("27-for-loop", "adding a string to an integer"),
+    ("28-web-component", "the web components"),
+    ("29-dom-list", "`ftd.insert_at`"),
+    ("30-web-component", "a conditional `padding`"),
+    ("31-advance-list", "the `role` of a column"),
+    ("31-ftd-len", "`ftd.len`"),
+    ("32-ftd-len", "the `background` of a column"),
+    ("33-list-indexing", "the `color` of a text"),
+    ("34-ftd-ui", "the `ftd.ui` lists"),
+    ("36-single-ui", "the `ftd.ui` arguments"),
+    ("37-expander", "the `align-content` of a column"),
+    (
+        "38-background-image-properties",
+        "the `background` of a column",
+    ),
+    ("40-code-themes", "`ftd.code`"),
+    ("41-document-favicon", "`ftd.document`"),
+    ("42-links", "the `ftd.device` global"),
+    ("43-image-object-fit", "the `spacing` in `rem`"),
+    ("44-local-storage", "`ftd.local_storage.set`"),
+    (
+        "44-module",
+        "the imports of the other `t/js` documents are not interpreted here",
+    ),
+    (
+        "45-re-module",
+        "the imports of the other `t/js` documents are not interpreted here",
+    ),
+    (
+        "45-re-re-module",
+        "the imports of the other `t/js` documents are not interpreted here",
+    ),
+    ("46-code-languages", "`ftd.code`"),
+    ("47-ftd-code-syntax", "the `space-around` spacing"),
+    ("48-video", "`ftd.video`"),
+    ("49-align-content", "the `role` of a text"),
+    ("50-iframe-fullscreen", "the `align-content` of a column"),
+    ("51-markdown-table", "the `color` of a boolean"),
+    ("52-events", "a conditional `background`"),
+    ("53-link-color", "the `link-color` of a text"),
+    ("54-class-fix", "the `background` of a text"),
+    (
+        "56-title-fix",
+        "the runtime shows the text as is, `fastn_js` renders its markdown",
+    ),
+    ("57-code-dark-mode", "`ftd.code`"),
+    ("59-text-shadow", "the `text-shadow` of a text"),
+    ("60-conditional-module-headers", "the `color` of a text"),
+    ("61-functions", "the `role` of a text"),
+    ("62-fallback-fonts", "the `color` of an integer"),
+    ("63-external-js", "the functions implemented in js"),
+    ("64-map", "the maps"),
+    ("65-grid", "`ftd.grid`"),
+    ("66-heading-ids", "the `region` of a text"),
+    (
+        "67-rendered-markdown",
+        "the runtime shows the text as is, `fastn_js` renders its markdown",
+    ),
+    ("68-transform-animation", "the `transform` of a text"),
+    ("69-layered-backgrounds", "the `background` of a column"),
+];
+
+#[track_caller]
+fn p(name: &str) {
+    let source = std::fs::read_to_string(format!("./t/js/{}.ftd", name)).unwrap();
+    let doc = ftd::test_helper::ftd_v2_interpret_helper("foo", source.as_str())
+        .unwrap_or_else(|e| panic!("{}: {:?}", name, e));
+
+    let module = doc
+        .generate_wasm()
+        .unwrap_or_else(|e| panic!("{}: {:?}", name, e));
+    let wat = fastn_wasm::encode(&module);
+    let html = fastn_runtime::Document::new(wat).initial_html();
+
+    let js_document_script = fastn_js::to_js(
+        ftd::js::document_into_js_ast(doc).asts.as_slice(),
+        true,
+        "foo",
+    );
+    let js_ftd_script =
+        fastn_js::to_js(ftd::js::default_bag_into_js_ast().as_slice(), false, "foo");
+    let ssr = fastn_js::ssr_with_js_string(
+        "foo",
+        format!("{js_ftd_script}\n{js_document_script}").as_str(),
+    );
+
+    assert_eq!(
+        outline(html.as_str()),
+        outline(ssr.as_str()),
+        "{}\nwasm: {}\nssr: {}",
+        name,
+        html,
+        ssr
+    );
+}
+
+/// The first element of `html`: an element is its tag, its attributes and its style in braces,
+/// and `[child, ..]` if it has child elements, its text if not. The `data-id`s are left out, and
+/// the classes `fastn_js` has the css of in the `<style id="styles">` are replaced by their css.
+/// The `<comment>`s, the placeholders of the false conditions and the loops, are left out, and
+/// the `<body>` of `fastn_js::ssr` is skipped so its root column lines up with the root of the
+/// `fastn_runtime::Dom`, the attributes of the roots are not compared.
+fn outline(html: &str) -> String {
+    let css = classes(html);
+    let mut stack: Vec<(&str, String, Vec<String>)> = vec![("", String::new(), vec![])];
+    let mut text = String::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let end = start + rest[start..].find('>').unwrap();
+        let tag = &rest[start + 1..end];
+        rest = &rest[end + 1..];
+
+        if tag == "body" || tag == "/body" || tag.starts_with("body ") {
+            // not an element of the document
+        } else if tag.starts_with("style") {
+            // the css of the classes, after the document
+            break;
+        } else if tag.starts_with('/') {
+            let (name, element, children) = stack.pop().unwrap();
+            let item = if !children.is_empty() {
+                format!("{}[{}]", element, children.join(","))
+            } else if text.trim().is_empty() {
+                element
+            } else {
+                format!("{} {}", element, text.trim())
+            };
+            if name != "comment" {
+                stack.last_mut().unwrap().2.push(item);
+            }
+        } else {
+            let name = tag.split_whitespace().next().unwrap();
+            let element = if stack.len() == 1 {
+                name.to_string()
+            } else {
+                element(tag, &css)
+            };
+            if tag.ends_with('/') {
+                stack.last_mut().unwrap().2.push(element);
+            } else {
+                stack.push((name, element, vec![]));
+            }
+        }
+        text.clear();
+    }
+
+    stack.remove(0).2.remove(0)
+}
+
+/// `tag` with its attributes but `data-id`, sorted, and the declarations of its style and of its
+/// classes in `css`, sorted: `div.ft_column{gap: 5px; width: 100%}`.
+fn element(tag: &str, css: &std::collections::HashMap<&str, Vec<&str>>) -> String {
+    let (name, mut rest) = tag.split_once(' ').unwrap_or((tag, ""));
+    let mut classes = vec![];
+    let mut attributes = vec![];
+    let mut style = vec![];
+    loop {
+        rest = rest.trim_start();
+        let end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        if end == 0 {
+            break;
+        }
+        let attribute = &rest[..end];
+        rest = &rest[end..];
+        let value = match rest.strip_prefix("=\"") {
+            Some(quoted) => {
+                let end = quoted.find('"').unwrap();
+                rest = &quoted[end + 1..];
+                &quoted[..end]
+            }
+            None => match rest.strip_prefix('=') {
+                Some(unquoted) => {
+                    let end = unquoted.find(char::is_whitespace).unwrap_or(unquoted.len());
+                    rest = &unquoted[end..];
+                    &unquoted[..end]
+                }
+                None => "",
+            },
+        };
+        match attribute {
+            "data-id" => {}
+            "class" => {
+                for class in value.split_whitespace() {
+                    match css.get(class) {
+                        Some(declarations) => style.extend(declarations.iter().copied()),
+                        None => classes.push(class),
+                    }
+                }
+            }
+            "style" => style.extend(value.split(';').map(str::trim).filter(|v| !v.is_empty())),
+            _ => attributes.push(format!("{}={:?}", attribute, value)),
+        }
+    }
+    classes.sort();
+    attributes.sort();
+    style.sort();
+
+    let mut element = name.to_string();
+    for class in classes {
+        element.push('.');
+        element.push_str(class);
+    }
+    if !attributes.is_empty() {
+        element.push_str(format!("({})", attributes.join(" ")).as_str());
+    }
+    if !style.is_empty() {
+        element.push_str(format!("{{{}}}", style.join("; ")).as_str());
+    }
+    element
+}
+
+/// The declarations of the classes in the `<style id="styles">` of `html`.
+fn classes(html: &str) -> std::collections::HashMap<&str, Vec<&str>> {
+    let styles = match html.split_once("<style id=\"styles\">") {
+        Some((_, styles)) => styles.split("</style>").next().unwrap(),
+        None => return Default::default(),
+    };
+    styles
+        .lines()
+        .filter_map(|line| {
+            let (class, declarations) = line.trim().strip_prefix('.')?.split_once('{')?;
+            Some((
+                class.trim(),
+                declarations
+                    .trim_end_matches('}')
+                    .split(';')
+                    .map(str::trim)
+                    .filter(|v| !v.is_empty())
+                    .collect(),
+            ))
+        })
+        .collect()
+}
+
+#[test]
+fn outline_test() {
+    assert_eq!(
+        outline(
+            r#"<div data-id="1" class="a"><div>x &amp; y</div><comment data-id="2"></comment><div class="ft_row __w-1" style="gap: 5px"> <div id="z">z</div> </div></div><style id="styles">
+    .__w-1 { width: 100%; }
+    </style>"#
+        ),
+        r#"div[div x &amp; y,div.ft_row{gap: 5px; width: 100%}[div(id="z") z]]"#
+    );
+    assert_eq!(
+        outline(
+            r#"<div data-id=1> <div data-id=2 class="ft_row" style="width: 100%; gap: 5px"></div></div>"#
+        ),
+        "div[div.ft_row{gap: 5px; width: 100%}]"
+    );
+}
+
+#[test]
+fn all_fixtures() {
+    let mut names = std::fs::read_dir("./t/js")
+        .unwrap()
+        .filter_map(|entry| {
+            let path = entry.unwrap().path();
+            match path.extension() {
+                Some(extension) if extension == "ftd" => {
+                    Some(path.file_stem().unwrap().to_str().unwrap().to_string())
+                }
+                _ => None,
+            }
+        })
+        .collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names.len(), FIXTURES.len() + EXCLUDED.len());
+    for name in names {
+        assert!(
+            FIXTURES.contains(&name.as_str()) != EXCLUDED.iter().any(|(n, _)| name.eq(n)),
+            "{} has to be in exactly one of FIXTURES and EXCLUDED",
+            name
+        );
+    }
+}
+
+#[test]
+fn ssr() {
+    for name in FIXTURES {
+        p(name)
+    }
+}
+
+#[test]
+fn integer_out_of_range() {
+    let doc =
+        ftd::test_helper::ftd_v2_interpret_helper("foo", "-- ftd.integer: 3000000000\n").unwrap();
+    assert_eq!(
+        doc.generate_wasm().unwrap_err().to_string(),
+        "foo:1 -> the integer 3000000000 does not fit in the 32 bits of the wasm runtime"
+    );
+}
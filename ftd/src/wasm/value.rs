@@ -1,18 +1,337 @@
-impl ftd::interpreter::Value {
-    pub fn create(&self) -> fastn_wasm::Expression {
-        match self {
-            ftd::interpreter::Value::String { text } => {
-                let data = text.as_bytes().to_vec();
-
-                fastn_wasm::Expression::Call {
-                    name: "string_new".to_string(),
-                    params: vec![
-                        fastn_wasm::Expression::I32Const(data.len() as i32),
-                        fastn_wasm::Expression::Data { offset: 0, data },
-                    ],
+/// The ftd names a function of the module can refer to, besides the globals.
+#[derive(Debug, Default)]
+pub(crate) struct Scope {
+    /// The ftd name, the local holding its pointer and its kind, the innermost is the last.
+    names: Vec<(String, String, ftd::interpreter::Kind)>,
+    pub(crate) locals: Vec<fastn_wasm::PL>,
+}
+
+impl Scope {
+    pub(crate) fn push(&mut self, name: &str, local: &str, kind: ftd::interpreter::Kind) {
+        self.names.push((name.to_string(), local.to_string(), kind))
+    }
+
+    pub(crate) fn pop(&mut self) {
+        self.names.pop();
+    }
+
+    /// The local `name` is in and the fields of it `name` refers to, `foo#person.name` is the
+    /// `name` field of the local of `foo#person`.
+    fn find<'b>(&self, name: &'b str) -> Option<(&str, &ftd::interpreter::Kind, Option<&'b str>)> {
+        self.names.iter().rev().find_map(|(n, local, kind)| {
+            if n.eq(name) {
+                Some((local.as_str(), kind, None))
+            } else {
+                name.strip_prefix(n.as_str())
+                    .and_then(|v| v.strip_prefix('.'))
+                    .map(|path| (local.as_str(), kind, Some(path)))
+            }
+        })
+    }
+}
+
+/// The type of a compiled expression: integers, decimals and booleans are wasm values, the rest
+/// are pointers.
+#[derive(Debug, Clone)]
+pub(crate) enum Ty {
+    Integer,
+    Decimal,
+    Boolean,
+    Pointer(ftd::interpreter::Kind),
+}
+
+impl<'a> ftd::wasm::Module<'a> {
+    /// Creates `value` in the memory of the runtime and returns the pointer to it, `body` gets
+    /// what has to run before.
+    pub(crate) fn value(
+        &mut self,
+        scope: &mut ftd::wasm::Scope,
+        value: &ftd::interpreter::PropertyValue,
+        body: &mut Vec<fastn_wasm::Expression>,
+    ) -> ftd::interpreter::Result<fastn_wasm::Expression> {
+        match value {
+            ftd::interpreter::PropertyValue::Value {
+                value, line_number, ..
+            } => self.value_(scope, value, *line_number, body),
+            ftd::interpreter::PropertyValue::Reference {
+                name, line_number, ..
+            } => Ok(self.reference(scope, name, *line_number)?.0),
+            ftd::interpreter::PropertyValue::Clone {
+                name, line_number, ..
+            } => {
+                let (pointer, kind) = self.reference(scope, name, *line_number)?;
+                match kind.ref_inner() {
+                    ftd::interpreter::Kind::String => {
+                        Ok(fastn_wasm::expression::call1("copy_string", pointer))
+                    }
+                    ftd::interpreter::Kind::Integer
+                    | ftd::interpreter::Kind::Decimal
+                    | ftd::interpreter::Kind::Boolean => {
+                        let (value, ty) = load(pointer, &kind);
+                        Ok(to_pointer(value, &ty))
+                    }
+                    t => ftd::interpreter::utils::e2(
+                        format!("can not clone `{}`, a {:?}", name, t),
+                        self.doc.name,
+                        *line_number,
+                    ),
                 }
             }
-            _ => panic!("Not implemented: {:?}", self),
+            ftd::interpreter::PropertyValue::FunctionCall(call) => {
+                self.function_call(scope, call, body)
+            }
+        }
+    }
+
+    fn value_(
+        &mut self,
+        scope: &mut ftd::wasm::Scope,
+        value: &ftd::interpreter::Value,
+        line_number: usize,
+        body: &mut Vec<fastn_wasm::Expression>,
+    ) -> ftd::interpreter::Result<fastn_wasm::Expression> {
+        Ok(match value {
+            ftd::interpreter::Value::String { text } => self.string_constant(text),
+            ftd::interpreter::Value::Integer { value } => fastn_wasm::expression::call1(
+                "create_i32",
+                fastn_wasm::expression::i32(self.integer(*value, line_number)?),
+            ),
+            ftd::interpreter::Value::Decimal { value } => fastn_wasm::expression::call1(
+                "create_f32",
+                fastn_wasm::Expression::F32Const(*value as f32),
+            ),
+            ftd::interpreter::Value::Boolean { value } => fastn_wasm::expression::call1(
+                "create_boolean",
+                fastn_wasm::expression::i32(*value as i32),
+            ),
+            ftd::interpreter::Value::Optional { data, .. } if data.is_some() => {
+                self.value_(scope, data.as_ref().as_ref().unwrap(), line_number, body)?
+            }
+            ftd::interpreter::Value::Record { name, fields } => {
+                let record = self.doc.get_record(name, line_number)?;
+                let list = self.local(scope, "record", fastn_wasm::Type::ExternRef);
+                body.push(fastn_wasm::expression::local_set(
+                    list.as_str(),
+                    fastn_wasm::expression::call("create_list"),
+                ));
+                for field in record.fields.iter() {
+                    let value = match fields.get(field.name.as_str()).or(field.value.as_ref()) {
+                        Some(value) => value,
+                        None => {
+                            return ftd::interpreter::utils::e2(
+                                format!("`{}` of `{}` has no value", field.name, name),
+                                self.doc.name,
+                                line_number,
+                            )
+                        }
+                    };
+                    let value = self.value(scope, value, body)?;
+                    body.push(self.list_push(
+                        list.as_str(),
+                        &field.kind.kind,
+                        value,
+                        line_number,
+                    )?);
+                }
+                fastn_wasm::expression::local(list.as_str())
+            }
+            ftd::interpreter::Value::List { data, kind } => {
+                let list = self.local(scope, "list", fastn_wasm::Type::ExternRef);
+                body.push(fastn_wasm::expression::local_set(
+                    list.as_str(),
+                    fastn_wasm::expression::call("create_list"),
+                ));
+                for item in data.iter() {
+                    let value = self.value(scope, item, body)?;
+                    body.push(self.list_push(list.as_str(), &kind.kind, value, line_number)?);
+                }
+                fastn_wasm::expression::local(list.as_str())
+            }
+            t => {
+                return ftd::interpreter::utils::e2(
+                    format!("{:?} can not be compiled to wasm yet", t),
+                    self.doc.name,
+                    line_number,
+                )
+            }
+        })
+    }
+
+    /// The integers are `i32`s in the runtime, an ftd integer out of their range is an error
+    /// rather than wrapped.
+    pub(crate) fn integer(&self, value: i64, line_number: usize) -> ftd::interpreter::Result<i32> {
+        match i32::try_from(value) {
+            Ok(value) => Ok(value),
+            Err(_) => ftd::interpreter::utils::e2(
+                format!(
+                    "the integer {} does not fit in the 32 bits of the wasm runtime",
+                    value
+                ),
+                self.doc.name,
+                line_number,
+            ),
         }
     }
+
+    /// `list_push` of `value`, a pointer to a `kind`, to the list in the local `list`.
+    pub(crate) fn list_push(
+        &self,
+        list: &str,
+        kind: &ftd::interpreter::Kind,
+        value: fastn_wasm::Expression,
+        line_number: usize,
+    ) -> ftd::interpreter::Result<fastn_wasm::Expression> {
+        Ok(fastn_wasm::expression::call3(
+            "list_push",
+            fastn_wasm::expression::local(list),
+            fastn_wasm::expression::i32(self.pointer_kind(kind, line_number)?.into()),
+            value,
+        ))
+    }
+
+    /// The pointer `name` refers to and its kind, `name` is a local of `scope` or a global, or a
+    /// field of one of them.
+    pub(crate) fn reference(
+        &mut self,
+        scope: &ftd::wasm::Scope,
+        name: &str,
+        line_number: usize,
+    ) -> ftd::interpreter::Result<(fastn_wasm::Expression, ftd::interpreter::Kind)> {
+        if let Some((local, kind, path)) = scope.find(name) {
+            let pointer = fastn_wasm::expression::local(local);
+            return match path {
+                Some(path) => self.fields(pointer, kind, path, line_number),
+                None => Ok((pointer, kind.clone())),
+            };
+        }
+
+        // the fields of a global follow the first `.` after the document name
+        let (global, path) = match name
+            .find('#')
+            .and_then(|h| name[h..].find('.').map(|d| h + d))
+        {
+            Some(d) => (&name[..d], Some(&name[d + 1..])),
+            None => (name, None),
+        };
+        if !global.contains('#') {
+            return ftd::interpreter::utils::e2(
+                format!("unknown variable `{}`", name),
+                self.doc.name,
+                line_number,
+            );
+        }
+        let (index, kind) = self.global(global, line_number)?;
+        let pointer =
+            fastn_wasm::expression::call1("get_global", fastn_wasm::expression::i32(index));
+        match path {
+            Some(path) => self.fields(pointer, &kind, path, line_number),
+            None => Ok((pointer, kind)),
+        }
+    }
+
+    /// The field at `path` of the record `pointer` points to, a record is a list of its fields
+    /// in the order of the record definition.
+    fn fields(
+        &self,
+        mut pointer: fastn_wasm::Expression,
+        kind: &ftd::interpreter::Kind,
+        path: &str,
+        line_number: usize,
+    ) -> ftd::interpreter::Result<(fastn_wasm::Expression, ftd::interpreter::Kind)> {
+        let mut kind = kind.clone();
+        for name in path.split('.') {
+            let record = match kind.ref_inner() {
+                ftd::interpreter::Kind::Record { name } => {
+                    self.doc.get_record(name, line_number)?
+                }
+                t => {
+                    return ftd::interpreter::utils::e2(
+                        format!("can not get `{}` of a {:?}", name, t),
+                        self.doc.name,
+                        line_number,
+                    )
+                }
+            };
+            let (index, field) = match record
+                .fields
+                .iter()
+                .enumerate()
+                .find(|(_, f)| f.name.eq(name))
+            {
+                Some(v) => v,
+                None => {
+                    return ftd::interpreter::utils::e2(
+                        format!("`{}` has no field `{}`", record.name, name),
+                        self.doc.name,
+                        line_number,
+                    )
+                }
+            };
+            pointer = fastn_wasm::expression::call2(
+                "get_func_arg_ref",
+                pointer,
+                fastn_wasm::expression::i32(index as i32),
+            );
+            kind = field.kind.kind.clone();
+        }
+        Ok((pointer, kind))
+    }
+
+    pub(crate) fn pointer_kind(
+        &self,
+        kind: &ftd::interpreter::Kind,
+        line_number: usize,
+    ) -> ftd::interpreter::Result<fastn_runtime::PointerKind> {
+        Ok(match kind.ref_inner() {
+            ftd::interpreter::Kind::String => fastn_runtime::PointerKind::String,
+            ftd::interpreter::Kind::Integer => fastn_runtime::PointerKind::Integer,
+            ftd::interpreter::Kind::Decimal => fastn_runtime::PointerKind::Decimal,
+            ftd::interpreter::Kind::Boolean => fastn_runtime::PointerKind::Boolean,
+            ftd::interpreter::Kind::Record { .. } => fastn_runtime::PointerKind::Record,
+            ftd::interpreter::Kind::OrType { .. } => fastn_runtime::PointerKind::OrType,
+            ftd::interpreter::Kind::List { .. } => fastn_runtime::PointerKind::List,
+            t => {
+                return ftd::interpreter::utils::e2(
+                    format!("{:?} can not be compiled to wasm yet", t),
+                    self.doc.name,
+                    line_number,
+                )
+            }
+        })
+    }
+}
+
+/// The value `pointer` points to, for the kinds that are wasm values.
+pub(crate) fn load(
+    pointer: fastn_wasm::Expression,
+    kind: &ftd::interpreter::Kind,
+) -> (fastn_wasm::Expression, ftd::wasm::Ty) {
+    match kind.ref_inner() {
+        ftd::interpreter::Kind::Integer => (
+            fastn_wasm::expression::call1("get_i32", pointer),
+            ftd::wasm::Ty::Integer,
+        ),
+        ftd::interpreter::Kind::Decimal => (
+            fastn_wasm::expression::call1("get_f32", pointer),
+            ftd::wasm::Ty::Decimal,
+        ),
+        ftd::interpreter::Kind::Boolean => (
+            fastn_wasm::expression::call1("get_boolean", pointer),
+            ftd::wasm::Ty::Boolean,
+        ),
+        t => (pointer, ftd::wasm::Ty::Pointer(t.clone())),
+    }
+}
+
+/// A pointer to `value`, the wasm values are created in the memory of the runtime.
+pub(crate) fn to_pointer(
+    value: fastn_wasm::Expression,
+    ty: &ftd::wasm::Ty,
+) -> fastn_wasm::Expression {
+    match ty {
+        ftd::wasm::Ty::Integer => fastn_wasm::expression::call1("create_i32", value),
+        ftd::wasm::Ty::Decimal => fastn_wasm::expression::call1("create_f32", value),
+        ftd::wasm::Ty::Boolean => fastn_wasm::expression::call1("create_boolean", value),
+        ftd::wasm::Ty::Pointer(_) => value,
+    }
 }
@@ -1,19 +1,46 @@
-impl ftd::interpreter::Variable {
-    // Input:
-    //  "foo#message", type: String, value: "Hello World"
-    // Output:
-    // (main (func $main (export "main")
-    //      (global.set (i32.const idx) (call $string_new (i32.const 12) (data (i32.const 0) "Hello World")))
-    // )
-    pub fn global_expression(&self, idx: usize) -> fastn_wasm::Expression {
-        let create = match &self.value {
-            ftd::interpreter::PropertyValue::Value { value, .. } => value.create(),
-            _ => panic!("Not implemented: {:?}", self),
-        };
+impl<'a> ftd::wasm::Module<'a> {
+    /// The index of the global `name` and its kind. The global is set in `main` the first time it
+    /// is referred to, after the globals its value refers to.
+    pub(crate) fn global(
+        &mut self,
+        name: &str,
+        line_number: usize,
+    ) -> ftd::interpreter::Result<(i32, ftd::interpreter::Kind)> {
+        if let Some(index) = self.globals.iter().position(|(n, _)| n.eq(name)) {
+            return Ok((index as i32, self.globals[index].1.clone()));
+        }
 
-        fastn_wasm::Expression::GlobalSet {
-            index: Box::new(fastn_wasm::Expression::I32Const(idx as i32)),
-            value: Box::new(create),
+        let variable = self.doc.get_variable(name, line_number)?;
+        if !variable.conditional_value.is_empty() {
+            return ftd::interpreter::utils::e2(
+                format!(
+                    "the conditional values of `{}` can not be compiled to wasm yet",
+                    name
+                ),
+                self.doc.name,
+                variable.line_number,
+            );
         }
+
+        // the globals are set in `main`, their locals are added to the ones of `main`
+        let mut scope = ftd::wasm::Scope::default();
+        scope.locals = std::mem::take(&mut self.globals_locals);
+        let mut body = vec![];
+        let value = self.value(&mut scope, &variable.value, &mut body);
+        // the globals `value` refers to are set before, with locals of their own
+        scope.locals.append(&mut self.globals_locals);
+        self.globals_locals = scope.locals;
+        let value = value?;
+
+        let index = self.globals.len() as i32;
+        self.globals.push((name.to_string(), variable.kind.kind));
+        self.globals_body.extend(body);
+        self.globals_body.push(fastn_wasm::expression::call2(
+            "set_global",
+            fastn_wasm::expression::i32(index),
+            value,
+        ));
+
+        Ok((index, self.globals.last().unwrap().1.clone()))
     }
 }